@@ -0,0 +1,116 @@
+use hftbacktest::depth::MarketDepth;
+
+use super::sim_time_secs;
+
+/// Elapse duration that coarsens during quiet stretches: after `quiet_ticks` consecutive steps
+/// with an unchanged top of book it doubles (up to `max_multiplier` x the base) and snaps back
+/// to the base duration on the next book change. `quiet_ticks` = 0 keeps fixed stepping.
+#[derive(Debug, Clone)]
+pub struct AdaptiveElapse {
+    base_ns: i64,
+    max_ns: i64,
+    quiet_ticks: usize,
+    current_ns: i64,
+    quiet_streak: usize,
+    /// Best bid/ask ticks and quantities at the previous step
+    last_book: Option<(i64, i64, f64, f64)>,
+    elapsed_secs: f64,
+}
+
+impl AdaptiveElapse {
+    pub fn new(base_ns: i64, quiet_ticks: usize, max_multiplier: i64) -> Self {
+        Self {
+            base_ns,
+            max_ns: base_ns * max_multiplier.max(1),
+            quiet_ticks,
+            current_ns: base_ns,
+            quiet_streak: 0,
+            last_book: None,
+            elapsed_secs: 0.0,
+        }
+    }
+
+    /// Duration to pass to the next `elapse` call
+    pub fn duration_ns(&self) -> i64 {
+        self.current_ns
+    }
+
+    /// Consecutive steps without a top-of-book change
+    #[allow(dead_code)]
+    pub fn quiet_streak(&self) -> usize {
+        self.quiet_streak
+    }
+
+    /// Simulated seconds covered by the steps observed so far
+    pub fn sim_time_secs(&self) -> f64 {
+        self.elapsed_secs
+    }
+
+    /// Record the step just elapsed and compare the book against the previous step
+    pub fn observe<MD: MarketDepth + ?Sized>(&mut self, depth: &MD) {
+        let book = (
+            depth.best_bid_tick(),
+            depth.best_ask_tick(),
+            depth.bid_qty_at_tick(depth.best_bid_tick()),
+            depth.ask_qty_at_tick(depth.best_ask_tick()),
+        );
+        let changed = self.last_book != Some(book);
+        self.last_book = Some(book);
+        self.observe_change(changed);
+    }
+
+    /// Record a step of `duration_ns()` and adapt the next duration
+    pub fn observe_change(&mut self, changed: bool) {
+        self.elapsed_secs += sim_time_secs(1, self.current_ns);
+        if self.quiet_ticks == 0 {
+            return;
+        }
+
+        if changed {
+            self.quiet_streak = 0;
+            self.current_ns = self.base_ns;
+            return;
+        }
+
+        self.quiet_streak += 1;
+        if self.quiet_streak >= self.quiet_ticks {
+            self.current_ns = (self.current_ns * 2).min(self.max_ns);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_duration_grows_when_quiet_and_resets_on_change() {
+        let mut stepper = AdaptiveElapse::new(100, 3, 8);
+        stepper.observe_change(true);
+        for _ in 0..2 {
+            stepper.observe_change(false);
+            assert_eq!(stepper.duration_ns(), 100);
+        }
+
+        stepper.observe_change(false);
+        assert_eq!(stepper.quiet_streak(), 3);
+        assert_eq!(stepper.duration_ns(), 200);
+        stepper.observe_change(false);
+        assert_eq!(stepper.duration_ns(), 400);
+        stepper.observe_change(false);
+        stepper.observe_change(false);
+        assert_eq!(stepper.duration_ns(), 800);
+
+        // 4 base steps + 200 + 400 + 800 + 800 of simulated time so far
+        stepper.observe_change(true);
+        assert_eq!(stepper.duration_ns(), 100);
+        assert_eq!(stepper.quiet_streak(), 0);
+        assert!((stepper.sim_time_secs() - 2_600e-9).abs() < 1e-15);
+
+        let mut fixed = AdaptiveElapse::new(100, 0, 8);
+        for _ in 0..10 {
+            fixed.observe_change(false);
+        }
+        assert_eq!(fixed.duration_ns(), 100);
+    }
+}
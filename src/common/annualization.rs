@@ -0,0 +1,27 @@
+/// Seconds in a 24/7 year (crypto trades around the clock)
+pub const SECONDS_PER_YEAR_24_7: f64 = 365.0 * 24.0 * 3600.0;
+
+/// Samples per year when one sample is taken every `sample_interval_ns` of trading time;
+/// annualized ratios scale by its square root. 0 for a non-positive interval
+pub fn annualization_factor(sample_interval_ns: f64, trading_seconds_per_year: f64) -> f64 {
+    if sample_interval_ns <= 0.0 {
+        return 0.0;
+    }
+    trading_seconds_per_year / (sample_interval_ns / 1_000_000_000.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_33ms_samples_over_a_24_7_year() {
+        // 31_536_000 s / 0.033 s
+        let factor = annualization_factor(33_000_000.0, SECONDS_PER_YEAR_24_7);
+        assert!((factor - 955_636_363.636_363_6).abs() < 1e-3);
+
+        // 252 days x 6.5 h of equities trading, one sample per second
+        assert_eq!(annualization_factor(1_000_000_000.0, 252.0 * 6.5 * 3600.0), 5_896_800.0);
+        assert_eq!(annualization_factor(0.0, SECONDS_PER_YEAR_24_7), 0.0);
+    }
+}
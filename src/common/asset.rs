@@ -0,0 +1,78 @@
+use hftbacktest::backtest::assettype::{AssetType, InverseAsset, LinearAsset};
+
+/// Contract type of the traded instrument, selecting the engine's asset type and the runners'
+/// PnL math
+///
+/// - Linear (USDT-margined): notional = multiplier * qty * price, and a long's PnL is
+///   multiplier * qty * (exit - entry) in the quote currency.
+/// - Inverse (coin-margined): notional = multiplier * qty / price, and a long's PnL is
+///   multiplier * qty * (1 / entry - 1 / exit) in the base currency. It is nonlinear in
+///   price: the same move pays less as the price rises.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[allow(dead_code)]
+pub enum AssetKind {
+    Linear { multiplier: f64 },
+    Inverse { multiplier: f64 },
+}
+
+impl AssetKind {
+    /// Value of `qty` contracts at `price` (quote currency for linear, base for inverse)
+    pub fn notional(&self, price: f64, qty: f64) -> f64 {
+        match *self {
+            AssetKind::Linear { multiplier } => multiplier * qty * price,
+            AssetKind::Inverse { multiplier } => multiplier * qty / price,
+        }
+    }
+
+    /// PnL of a long of `qty` contracts from `entry` to `exit`; negate for a short
+    pub fn pnl(&self, entry: f64, exit: f64, qty: f64) -> f64 {
+        match *self {
+            AssetKind::Linear { multiplier } => multiplier * qty * (exit - entry),
+            AssetKind::Inverse { multiplier } => multiplier * qty * (1.0 / entry - 1.0 / exit),
+        }
+    }
+}
+
+impl AssetType for AssetKind {
+    fn amount(&self, price: f64, qty: f64) -> f64 {
+        match *self {
+            AssetKind::Linear { multiplier } => LinearAsset::new(multiplier).amount(price, qty),
+            AssetKind::Inverse { multiplier } => InverseAsset::new(multiplier).amount(price, qty),
+        }
+    }
+
+    fn equity(&self, price: f64, balance: f64, position: f64, fee: f64) -> f64 {
+        match *self {
+            AssetKind::Linear { multiplier } => LinearAsset::new(multiplier).equity(price, balance, position, fee),
+            AssetKind::Inverse { multiplier } => InverseAsset::new(multiplier).equity(price, balance, position, fee),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_inverse_pnl_is_in_base_currency_and_nonlinear() {
+        let linear = AssetKind::Linear { multiplier: 1.0 };
+        let inverse = AssetKind::Inverse { multiplier: 100.0 };
+
+        // 100 USD contracts, long 10 from 20_000 to 25_000
+        assert_eq!(linear.pnl(20_000.0, 25_000.0, 10.0), 50_000.0);
+        let base_pnl = inverse.pnl(20_000.0, 25_000.0, 10.0);
+        assert!((base_pnl - 0.01).abs() < 1e-12);
+        assert!((inverse.notional(20_000.0, 10.0) - 0.05).abs() < 1e-12);
+
+        // The same 5_000 move higher up pays less for inverse, identically for linear
+        let higher = inverse.pnl(25_000.0, 30_000.0, 10.0);
+        assert!(higher < base_pnl);
+        assert_eq!(linear.pnl(25_000.0, 30_000.0, 10.0), 50_000.0);
+
+        // A long loses more on a 5_000 drop than it gains on the same rise
+        assert!(-inverse.pnl(20_000.0, 15_000.0, 10.0) > base_pnl);
+        // Matches the engine's own amounts
+        let amount = inverse.amount(20_000.0, 10.0) - inverse.amount(25_000.0, 10.0);
+        assert!((amount - base_pnl).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,92 @@
+use std::collections::VecDeque;
+
+/// Average true range over the last `period` bars, each bar built from `bar_ticks` consecutive
+/// price samples (the runners feed one mid price per book update)
+#[derive(Debug, Clone)]
+pub struct AtrTracker {
+    bar_ticks: usize,
+    period: usize,
+    /// High, low and close of the bar being built, plus its sample count
+    bar: Option<(f64, f64, f64, usize)>,
+    prev_close: Option<f64>,
+    true_ranges: VecDeque<f64>,
+}
+
+impl AtrTracker {
+    pub fn new(bar_ticks: usize, period: usize) -> Self {
+        Self {
+            bar_ticks: bar_ticks.max(1),
+            period: period.max(1),
+            bar: None,
+            prev_close: None,
+            true_ranges: VecDeque::with_capacity(period.max(1)),
+        }
+    }
+
+    pub fn update(&mut self, price: f64) {
+        let (high, low, _, samples) = self.bar.unwrap_or((price, price, price, 0));
+        let (high, low, samples) = (high.max(price), low.min(price), samples + 1);
+        if samples < self.bar_ticks {
+            self.bar = Some((high, low, price, samples));
+            return;
+        }
+
+        // Bar complete: true range also covers a gap from the previous close
+        let true_range = match self.prev_close {
+            Some(prev) => (high - low).max((high - prev).abs()).max((low - prev).abs()),
+            None => high - low,
+        };
+        if self.true_ranges.len() == self.period {
+            self.true_ranges.pop_front();
+        }
+        self.true_ranges.push_back(true_range);
+        self.prev_close = Some(price);
+        self.bar = None;
+    }
+
+    /// None until `period` bars have completed
+    pub fn value(&self) -> Option<f64> {
+        (self.true_ranges.len() == self.period)
+            .then(|| self.true_ranges.iter().sum::<f64>() / self.period as f64)
+    }
+
+    pub fn reset(&mut self) {
+        self.bar = None;
+        self.prev_close = None;
+        self.true_ranges.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::StopKind;
+
+    #[test]
+    fn test_atr_stop_widens_when_ranges_expand() {
+        let mut atr = AtrTracker::new(4, 5);
+        let stop = StopKind::Atr { multiplier: 2.0 };
+        let entry = 100.0;
+
+        // Calm market: price oscillates by 0.01
+        for i in 0..20 {
+            atr.update(entry + if i % 2 == 0 { 0.0 } else { 0.01 });
+        }
+        let calm = atr.value().unwrap();
+        assert!((calm - 0.01).abs() < 1e-9);
+        let calm_stop = stop.stop_pct(entry, 0.01, 0.01, atr.value());
+
+        // Volatile market: swings of 0.2
+        for i in 0..20 {
+            atr.update(entry + if i % 2 == 0 { 0.0 } else { 0.2 });
+        }
+        let volatile_stop = stop.stop_pct(entry, 0.01, 0.01, atr.value());
+        assert!((volatile_stop - 2.0 * 0.2 / entry).abs() < 1e-9);
+        assert!(volatile_stop > calm_stop * 10.0);
+
+        // Not yet warmed up: falls back to the percentage stop
+        atr.reset();
+        assert_eq!(atr.value(), None);
+        assert_eq!(stop.stop_pct(entry, 0.01, 0.01, atr.value()), 0.01);
+    }
+}
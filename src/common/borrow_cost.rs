@@ -0,0 +1,89 @@
+const NS_PER_DAY: f64 = 86_400.0 * 1_000_000_000.0;
+
+/// Carry cost of short positions: `rate_bps_per_day` of the short notional, accrued over
+/// simulation time. Longs and flat periods cost nothing.
+#[derive(Debug, Clone)]
+pub struct BorrowCost {
+    /// Per-day rate as a fraction (None = shorts are free to hold)
+    rate_per_day: Option<f64>,
+    accrued: f64,
+    last_ns: Option<i64>,
+    /// When the current short was opened (None when not short)
+    short_since_ns: Option<i64>,
+}
+
+impl BorrowCost {
+    pub fn new(rate_bps_per_day: Option<f64>) -> Self {
+        Self {
+            rate_per_day: rate_bps_per_day.map(|bps| bps / 10_000.0),
+            accrued: 0.0,
+            last_ns: None,
+            short_since_ns: None,
+        }
+    }
+
+    /// Charge the time since the previous call on `position` (signed, negative = short)
+    /// valued at `price`
+    pub fn accrue(&mut self, position: f64, price: f64, now_ns: i64) {
+        let Some(rate) = self.rate_per_day else {
+            return;
+        };
+        if let (Some(last_ns), Some(_)) = (self.last_ns, self.short_since_ns) {
+            let days = (now_ns - last_ns).max(0) as f64 / NS_PER_DAY;
+            self.accrued += position.min(0.0).abs() * price * rate * days;
+        }
+        self.short_since_ns = if position < 0.0 { self.short_since_ns.or(Some(now_ns)) } else { None };
+        self.last_ns = Some(now_ns);
+    }
+
+    /// Total borrow cost so far, to be taken off equity
+    pub fn accrued(&self) -> f64 {
+        self.accrued
+    }
+
+    /// Simulation seconds the current short has been open (0 when not short)
+    #[allow(dead_code)]
+    pub fn short_duration_secs(&self, now_ns: i64) -> f64 {
+        self.short_since_ns.map_or(0.0, |since| (now_ns - since).max(0) as f64 / 1_000_000_000.0)
+    }
+
+    pub fn reset(&mut self) {
+        self.accrued = 0.0;
+        self.last_ns = None;
+        self.short_since_ns = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_one_day_short_accrues_rate_on_notional() {
+        let day_ns = 86_400 * 1_000_000_000;
+        let mut borrow = BorrowCost::new(Some(5.0));
+
+        // Long for a day: free
+        borrow.accrue(2.0, 100.0, 0);
+        borrow.accrue(2.0, 100.0, day_ns);
+        assert_eq!(borrow.accrued(), 0.0);
+
+        // Short 2 @ 100 for a day, in hourly steps: 5bps of $200
+        for hour in 0..=24 {
+            borrow.accrue(-2.0, 100.0, day_ns + hour * day_ns / 24);
+        }
+        assert!((borrow.accrued() - 0.1).abs() < 1e-9);
+        assert!((borrow.short_duration_secs(2 * day_ns) - 86_400.0).abs() < 1e-9);
+
+        // Twice the notional costs twice as much
+        let mut double = BorrowCost::new(Some(5.0));
+        double.accrue(-4.0, 100.0, 0);
+        double.accrue(-4.0, 100.0, day_ns);
+        assert!((double.accrued() - 0.2).abs() < 1e-9);
+
+        let mut off = BorrowCost::new(None);
+        off.accrue(-4.0, 100.0, 0);
+        off.accrue(-4.0, 100.0, day_ns);
+        assert_eq!(off.accrued(), 0.0);
+    }
+}
@@ -0,0 +1,108 @@
+use hftbacktest::types::{Event, BUY_EVENT, SELL_EVENT};
+
+/// 누적 거래량 델타(CVD) 추적기
+///
+/// 체결 이벤트의 공격 방향(aggressor side)을 기준으로
+/// 매수 주도 체결량은 더하고 매도 주도 체결량은 빼서 누적한다.
+#[derive(Debug, Clone, Default)]
+pub struct CvdTracker {
+    /// 누적 델타 (매수 주도 - 매도 주도)
+    cvd: f64,
+    /// 누적 매수 주도 체결량
+    buy_volume: f64,
+    /// 누적 매도 주도 체결량
+    sell_volume: f64,
+}
+
+#[allow(dead_code)]
+impl CvdTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 단일 체결 반영
+    pub fn update_trade(&mut self, is_buy: bool, qty: f64) {
+        if is_buy {
+            self.buy_volume += qty;
+            self.cvd += qty;
+        } else {
+            self.sell_volume += qty;
+            self.cvd -= qty;
+        }
+    }
+
+    /// 백테스트의 last_trades 이벤트 반영 (방향 플래그가 없는 이벤트는 무시)
+    pub fn update_from_events(&mut self, trades: &[Event]) {
+        for trade in trades {
+            if trade.is(BUY_EVENT) {
+                self.update_trade(true, trade.qty);
+            } else if trade.is(SELL_EVENT) {
+                self.update_trade(false, trade.qty);
+            }
+        }
+    }
+
+    /// 현재 CVD 값
+    pub fn value(&self) -> f64 {
+        self.cvd
+    }
+
+    pub fn buy_volume(&self) -> f64 {
+        self.buy_volume
+    }
+
+    pub fn sell_volume(&self) -> f64 {
+        self.sell_volume
+    }
+
+    /// 누적값 초기화 (파일 변경 시)
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hftbacktest::types::{EXCH_BUY_TRADE_EVENT, EXCH_SELL_TRADE_EVENT};
+
+    fn trade(ev: u64, qty: f64) -> Event {
+        Event {
+            ev,
+            exch_ts: 0,
+            local_ts: 0,
+            px: 100.0,
+            qty,
+            order_id: 0,
+            ival: 0,
+            fval: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_cvd_positive_on_buy_heavy_sequence() {
+        let mut tracker = CvdTracker::new();
+
+        let trades = vec![
+            trade(EXCH_BUY_TRADE_EVENT, 2.0),
+            trade(EXCH_SELL_TRADE_EVENT, 0.5),
+            trade(EXCH_BUY_TRADE_EVENT, 1.5),
+            trade(EXCH_SELL_TRADE_EVENT, 1.0),
+        ];
+        tracker.update_from_events(&trades);
+
+        assert!((tracker.value() - 2.0).abs() < 1e-9);
+        assert!((tracker.buy_volume() - 3.5).abs() < 1e-9);
+        assert!((tracker.sell_volume() - 1.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_cvd_reset() {
+        let mut tracker = CvdTracker::new();
+        tracker.update_trade(false, 3.0);
+        assert!(tracker.value() < 0.0);
+
+        tracker.reset();
+        assert_eq!(tracker.value(), 0.0);
+    }
+}
@@ -0,0 +1,62 @@
+const MINUTE_NS: i64 = 60_000_000_000;
+const MINUTES_PER_DAY: i64 = 24 * 60;
+
+/// Time-of-day filter for new entries. Windows are `[start, end)` in minutes after UTC
+/// midnight of the event timestamp; a window whose start is after its end wraps past
+/// midnight. Exits are never filtered, and an empty list allows entries all day.
+#[derive(Debug, Clone)]
+pub struct EntryWindows {
+    windows: &'static [(u32, u32)],
+}
+
+impl EntryWindows {
+    pub fn new(windows: &'static [(u32, u32)]) -> Self {
+        Self { windows }
+    }
+
+    /// Whether an entry at `timestamp_ns` (ns since the Unix epoch) falls in an allowed window
+    pub fn allows_entry(&self, timestamp_ns: i64) -> bool {
+        if self.windows.is_empty() {
+            return true;
+        }
+        let minute = minute_of_day(timestamp_ns);
+        self.windows.iter().any(|&(start, end)| {
+            let (start, end) = (start as i64, end as i64);
+            if start <= end {
+                (start..end).contains(&minute)
+            } else {
+                minute >= start || minute < end
+            }
+        })
+    }
+}
+
+/// Minutes since UTC midnight
+fn minute_of_day(timestamp_ns: i64) -> i64 {
+    timestamp_ns.div_euclid(MINUTE_NS).rem_euclid(MINUTES_PER_DAY)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_entries_limited_to_windows() {
+        // 2024-01-02 00:00 UTC
+        let midnight = 1_704_153_600 * 1_000_000_000;
+        let at = |hour: i64, minute: i64| midnight + (hour * 60 + minute) * MINUTE_NS;
+
+        let session = EntryWindows::new(&[(9 * 60 + 45, 15 * 60 + 45)]);
+        assert!(!session.allows_entry(at(9, 44)));
+        assert!(session.allows_entry(at(9, 45)));
+        assert!(session.allows_entry(at(15, 44)));
+        assert!(!session.allows_entry(at(15, 45)));
+
+        let overnight = EntryWindows::new(&[(22 * 60, 2 * 60)]);
+        assert!(overnight.allows_entry(at(23, 0)));
+        assert!(overnight.allows_entry(at(1, 59)));
+        assert!(!overnight.allows_entry(at(12, 0)));
+
+        assert!(EntryWindows::new(&[]).allows_entry(at(3, 0)));
+    }
+}
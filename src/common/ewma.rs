@@ -0,0 +1,75 @@
+/// EWMA(지수가중이동평균) 변동성 추정기
+///
+/// `σ²_t = λ·σ²_{t-1} + (1-λ)·r²_t` (RiskMetrics 방식, 평균 수익률 0 가정)
+/// 버퍼 재계산 없이 업데이트당 O(1)로 동작한다.
+#[derive(Debug, Clone)]
+pub struct EwmaVolatility {
+    /// 감쇠 계수 λ (0 < λ < 1, 클수록 과거 비중이 큼)
+    lambda: f64,
+    variance: f64,
+    samples: usize,
+}
+
+#[allow(dead_code)]
+impl EwmaVolatility {
+    pub fn new(lambda: f64) -> Self {
+        Self {
+            lambda: lambda.clamp(0.0, 1.0),
+            variance: 0.0,
+            samples: 0,
+        }
+    }
+
+    /// 새 수익률 반영
+    pub fn update(&mut self, ret: f64) {
+        self.variance = self.lambda * self.variance + (1.0 - self.lambda) * ret * ret;
+        self.samples += 1;
+    }
+
+    /// 현재 변동성 (표준편차)
+    pub fn value(&self) -> f64 {
+        self.variance.sqrt()
+    }
+
+    pub fn variance(&self) -> f64 {
+        self.variance
+    }
+
+    pub fn samples(&self) -> usize {
+        self.samples
+    }
+
+    pub fn reset(&mut self) {
+        self.variance = 0.0;
+        self.samples = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_step_change_decays_at_expected_rate() {
+        let lambda = 0.9;
+        let mut ewma = EwmaVolatility::new(lambda);
+
+        for _ in 0..100 {
+            ewma.update(0.0);
+        }
+        assert_eq!(ewma.value(), 0.0);
+
+        // 수익률이 0 → 1%로 바뀌면 분산은 (1 - λ^n)·r² 로 수렴
+        let r: f64 = 0.01;
+        for n in 1..=20 {
+            ewma.update(r);
+            let expected = (1.0 - lambda.powi(n)) * r * r;
+            assert!((ewma.variance() - expected).abs() < 1e-12);
+        }
+
+        for _ in 0..500 {
+            ewma.update(r);
+        }
+        assert!((ewma.value() - r).abs() < 1e-6);
+    }
+}
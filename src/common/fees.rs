@@ -0,0 +1,316 @@
+use hftbacktest::types::Order;
+
+/// Commission model for the runners' own PnL accounting
+///
+/// The simulated engine charges a flat `CommonFees` rate (the taker runners' is
+/// `ESTIMATED_FEE_RATE` per side), so minimums and tiers only show up in the PnL the runners
+/// report.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[allow(dead_code)]
+pub enum FeeModelKind {
+    /// Flat rate on traded notional
+    BpsOnly,
+    /// Flat rate, but never less than `min_usd` per fill
+    BpsWithMinimum { min_usd: f64 },
+    /// `(cumulative_volume_usd, rate)` pairs sorted by volume; a rate applies to the part of
+    /// a fill traded after cumulative volume passes its breakpoint (below the first one the
+    /// base rate applies)
+    Tiered { breakpoints: &'static [(f64, f64)] },
+}
+
+/// Applies a `FeeModelKind` fill by fill, tracking cumulative traded volume for tiers
+#[derive(Debug, Clone)]
+pub struct FeeCalculator {
+    kind: FeeModelKind,
+    base_rate: f64,
+    cumulative_volume: f64,
+    /// Fees charged since the last `reset_total_fees`
+    total_fees: f64,
+}
+
+impl FeeCalculator {
+    pub fn new(kind: FeeModelKind, base_rate: f64) -> Self {
+        Self {
+            kind,
+            base_rate,
+            cumulative_volume: 0.0,
+            total_fees: 0.0,
+        }
+    }
+
+    /// Fee for one fill of `notional` USD; advances the cumulative volume
+    pub fn charge(&mut self, notional: f64) -> f64 {
+        let notional = notional.abs();
+        let fee = match self.kind {
+            FeeModelKind::BpsOnly => notional * self.base_rate,
+            FeeModelKind::BpsWithMinimum { min_usd } => (notional * self.base_rate).max(min_usd),
+            FeeModelKind::Tiered { breakpoints } => self.tiered_fee(notional, breakpoints),
+        };
+        self.cumulative_volume += notional;
+        self.total_fees += fee;
+        fee
+    }
+
+    fn tiered_fee(&self, notional: f64, breakpoints: &[(f64, f64)]) -> f64 {
+        let start = self.cumulative_volume;
+        let end = start + notional;
+        let mut fee = 0.0;
+        let mut tier_start = 0.0;
+        let mut rate = self.base_rate;
+
+        for &(breakpoint, next_rate) in breakpoints {
+            fee += Self::overlap(start, end, tier_start, breakpoint) * rate;
+            tier_start = breakpoint;
+            rate = next_rate;
+        }
+        fee + Self::overlap(start, end, tier_start, f64::INFINITY) * rate
+    }
+
+    fn overlap(start: f64, end: f64, lo: f64, hi: f64) -> f64 {
+        (end.min(hi) - start.max(lo)).max(0.0)
+    }
+
+    /// Rate the next fill would pay at the current cumulative volume (ignores any minimum)
+    pub fn marginal_rate(&self) -> f64 {
+        match self.kind {
+            FeeModelKind::Tiered { breakpoints } => breakpoints.iter()
+                .take_while(|(breakpoint, _)| self.cumulative_volume >= *breakpoint)
+                .last()
+                .map_or(self.base_rate, |&(_, rate)| rate),
+            _ => self.base_rate,
+        }
+    }
+
+    /// Whether a take-profit at `gross_pnl_pct` earns back the round-trip fee plus `margin_pct`
+    /// (None = no filter, every take-profit passes)
+    pub fn clears_round_trip(&self, gross_pnl_pct: f64, margin_pct: Option<f64>) -> bool {
+        margin_pct.is_none_or(|margin| gross_pnl_pct > 2.0 * self.marginal_rate() + margin)
+    }
+
+    #[allow(dead_code)]
+    pub fn cumulative_volume(&self) -> f64 {
+        self.cumulative_volume
+    }
+
+    pub fn total_fees(&self) -> f64 {
+        self.total_fees
+    }
+
+    /// Start a new file's fee total; cumulative volume (and so the tier) carries over
+    pub fn reset_total_fees(&mut self) {
+        self.total_fees = 0.0;
+    }
+
+    pub fn reset(&mut self) {
+        self.cumulative_volume = 0.0;
+        self.total_fees = 0.0;
+    }
+}
+
+/// How much of the gross return went to fees
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FeeDrag {
+    pub total_fees: f64,
+    /// Return before fees
+    pub gross_pnl: f64,
+    /// `total_fees / |gross_pnl|` (None while the gross PnL is zero)
+    pub drag_ratio: Option<f64>,
+    /// Fees as a percentage of initial capital
+    pub capital_pct: f64,
+}
+
+impl FeeDrag {
+    pub fn new(gross_pnl: f64, total_fees: f64, initial_capital: f64) -> Self {
+        let drag_ratio = (gross_pnl != 0.0).then(|| total_fees / gross_pnl.abs());
+        let capital_pct = if initial_capital > 0.0 { total_fees / initial_capital * 100.0 } else { 0.0 };
+        Self { total_fees, gross_pnl, drag_ratio, capital_pct }
+    }
+
+    /// From the net PnL, which already has `total_fees` taken off
+    pub fn from_net(net_pnl: f64, total_fees: f64, initial_capital: f64) -> Self {
+        Self::new(net_pnl + total_fees, total_fees, initial_capital)
+    }
+
+    /// e.g. "$12.34 (25.0% of gross, 0.12% of capital)"
+    pub fn summary(&self) -> String {
+        match self.drag_ratio {
+            Some(ratio) => format!("${:.2} ({:.1}% of gross, {:.2}% of capital)",
+                                   self.total_fees, ratio * 100.0, self.capital_pct),
+            None => format!("${:.2} ({:.2}% of capital)", self.total_fees, self.capital_pct),
+        }
+    }
+}
+
+/// Whether a fill rested on the book and was hit (maker) or crossed it on arrival (taker)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Liquidity {
+    Maker,
+    Taker,
+}
+
+impl Liquidity {
+    /// Fee rate of this side of the trade (negative = rebate)
+    pub fn fee_rate(self, maker_rate: f64, taker_rate: f64) -> f64 {
+        match self {
+            Liquidity::Maker => maker_rate,
+            Liquidity::Taker => taker_rate,
+        }
+    }
+}
+
+/// How fills are classified as maker or taker
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[allow(dead_code)]
+pub enum LiquidityClassification {
+    /// The simulated exchange's `Order::maker` flag
+    Engine,
+    /// The order's price against the touch when it was submitted: a buy at or above the best
+    /// ask (a sell at or below the best bid) crossed and took liquidity. Orders submitted
+    /// without a recorded touch fall back to the exchange's flag
+    Touch,
+}
+
+impl LiquidityClassification {
+    /// `crossed_touch`: whether the order was priced through the opposite touch on submission
+    pub fn classify(self, order: &Order, crossed_touch: Option<bool>) -> Liquidity {
+        let maker = match (self, crossed_touch) {
+            (LiquidityClassification::Touch, Some(crossed)) => !crossed,
+            _ => order.maker,
+        };
+        if maker { Liquidity::Maker } else { Liquidity::Taker }
+    }
+}
+
+/// Whether a limit order at `price_tick` crosses the opposite touch
+pub fn crosses_touch(is_buy: bool, price_tick: i64, best_bid_tick: i64, best_ask_tick: i64) -> bool {
+    if is_buy { price_tick >= best_ask_tick } else { price_tick <= best_bid_tick }
+}
+
+/// Fill counts and fees paid per liquidity side (negative fees = rebates received)
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct LiquidityStats {
+    pub maker_fills: usize,
+    pub taker_fills: usize,
+    pub maker_fees: f64,
+    pub taker_fees: f64,
+}
+
+impl LiquidityStats {
+    pub fn record(&mut self, liquidity: Liquidity, fee: f64) {
+        match liquidity {
+            Liquidity::Maker => {
+                self.maker_fills += 1;
+                self.maker_fees += fee;
+            }
+            Liquidity::Taker => {
+                self.taker_fills += 1;
+                self.taker_fees += fee;
+            }
+        }
+    }
+
+    pub fn total_fees(&self) -> f64 {
+        self.maker_fees + self.taker_fees
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hftbacktest::depth::MarketDepth;
+    use hftbacktest::prelude::{Bot, OrdType, Status, TimeInForce};
+    use hftbacktest::types::{BUY_EVENT, DEPTH_EVENT, EXCH_EVENT, LOCAL_EVENT, SELL_EVENT, TRADE_EVENT};
+    use crate::common::test_utils::{backtest_from_events, event};
+    use crate::config::TICK_SIZE;
+
+    #[test]
+    fn test_minimum_fee_floor() {
+        let mut fees = FeeCalculator::new(FeeModelKind::BpsWithMinimum { min_usd: 0.05 }, 0.0001);
+        // $10 trade would be $0.001 at 1bp
+        assert!((fees.charge(10.0) - 0.05).abs() < 1e-12);
+        assert!((fees.charge(10_000.0) - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_tier_breakpoint_lowers_marginal_rate() {
+        const TIERS: &[(f64, f64)] = &[(1_000.0, 0.00005)];
+        let mut fees = FeeCalculator::new(FeeModelKind::Tiered { breakpoints: TIERS }, 0.0001);
+
+        assert!((fees.charge(800.0) - 0.08).abs() < 1e-12);
+        // 200 at the base rate + 300 past the breakpoint at the lower rate
+        assert!((fees.charge(500.0) - (0.02 + 0.015)).abs() < 1e-12);
+        assert!((fees.charge(100.0) - 0.005).abs() < 1e-12);
+        assert!((fees.cumulative_volume() - 1_400.0).abs() < 1e-9);
+        assert_eq!(fees.marginal_rate(), 0.00005);
+    }
+
+    #[test]
+    fn test_take_profit_below_round_trip_fee_is_held() {
+        let fees = FeeCalculator::new(FeeModelKind::BpsOnly, 0.0007);
+        // 5bps target vs 14bps round trip
+        assert!(!fees.clears_round_trip(0.0005, Some(0.0)));
+        assert!(fees.clears_round_trip(0.0005, None));
+        assert!(fees.clears_round_trip(0.0020, Some(0.0005)));
+        assert!(!fees.clears_round_trip(0.0018, Some(0.0005)));
+    }
+
+    #[test]
+    fn test_fee_drag_against_gross_pnl_and_capital() {
+        let mut fees = FeeCalculator::new(FeeModelKind::BpsOnly, 0.001);
+        fees.charge(20_000.0);
+        fees.charge(30_000.0);
+        assert!((fees.total_fees() - 50.0).abs() < 1e-9);
+
+        // $200 gross, $150 net after $50 of fees
+        let drag = FeeDrag::from_net(150.0, fees.total_fees(), 10_000.0);
+        assert!((drag.gross_pnl - 200.0).abs() < 1e-9);
+        assert!((drag.drag_ratio.unwrap() - 0.25).abs() < 1e-12);
+        assert!((drag.capital_pct - 0.5).abs() < 1e-12);
+
+        // A losing gross return is measured against its size
+        assert!((FeeDrag::new(-100.0, 50.0, 10_000.0).drag_ratio.unwrap() - 0.5).abs() < 1e-12);
+        assert_eq!(FeeDrag::new(0.0, 50.0, 10_000.0).drag_ratio, None);
+
+        fees.reset_total_fees();
+        assert_eq!(fees.total_fees(), 0.0);
+        assert!((fees.cumulative_volume() - 50_000.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_resting_fill_is_maker_and_crossing_fill_is_taker() {
+        let events = vec![
+            event(DEPTH_EVENT | BUY_EVENT | EXCH_EVENT | LOCAL_EVENT, 1_000, 99.0, 1.0),
+            event(DEPTH_EVENT | SELL_EVENT | EXCH_EVENT | LOCAL_EVENT, 1_000, 101.0, 1.0),
+            // A sell trade through 100 reaches the resting bid
+            event(TRADE_EVENT | SELL_EVENT | EXCH_EVENT | LOCAL_EVENT, 50_000, 99.5, 1.0),
+            event(DEPTH_EVENT | BUY_EVENT | EXCH_EVENT | LOCAL_EVENT, 100_000, 99.0, 1.0),
+        ];
+        let mut hbt = backtest_from_events(&events);
+        hbt.elapse(10_000).unwrap();
+
+        let (best_bid, best_ask) = (hbt.depth(0).best_bid_tick(), hbt.depth(0).best_ask_tick());
+        let resting_tick = (100.0 / TICK_SIZE).round() as i64;
+        hbt.submit_buy_order(0, 1, 100.0, 0.1, TimeInForce::GTC, OrdType::Limit, false).unwrap();
+        hbt.submit_buy_order(0, 2, 101.0, 0.1, TimeInForce::GTC, OrdType::Limit, false).unwrap();
+        hbt.elapse(90_000).unwrap();
+
+        let orders = hbt.orders(0);
+        let (resting, crossing) = (&orders[&1], &orders[&2]);
+        assert_eq!(resting.status, Status::Filled);
+        assert_eq!(crossing.status, Status::Filled);
+
+        let resting_crossed = Some(crosses_touch(true, resting_tick, best_bid, best_ask));
+        let crossing_crossed = Some(crosses_touch(true, best_ask, best_bid, best_ask));
+        for classification in [LiquidityClassification::Engine, LiquidityClassification::Touch] {
+            assert_eq!(classification.classify(resting, resting_crossed), Liquidity::Maker);
+            assert_eq!(classification.classify(crossing, crossing_crossed), Liquidity::Taker);
+        }
+
+        let mut stats = LiquidityStats::default();
+        stats.record(Liquidity::Maker, 10.0 * Liquidity::Maker.fee_rate(-0.0001, 0.0004));
+        stats.record(Liquidity::Taker, 10.1 * Liquidity::Taker.fee_rate(-0.0001, 0.0004));
+        assert_eq!((stats.maker_fills, stats.taker_fills), (1, 1));
+        assert!((stats.maker_fees + 0.001).abs() < 1e-12);
+        assert!((stats.taker_fees - 0.00404).abs() < 1e-12);
+    }
+}
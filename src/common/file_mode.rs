@@ -0,0 +1,19 @@
+/// How a run over several data files treats the boundary between them
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[allow(dead_code)]
+pub enum FileMode {
+    /// Every file is its own session: the position is flattened at file end and per-file
+    /// state starts over (the GUI charts too)
+    Independent,
+    /// Consecutive files are one session: the position, PnL, model and indicators carry
+    /// over and the boundary is just another gap between ticks. Working orders live in the
+    /// file's engine, so they are dropped at the boundary
+    Continuous,
+}
+
+impl FileMode {
+    /// Whether state carries from the file that just ended into `has_next_file`'s next one
+    pub fn carries_over(self, has_next_file: bool) -> bool {
+        self == FileMode::Continuous && has_next_file
+    }
+}
@@ -0,0 +1,75 @@
+/// Distribution of simulated time from order submission to its complete fill, i.e. how long
+/// quotes wait in the queue. Measured on the local clock, so it includes the backtest's
+/// order and response latency, but is dominated by queue position for resting orders
+#[derive(Debug, Clone, Default)]
+pub struct FillLatency {
+    samples_ns: Vec<i64>,
+}
+
+/// Fill latency statistics in seconds
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FillLatencySummary {
+    pub fills: usize,
+    pub mean_secs: f64,
+    pub median_secs: f64,
+    pub p95_secs: f64,
+}
+
+impl FillLatency {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record one fill; timestamps are simulated nanoseconds (negative gaps count as 0)
+    pub fn record(&mut self, submitted_ns: i64, filled_ns: i64) {
+        self.samples_ns.push((filled_ns - submitted_ns).max(0));
+    }
+
+    /// None before any fill
+    pub fn summary(&self) -> Option<FillLatencySummary> {
+        if self.samples_ns.is_empty() {
+            return None;
+        }
+        let mut sorted = self.samples_ns.clone();
+        sorted.sort_unstable();
+        let to_secs = |ns: i64| ns as f64 / 1e9;
+        // Nearest rank on the sorted samples
+        let percentile = |p: f64| to_secs(sorted[((sorted.len() - 1) as f64 * p).round() as usize]);
+
+        Some(FillLatencySummary {
+            fills: sorted.len(),
+            mean_secs: sorted.iter().map(|&ns| to_secs(ns)).sum::<f64>() / sorted.len() as f64,
+            median_secs: percentile(0.5),
+            p95_secs: percentile(0.95),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fill_two_seconds_after_submission_reports_two_seconds() {
+        let mut latency = FillLatency::new();
+        assert!(latency.summary().is_none());
+
+        latency.record(1_000_000_000, 3_000_000_000);
+        let summary = latency.summary().unwrap();
+        assert_eq!(summary.fills, 1);
+        assert_eq!(summary.mean_secs, 2.0);
+        assert_eq!(summary.median_secs, 2.0);
+        assert_eq!(summary.p95_secs, 2.0);
+
+        // The slow tail shows up in p95 but not the median
+        for _ in 0..17 {
+            latency.record(0, 1_000_000_000);
+        }
+        latency.record(0, 30_000_000_000);
+        latency.record(0, 30_000_000_000);
+        let summary = latency.summary().unwrap();
+        assert_eq!(summary.fills, 20);
+        assert_eq!(summary.median_secs, 1.0);
+        assert_eq!(summary.p95_secs, 30.0);
+    }
+}
@@ -0,0 +1,142 @@
+use hftbacktest::{
+    backtest::{Backtest, BacktestError},
+    depth::MarketDepth,
+    prelude::{Bot, OrdType, Status, TimeInForce},
+    types::ElapseResult,
+};
+use super::OrderIdGenerator;
+
+/// Outcome of `force_flatten`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FlattenResult {
+    /// Quantity actually closed (unsigned)
+    pub filled_qty: f64,
+    /// Volume-weighted fill price (0 when nothing filled)
+    pub avg_price: f64,
+    /// Signed position still open after the last attempt (0 when flat)
+    pub remaining: f64,
+    pub attempts: usize,
+}
+
+impl FlattenResult {
+    pub fn is_flat(&self) -> bool {
+        self.remaining == 0.0
+    }
+}
+
+/// Close `position` (signed) by repeatedly crossing the spread, moving the limit price
+/// `step_ticks` further through the book on every attempt until the position is confirmed flat
+/// or `max_attempts` is reached. Working orders are canceled before the next attempt. Every
+/// attempt takes a fresh ID from `order_ids`, so repeated flattens never reuse one.
+pub fn force_flatten<MD>(
+    hbt: &mut Backtest<MD>,
+    order_ids: &mut OrderIdGenerator,
+    position: f64,
+    lot_size: f64,
+    step_ticks: i64,
+    max_attempts: usize,
+    wait_timeout_ns: i64,
+) -> Result<FlattenResult, BacktestError>
+where
+    MD: MarketDepth,
+{
+    let is_sell = position > 0.0;
+    let mut remaining = position.abs();
+    let mut result = FlattenResult::default();
+    let mut notional = 0.0;
+
+    while remaining >= lot_size / 2.0 && result.attempts < max_attempts {
+        let depth = hbt.depth(0);
+        let tick_size = depth.tick_size();
+        let offset = step_ticks * result.attempts as i64;
+        let price_tick = if is_sell {
+            depth.best_bid_tick() - offset
+        } else {
+            depth.best_ask_tick() + offset
+        };
+        let price = price_tick as f64 * tick_size;
+
+        let order_id = order_ids.next_id();
+        result.attempts += 1;
+
+        if is_sell {
+            hbt.submit_sell_order(0, order_id, price, remaining, TimeInForce::GTC, OrdType::Limit, false)?;
+        } else {
+            hbt.submit_buy_order(0, order_id, price, remaining, TimeInForce::GTC, OrdType::Limit, false)?;
+        }
+        let response = hbt.wait_order_response(0, order_id, wait_timeout_ns)?;
+
+        let still_working = hbt.orders(0).get(&order_id)
+            .is_some_and(|order| matches!(order.status, Status::New | Status::PartiallyFilled));
+        if still_working {
+            hbt.cancel(0, order_id, false)?;
+            hbt.wait_order_response(0, order_id, wait_timeout_ns)?;
+        }
+
+        if let Some(order) = hbt.orders(0).get(&order_id) {
+            let filled_qty = order.qty - order.leaves_qty;
+            if filled_qty > 0.0 {
+                notional += order.exec_price_tick as f64 * order.tick_size * filled_qty;
+                result.filled_qty += filled_qty;
+                remaining -= filled_qty;
+            }
+        }
+
+        // Nothing more can fill once the feed has ended
+        if matches!(response, ElapseResult::EndOfData) {
+            break;
+        }
+    }
+
+    if result.filled_qty > 0.0 {
+        result.avg_price = notional / result.filled_qty;
+    }
+    if remaining >= lot_size / 2.0 {
+        result.remaining = if is_sell { remaining } else { -remaining };
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hftbacktest::types::{
+        DEPTH_EVENT, SELL_EVENT, EXCH_EVENT, LOCAL_EVENT,
+        EXCH_BID_DEPTH_EVENT, LOCAL_BID_DEPTH_EVENT,
+    };
+    use crate::common::test_utils::{backtest_from_events, event};
+    use crate::config::LOT_SIZE;
+
+    #[test]
+    fn test_force_flatten_converges_to_zero() {
+        // The local book still shows a bid at 99.0, but the exchange bid is already 98.0,
+        // so selling at the local touch rests and has to be walked down.
+        let events = vec![
+            event(DEPTH_EVENT | SELL_EVENT | EXCH_EVENT | LOCAL_EVENT, 1_000, 101.0, 10.0),
+            event(EXCH_BID_DEPTH_EVENT, 1_000, 98.0, 10.0),
+            event(LOCAL_BID_DEPTH_EVENT, 1_000, 99.0, 10.0),
+            event(DEPTH_EVENT | SELL_EVENT | EXCH_EVENT | LOCAL_EVENT, 10_000_000_000, 101.0, 10.0),
+        ];
+        let mut hbt = backtest_from_events(&events);
+        hbt.elapse(1_000_000).unwrap();
+
+        hbt.submit_buy_order(0, 1, 101.0, 0.5, TimeInForce::GTC, OrdType::Limit, true).unwrap();
+        assert!((hbt.position(0) - 0.5).abs() < 1e-9);
+
+        // 50,000 ticks = 0.5 per attempt: 99.0 -> 98.5 -> 98.0
+        let mut order_ids = OrderIdGenerator::in_namespace(1);
+        let result = force_flatten(&mut hbt, &mut order_ids, 0.5, LOT_SIZE, 50_000, 10, 100_000_000).unwrap();
+
+        assert!(result.is_flat());
+        assert_eq!(result.attempts, 3);
+        assert!((result.filled_qty - 0.5).abs() < 1e-9);
+        assert!((result.avg_price - 98.0).abs() < 1e-9);
+        assert!(hbt.position(0).abs() < 1e-9);
+
+        // A second flatten on the same backtest gets new IDs, with the first one's orders still there
+        hbt.submit_buy_order(0, 2, 101.0, 0.5, TimeInForce::GTC, OrdType::Limit, true).unwrap();
+        let result = force_flatten(&mut hbt, &mut order_ids, 0.5, LOT_SIZE, 50_000, 10, 100_000_000).unwrap();
+        assert!(result.is_flat());
+        assert!(hbt.position(0).abs() < 1e-9);
+    }
+}
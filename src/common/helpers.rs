@@ -4,14 +4,54 @@ use hftbacktest::depth::MarketDepth;
 pub mod helpers {
     use super::*;
 
-    /// MarketDepth에서 mid price 계산
-    pub fn calculate_mid_price<MD: MarketDepth>(depth: &MD) -> f64 {
-        let tick_size = depth.tick_size();
-        (depth.best_bid_tick() as f64 + depth.best_ask_tick() as f64) / 2.0 * tick_size
+    /// MarketDepth에서 mid price 계산, 한쪽 최우선 호가가 비었거나 수량이 0이면 None
+    pub fn calculate_mid_price<MD: MarketDepth + ?Sized>(depth: &MD) -> Option<f64> {
+        let (best_bid_tick, best_ask_tick) = (depth.best_bid_tick(), depth.best_ask_tick());
+        if best_bid_tick == i64::MIN || best_ask_tick == i64::MAX
+            || depth.best_bid_qty() <= 0.0 || depth.best_ask_qty() <= 0.0 {
+            return None;
+        }
+        Some((best_bid_tick as f64 + best_ask_tick as f64) / 2.0 * depth.tick_size())
     }
 
-    /// MarketDepth가 유효한지 확인
-    pub fn is_valid_depth<MD: MarketDepth>(depth: &MD) -> bool {
-        depth.best_bid_tick() != i64::MIN && depth.best_ask_tick() != i64::MAX
+    /// MarketDepth가 유효한지 확인 (양쪽 최우선 호가가 모두 있음)
+    pub fn is_valid_depth<MD: MarketDepth + ?Sized>(depth: &MD) -> bool {
+        calculate_mid_price(depth).is_some()
+    }
+
+    /// `elapse_ns` 간격으로 `steps`번 진행한 시뮬레이션 시간 (초, GUI 타임스탬프용)
+    pub fn sim_time_secs(steps: u64, elapse_ns: i64) -> f64 {
+        steps as f64 * (elapse_ns as f64 / 1_000_000_000.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hftbacktest::prelude::Bot;
+    use hftbacktest::types::{DEPTH_EVENT, BUY_EVENT, SELL_EVENT, EXCH_EVENT, LOCAL_EVENT};
+    use super::helpers::{calculate_mid_price, sim_time_secs};
+    use crate::common::test_utils::{backtest_from_events, event};
+
+    #[test]
+    fn test_sim_time_follows_elapse_duration() {
+        assert!((sim_time_secs(25, 100_000_000) - 2.5).abs() < 1e-12);
+        // 10ms steps: 1000 steps = 10s; 1s steps: 90 steps = 90s
+        assert!((sim_time_secs(1_000, 10_000_000) - 10.0).abs() < 1e-12);
+        assert!((sim_time_secs(90, 1_000_000_000) - 90.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_one_sided_book_has_no_mid_price() {
+        let mut hbt = backtest_from_events(&[
+            event(DEPTH_EVENT | BUY_EVENT | EXCH_EVENT | LOCAL_EVENT, 1_000, 99.0, 2.0),
+            event(DEPTH_EVENT | SELL_EVENT | EXCH_EVENT | LOCAL_EVENT, 2_000, 101.0, 1.0),
+        ]);
+        hbt.elapse(1_500).unwrap();
+        // 매도 호가가 아직 없음
+        assert_eq!(calculate_mid_price(hbt.depth(0)), None);
+
+        hbt.elapse(1_000).unwrap();
+        let mid = calculate_mid_price(hbt.depth(0)).unwrap();
+        assert!((mid - 100.0).abs() < 1e-9);
     }
 }
@@ -0,0 +1,101 @@
+/// Tilt guard: halts new entries after `max_consecutive_losses` losing trades in a row
+/// (net of fees). A win resets the streak; once halted, entries stay blocked until the run is
+/// resumed or a win from a still-open position clears it. Exits are never blocked.
+#[derive(Debug, Clone)]
+pub struct LossStreakHalt {
+    max_consecutive_losses: Option<usize>,
+    streak: usize,
+    halted: bool,
+    /// Set when the halt trips, until the runner has reported it
+    notice_pending: bool,
+}
+
+impl LossStreakHalt {
+    /// `None` = never halt
+    pub fn new(max_consecutive_losses: Option<usize>) -> Self {
+        Self {
+            max_consecutive_losses,
+            streak: 0,
+            halted: false,
+            notice_pending: false,
+        }
+    }
+
+    /// Record a closed trade's net PnL
+    pub fn record_trade(&mut self, net_pnl: f64) {
+        let Some(max) = self.max_consecutive_losses else {
+            return;
+        };
+        if net_pnl > 0.0 {
+            self.resume();
+            return;
+        }
+
+        self.streak += 1;
+        if self.streak >= max && !self.halted {
+            self.halted = true;
+            self.notice_pending = true;
+        }
+    }
+
+    pub fn allows_entry(&self) -> bool {
+        !self.halted
+    }
+
+    pub fn is_halted(&self) -> bool {
+        self.halted
+    }
+
+    #[allow(dead_code)]
+    pub fn streak(&self) -> usize {
+        self.streak
+    }
+
+    /// Message for a halt that hasn't been reported yet
+    pub fn take_halt_notice(&mut self) -> Option<String> {
+        if !std::mem::take(&mut self.notice_pending) {
+            return None;
+        }
+        Some(format!("Entries halted after {} consecutive losing trades; resume to continue", self.streak))
+    }
+
+    /// Clear the streak and lift the halt
+    pub fn resume(&mut self) {
+        self.streak = 0;
+        self.halted = false;
+        self.notice_pending = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nth_consecutive_loss_halts_and_win_resets() {
+        let mut guard = LossStreakHalt::new(Some(3));
+        guard.record_trade(-1.0);
+        guard.record_trade(-1.0);
+        // A win in between restarts the count
+        guard.record_trade(2.0);
+        assert_eq!(guard.streak(), 0);
+
+        guard.record_trade(-1.0);
+        guard.record_trade(0.0);
+        assert!(guard.allows_entry());
+        guard.record_trade(-1.0);
+        assert!(!guard.allows_entry());
+        assert!(guard.take_halt_notice().unwrap().contains("3 consecutive losing trades"));
+        assert_eq!(guard.take_halt_notice(), None);
+
+        // Closing a still-open position at a profit lifts the halt
+        guard.record_trade(1.0);
+        assert!(guard.allows_entry());
+
+        let mut disabled = LossStreakHalt::new(None);
+        for _ in 0..10 {
+            disabled.record_trade(-1.0);
+        }
+        assert!(disabled.allows_entry());
+    }
+}
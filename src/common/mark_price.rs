@@ -0,0 +1,80 @@
+use hftbacktest::{depth::MarketDepth, types::Event};
+
+use crate::common::calculate_mid_price;
+use crate::strategy::market_maker::MicroPriceCalculator;
+
+/// Price an open position is marked at for unrealized PnL, position value and equity
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[allow(dead_code)]
+pub enum MarkPrice {
+    /// Midpoint of the best bid and ask
+    Mid,
+    /// Touch microprice: weighted toward the side with less resting size, so a long into a
+    /// heavy bid marks above the mid
+    Micro,
+    /// Last market trade (the mid until the first trade is seen)
+    Last,
+}
+
+/// Marks positions per `MarkPrice`, remembering the last trade price for `Last`
+#[derive(Debug, Clone)]
+pub struct MarkPricer {
+    mode: MarkPrice,
+    last_trade: Option<f64>,
+}
+
+impl MarkPricer {
+    pub fn new(mode: MarkPrice) -> Self {
+        Self { mode, last_trade: None }
+    }
+
+    /// Feed the backtest's `last_trades` before they are cleared
+    pub fn observe_trades(&mut self, trades: &[Event]) {
+        if let Some(trade) = trades.last() {
+            self.last_trade = Some(trade.px);
+        }
+    }
+
+    /// None while the book is one-sided (and, for `Last`, before the first trade)
+    pub fn mark<MD: MarketDepth>(&self, depth: &MD) -> Option<f64> {
+        match self.mode {
+            MarkPrice::Mid => calculate_mid_price(depth),
+            MarkPrice::Micro => calculate_mid_price(depth).map(|_| MicroPriceCalculator::new(1).calculate(depth)),
+            MarkPrice::Last => self.last_trade.or_else(|| calculate_mid_price(depth)),
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.last_trade = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hftbacktest::prelude::Bot;
+    use hftbacktest::types::{DEPTH_EVENT, TRADE_EVENT, BUY_EVENT, SELL_EVENT, EXCH_EVENT, LOCAL_EVENT};
+    use crate::common::test_utils::{backtest_from_events, event};
+
+    #[test]
+    fn test_micro_mark_leans_toward_thin_side_of_skewed_book() {
+        // Heavy bid: buyers outnumber sellers 9:1 at the touch
+        let mut hbt = backtest_from_events(&[
+            event(DEPTH_EVENT | BUY_EVENT | EXCH_EVENT | LOCAL_EVENT, 1_000, 99.0, 9.0),
+            event(DEPTH_EVENT | SELL_EVENT | EXCH_EVENT | LOCAL_EVENT, 1_000, 101.0, 1.0),
+        ]);
+        hbt.elapse(10_000).unwrap();
+        let depth = hbt.depth(0);
+
+        let mid = MarkPricer::new(MarkPrice::Mid).mark(depth).unwrap();
+        let micro = MarkPricer::new(MarkPrice::Micro).mark(depth).unwrap();
+        assert!((mid - 100.0).abs() < 1e-9);
+        assert!((micro - 100.8).abs() < 1e-9);
+        assert!(micro > mid);
+
+        let mut last = MarkPricer::new(MarkPrice::Last);
+        assert_eq!(last.mark(depth), Some(mid));
+        last.observe_trades(&[event(TRADE_EVENT | SELL_EVENT | EXCH_EVENT | LOCAL_EVENT, 2_000, 99.0, 0.5)]);
+        assert_eq!(last.mark(depth), Some(99.0));
+    }
+}
@@ -1,3 +1,72 @@
 pub mod helpers;
+pub mod cvd;
+pub mod time_in_force;
+pub mod order_pricing;
+pub mod ewma;
+pub mod fees;
+pub mod flatten;
+pub mod reconcile;
+pub mod preload;
+pub mod performance_fee;
+pub mod synthetic;
+pub mod signal_delay;
+pub mod session;
+pub mod asset;
+pub mod adaptive_elapse;
+pub mod trade_throttle;
+pub mod atr;
+pub mod stop;
+pub mod run_target;
+pub mod mark_price;
+pub mod no_trade_zone;
+pub mod order_id;
+pub mod borrow_cost;
+pub mod stale_book;
+pub mod entry_window;
+pub mod loss_streak;
+pub mod partial_fill;
+pub mod run_log;
+pub mod price_impact;
+pub mod price_bucket;
+pub mod file_mode;
+pub mod annualization;
+pub mod fill_latency;
+pub mod signal_log;
+#[cfg(test)]
+pub mod test_utils;
 
 pub use helpers::helpers::*;
+pub use cvd::CvdTracker;
+pub use time_in_force::{validate_time_in_force, is_closed_unfilled};
+pub use order_pricing::{entry_price_tick, entry_ladder, summarize_ladder_fills, order_quantity, SizingMode,
+    PriceRounding, bid_price_tick, ask_price_tick};
+pub use ewma::EwmaVolatility;
+pub use fees::{FeeCalculator, FeeDrag, FeeModelKind, Liquidity, LiquidityClassification, LiquidityStats, crosses_touch};
+pub use flatten::force_flatten;
+pub use performance_fee::HighWaterMark;
+pub use preload::{FilePreloader, data_source, load_events};
+pub use reconcile::{reconcile, set_reconcile_enabled};
+pub use synthetic::{SyntheticConfig, write_synthetic_file};
+pub use signal_delay::SignalDelay;
+pub use session::SessionClock;
+pub use asset::AssetKind;
+pub use adaptive_elapse::AdaptiveElapse;
+pub use trade_throttle::TradeThrottle;
+pub use atr::AtrTracker;
+pub use stop::{StopKind, validate_stop_kind, validate_scale_out_levels};
+pub use run_target::{RunTarget, RunStopReason};
+pub use mark_price::{MarkPrice, MarkPricer};
+pub use no_trade_zone::NoTradeZone;
+pub use order_id::{OrderIdGenerator, FLATTEN_ORDER_ID_NAMESPACE};
+pub use borrow_cost::BorrowCost;
+pub use stale_book::{StaleBookGuard, StaleBookEvent, last_book_update_ns};
+pub use entry_window::EntryWindows;
+pub use loss_streak::LossStreakHalt;
+pub use partial_fill::FillTracker;
+pub use run_log::{set_log_file, close_log_file};
+pub use price_impact::impact_fill_price;
+pub use price_bucket::bucketed_book;
+pub use file_mode::FileMode;
+pub use annualization::{annualization_factor, SECONDS_PER_YEAR_24_7};
+pub use fill_latency::{FillLatency, FillLatencySummary};
+pub use signal_log::{SignalLog, set_signal_log_file, close_signal_log_file};
@@ -0,0 +1,80 @@
+/// Price-anchored re-entry block against self-churn: after an exit, entries within
+/// `zone_ticks` of the exit price are suppressed for the next `duration_ticks` strategy ticks.
+/// Unlike a time cooldown, an entry once price has moved away proceeds straight away.
+#[derive(Debug, Clone)]
+pub struct NoTradeZone {
+    /// None = off
+    zone_ticks: Option<f64>,
+    duration_ticks: usize,
+    last_exit: Option<f64>,
+    ticks_left: usize,
+}
+
+impl NoTradeZone {
+    pub fn new(zone_ticks: Option<f64>, duration_ticks: usize) -> Self {
+        Self {
+            zone_ticks,
+            duration_ticks,
+            last_exit: None,
+            ticks_left: 0,
+        }
+    }
+
+    pub fn record_exit(&mut self, price: f64) {
+        if self.zone_ticks.is_some() {
+            self.last_exit = Some(price);
+            self.ticks_left = self.duration_ticks;
+        }
+    }
+
+    /// Advance one strategy tick; the zone lifts once its duration has run out
+    pub fn tick(&mut self) {
+        if self.ticks_left > 0 {
+            self.ticks_left -= 1;
+            if self.ticks_left == 0 {
+                self.last_exit = None;
+            }
+        }
+    }
+
+    /// Whether an entry at `price` falls inside the zone around the last exit
+    pub fn blocks(&self, price: f64, tick_size: f64) -> bool {
+        match (self.zone_ticks, self.last_exit) {
+            (Some(zone_ticks), Some(exit)) if self.ticks_left > 0 => {
+                (price - exit).abs() <= zone_ticks * tick_size + tick_size * 1e-9
+            }
+            _ => false,
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.last_exit = None;
+        self.ticks_left = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_zone_blocks_near_exit_until_it_expires() {
+        let mut zone = NoTradeZone::new(Some(5.0), 3);
+        assert!(!zone.blocks(100.0, 0.01));
+
+        zone.record_exit(100.0);
+        assert!(zone.blocks(100.05, 0.01));
+        assert!(zone.blocks(99.95, 0.01));
+        assert!(!zone.blocks(100.06, 0.01));
+
+        zone.tick();
+        zone.tick();
+        assert!(zone.blocks(100.0, 0.01));
+        zone.tick();
+        assert!(!zone.blocks(100.0, 0.01));
+
+        let mut off = NoTradeZone::new(None, 3);
+        off.record_exit(100.0);
+        assert!(!off.blocks(100.0, 0.01));
+    }
+}
@@ -0,0 +1,248 @@
+use std::collections::HashMap;
+use hftbacktest::types::{Order, Status};
+
+/// 포지션 크기 해석 방식
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[allow(dead_code)]
+pub enum SizingMode {
+    /// `position_size`를 수량으로 사용
+    Quantity,
+    /// `position_size`를 달러 금액으로 보고 현재 mid price로 수량 환산
+    Notional,
+    /// `position_size`를 현재 자산 대비 비율로 보고 진입마다 `비율 * equity / mid`로 환산 (복리)
+    FractionOfEquity,
+}
+
+/// 주문 가격 → 틱 변환 방식
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[allow(dead_code)]
+pub enum PriceRounding {
+    /// 가장 가까운 틱 (좁은 시장에서 매수가가 ask 위로, 매도가가 bid 아래로 넘어갈 수 있음)
+    Nearest,
+    /// 매수는 내림, 매도는 올림 → 메이커 주문이 반올림 때문에 반대편 호가를 넘지 않음
+    Directional,
+}
+
+/// 부동소수 오차로 정확히 틱 위에 있는 가격이 한 틱 밀리지 않도록 허용하는 오차 (틱 단위)
+const TICK_ROUNDING_EPSILON: f64 = 1e-9;
+
+/// 매수 주문 가격을 틱으로 변환
+pub fn bid_price_tick(price: f64, tick_size: f64, rounding: PriceRounding) -> i64 {
+    let ticks = price / tick_size;
+    match rounding {
+        PriceRounding::Nearest => ticks.round() as i64,
+        PriceRounding::Directional => (ticks + TICK_ROUNDING_EPSILON).floor() as i64,
+    }
+}
+
+/// 매도 주문 가격을 틱으로 변환
+pub fn ask_price_tick(price: f64, tick_size: f64, rounding: PriceRounding) -> i64 {
+    let ticks = price / tick_size;
+    match rounding {
+        PriceRounding::Nearest => ticks.round() as i64,
+        PriceRounding::Directional => (ticks - TICK_ROUNDING_EPSILON).ceil() as i64,
+    }
+}
+
+/// 설정된 포지션 크기를 주문 수량으로 변환 (Notional/FractionOfEquity면 lot 단위로 반올림)
+///
+/// `equity`는 FractionOfEquity에서만 사용하는 현재 자산
+pub fn order_quantity(position_size: f64, mode: SizingMode, mid_price: f64, equity: f64, lot_size: f64) -> f64 {
+    let notional = match mode {
+        SizingMode::Quantity => return position_size,
+        SizingMode::Notional => position_size,
+        SizingMode::FractionOfEquity => position_size * equity.max(0.0),
+    };
+    if mid_price <= 0.0 {
+        return 0.0;
+    }
+    (notional / mid_price / lot_size).round() * lot_size
+}
+
+/// 진입 주문 가격(틱) 계산
+///
+/// - `None`: 반대편 최우선 호가를 가로지름 (매수 = best ask, 매도 = best bid)
+/// - `Some(n)`: 같은 편 최우선 호가 기준으로 n틱 공격적인 가격
+///   - 0이면 최우선 호가에 대기 (join the touch)
+///   - 음수면 호가 뒤쪽에 대기
+///   - 스프레드 이상이면 반대편 호가를 가로질러 더 깊게 체결
+pub fn entry_price_tick(
+    best_bid_tick: i64,
+    best_ask_tick: i64,
+    is_buy: bool,
+    aggressiveness: Option<i64>,
+) -> i64 {
+    match (aggressiveness, is_buy) {
+        (None, true) => best_ask_tick,
+        (None, false) => best_bid_tick,
+        (Some(offset), true) => best_bid_tick + offset,
+        (Some(offset), false) => best_ask_tick - offset,
+    }
+}
+
+/// 진입 주문을 여러 가격 레벨로 분할 (MM 레이어링과 같은 방식)
+///
+/// 첫 레벨은 `base_tick`, 이후 레벨은 `spacing_ticks`씩 호가 뒤쪽(매수는 아래, 매도는 위)에 배치.
+/// 수량은 lot 단위로 균등 분할하고 나머지는 마지막 레벨에 붙여 합계가 `total_qty`와 같도록 함.
+/// lot 단위로 나눌 수 없을 만큼 작으면 단일 주문으로 처리.
+pub fn entry_ladder(
+    base_tick: i64,
+    is_buy: bool,
+    total_qty: f64,
+    levels: usize,
+    spacing_ticks: i64,
+    lot_size: f64,
+) -> Vec<(i64, f64)> {
+    let levels = levels.max(1);
+    let per_level = (total_qty / levels as f64 / lot_size).floor() * lot_size;
+    if levels == 1 || per_level <= 0.0 {
+        return vec![(base_tick, total_qty)];
+    }
+
+    let direction = if is_buy { -1 } else { 1 };
+    (0..levels)
+        .map(|level| {
+            let tick = base_tick + direction * spacing_ticks * level as i64;
+            let qty = if level == levels - 1 {
+                total_qty - per_level * (levels - 1) as f64
+            } else {
+                per_level
+            };
+            (tick, qty)
+        })
+        .collect()
+}
+
+/// 분할 주문들의 체결 집계
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LadderFills {
+    pub filled_qty: f64,
+    /// 체결 수량 가중 평균 체결가 (지정가가 아닌 실제 체결가 기준, 체결이 없으면 0)
+    pub avg_price: f64,
+    pub filled_orders: usize,
+    /// 아직 살아있는 (체결/종료되지 않은) 주문 ID
+    pub working: Vec<u64>,
+}
+
+/// `order_ids`에 해당하는 주문들의 체결 수량과 평균가를 합산
+pub fn summarize_ladder_fills(orders: &HashMap<u64, Order>, order_ids: &[u64]) -> LadderFills {
+    let mut fills = LadderFills::default();
+    let mut notional = 0.0;
+
+    for order_id in order_ids {
+        let order = match orders.get(order_id) {
+            Some(order) => order,
+            None => continue,
+        };
+
+        let filled_qty = order.qty - order.leaves_qty;
+        if filled_qty > 0.0 {
+            notional += order.exec_price_tick as f64 * order.tick_size * filled_qty;
+            fills.filled_qty += filled_qty;
+            fills.filled_orders += 1;
+        }
+
+        if matches!(order.status, Status::New | Status::PartiallyFilled) {
+            fills.working.push(*order_id);
+        }
+    }
+
+    if fills.filled_qty > 0.0 {
+        fills.avg_price = notional / fills.filled_qty;
+    }
+    fills
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_directional_rounding_floors_bids_and_ceils_asks() {
+        let tick = 0.01;
+        assert_eq!(bid_price_tick(100.016, tick, PriceRounding::Directional), 10_001);
+        assert_eq!(ask_price_tick(100.014, tick, PriceRounding::Directional), 10_002);
+        assert_eq!(bid_price_tick(100.016, tick, PriceRounding::Nearest), 10_002);
+        assert_eq!(ask_price_tick(100.014, tick, PriceRounding::Nearest), 10_001);
+
+        // 이미 틱 위에 있는 가격은 부동소수 오차가 있어도 그대로
+        assert_eq!(bid_price_tick(10_001.0 * tick, tick, PriceRounding::Directional), 10_001);
+        assert_eq!(ask_price_tick(10_001.0 * tick, tick, PriceRounding::Directional), 10_001);
+        assert_eq!(bid_price_tick(0.3, 0.1, PriceRounding::Directional), 3);
+        assert_eq!(ask_price_tick(0.7, 0.1, PriceRounding::Directional), 7);
+    }
+
+    #[test]
+    fn test_entry_price_tick() {
+        // bid 100, ask 103 (3틱 스프레드)
+        assert_eq!(entry_price_tick(100, 103, true, None), 103);
+        assert_eq!(entry_price_tick(100, 103, false, None), 100);
+
+        // 0틱: 최우선 호가에 대기
+        assert_eq!(entry_price_tick(100, 103, true, Some(0)), 100);
+        assert_eq!(entry_price_tick(100, 103, false, Some(0)), 103);
+
+        assert_eq!(entry_price_tick(100, 103, true, Some(-2)), 98);
+        assert_eq!(entry_price_tick(100, 103, true, Some(4)), 104);
+    }
+
+    #[test]
+    fn test_entry_ladder_splits_total_qty() {
+        let ladder = entry_ladder(100, true, 1.0, 3, 1, 0.001);
+        assert_eq!(ladder.len(), 3);
+        assert_eq!(ladder.iter().map(|(tick, _)| *tick).collect::<Vec<_>>(), vec![100, 99, 98]);
+        let total: f64 = ladder.iter().map(|(_, qty)| qty).sum();
+        assert!((total - 1.0).abs() < 1e-9);
+
+        let ladder = entry_ladder(100, false, 1.0, 3, 2, 0.001);
+        assert_eq!(ladder.iter().map(|(tick, _)| *tick).collect::<Vec<_>>(), vec![100, 102, 104]);
+
+        // lot 단위로 나눌 수 없으면 단일 주문
+        assert_eq!(entry_ladder(100, true, 0.002, 3, 1, 0.001), vec![(100, 0.002)]);
+    }
+
+    #[test]
+    fn test_notional_sizing() {
+        assert_eq!(order_quantity(0.05, SizingMode::Quantity, 100.0, 0.0, 0.001), 0.05);
+        assert!((order_quantity(1000.0, SizingMode::Notional, 100.0, 0.0, 0.001) - 10.0).abs() < 1e-9);
+        // 1000 / 3 = 333.3333... -> lot 0.001 단위
+        assert!((order_quantity(1000.0, SizingMode::Notional, 3.0, 0.0, 0.001) - 333.333).abs() < 1e-9);
+        // 1000 / 300 = 3.33 -> lot 0.5 단위로 반올림
+        assert!((order_quantity(1000.0, SizingMode::Notional, 300.0, 0.0, 0.5) - 3.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_fraction_of_equity_sizing_compounds() {
+        // 10% of 10,000 at 100 -> 10
+        let base = order_quantity(0.1, SizingMode::FractionOfEquity, 100.0, 10_000.0, 0.001);
+        assert!((base - 10.0).abs() < 1e-9);
+
+        // 자산이 늘면 수량도 같은 비율로 증가
+        let grown = order_quantity(0.1, SizingMode::FractionOfEquity, 100.0, 15_000.0, 0.001);
+        assert!((grown / base - 1.5).abs() < 1e-9);
+
+        // 고정 수량은 자산과 무관
+        assert_eq!(order_quantity(0.1, SizingMode::Quantity, 100.0, 15_000.0, 0.001), 0.1);
+        assert_eq!(order_quantity(0.1, SizingMode::FractionOfEquity, 100.0, -50.0, 0.001), 0.0);
+    }
+
+    #[test]
+    fn test_ladder_fills_average_execution_prices() {
+        use hftbacktest::prelude::{OrdType, Side, TimeInForce};
+
+        // 호가를 넘은 지정가 두 개가 각각 최우선 호가에 체결
+        let mut orders = HashMap::new();
+        for (id, limit_tick, exec_tick) in [(1, 10_005, 10_001), (2, 10_004, 10_003)] {
+            let mut order = Order::new(id, limit_tick, 0.01, 1.0, Side::Buy, OrdType::Limit, TimeInForce::GTC);
+            order.leaves_qty = 0.0;
+            order.status = Status::Filled;
+            order.exec_price_tick = exec_tick;
+            orders.insert(id, order);
+        }
+
+        let fills = summarize_ladder_fills(&orders, &[1, 2]);
+        assert_eq!((fills.filled_qty, fills.filled_orders), (2.0, 2));
+        assert!((fills.avg_price - 100.02).abs() < 1e-9);
+        assert!(fills.working.is_empty());
+    }
+}
@@ -0,0 +1,114 @@
+use std::collections::HashMap;
+use hftbacktest::types::{Order, Status};
+use super::is_closed_unfilled;
+
+/// Quantity an order executed since it was last polled
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FillDelta {
+    pub qty: f64,
+    /// Quantity booked for the order in all, this poll included
+    pub total_qty: f64,
+    /// Price the engine last executed the order at, which is better than the limit when it
+    /// crossed the touch
+    pub price: f64,
+    /// No further fills can come: filled in full, or canceled/expired/rejected
+    pub done: bool,
+}
+
+/// Books order executions incrementally, so a partially filled order counts for what has
+/// executed while its rest stays live. With `book_partial_fills` off only a complete fill is
+/// booked (the whole order at once) and partial executions are ignored, as on an exchange
+/// model that never fills partially.
+#[derive(Debug, Clone)]
+pub struct FillTracker {
+    book_partial_fills: bool,
+    /// Quantity already booked per order that is still working
+    booked: HashMap<u64, f64>,
+}
+
+impl FillTracker {
+    pub fn new(book_partial_fills: bool) -> Self {
+        Self {
+            book_partial_fills,
+            booked: HashMap::new(),
+        }
+    }
+
+    /// New executed quantity of `order` (zero when nothing changed); the order is forgotten
+    /// once it's done, so its ID may be reused afterwards
+    pub fn poll(&mut self, order: &Order) -> FillDelta {
+        let done = order.status == Status::Filled || is_closed_unfilled(order.status);
+        let executed = if order.status == Status::Filled {
+            order.qty
+        } else if self.book_partial_fills {
+            order.qty - order.leaves_qty
+        } else {
+            0.0
+        };
+
+        let booked = if done {
+            self.booked.remove(&order.order_id).unwrap_or(0.0)
+        } else {
+            let booked = self.booked.entry(order.order_id).or_insert(0.0);
+            let previous = *booked;
+            *booked = previous.max(executed);
+            previous
+        };
+
+        let qty = (executed - booked).max(0.0);
+        FillDelta {
+            qty,
+            total_qty: booked + qty,
+            price: order.exec_price_tick as f64 * order.tick_size,
+            done,
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.booked.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hftbacktest::prelude::{OrdType, Side, TimeInForce};
+
+    fn order(qty: f64, leaves_qty: f64, status: Status) -> Order {
+        let mut order = Order::new(7, 10_000, 0.01, qty, Side::Buy, OrdType::Limit, TimeInForce::GTC);
+        order.leaves_qty = leaves_qty;
+        order.status = status;
+        order.exec_price_tick = 10_000;
+        order
+    }
+
+    #[test]
+    fn test_partial_fill_books_increments_until_done() {
+        let mut tracker = FillTracker::new(true);
+        assert_eq!(tracker.poll(&order(3.0, 3.0, Status::New)).qty, 0.0);
+
+        let first = tracker.poll(&order(3.0, 2.0, Status::PartiallyFilled));
+        assert_eq!((first.qty, first.price, first.done), (1.0, 100.0, false));
+        // Polling again without a new execution books nothing twice
+        assert_eq!(tracker.poll(&order(3.0, 2.0, Status::PartiallyFilled)).qty, 0.0);
+
+        let last = tracker.poll(&order(3.0, 0.0, Status::Filled));
+        assert_eq!((last.qty, last.done), (2.0, true));
+
+        // Canceled after more executed: the part not booked yet still counts
+        tracker.poll(&order(3.0, 2.0, Status::PartiallyFilled));
+        let canceled = tracker.poll(&order(3.0, 1.0, Status::Canceled));
+        assert_eq!((canceled.qty, canceled.total_qty, canceled.done), (1.0, 2.0, true));
+
+        // Disabled: only the complete fill counts, in one go
+        let mut full_only = FillTracker::new(false);
+        assert_eq!(full_only.poll(&order(3.0, 2.0, Status::PartiallyFilled)).qty, 0.0);
+        assert_eq!(full_only.poll(&order(3.0, 0.0, Status::Filled)).qty, 3.0);
+
+        // A limit through the touch fills at the touch, not at its limit
+        let mut crossed = order(3.0, 0.0, Status::Filled);
+        crossed.exec_price_tick = 9_990;
+        let fill = FillTracker::new(true).poll(&crossed);
+        assert_eq!((fill.qty, fill.price), (3.0, 99.9));
+    }
+}
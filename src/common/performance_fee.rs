@@ -0,0 +1,73 @@
+/// High-water-mark performance fee accrued over the equity series
+///
+/// Fed once per strategy tick so the fee doesn't depend on how often the GUI is updated. Every
+/// new equity high accrues `fee_pct` of the gain over the previous high; the accrued
+/// total is taken off the reported (net) equity. Drawdowns don't refund anything, and equity
+/// only accrues again once it climbs back above the mark.
+#[derive(Debug, Clone)]
+pub struct HighWaterMark {
+    fee_pct: f64,
+    high_water_mark: f64,
+    accrued: f64,
+    net_equity: f64,
+}
+
+impl HighWaterMark {
+    pub fn new(fee_pct: f64, initial_equity: f64) -> Self {
+        Self {
+            fee_pct,
+            high_water_mark: initial_equity,
+            accrued: 0.0,
+            net_equity: initial_equity,
+        }
+    }
+
+    /// Feed the gross equity; returns the net equity after accrued fees
+    pub fn update(&mut self, gross_equity: f64) -> f64 {
+        if gross_equity > self.high_water_mark {
+            self.accrued += self.fee_pct * (gross_equity - self.high_water_mark);
+            self.high_water_mark = gross_equity;
+        }
+        self.net_equity = gross_equity - self.accrued;
+        self.net_equity
+    }
+
+    /// Net equity as of the last `update`
+    pub fn net_equity(&self) -> f64 {
+        self.net_equity
+    }
+
+    pub fn accrued(&self) -> f64 {
+        self.accrued
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rising_equity_accrues_fee_on_total_gain() {
+        let mut hwm = HighWaterMark::new(0.2, 10_000.0);
+        let mut net = 0.0;
+        for step in 1..=10 {
+            net = hwm.update(10_000.0 + step as f64 * 50.0);
+        }
+
+        assert!((hwm.accrued() - 0.2 * 500.0).abs() < 1e-9);
+        assert!((net - (10_500.0 - 100.0)).abs() < 1e-9);
+        assert_eq!(hwm.net_equity(), net);
+    }
+
+    #[test]
+    fn test_no_fee_until_previous_high_is_recovered() {
+        let mut hwm = HighWaterMark::new(0.1, 10_000.0);
+        hwm.update(11_000.0);
+        hwm.update(10_500.0);
+        hwm.update(10_900.0);
+        assert!((hwm.accrued() - 100.0).abs() < 1e-9);
+
+        hwm.update(11_200.0);
+        assert!((hwm.accrued() - 120.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,150 @@
+use std::sync::{Arc, atomic::{AtomicBool, Ordering}};
+use std::thread::{self, JoinHandle};
+
+use crossbeam_channel::{bounded, Receiver};
+use hftbacktest::{
+    backtest::data::{read_npy_file, read_npz_file, Data, DataSource},
+    types::Event,
+};
+use crate::log_error;
+
+/// Reads the next data file on a background thread while the current file is still running,
+/// so moving to it doesn't stall on loading.
+///
+/// `Backtest` and `Data` are not `Send`, so the events are handed over as a plain `Vec` and the
+/// backtest itself is still built on the runner thread (see `data_source`). Dropping the
+/// preloader (stop, or the run ending early) discards the result without waiting: the loader
+/// thread is detached and exits once its read finishes.
+pub struct FilePreloader {
+    path: String,
+    rx: Receiver<std::io::Result<Vec<Event>>>,
+    cancelled: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl FilePreloader {
+    pub fn spawn(path: String) -> Self {
+        Self::spawn_with(path, load_events)
+    }
+
+    fn spawn_with<F>(path: String, load: F) -> Self
+    where
+        F: FnOnce(&str) -> std::io::Result<Vec<Event>> + Send + 'static,
+    {
+        let (tx, rx) = bounded(1);
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let thread_cancelled = Arc::clone(&cancelled);
+        let thread_path = path.clone();
+
+        let handle = thread::spawn(move || {
+            let result = load(&thread_path);
+            if !thread_cancelled.load(Ordering::Relaxed) {
+                let _ = tx.send(result);
+            }
+        });
+
+        Self {
+            path,
+            rx,
+            cancelled,
+            handle: Some(handle),
+        }
+    }
+
+    /// Block until the file is loaded. None if loading failed; the caller then falls back to
+    /// letting the backtest read the file itself, which reports the error.
+    pub fn wait(mut self) -> Option<Vec<Event>> {
+        let result = self.rx.recv().ok();
+        self.join();
+        match result? {
+            Ok(events) => Some(events),
+            Err(e) => {
+                log_error!("Preload failed for {}: {}", self.path, e);
+                None
+            }
+        }
+    }
+
+    fn join(&mut self) {
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl Drop for FilePreloader {
+    fn drop(&mut self) {
+        // Don't join: the read can't be interrupted, and a stop shouldn't wait for it
+        self.cancelled.store(true, Ordering::Relaxed);
+        self.handle.take();
+    }
+}
+
+/// Read every event of an `.npy`/`.npz` file into memory
+pub fn load_events(path: &str) -> std::io::Result<Vec<Event>> {
+    let data: Data<Event> = if path.ends_with(".npy") {
+        read_npy_file(path)?
+    } else {
+        read_npz_file(path, "data")?
+    };
+    Ok((0..data.len()).map(|i| data[i].clone()).collect())
+}
+
+/// Backtest data source: the preloaded events when available, otherwise the file itself
+pub fn data_source(path: &str, preloaded: Option<Vec<Event>>) -> DataSource<Event> {
+    match preloaded {
+        Some(events) => DataSource::Data(Data::from_data(&events)),
+        None => DataSource::File(path.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hftbacktest::backtest::data::write_npy;
+    use hftbacktest::types::{DEPTH_EVENT, BUY_EVENT, EXCH_EVENT, LOCAL_EVENT};
+    use crate::common::test_utils::event;
+
+    fn write_events(name: &str, events: &[Event]) -> String {
+        let path = std::env::temp_dir().join(format!("{}_{}.npy", name, std::process::id()));
+        let mut file = std::fs::File::create(&path).unwrap();
+        write_npy(&mut file, events).unwrap();
+        path.to_string_lossy().into_owned()
+    }
+
+    #[test]
+    fn test_preload_hands_over_events() {
+        let events = vec![
+            event(DEPTH_EVENT | BUY_EVENT | EXCH_EVENT | LOCAL_EVENT, 1_000, 99.0, 1.0),
+            event(DEPTH_EVENT | BUY_EVENT | EXCH_EVENT | LOCAL_EVENT, 2_000, 99.5, 2.0),
+        ];
+        let path = write_events("preload_test", &events);
+
+        let loaded = FilePreloader::spawn(path.clone()).wait();
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(loaded, Some(events));
+    }
+
+    #[test]
+    fn test_failed_preload_falls_back_to_file() {
+        assert!(FilePreloader::spawn("missing_file.npz".to_string()).wait().is_none());
+    }
+
+    #[test]
+    fn test_dropping_preloader_does_not_wait_for_slow_load() {
+        let (release_tx, release_rx) = bounded::<()>(0);
+        let (done_tx, done_rx) = bounded(1);
+        let preloader = FilePreloader::spawn_with("slow.npz".to_string(), move |_| {
+            // Stays blocked until the preloader has been dropped
+            let _ = release_rx.recv();
+            let _ = done_tx.send(());
+            Ok(Vec::new())
+        });
+
+        // Would deadlock if the drop joined the loader thread
+        drop(preloader);
+        release_tx.send(()).unwrap();
+        done_rx.recv().unwrap();
+    }
+}
@@ -0,0 +1,70 @@
+use hftbacktest::depth::MarketDepth;
+
+/// `(tick, qty)` of one side of the book summed into buckets of `bucket_ticks` ticks, `levels`
+/// buckets from the touch outward with empty ones skipped (1 = the plain per-tick book).
+/// Buckets sit on multiples of `bucket_ticks`; bids are labelled with the bucket's lowest tick
+/// and asks with its highest, so a bucket never shows a better price than it holds
+pub fn bucket_side(best_tick: i64, is_bid: bool, levels: usize, bucket_ticks: usize,
+                   qty_at: impl Fn(i64) -> f64) -> Vec<(i64, f64)> {
+    let width = bucket_ticks.max(1) as i64;
+    let first_low = if is_bid {
+        best_tick.div_euclid(width) * width
+    } else {
+        (best_tick - 1).div_euclid(width) * width + 1
+    };
+
+    (0..levels as i64)
+        .filter_map(|i| {
+            let low = if is_bid { first_low - i * width } else { first_low + i * width };
+            let qty: f64 = (low..low + width).map(&qty_at).sum();
+            let label = if is_bid { low } else { low + width - 1 };
+            (qty > 0.0).then_some((label, qty))
+        })
+        .collect()
+}
+
+/// `(price, qty)` per side, best first, in buckets of `bucket_ticks` (see `bucket_side`)
+pub fn bucketed_book<MD: MarketDepth>(depth: &MD, levels: usize, bucket_ticks: usize)
+    -> (Vec<(f64, f64)>, Vec<(f64, f64)>) {
+    let tick_size = depth.tick_size();
+    let to_prices = |side: Vec<(i64, f64)>| -> Vec<(f64, f64)> {
+        side.into_iter().map(|(tick, qty)| (tick as f64 * tick_size, qty)).collect()
+    };
+
+    let bids = match depth.best_bid_tick() {
+        i64::MIN => Vec::new(),
+        best => to_prices(bucket_side(best, true, levels, bucket_ticks, |tick| depth.bid_qty_at_tick(tick))),
+    };
+    let asks = match depth.best_ask_tick() {
+        i64::MAX => Vec::new(),
+        best => to_prices(bucket_side(best, false, levels, bucket_ticks, |tick| depth.ask_qty_at_tick(tick))),
+    };
+    (bids, asks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_five_tick_buckets_merge_levels_and_conserve_quantity() {
+        // Bids at ticks 103..=96, asks at 104..=111, 1.0 each plus a gap at 99
+        let bid_qty = |tick: i64| if (96..=103).contains(&tick) && tick != 99 { 1.0 } else { 0.0 };
+        let ask_qty = |tick: i64| if (104..=111).contains(&tick) { 1.0 } else { 0.0 };
+
+        let bids = bucket_side(103, true, 3, 5, bid_qty);
+        // [100, 104] holds 100..=103, [95, 99] holds 96..=98
+        assert_eq!(bids, vec![(100, 4.0), (95, 3.0)]);
+        let asks = bucket_side(104, false, 3, 5, ask_qty);
+        // [101, 105] holds 104..=105, [106, 110] holds all five, [111, 115] the last one
+        assert_eq!(asks, vec![(105, 2.0), (110, 5.0), (115, 1.0)]);
+
+        let total = |side: &[(i64, f64)]| side.iter().map(|(_, qty)| qty).sum::<f64>();
+        assert_eq!(total(&bids), 7.0);
+        assert_eq!(total(&asks), 8.0);
+
+        // Width 1 is the per-tick book
+        assert_eq!(bucket_side(103, true, 5, 1, bid_qty), vec![(103, 1.0), (102, 1.0), (101, 1.0), (100, 1.0)]);
+        assert_eq!(bucket_side(110, false, 2, 1, ask_qty), vec![(110, 1.0), (111, 1.0)]);
+    }
+}
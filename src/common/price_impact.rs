@@ -0,0 +1,73 @@
+use hftbacktest::depth::MarketDepth;
+
+/// Volume-weighted average price of a taker order for `qty` walking `levels` (price, qty) from
+/// the touch outward; whatever the levels don't cover fills at the deepest one (None for an
+/// empty book or a non-positive quantity)
+pub fn walk_book_vwap(levels: &[(f64, f64)], qty: f64) -> Option<f64> {
+    let &(last_price, _) = levels.last()?;
+    if qty <= 0.0 {
+        return None;
+    }
+
+    let mut remaining = qty;
+    let mut notional = 0.0;
+    for &(price, level_qty) in levels {
+        let take = remaining.min(level_qty.max(0.0));
+        notional += price * take;
+        remaining -= take;
+        if remaining <= 0.0 {
+            break;
+        }
+    }
+    notional += last_price * remaining.max(0.0);
+    Some(notional / qty)
+}
+
+/// Levels a taker order would hit within `max_ticks` of the touch: asks for a buy, bids for a
+/// sell, best first
+pub fn taker_levels<MD: MarketDepth>(depth: &MD, is_buy: bool, max_ticks: usize) -> Vec<(f64, f64)> {
+    let tick_size = depth.tick_size();
+    let (best_tick, step) = if is_buy { (depth.best_ask_tick(), 1) } else { (depth.best_bid_tick(), -1) };
+    if best_tick == i64::MAX || best_tick == i64::MIN {
+        return Vec::new();
+    }
+
+    (0..max_ticks.max(1) as i64)
+        .map(|i| best_tick + i * step)
+        .map(|tick| {
+            let qty = if is_buy { depth.ask_qty_at_tick(tick) } else { depth.bid_qty_at_tick(tick) };
+            (tick as f64 * tick_size, qty)
+        })
+        .filter(|&(_, qty)| qty > 0.0)
+        .collect()
+}
+
+/// Effective fill price of a taker order for `qty` that is larger than the touch size: the
+/// VWAP of walking the book up to `max_ticks` deep (None = it fits in the touch)
+pub fn impact_fill_price<MD: MarketDepth>(depth: &MD, is_buy: bool, qty: f64, max_ticks: usize) -> Option<f64> {
+    let levels = taker_levels(depth, is_buy, max_ticks);
+    let &(_, touch_qty) = levels.first()?;
+    if qty <= touch_qty {
+        return None;
+    }
+    walk_book_vwap(&levels, qty)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_walking_the_book_beyond_the_touch() {
+        let asks = [(100.0, 0.02), (100.01, 0.03), (100.05, 1.0)];
+        // Fits in the touch
+        assert_eq!(walk_book_vwap(&asks, 0.01), Some(100.0));
+        // 0.02 @ 100.00 + 0.03 @ 100.01 + 0.01 @ 100.05
+        let vwap = walk_book_vwap(&asks, 0.06).unwrap();
+        assert!((vwap - (2.0 + 3.0003 + 1.0005) / 0.06).abs() < 1e-9);
+        // Past the visible depth the rest fills at the deepest level
+        let vwap = walk_book_vwap(&asks[..1], 0.04).unwrap();
+        assert!((vwap - 100.0).abs() < 1e-12);
+        assert_eq!(walk_book_vwap(&[], 1.0), None);
+    }
+}
@@ -0,0 +1,112 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use hftbacktest::{
+    backtest::Backtest,
+    depth::MarketDepth,
+    prelude::Bot,
+};
+
+use crate::common::calculate_mid_price;
+use crate::{log_error, log_info, log_warn};
+use crate::config::{RECONCILE_PNL_TOLERANCE_USD, RECONCILE_POSITION_TOLERANCE};
+
+/// Set once from the `--reconcile` command-line flag
+static RECONCILE_ENABLED: AtomicBool = AtomicBool::new(false);
+
+pub fn set_reconcile_enabled(enabled: bool) {
+    RECONCILE_ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+pub fn reconcile_enabled() -> bool {
+    RECONCILE_ENABLED.load(Ordering::Relaxed)
+}
+
+/// Runner-tracked position/PnL next to the engine's own accounting
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReconcileReport {
+    pub tracked_position: f64,
+    pub engine_position: f64,
+    pub tracked_pnl: f64,
+    /// `balance + position * mid - fee` from the engine's state values
+    pub engine_pnl: f64,
+}
+
+impl ReconcileReport {
+    /// `tracked_position` is signed; `tracked_pnl` is realized plus unrealized marked at mid.
+    /// None when the book is one-sided, since the engine PnL has no mid to mark at
+    pub fn from_engine<MD>(hbt: &Backtest<MD>, tracked_position: f64, tracked_pnl: f64) -> Option<Self>
+    where
+        MD: MarketDepth,
+    {
+        let mid_price = calculate_mid_price(hbt.depth(0))?;
+        let values = hbt.state_values(0);
+
+        Some(Self {
+            tracked_position,
+            engine_position: values.position,
+            tracked_pnl,
+            engine_pnl: values.balance + values.position * mid_price - values.fee,
+        })
+    }
+
+    /// One message per field whose values disagree by more than the tolerance
+    pub fn discrepancies(&self, position_tolerance: f64, pnl_tolerance: f64) -> Vec<String> {
+        let mut messages = Vec::new();
+        if (self.tracked_position - self.engine_position).abs() > position_tolerance {
+            messages.push(format!("position tracked {:.4} vs engine {:.4}",
+                                  self.tracked_position, self.engine_position));
+        }
+        if (self.tracked_pnl - self.engine_pnl).abs() > pnl_tolerance {
+            messages.push(format!("PnL tracked ${:.4} vs engine ${:.4} (diff {:+.4})",
+                                  self.tracked_pnl, self.engine_pnl, self.tracked_pnl - self.engine_pnl));
+        }
+        messages
+    }
+}
+
+/// With `--reconcile`, compare the runner's tracked position/PnL against the engine at file
+/// end and warn about every mismatch beyond the configured tolerances
+pub fn reconcile<MD>(hbt: &Backtest<MD>, label: &str, tracked_position: f64, tracked_pnl: f64)
+where
+    MD: MarketDepth,
+{
+    if !reconcile_enabled() {
+        return;
+    }
+
+    let Some(report) = ReconcileReport::from_engine(hbt, tracked_position, tracked_pnl) else {
+        log_warn!("⚠️  [{}] Reconcile skipped: one-sided book at file end", label);
+        return;
+    };
+    let messages = report.discrepancies(RECONCILE_POSITION_TOLERANCE, RECONCILE_PNL_TOLERANCE_USD);
+    if messages.is_empty() {
+        log_info!("✓ [{}] Reconciled with engine: position {:.4}, PnL ${:.4}",
+                  label, report.engine_position, report.engine_pnl);
+    }
+    for message in messages {
+        log_error!("⚠️  [{}] Reconcile mismatch: {}", label, message);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hftbacktest::prelude::{OrdType, TimeInForce};
+    use crate::common::test_utils::{backtest_from_events, two_sided_book};
+
+    #[test]
+    fn test_report_flags_pnl_drift() {
+        let mut hbt = backtest_from_events(&two_sided_book(99.0, 101.0, 10.0, [10_000_000_000]));
+        hbt.elapse(1_000_000).unwrap();
+        hbt.submit_buy_order(0, 1, 101.0, 0.5, TimeInForce::GTC, OrdType::Limit, true).unwrap();
+
+        // Bought 0.5 @ 101, marked at mid 100
+        let report = ReconcileReport::from_engine(&hbt, 0.5, -0.5).unwrap();
+        assert!((report.engine_pnl + 0.5).abs() < 1e-9);
+        assert!(report.discrepancies(1e-6, 1e-6).is_empty());
+
+        // Sign-flipped PnL and a missed fill both show up
+        let report = ReconcileReport::from_engine(&hbt, 0.0, 0.5).unwrap();
+        assert_eq!(report.discrepancies(1e-6, 1e-6).len(), 2);
+    }
+}
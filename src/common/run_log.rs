@@ -0,0 +1,120 @@
+use std::fmt;
+use std::fs::File;
+use std::io::{LineWriter, Write};
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::Result;
+
+/// Severity of a run-log line
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogLevel {
+    Info,
+    Warn,
+    Error,
+}
+
+impl fmt::Display for LogLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            LogLevel::Info => "INFO",
+            LogLevel::Warn => "WARN",
+            LogLevel::Error => "ERROR",
+        };
+        // Padded so the messages line up
+        f.pad(label)
+    }
+}
+
+/// Set from the `--log-file` command-line flag; None = console only
+static LOG_FILE: Mutex<Option<LineWriter<File>>> = Mutex::new(None);
+
+/// Tee every following log line into `path` (created or truncated)
+pub fn set_log_file(path: &Path) -> Result<()> {
+    let file = File::create(path)?;
+    *LOG_FILE.lock().unwrap_or_else(|e| e.into_inner()) = Some(LineWriter::new(file));
+    Ok(())
+}
+
+/// Stop writing to the log file, flushing what's buffered
+pub fn close_log_file() {
+    if let Some(mut writer) = LOG_FILE.lock().unwrap_or_else(|e| e.into_inner()).take() {
+        let _ = writer.flush();
+    }
+}
+
+/// Print `args` to the console (stderr for errors) and append it to the log file, if one is
+/// set, as `<UTC timestamp> <LEVEL> <message>`. Use the `log_info!`/`log_warn!`/`log_error!`
+/// macros rather than calling this directly
+pub fn log(level: LogLevel, args: fmt::Arguments<'_>) {
+    let message = args.to_string();
+    match level {
+        LogLevel::Error => eprintln!("{}", message),
+        LogLevel::Info | LogLevel::Warn => println!("{}", message),
+    }
+
+    let mut log_file = LOG_FILE.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(writer) = log_file.as_mut() {
+        let timestamp = format_utc(SystemTime::now());
+        // Console spacing (leading blank lines, indentation) is dropped in the file
+        for line in message.lines().map(str::trim).filter(|line| !line.is_empty()) {
+            let _ = writeln!(writer, "{} {:<5} {}", timestamp, level, line);
+        }
+    }
+}
+
+/// `YYYY-MM-DDTHH:MM:SS.mmmZ`
+fn format_utc(time: SystemTime) -> String {
+    let since_epoch = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+    let secs = since_epoch.as_secs() as i64;
+    let (days, secs_of_day) = (secs.div_euclid(86_400), secs.rem_euclid(86_400));
+
+    // Civil date from days since 1970-01-01 (proleptic Gregorian)
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    format!("{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+            year, month, day, secs_of_day / 3_600, secs_of_day % 3_600 / 60, secs_of_day % 60,
+            since_epoch.subsec_millis())
+}
+
+/// Strategy event: printed to stdout and teed to the `--log-file`
+#[macro_export]
+macro_rules! log_info {
+    () => { $crate::common::run_log::log($crate::common::run_log::LogLevel::Info, format_args!("")) };
+    ($($arg:tt)*) => { $crate::common::run_log::log($crate::common::run_log::LogLevel::Info, format_args!($($arg)*)) };
+}
+
+/// Something went wrong but the run continues (see `log_info!`)
+#[macro_export]
+macro_rules! log_warn {
+    ($($arg:tt)*) => { $crate::common::run_log::log($crate::common::run_log::LogLevel::Warn, format_args!($($arg)*)) };
+}
+
+/// Error: printed to stderr and teed to the `--log-file`
+#[macro_export]
+macro_rules! log_error {
+    ($($arg:tt)*) => { $crate::common::run_log::log($crate::common::run_log::LogLevel::Error, format_args!($($arg)*)) };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_utc_timestamp_format() {
+        let time = UNIX_EPOCH + Duration::from_millis(1_709_210_096_789);
+        assert_eq!(format_utc(time), "2024-02-29T12:34:56.789Z");
+        assert_eq!(format_utc(UNIX_EPOCH), "1970-01-01T00:00:00.000Z");
+        assert_eq!(format!("{:<5}|", LogLevel::Warn), "WARN |");
+    }
+}
@@ -0,0 +1,82 @@
+use std::fmt;
+
+/// Why a run was ended before its data ran out
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RunStopReason {
+    ProfitTarget { return_pct: f64 },
+    LossLimit { return_pct: f64 },
+}
+
+impl fmt::Display for RunStopReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RunStopReason::ProfitTarget { return_pct } => {
+                write!(f, "Run profit target reached ({:+.2}%)", return_pct * 100.0)
+            }
+            RunStopReason::LossLimit { return_pct } => {
+                write!(f, "Run loss limit reached ({:+.2}%)", return_pct * 100.0)
+            }
+        }
+    }
+}
+
+/// Whole-run take-profit / loss limit on total return (e.g. a daily goal), as opposed to the
+/// per-trade stop and take levels. Both are fractions of the initial equity; None disables a side
+#[derive(Debug, Clone, Copy)]
+pub struct RunTarget {
+    profit_target_pct: Option<f64>,
+    loss_limit_pct: Option<f64>,
+}
+
+impl RunTarget {
+    pub fn new(profit_target_pct: Option<f64>, loss_limit_pct: Option<f64>) -> Self {
+        Self { profit_target_pct, loss_limit_pct }
+    }
+
+    /// Reason to stop once the return from `initial_equity` to `equity` crosses either limit
+    pub fn check(&self, initial_equity: f64, equity: f64) -> Option<RunStopReason> {
+        if initial_equity <= 0.0 {
+            return None;
+        }
+
+        let return_pct = equity / initial_equity - 1.0;
+        if self.profit_target_pct.is_some_and(|target| return_pct >= target) {
+            Some(RunStopReason::ProfitTarget { return_pct })
+        } else if self.loss_limit_pct.is_some_and(|limit| return_pct <= -limit) {
+            Some(RunStopReason::LossLimit { return_pct })
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profit_target_and_loss_limit_stop_the_run() {
+        let target = RunTarget::new(Some(0.02), Some(0.01));
+
+        assert_eq!(target.check(10_000.0, 10_100.0), None);
+        assert!(matches!(target.check(10_000.0, 10_300.0),
+                         Some(RunStopReason::ProfitTarget { return_pct }) if (return_pct - 0.03).abs() < 1e-12));
+        assert!(matches!(target.check(10_000.0, 9_800.0),
+                         Some(RunStopReason::LossLimit { return_pct }) if (return_pct + 0.02).abs() < 1e-12));
+    }
+
+    #[test]
+    fn test_no_thresholds_never_stop() {
+        let target = RunTarget::new(None, None);
+        assert_eq!(target.check(10_000.0, 100_000.0), None);
+        assert_eq!(target.check(10_000.0, 0.0), None);
+    }
+
+    #[test]
+    fn test_equity_exactly_at_threshold_stops() {
+        // Returns of exactly +50% / -25%, representable without rounding
+        let target = RunTarget::new(Some(0.5), Some(0.25));
+        assert_eq!(target.check(10_000.0, 15_000.0), Some(RunStopReason::ProfitTarget { return_pct: 0.5 }));
+        assert_eq!(target.check(10_000.0, 7_500.0), Some(RunStopReason::LossLimit { return_pct: -0.25 }));
+    }
+}
@@ -0,0 +1,56 @@
+use hftbacktest::types::Event;
+
+/// Time span of one data file, used to tell how much of the session is left
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SessionClock {
+    start_ns: i64,
+    end_ns: i64,
+}
+
+impl SessionClock {
+    /// First and last local timestamps of the events (None when empty or zero-length)
+    pub fn from_events(events: &[Event]) -> Option<Self> {
+        let start_ns = events.iter().map(|ev| ev.local_ts).min()?;
+        let end_ns = events.iter().map(|ev| ev.local_ts).max()?;
+        (end_ns > start_ns).then_some(Self { start_ns, end_ns })
+    }
+
+    /// Share of the session still ahead of `now_ns`, from 1.0 at the start to 0.0 at the end
+    pub fn remaining_fraction(&self, now_ns: i64) -> f64 {
+        let remaining = (self.end_ns - now_ns) as f64 / (self.end_ns - self.start_ns) as f64;
+        remaining.clamp(0.0, 1.0)
+    }
+
+    /// Progress through the final `fraction` of the session: None before it starts, then
+    /// 0.0 rising to 1.0 at the last event
+    pub fn wind_down_progress(&self, now_ns: i64, fraction: f64) -> Option<f64> {
+        let remaining = self.remaining_fraction(now_ns);
+        if fraction <= 0.0 || remaining > fraction {
+            return None;
+        }
+        Some(1.0 - remaining / fraction)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hftbacktest::types::{DEPTH_EVENT, BUY_EVENT, EXCH_EVENT, LOCAL_EVENT};
+    use crate::common::test_utils::event;
+
+    #[test]
+    fn test_wind_down_covers_last_fraction() {
+        let ev = DEPTH_EVENT | BUY_EVENT | EXCH_EVENT | LOCAL_EVENT;
+        let clock = SessionClock::from_events(&[event(ev, 1_000, 1.0, 1.0), event(ev, 11_000, 1.0, 1.0)]).unwrap();
+
+        assert_eq!(clock.remaining_fraction(1_000), 1.0);
+        assert_eq!(clock.remaining_fraction(6_000), 0.5);
+        assert_eq!(clock.wind_down_progress(8_000, 0.2), None);
+        assert!((clock.wind_down_progress(10_000, 0.2).unwrap() - 0.5).abs() < 1e-9);
+        assert_eq!(clock.wind_down_progress(20_000, 0.2), Some(1.0));
+        assert_eq!(clock.wind_down_progress(10_000, 0.0), None);
+
+        assert_eq!(SessionClock::from_events(&[]), None);
+        assert_eq!(SessionClock::from_events(&[event(ev, 5, 1.0, 1.0)]), None);
+    }
+}
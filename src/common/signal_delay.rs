@@ -0,0 +1,74 @@
+/// Entry confirmation delay: a signal is only acted on once it has held for `delay_ticks`
+/// further strategy ticks, so one-tick spikes that reverse don't trade. Models decision latency
+/// on top of the exchange latency; 0 passes every signal straight through.
+#[derive(Debug, Clone)]
+pub struct SignalDelay<T> {
+    delay_ticks: usize,
+    neutral: T,
+    /// Signal waiting for confirmation and how many ticks it has held since firing
+    pending: Option<(T, usize)>,
+}
+
+impl<T: Copy + PartialEq> SignalDelay<T> {
+    pub fn new(delay_ticks: usize, neutral: T) -> Self {
+        Self {
+            delay_ticks,
+            neutral,
+            pending: None,
+        }
+    }
+
+    /// Feed this tick's signal; returns it once confirmed, otherwise the neutral signal
+    pub fn confirm(&mut self, signal: T) -> T {
+        if self.delay_ticks == 0 {
+            return signal;
+        }
+        if signal == self.neutral {
+            self.pending = None;
+            return self.neutral;
+        }
+
+        match self.pending {
+            Some((pending, held)) if pending == signal => {
+                if held + 1 >= self.delay_ticks {
+                    self.pending = None;
+                    signal
+                } else {
+                    self.pending = Some((pending, held + 1));
+                    self.neutral
+                }
+            }
+            _ => {
+                self.pending = Some((signal, 0));
+                self.neutral
+            }
+        }
+    }
+
+    /// Drop any signal waiting for confirmation (e.g. once a position is open)
+    pub fn reset(&mut self) {
+        self.pending = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signal_needs_to_hold_for_delay() {
+        let mut delay = SignalDelay::new(2, 0i8);
+        assert_eq!(delay.confirm(1), 0);
+        assert_eq!(delay.confirm(1), 0);
+        assert_eq!(delay.confirm(1), 1);
+
+        // Reversal restarts the wait, neutral cancels it
+        assert_eq!(delay.confirm(1), 0);
+        assert_eq!(delay.confirm(-1), 0);
+        assert_eq!(delay.confirm(0), 0);
+        assert_eq!(delay.confirm(-1), 0);
+
+        let mut immediate = SignalDelay::new(0, 0i8);
+        assert_eq!(immediate.confirm(-1), -1);
+    }
+}
@@ -0,0 +1,176 @@
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{LineWriter, Write};
+use std::path::Path;
+use std::sync::Mutex;
+
+use anyhow::Result;
+
+/// Set from the `--signal-log` command-line flag; None = signals aren't logged
+static SIGNAL_LOG: Mutex<Option<LineWriter<File>>> = Mutex::new(None);
+
+const HEADER: &str = "strategy,timestamp_ns,signal,value,mid_price,forward_return";
+
+/// Write every following signal row to `path` as CSV (created or truncated)
+pub fn set_signal_log_file(path: &Path) -> Result<()> {
+    let mut writer = LineWriter::new(File::create(path)?);
+    writeln!(writer, "{}", HEADER)?;
+    *SIGNAL_LOG.lock().unwrap_or_else(|e| e.into_inner()) = Some(writer);
+    Ok(())
+}
+
+/// Stop writing the signal log, flushing what's buffered
+pub fn close_signal_log_file() {
+    if let Some(mut writer) = SIGNAL_LOG.lock().unwrap_or_else(|e| e.into_inner()).take() {
+        let _ = writer.flush();
+    }
+}
+
+fn signal_log_enabled() -> bool {
+    SIGNAL_LOG.lock().unwrap_or_else(|e| e.into_inner()).is_some()
+}
+
+/// One generated signal and the mid return over the following `horizon_ticks` steps
+#[derive(Debug, Clone, PartialEq)]
+pub struct SignalRow {
+    pub timestamp_ns: i64,
+    pub signal: &'static str,
+    /// Indicator or prediction value behind the signal
+    pub value: f64,
+    pub mid_price: f64,
+    /// `forward_mid / mid_price - 1`; None when the file ended before the horizon
+    pub forward_return: Option<f64>,
+}
+
+impl SignalRow {
+    fn csv_line(&self, strategy: &str) -> String {
+        let forward_return = self.forward_return.map(|r| r.to_string()).unwrap_or_default();
+        format!("{},{},{},{},{},{}", strategy, self.timestamp_ns, self.signal, self.value,
+                self.mid_price, forward_return)
+    }
+}
+
+/// Per-runner hook into the `--signal-log` stream: `record` every signal the strategy
+/// generates, traded or not, and `observe_mid` once per step; a row is written when its
+/// forward return is known, `horizon_ticks` observations after the signal. Does nothing when
+/// no signal log is set
+#[derive(Debug)]
+pub struct SignalLog {
+    strategy: String,
+    horizon_ticks: usize,
+    enabled: bool,
+    ticks: u64,
+    /// (tick the forward return is taken at, row), oldest first
+    pending: VecDeque<(u64, SignalRow)>,
+}
+
+impl SignalLog {
+    pub fn new(strategy: &str, horizon_ticks: usize) -> Self {
+        Self::with_enabled(strategy, horizon_ticks, signal_log_enabled())
+    }
+
+    fn with_enabled(strategy: &str, horizon_ticks: usize, enabled: bool) -> Self {
+        Self {
+            strategy: strategy.to_string(),
+            horizon_ticks,
+            enabled,
+            ticks: 0,
+            pending: VecDeque::new(),
+        }
+    }
+
+    /// False without `--signal-log`, so callers can skip computing signals only the log needs
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn record(&mut self, timestamp_ns: i64, signal: &'static str, value: f64, mid_price: f64) {
+        if !self.enabled {
+            return;
+        }
+        let row = SignalRow { timestamp_ns, signal, value, mid_price, forward_return: None };
+        self.pending.push_back((self.ticks + self.horizon_ticks as u64, row));
+    }
+
+    pub fn observe_mid(&mut self, mid_price: f64) {
+        if !self.enabled {
+            return;
+        }
+        let rows = self.advance(mid_price);
+        self.write(&rows);
+    }
+
+    /// Write the signals still short of their horizon without a forward return, e.g. at the
+    /// end of a file whose mids don't continue into the next
+    pub fn flush(&mut self) {
+        let rows: Vec<_> = self.pending.drain(..).map(|(_, row)| row).collect();
+        self.write(&rows);
+        self.ticks = 0;
+    }
+
+    /// Rows whose horizon ends at this observation
+    fn advance(&mut self, mid_price: f64) -> Vec<SignalRow> {
+        self.ticks += 1;
+        let mut completed = Vec::new();
+        while self.pending.front().is_some_and(|(due, _)| *due <= self.ticks) {
+            let (_, mut row) = self.pending.pop_front().unwrap();
+            if row.mid_price > 0.0 {
+                row.forward_return = Some(mid_price / row.mid_price - 1.0);
+            }
+            completed.push(row);
+        }
+        completed
+    }
+
+    fn write(&self, rows: &[SignalRow]) {
+        if rows.is_empty() {
+            return;
+        }
+        let mut signal_log = SIGNAL_LOG.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(writer) = signal_log.as_mut() {
+            for row in rows {
+                let _ = writeln!(writer, "{}", row.csv_line(&self.strategy));
+            }
+        }
+    }
+}
+
+impl Drop for SignalLog {
+    fn drop(&mut self) {
+        self.flush();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_one_row_per_signal_with_forward_return_after_horizon() {
+        let mut log = SignalLog::with_enabled("Test", 3, true);
+        let mids = [100.0, 101.0, 102.0, 103.0, 104.0, 105.0];
+        let mut rows = Vec::new();
+
+        for (i, &mid) in mids.iter().enumerate() {
+            rows.extend(log.advance(mid));
+            // Signals at the first and second steps, traded or not
+            if i < 2 {
+                log.record(i as i64, if i == 0 { "long" } else { "short" }, 0.5, mid);
+            }
+        }
+
+        assert_eq!(rows.len(), 2);
+        // 3 steps after 100 is 103, 3 after 101 is 104
+        assert_eq!(rows[0].signal, "long");
+        assert!((rows[0].forward_return.unwrap() - 0.03).abs() < 1e-12);
+        assert_eq!(rows[1].signal, "short");
+        assert!((rows[1].forward_return.unwrap() - (104.0 / 101.0 - 1.0)).abs() < 1e-12);
+        assert_eq!(rows[1].csv_line("Test"), format!("Test,1,short,0.5,101,{}", 104.0 / 101.0 - 1.0));
+
+        // A signal the data ends before resolving stays pending with no forward return
+        log.record(6, "long", 0.5, 105.0);
+        assert!(log.advance(105.0).is_empty());
+        assert_eq!(log.pending.len(), 1);
+        assert!(log.pending[0].1.forward_return.is_none());
+    }
+}
@@ -0,0 +1,96 @@
+use hftbacktest::{
+    backtest::Backtest,
+    depth::MarketDepth,
+    prelude::Bot,
+};
+
+/// Transition reported by `StaleBookGuard::update`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StaleBookEvent {
+    /// No book update for `gap_ns` (longer than the limit); trading is suspended from here
+    Stale { gap_ns: i64 },
+    /// A new update arrived after a gap of `gap_ns` between it and the previous one
+    Resumed { gap_ns: i64 },
+}
+
+/// Data-gap detector: once the last market-data update is older than `max_staleness_ns` the
+/// book is treated as stale and trading is suspended until fresh data arrives, so nothing is
+/// decided (or marked) on a frozen book.
+#[derive(Debug, Clone)]
+pub struct StaleBookGuard {
+    max_staleness_ns: Option<i64>,
+    flatten_on_stale: bool,
+    /// Timestamp of the last update before the current gap (None = book is fresh)
+    stale_since: Option<i64>,
+}
+
+impl StaleBookGuard {
+    /// `None` = never stale
+    pub fn new(max_staleness_ns: Option<i64>, flatten_on_stale: bool) -> Self {
+        Self {
+            max_staleness_ns,
+            flatten_on_stale,
+            stale_since: None,
+        }
+    }
+
+    /// Compare the last update time with the simulation clock; returns the transition, if any
+    pub fn update(&mut self, last_update_ns: Option<i64>, now_ns: i64) -> Option<StaleBookEvent> {
+        let max_staleness_ns = self.max_staleness_ns?;
+        let last_update_ns = last_update_ns?;
+        let gap_ns = now_ns - last_update_ns;
+
+        match self.stale_since {
+            None if gap_ns > max_staleness_ns => {
+                self.stale_since = Some(last_update_ns);
+                Some(StaleBookEvent::Stale { gap_ns })
+            }
+            Some(since) if gap_ns <= max_staleness_ns => {
+                self.stale_since = None;
+                Some(StaleBookEvent::Resumed { gap_ns: last_update_ns - since })
+            }
+            _ => None,
+        }
+    }
+
+    pub fn is_stale(&self) -> bool {
+        self.stale_since.is_some()
+    }
+
+    /// Whether open positions should be closed when the book goes stale
+    pub fn flattens(&self) -> bool {
+        self.flatten_on_stale
+    }
+
+    pub fn reset(&mut self) {
+        self.stale_since = None;
+    }
+}
+
+/// Local timestamp of the last market-data event the engine delivered (None before the first)
+pub fn last_book_update_ns<MD: MarketDepth>(hbt: &Backtest<MD>) -> Option<i64> {
+    hbt.feed_latency(0).map(|(_, local_ts)| local_ts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gap_suspends_until_fresh_update() {
+        let mut guard = StaleBookGuard::new(Some(1_000), false);
+        assert_eq!(guard.update(Some(0), 500), None);
+        assert!(!guard.is_stale());
+
+        assert_eq!(guard.update(Some(0), 1_500), Some(StaleBookEvent::Stale { gap_ns: 1_500 }));
+        assert!(guard.is_stale());
+        assert_eq!(guard.update(Some(0), 5_000), None);
+
+        assert_eq!(guard.update(Some(6_000), 6_100), Some(StaleBookEvent::Resumed { gap_ns: 6_000 }));
+        assert!(!guard.is_stale());
+
+        let mut disabled = StaleBookGuard::new(None, false);
+        assert_eq!(disabled.update(Some(0), i64::MAX), None);
+        assert!(!disabled.is_stale());
+    }
+}
@@ -0,0 +1,66 @@
+use anyhow::{bail, Result};
+
+/// How far from entry the stop-loss sits
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[allow(dead_code)]
+pub enum StopKind {
+    /// Fixed fraction of the entry price (the strategy's `stop_loss_pct`)
+    Percent,
+    /// Fixed number of ticks from the entry price
+    Ticks(f64),
+    /// Multiple of the rolling average true range (see `AtrTracker`), so the stop follows
+    /// the instrument's volatility
+    Atr { multiplier: f64 },
+}
+
+impl StopKind {
+    /// Stop distance as a fraction of `entry_price`. An ATR stop falls back to `stop_loss_pct`
+    /// until the ATR has warmed up
+    pub fn stop_pct(&self, entry_price: f64, stop_loss_pct: f64, tick_size: f64, atr: Option<f64>) -> f64 {
+        if entry_price <= 0.0 {
+            return stop_loss_pct;
+        }
+        match *self {
+            StopKind::Percent => stop_loss_pct,
+            StopKind::Ticks(ticks) => ticks * tick_size / entry_price,
+            StopKind::Atr { multiplier } => atr.map_or(stop_loss_pct, |atr| multiplier * atr / entry_price),
+        }
+    }
+}
+
+pub fn validate_stop_kind(kind: StopKind) -> Result<()> {
+    match kind {
+        StopKind::Ticks(ticks) if ticks <= 0.0 => bail!("Tick stop distance must be positive, got {}", ticks),
+        StopKind::Atr { multiplier } if multiplier <= 0.0 => {
+            bail!("ATR stop multiplier must be positive, got {}", multiplier)
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Scale-out levels are (profit_pct, fraction_to_close) pairs: profits positive and strictly
+/// increasing, fractions of the opened size in (0, 1] adding up to at most 1
+pub fn validate_scale_out_levels(levels: &[(f64, f64)], trail_pct: f64) -> Result<()> {
+    if levels.is_empty() {
+        return Ok(());
+    }
+    if trail_pct <= 0.0 {
+        bail!("Scale-out trailing stop must be positive, got {}", trail_pct);
+    }
+    let mut last_profit_pct = 0.0;
+    let mut total_fraction = 0.0;
+    for &(profit_pct, fraction) in levels {
+        if profit_pct <= last_profit_pct {
+            bail!("Scale-out profit levels must be positive and increasing, got {} after {}", profit_pct, last_profit_pct);
+        }
+        if fraction <= 0.0 || fraction > 1.0 {
+            bail!("Scale-out fraction must be in (0, 1], got {}", fraction);
+        }
+        last_profit_pct = profit_pct;
+        total_fraction += fraction;
+    }
+    if total_fraction > 1.0 + 1e-9 {
+        bail!("Scale-out fractions add up to {:.2}, more than the whole position", total_fraction);
+    }
+    Ok(())
+}
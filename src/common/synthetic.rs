@@ -0,0 +1,238 @@
+use std::path::Path;
+
+use anyhow::Result;
+use hftbacktest::{
+    backtest::data::{write_npy, Data, DataSource},
+    types::{Event, DEPTH_EVENT, TRADE_EVENT, BUY_EVENT, SELL_EVENT, EXCH_EVENT, LOCAL_EVENT},
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::config::{
+    TICK_SIZE, LOT_SIZE, SYNTHETIC_SEED, SYNTHETIC_INITIAL_PRICE, SYNTHETIC_DRIFT,
+    SYNTHETIC_VOLATILITY, SYNTHETIC_DURATION_NS, SYNTHETIC_STEP_NS, SYNTHETIC_SPREAD_TICKS,
+};
+
+const SECONDS_PER_YEAR: f64 = 365.0 * 24.0 * 3600.0;
+
+/// Parameters of the random-walk book generated by `generate_events`
+#[derive(Debug, Clone)]
+pub struct SyntheticConfig {
+    pub seed: u64,
+    pub initial_price: f64,
+    /// Annualized GBM drift
+    pub drift: f64,
+    /// Annualized GBM volatility
+    pub volatility: f64,
+    pub tick_size: f64,
+    pub lot_size: f64,
+    pub duration_ns: i64,
+    /// Time between book updates
+    pub step_ns: i64,
+    /// Quoted spread in calm steps; occasionally widens by up to the same amount again
+    pub spread_ticks: i64,
+    /// Price levels per book side
+    pub depth_levels: usize,
+    /// Quantity at the touch; deeper levels grow linearly
+    pub level_qty: f64,
+}
+
+impl Default for SyntheticConfig {
+    fn default() -> Self {
+        Self {
+            seed: SYNTHETIC_SEED,
+            initial_price: SYNTHETIC_INITIAL_PRICE,
+            drift: SYNTHETIC_DRIFT,
+            volatility: SYNTHETIC_VOLATILITY,
+            tick_size: TICK_SIZE,
+            lot_size: LOT_SIZE,
+            duration_ns: SYNTHETIC_DURATION_NS,
+            step_ns: SYNTHETIC_STEP_NS,
+            spread_ticks: SYNTHETIC_SPREAD_TICKS,
+            depth_levels: 20,
+            level_qty: 1.0,
+        }
+    }
+}
+
+/// Seeded GBM mid-price path, one value per step (including the start)
+pub fn gbm_path(config: &SyntheticConfig, rng: &mut StdRng) -> Vec<f64> {
+    let steps = (config.duration_ns / config.step_ns).max(0) as usize;
+    let dt = config.step_ns as f64 / 1_000_000_000.0 / SECONDS_PER_YEAR;
+    let drift = (config.drift - 0.5 * config.volatility * config.volatility) * dt;
+    let diffusion = config.volatility * dt.sqrt();
+
+    let mut path = Vec::with_capacity(steps + 1);
+    let mut price = config.initial_price;
+    path.push(price);
+    for _ in 0..steps {
+        price *= (drift + diffusion * standard_normal(rng)).exp();
+        path.push(price);
+    }
+    path
+}
+
+/// L2 depth and trade events following `gbm_path`: every step re-quotes `depth_levels` per side
+/// around the tick-rounded mid, removes levels that fell out of range, and prints one trade at
+/// a random touch
+pub fn generate_events(config: &SyntheticConfig) -> Vec<Event> {
+    let mut rng = StdRng::seed_from_u64(config.seed);
+    let path = gbm_path(config, &mut rng);
+    let levels = config.depth_levels.max(1) as i64;
+
+    let mut events = Vec::with_capacity(path.len() * (4 * levels as usize + 1));
+    let mut prev_touch: Option<(i64, i64)> = None;
+
+    for (step, mid) in path.into_iter().enumerate() {
+        let ts = (step as i64 + 1) * config.step_ns;
+        let spread_ticks = config.spread_ticks.max(1) + if rng.gen_bool(0.1) {
+            rng.gen_range(0..=config.spread_ticks.max(1))
+        } else {
+            0
+        };
+        let bid_tick = (mid / config.tick_size - spread_ticks as f64 / 2.0).round() as i64;
+        let ask_tick = bid_tick + spread_ticks;
+
+        if let Some((prev_bid, prev_ask)) = prev_touch {
+            for tick in (prev_bid - levels + 1)..=prev_bid {
+                if tick > bid_tick || tick <= bid_tick - levels {
+                    events.push(depth_event(BUY_EVENT, ts, tick, config.tick_size, 0.0));
+                }
+            }
+            for tick in prev_ask..(prev_ask + levels) {
+                if tick < ask_tick || tick >= ask_tick + levels {
+                    events.push(depth_event(SELL_EVENT, ts, tick, config.tick_size, 0.0));
+                }
+            }
+        }
+
+        for level in 0..levels {
+            let qty = round_to_lot(config.level_qty * (1.0 + level as f64), config.lot_size);
+            events.push(depth_event(BUY_EVENT, ts, bid_tick - level, config.tick_size, qty));
+            events.push(depth_event(SELL_EVENT, ts, ask_tick + level, config.tick_size, qty));
+        }
+
+        let (side, tick) = if rng.gen_bool(0.5) { (BUY_EVENT, ask_tick) } else { (SELL_EVENT, bid_tick) };
+        let trade_qty = round_to_lot(config.level_qty * rng.gen_range(0.1..1.0), config.lot_size);
+        events.push(Event {
+            ev: TRADE_EVENT | side | EXCH_EVENT | LOCAL_EVENT,
+            exch_ts: ts,
+            local_ts: ts,
+            px: tick as f64 * config.tick_size,
+            qty: trade_qty,
+            order_id: 0,
+            ival: 0,
+            fval: 0.0,
+        });
+
+        prev_touch = Some((bid_tick, ask_tick));
+    }
+
+    events
+}
+
+/// In-memory backtest data for `config`
+#[allow(dead_code)]
+pub fn synthetic_data_source(config: &SyntheticConfig) -> DataSource<Event> {
+    DataSource::Data(Data::from_data(&generate_events(config)))
+}
+
+/// Generate `config` into an `.npy` file the runners can load like any recorded data file
+pub fn write_synthetic_file(config: &SyntheticConfig, path: &Path) -> Result<()> {
+    let events = generate_events(config);
+    let mut file = std::fs::File::create(path)?;
+    write_npy(&mut file, &events)?;
+    Ok(())
+}
+
+fn depth_event(side: u64, ts: i64, tick: i64, tick_size: f64, qty: f64) -> Event {
+    Event {
+        ev: DEPTH_EVENT | side | EXCH_EVENT | LOCAL_EVENT,
+        exch_ts: ts,
+        local_ts: ts,
+        px: tick as f64 * tick_size,
+        qty,
+        order_id: 0,
+        ival: 0,
+        fval: 0.0,
+    }
+}
+
+fn round_to_lot(qty: f64, lot_size: f64) -> f64 {
+    ((qty / lot_size).round() * lot_size).max(lot_size)
+}
+
+/// Box-Muller transform
+fn standard_normal(rng: &mut StdRng) -> f64 {
+    let u1: f64 = rng.gen_range(f64::EPSILON..1.0);
+    let u2: f64 = rng.gen();
+    (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> SyntheticConfig {
+        SyntheticConfig {
+            seed: 7,
+            initial_price: 100.0,
+            drift: 0.0,
+            volatility: 2.0,
+            tick_size: 0.01,
+            lot_size: 0.001,
+            duration_ns: 20_000 * 1_000_000_000,
+            step_ns: 1_000_000_000,
+            spread_ticks: 1,
+            depth_levels: 5,
+            level_qty: 1.0,
+        }
+    }
+
+    #[test]
+    fn test_prices_on_tick_and_realized_volatility_matches() {
+        let config = config();
+        let events = generate_events(&config);
+
+        assert!(events.windows(2).all(|pair| pair[0].exch_ts <= pair[1].exch_ts));
+        for ev in &events {
+            let ticks = ev.px / config.tick_size;
+            assert!((ticks - ticks.round()).abs() < 1e-6, "price {} is off tick", ev.px);
+        }
+
+        // Realized volatility of the generating path, annualized
+        let mut rng = StdRng::seed_from_u64(config.seed);
+        let path = gbm_path(&config, &mut rng);
+        let returns: Vec<f64> = path.windows(2).map(|pair| (pair[1] / pair[0]).ln()).collect();
+        let mean = returns.iter().sum::<f64>() / returns.len() as f64;
+        let variance = returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / (returns.len() - 1) as f64;
+        let dt = config.step_ns as f64 / 1_000_000_000.0 / SECONDS_PER_YEAR;
+        let realized = (variance / dt).sqrt();
+
+        assert!((realized / config.volatility - 1.0).abs() < 0.05, "realized vol {}", realized);
+    }
+
+    #[test]
+    fn test_same_seed_same_events() {
+        let mut config = config();
+        config.duration_ns = 100 * config.step_ns;
+        assert_eq!(generate_events(&config), generate_events(&config));
+        let first = generate_events(&config);
+        config.seed += 1;
+        assert_ne!(generate_events(&config), first);
+    }
+
+    #[test]
+    fn test_default_series_drives_a_backtest() {
+        use hftbacktest::{depth::MarketDepth, prelude::Bot};
+        use crate::common::test_utils::backtest_from_events;
+
+        let config = SyntheticConfig { duration_ns: 100 * SYNTHETIC_STEP_NS, ..SyntheticConfig::default() };
+        let mut hbt = backtest_from_events(&generate_events(&config));
+        hbt.elapse(50 * SYNTHETIC_STEP_NS).unwrap();
+
+        let depth = hbt.depth(0);
+        assert!(depth.best_bid_tick() < depth.best_ask_tick());
+        let mid = (depth.best_bid() + depth.best_ask()) / 2.0;
+        assert!((mid / config.initial_price - 1.0).abs() < 0.01);
+    }
+}
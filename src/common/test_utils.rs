@@ -0,0 +1,58 @@
+use hftbacktest::{
+    backtest::{Backtest, ExchangeKind, L2AssetBuilder, assettype::LinearAsset,
+        data::{Data, DataSource}, models::{CommonFees, ConstantLatency, ProbQueueModel,
+        PowerProbQueueFunc3, TradingValueFeeModel}},
+    prelude::HashMapMarketDepth,
+    types::{Event, DEPTH_EVENT, BUY_EVENT, SELL_EVENT, EXCH_EVENT, LOCAL_EVENT},
+};
+use crate::config::{TICK_SIZE, LOT_SIZE};
+
+/// 테스트용 이벤트 생성
+pub fn event(ev: u64, ts: i64, px: f64, qty: f64) -> Event {
+    Event {
+        ev,
+        exch_ts: ts,
+        local_ts: ts,
+        px,
+        qty,
+        order_id: 0,
+        ival: 0,
+        fval: 0.0,
+    }
+}
+
+/// 1µs에 `bid`/`ask` 양방향 호가(각 `qty`), 이후 `refresh_ts` 시점마다 같은 매수호가를 다시
+/// 게시해 데이터가 일찍 끝나지 않게 함
+pub fn two_sided_book(bid: f64, ask: f64, qty: f64, refresh_ts: impl IntoIterator<Item = i64>) -> Vec<Event> {
+    let bid_event = DEPTH_EVENT | BUY_EVENT | EXCH_EVENT | LOCAL_EVENT;
+    let mut events = vec![
+        event(bid_event, 1_000, bid, qty),
+        event(DEPTH_EVENT | SELL_EVENT | EXCH_EVENT | LOCAL_EVENT, 1_000, ask, qty),
+    ];
+    events.extend(refresh_ts.into_iter().map(|ts| event(bid_event, ts, bid, qty)));
+    events
+}
+
+/// 메모리 이벤트로 백테스트 생성 (수수료 0, 지연 1µs)
+pub fn backtest_from_events(events: &[Event]) -> Backtest<HashMapMarketDepth> {
+    backtest_from_events_on(events, ExchangeKind::NoPartialFillExchange)
+}
+
+/// 거래소 모델을 지정해 백테스트 생성 (부분 체결 테스트용)
+pub fn backtest_from_events_on(events: &[Event], exchange: ExchangeKind) -> Backtest<HashMapMarketDepth> {
+    let asset = L2AssetBuilder::new()
+        .data(vec![DataSource::Data(Data::from_data(events))])
+        .exchange(exchange)
+        .latency_model(ConstantLatency::new(1_000, 1_000))
+        .fee_model(TradingValueFeeModel::new(CommonFees::new(0.0, 0.0)))
+        .queue_model(ProbQueueModel::new(PowerProbQueueFunc3::new(3.0)))
+        .asset_type(LinearAsset::new(1.0))
+        .depth(|| HashMapMarketDepth::new(TICK_SIZE, LOT_SIZE))
+        .build()
+        .unwrap();
+
+    Backtest::builder()
+        .add_asset(asset)
+        .build()
+        .unwrap()
+}
@@ -0,0 +1,48 @@
+use anyhow::{bail, Result};
+use hftbacktest::backtest::ExchangeKind;
+use hftbacktest::prelude::{Status, TimeInForce};
+
+/// 거래소 모델별 지원 TIF (hftbacktest 0.8.1 기준)
+///
+/// - `NoPartialFillExchange`: GTC, GTX, IOC
+///   - IOC는 즉시 체결되지 않으면 Expired 처리된다.
+///   - FOK는 IOC와 동일하게 처리되어 "전량 체결" 조건이 보장되지 않으므로 거부한다.
+/// - `PartialFillExchange`: GTC, GTX, IOC, FOK
+pub fn validate_time_in_force(exchange: &ExchangeKind, tif: TimeInForce) -> Result<()> {
+    let supported = match exchange {
+        ExchangeKind::NoPartialFillExchange => {
+            matches!(tif, TimeInForce::GTC | TimeInForce::GTX | TimeInForce::IOC)
+        }
+        ExchangeKind::PartialFillExchange => {
+            matches!(tif, TimeInForce::GTC | TimeInForce::GTX | TimeInForce::IOC | TimeInForce::FOK)
+        }
+    };
+
+    if !supported {
+        let exchange_name = match exchange {
+            ExchangeKind::NoPartialFillExchange => "NoPartialFillExchange",
+            ExchangeKind::PartialFillExchange => "PartialFillExchange",
+        };
+        bail!("Time-in-force {:?} is not supported by {}", tif, exchange_name);
+    }
+    Ok(())
+}
+
+/// 체결 없이 종료된 주문인지 확인 (IOC 미체결, GTX 거부 등)
+pub fn is_closed_unfilled(status: Status) -> bool {
+    matches!(status, Status::Expired | Status::Canceled | Status::Rejected)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_time_in_force() {
+        assert!(validate_time_in_force(&ExchangeKind::NoPartialFillExchange, TimeInForce::GTC).is_ok());
+        assert!(validate_time_in_force(&ExchangeKind::NoPartialFillExchange, TimeInForce::IOC).is_ok());
+        assert!(validate_time_in_force(&ExchangeKind::NoPartialFillExchange, TimeInForce::FOK).is_err());
+        assert!(validate_time_in_force(&ExchangeKind::PartialFillExchange, TimeInForce::FOK).is_ok());
+        assert!(validate_time_in_force(&ExchangeKind::PartialFillExchange, TimeInForce::Unsupported).is_err());
+    }
+}
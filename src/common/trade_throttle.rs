@@ -0,0 +1,66 @@
+use std::collections::VecDeque;
+
+const MINUTE_NS: i64 = 60_000_000_000;
+
+/// Sliding one-minute cap on new entries, in simulation time. Only entries are counted and
+/// blocked; exits always go through so a throttled runner can still get flat.
+#[derive(Debug, Clone)]
+pub struct TradeThrottle {
+    max_per_minute: Option<usize>,
+    /// Timestamps (ns) of the entries inside the current window, oldest first
+    entries: VecDeque<i64>,
+}
+
+impl TradeThrottle {
+    /// `None` = unlimited
+    pub fn new(max_per_minute: Option<usize>) -> Self {
+        Self {
+            max_per_minute,
+            entries: VecDeque::new(),
+        }
+    }
+
+    /// Whether an entry at `now_ns` stays within the limit
+    pub fn allows_entry(&mut self, now_ns: i64) -> bool {
+        let Some(max) = self.max_per_minute else {
+            return true;
+        };
+        while self.entries.front().is_some_and(|&ts| now_ns - ts >= MINUTE_NS) {
+            self.entries.pop_front();
+        }
+        self.entries.len() < max
+    }
+
+    pub fn record_entry(&mut self, now_ns: i64) {
+        if self.max_per_minute.is_some() {
+            self.entries.push_back(now_ns);
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.entries.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_window_slides_in_simulation_time() {
+        let mut throttle = TradeThrottle::new(Some(2));
+        for ts in [0, 10_000_000_000] {
+            assert!(throttle.allows_entry(ts));
+            throttle.record_entry(ts);
+        }
+        assert!(!throttle.allows_entry(59_999_999_999));
+        // The first entry leaves the window a minute after it was made
+        assert!(throttle.allows_entry(MINUTE_NS));
+
+        let mut unlimited = TradeThrottle::new(None);
+        for ts in 0..100 {
+            assert!(unlimited.allows_entry(ts));
+            unlimited.record_entry(ts);
+        }
+    }
+}
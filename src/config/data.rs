@@ -5,3 +5,27 @@ const DEFAULT_DATA_FILE_PATH: &str = r"";
 pub fn get_data_file_path() -> String {
     env::var("DATA_FILE_PATH").unwrap_or_else(|_| DEFAULT_DATA_FILE_PATH.to_string())
 }
+
+// `--synthetic` random-walk data (see common::synthetic)
+pub const SYNTHETIC_SEED: u64 = 42;
+pub const SYNTHETIC_INITIAL_PRICE: f64 = 1.0;
+/// Annualized GBM drift and volatility
+pub const SYNTHETIC_DRIFT: f64 = 0.0;
+pub const SYNTHETIC_VOLATILITY: f64 = 0.8;
+/// One hour of book updates every 100ms
+pub const SYNTHETIC_DURATION_NS: i64 = 3_600_000_000_000;
+pub const SYNTHETIC_STEP_NS: i64 = 100_000_000;
+pub const SYNTHETIC_SPREAD_TICKS: i64 = 2;
+
+// `--dump-depth` order book export (see strategy::depth_dump)
+/// Price levels per side written in each snapshot
+pub const DEPTH_DUMP_LEVELS: usize = 10;
+/// Size after which the dump continues in a new numbered file
+pub const DEPTH_DUMP_MAX_FILE_BYTES: u64 = 512 * 1024 * 1024;
+/// Check each snapshot's book for ordering violations, non-positive sizes and a crossed
+/// touch, reporting the cumulative size over this many levels (None = no report)
+pub const DEPTH_DUMP_CONSISTENCY_LEVELS: Option<usize> = Some(5);
+
+// `--signal-log` signal export (see common::signal_log)
+/// Steps after each signal its forward mid return is measured at
+pub const SIGNAL_LOG_HORIZON_TICKS: usize = 10;
@@ -1,3 +1,13 @@
+use hftbacktest::prelude::TimeInForce;
+use crate::common::{FileMode, SizingMode, StopKind};
+use super::ELAPSE_DURATION_NS;
+
+// Shared indicator configuration
+/// Decay factor (lambda) for the EWMA volatility used by the MM risk manager and the ML features
+pub const VOLATILITY_EWMA_LAMBDA: f64 = 0.94;
+/// Tick distance between child orders when a taker entry is split across levels
+pub const ENTRY_LEVEL_SPACING_TICKS: i64 = 1;
+
 // Market Making Strategy Configuration
 #[derive(Debug, Clone)]
 #[allow(dead_code)]
@@ -9,7 +19,17 @@ pub struct MarketMakerConfig {
     pub order_size: f64,
     pub depth_levels: usize,
     pub order_layers: usize,
+    pub layer_spacing_ticks: f64,
     pub fixed_spread_ticks: f64,
+    pub imbalance_sensitivity: f64,
+    pub min_quote_spread_ticks: Option<i64>,
+    pub clamp_crossed_quotes: bool,
+    pub wind_down_fraction: f64,
+    pub volatility_min_samples: usize,
+    pub requote_on_price_move: bool,
+    pub requote_threshold_ticks: f64,
+    pub elapse_duration_ns: i64,
+    pub time_in_force: TimeInForce,
 }
 
 impl Default for MarketMakerConfig {
@@ -22,7 +42,17 @@ impl Default for MarketMakerConfig {
             order_size: 0.01,
             depth_levels: 20,
             order_layers: 2,
+            layer_spacing_ticks: 1.0,
             fixed_spread_ticks: 10.0,
+            imbalance_sensitivity: 0.1,
+            min_quote_spread_ticks: None,
+            clamp_crossed_quotes: true,
+            wind_down_fraction: 0.0,
+            volatility_min_samples: 20,
+            requote_on_price_move: false,
+            requote_threshold_ticks: 2.0,
+            elapse_duration_ns: ELAPSE_DURATION_NS,
+            time_in_force: TimeInForce::GTX,
         }
     }
 }
@@ -30,40 +60,182 @@ impl Default for MarketMakerConfig {
 pub const GAMMA: f64 = 0.001;
 pub const INITIAL_KAPPA: f64 = 0.1;
 pub const MAX_INVENTORY: f64 = 5.0;
+/// (soft, hard) |inventory| bands: quote normally up to soft, shrink the side that would grow
+/// the position linearly to zero between soft and hard, and stop quoting it from hard on
+/// (None = both sides always quoted)
+pub const MM_INVENTORY_BANDS: Option<(f64, f64)> = None;
 pub const VOLATILITY_THRESHOLD: f64 = 5.0;
 pub const ORDER_SIZE: f64 = 0.01;
 pub const DEPTH_LEVELS: usize = 20;
 pub const ORDER_LAYERS: usize = 2;
+/// Ticks between consecutive quote layers (layer n sits n * spacing ticks further out)
+pub const MM_LAYER_SPACING_TICKS: f64 = 1.0;
 pub const FIXED_SPREAD_TICKS: f64 = 10.0;
+/// Fraction of the half spread the quotes are skewed by at full book imbalance (must be >= 0)
+pub const MM_IMBALANCE_SENSITIVITY: f64 = 0.1;
+/// Exponent `n` of the MM backtest's power queue model; also used for the expected fill rate
+pub const MM_QUEUE_POWER: f64 = 3.0;
+/// Never quote inside the touch and skip a layer whose bid/ask would be fewer than this many
+/// ticks apart (None = quote wherever the fair spread lands)
+pub const MM_MIN_QUOTE_SPREAD_TICKS: Option<i64> = None;
+/// Widen the quoted half spread to at least the round-trip maker fee per unit plus this many
+/// ticks of expected adverse selection, so quotes never sit at a structural loss (None = off)
+pub const MM_ADVERSE_SELECTION_BUFFER_TICKS: Option<f64> = None;
+/// Audit each quote before it goes out: a bid at or above the best ask (or an ask at or below
+/// the best bid) would be rejected as post-only, leaving the layer empty, so it is logged and
+/// pulled back to the near touch instead (false = submit as computed)
+pub const MM_CLAMP_CROSSED_QUOTES: bool = true;
+/// Last fraction of each file (by event time) in which the MM stops adding inventory: it only
+/// quotes the side that reduces it, moving that quote toward crossing as the file end nears
+/// (0.0 = off, must be below 1.0)
+pub const MM_WIND_DOWN_FRACTION: f64 = 0.0;
+pub const MM_TIME_IN_FORCE: TimeInForce = TimeInForce::GTX;
+/// Price returns the MM's volatility estimate warms up on before the first quotes go out, so
+/// the spread isn't priced off a near-zero volatility (0 = quote right away)
+pub const MM_VOLATILITY_MIN_SAMPLES: usize = 20;
+/// Event-driven requoting: pull and replace the quotes as soon as the reservation price has
+/// moved more than MM_REQUOTE_THRESHOLD_TICKS since they were priced, instead of waiting for
+/// a fill or expiry (false = requote only on fills/expiries every UPDATE_INTERVAL)
+pub const MM_REQUOTE_ON_PRICE_MOVE: bool = false;
+pub const MM_REQUOTE_THRESHOLD_TICKS: f64 = 2.0;
+/// Simulated time per backtest step; smaller = finer fills/queue simulation, larger = faster runs
+pub const MM_ELAPSE_DURATION_NS: i64 = ELAPSE_DURATION_NS;
 
 // Momentum Strategy Configuration
 #[derive(Debug, Clone)]
 #[allow(dead_code)]
 pub struct MomentumConfig {
     pub lookback_period: usize,
+    pub short_lookback_period: Option<usize>,
+    pub require_timeframe_confirmation: bool,
+    pub confirmation_lookback_period: usize,
     pub momentum_threshold: f64,
     pub position_size: f64,
     pub stop_loss_pct: f64,
+    pub stop_kind: StopKind,
     pub take_profit_pct: f64,
+    pub time_in_force: TimeInForce,
+    pub entry_aggressiveness: Option<i64>,
+    pub entry_levels: usize,
+    pub sizing_mode: SizingMode,
+    pub size_scaling_max: Option<f64>,
+    pub confidence_scale: f64,
+    pub min_confidence: f64,
+    pub order_timeout_ns: i64,
+    pub max_requotes: usize,
+    pub signal_delay_ticks: usize,
+    pub max_trades_per_minute: Option<usize>,
+    pub max_consecutive_losses: Option<usize>,
+    pub no_trade_zone_ticks: Option<f64>,
+    pub no_trade_zone_duration: usize,
+    pub dry_run: bool,
+    pub elapse_duration_ns: i64,
 }
 
 impl Default for MomentumConfig {
     fn default() -> Self {
         Self {
             lookback_period: 100,
+            short_lookback_period: None,
+            require_timeframe_confirmation: false,
+            confirmation_lookback_period: 300,
             momentum_threshold: 0.002,
             position_size: 0.05,
             stop_loss_pct: 0.01,
+            stop_kind: StopKind::Percent,
             take_profit_pct: 0.02,
+            time_in_force: TimeInForce::GTC,
+            entry_aggressiveness: None,
+            entry_levels: 1,
+            sizing_mode: SizingMode::Quantity,
+            size_scaling_max: None,
+            confidence_scale: 3.0,
+            min_confidence: 0.0,
+            order_timeout_ns: 1_000_000_000,
+            max_requotes: 0,
+            signal_delay_ticks: 0,
+            max_trades_per_minute: None,
+            max_consecutive_losses: None,
+            no_trade_zone_ticks: None,
+            no_trade_zone_duration: 100,
+            dry_run: false,
+            elapse_duration_ns: ELAPSE_DURATION_NS,
         }
     }
 }
 
 pub const MOMENTUM_LOOKBACK_PERIOD: usize = 100;
+/// Short window for dual-lookback momentum (None = single lookback over MOMENTUM_LOOKBACK_PERIOD)
+pub const MOMENTUM_SHORT_LOOKBACK_PERIOD: Option<usize> = None;
+/// Only enter when a second, longer-lookback indicator agrees in direction and clears the same
+/// threshold; exits still follow the primary indicator alone
+pub const MOMENTUM_REQUIRE_TIMEFRAME_CONFIRMATION: bool = false;
+pub const MOMENTUM_CONFIRMATION_LOOKBACK_PERIOD: usize = 300;
 pub const MOMENTUM_THRESHOLD: f64 = 0.002;
 pub const MOMENTUM_POSITION_SIZE: f64 = 0.05;
 pub const MOMENTUM_STOP_LOSS_PCT: f64 = 0.01;
+/// Stop distance: Percent = MOMENTUM_STOP_LOSS_PCT of entry, Ticks(n) = n ticks from entry,
+/// Atr { multiplier } = multiple of the rolling ATR (percent stop until the ATR has warmed up)
+pub const MOMENTUM_STOP_KIND: StopKind = StopKind::Percent;
+/// Price samples per ATR bar and bars averaged, shared by the ATR-based stops
+pub const ATR_BAR_TICKS: usize = 100;
+pub const ATR_PERIOD: usize = 14;
 pub const MOMENTUM_TAKE_PROFIT_PCT: f64 = 0.02;
+/// Volatility-scaled take-profit: each entry's target is
+/// `MOMENTUM_TAKE_PROFIT_PCT * (1 + k * ATR / entry price)`, fixed for the trade. The base
+/// target applies until the ATR has warmed up (None = fixed take-profit, must be >= 0)
+pub const MOMENTUM_TAKE_PROFIT_VOL_K: Option<f64> = None;
+/// Scale-out exits as (profit_pct, fraction_to_close) pairs, fractions of the opened size.
+/// Each level fires once per position; once the first has, the rest of the position runs with
+/// a trailing stop MOMENTUM_SCALE_OUT_TRAIL_PCT behind the best price since. Non-empty levels
+/// replace the all-or-nothing MOMENTUM_TAKE_PROFIT_PCT (empty = off)
+pub const MOMENTUM_SCALE_OUT_LEVELS: &[(f64, f64)] = &[];
+pub const MOMENTUM_SCALE_OUT_TRAIL_PCT: f64 = 0.005;
+pub const MOMENTUM_TIME_IN_FORCE: TimeInForce = TimeInForce::GTC;
+/// Entry price offset in ticks from the same-side touch (None = cross the spread,
+/// 0 = rest at the best price, negative = rest behind it, >= spread = cross deeper)
+pub const MOMENTUM_ENTRY_AGGRESSIVENESS: Option<i64> = None;
+/// Number of price levels the entry is split across (1 = single order at the entry price)
+pub const MOMENTUM_ENTRY_LEVELS: usize = 1;
+/// How `position_size` is read: Quantity = base units, Notional = USD converted at the mid price,
+/// FractionOfEquity = fraction of current equity converted at the mid price (compounds)
+pub const MOMENTUM_SIZING_MODE: SizingMode = SizingMode::Quantity;
+/// Scale each entry by |momentum| / threshold, capped at this multiple of `position_size`
+/// (None = fixed size on every entry)
+pub const MOMENTUM_SIZE_SCALING_MAX: Option<f64> = None;
+/// Signal confidence is |momentum| / (MOMENTUM_CONFIDENCE_SCALE x threshold), clamped to [0, 1];
+/// entries below MOMENTUM_MIN_CONFIDENCE are skipped (0.0 = trade every signal)
+pub const MOMENTUM_CONFIDENCE_SCALE: f64 = 3.0;
+pub const MOMENTUM_MIN_CONFIDENCE: f64 = 0.0;
+/// Times an entry left unfilled after `PASSIVE_ENTRY_TIMEOUT_NS` is canceled and resubmitted
+/// at a fresh price before giving up (0 = no requote)
+pub const MOMENTUM_MAX_REQUOTES: usize = 0;
+/// Strategy ticks an entry signal must keep holding before the runner acts on it
+/// (0 = enter on the tick the signal fires). Separate from `UPDATE_INTERVAL`
+pub const MOMENTUM_SIGNAL_DELAY_TICKS: usize = 0;
+/// Strategy ticks a position must be held before anything but the hard stop-loss may close it
+/// (reversals, take-profit, scale-outs, trailing stop), so signal flicker doesn't pay a round
+/// trip of fees for nothing. Forced exits (stale book, end of data) are unaffected (0 = off)
+pub const MOMENTUM_MIN_HOLD_TICKS: usize = 0;
+/// Only enter on a fresh signal: the signal must have changed direction (e.g. Neutral -> Long)
+/// since the last entry, so a persistent signal doesn't re-enter the same exhausted move right
+/// after a stop-out
+pub const MOMENTUM_REQUIRE_SIGNAL_TRANSITION: bool = false;
+/// Cap on new entries per minute of simulation time, modelling exchange rate limits; entries
+/// over the cap are skipped, exits are never held back (None = unlimited)
+pub const MOMENTUM_MAX_TRADES_PER_MINUTE: Option<usize> = None;
+/// Halt new entries after this many losing trades in a row (net of fees), pausing the run until
+/// it is resumed; a win resets the streak. Separate from the whole-run loss limit (None = off)
+pub const MOMENTUM_MAX_CONSECUTIVE_LOSSES: Option<usize> = None;
+/// After an exit, skip entries within this many ticks of the exit price (None = off) for
+/// the next MOMENTUM_NO_TRADE_ZONE_DURATION strategy ticks; curbs enter-reverse-re-enter churn
+pub const MOMENTUM_NO_TRADE_ZONE_TICKS: Option<f64> = None;
+pub const MOMENTUM_NO_TRADE_ZONE_DURATION: usize = 100;
+/// Signals-only run: entries/exits are logged and filled hypothetically at the mid price,
+/// no orders reach the backtest engine
+pub const MOMENTUM_DRY_RUN: bool = false;
+/// Simulated time per backtest step (see MM_ELAPSE_DURATION_NS)
+pub const MOMENTUM_ELAPSE_DURATION_NS: i64 = ELAPSE_DURATION_NS;
 
 // ML Prediction Strategy Configuration
 #[derive(Debug, Clone)]
@@ -71,9 +243,39 @@ pub const MOMENTUM_TAKE_PROFIT_PCT: f64 = 0.02;
 pub struct PredictionConfig {
     pub position_size: f64,
     pub stop_loss_pct: f64,
+    pub stop_kind: StopKind,
     pub take_profit_pct: f64,
     pub min_prediction_confidence: f64,
+    pub exit_threshold: Option<f64>,
     pub learning_rate: f64,
+    pub include_cvd: bool,
+    pub lag_depth: usize,
+    pub min_feature_levels: usize,
+    pub time_in_force: TimeInForce,
+    pub entry_aggressiveness: Option<i64>,
+    pub entry_levels: usize,
+    pub sizing_mode: SizingMode,
+    pub order_timeout_ns: i64,
+    pub max_requotes: usize,
+    pub reset_between_files: bool,
+    pub breakeven_trigger_pct: Option<f64>,
+    pub signal_delay_ticks: usize,
+    pub max_trades_per_minute: Option<usize>,
+    pub max_consecutive_losses: Option<usize>,
+    pub no_trade_zone_ticks: Option<f64>,
+    pub no_trade_zone_duration: usize,
+    pub bad_update_lr_decay: f64,
+    pub max_bad_updates: usize,
+    pub early_stopping_patience: Option<usize>,
+    pub ensemble_size: usize,
+    pub regime_accuracy_floor: Option<f64>,
+    pub regime_accuracy_window: usize,
+    pub regime_volatility_jump: Option<f64>,
+    pub warm_restart_lr_multiplier: f64,
+    pub warm_restart_batches: usize,
+    pub warm_restart_normalization_samples: Option<usize>,
+    pub dry_run: bool,
+    pub elapse_duration_ns: i64,
 }
 
 impl Default for PredictionConfig {
@@ -81,15 +283,187 @@ impl Default for PredictionConfig {
         Self {
             position_size: 0.05,
             stop_loss_pct: 0.005,
+            stop_kind: StopKind::Percent,
             take_profit_pct: 0.01,
             min_prediction_confidence: 0.001,
+            exit_threshold: None,
             learning_rate: 0.001,
+            include_cvd: true,
+            lag_depth: 0,
+            min_feature_levels: 1,
+            time_in_force: TimeInForce::GTC,
+            entry_aggressiveness: None,
+            entry_levels: 1,
+            sizing_mode: SizingMode::Quantity,
+            order_timeout_ns: 1_000_000_000,
+            max_requotes: 0,
+            reset_between_files: false,
+            breakeven_trigger_pct: None,
+            signal_delay_ticks: 0,
+            max_trades_per_minute: None,
+            max_consecutive_losses: None,
+            no_trade_zone_ticks: None,
+            no_trade_zone_duration: 100,
+            bad_update_lr_decay: 0.5,
+            max_bad_updates: 10,
+            early_stopping_patience: None,
+            ensemble_size: 1,
+            regime_accuracy_floor: None,
+            regime_accuracy_window: 200,
+            regime_volatility_jump: None,
+            warm_restart_lr_multiplier: 3.0,
+            warm_restart_batches: 20,
+            warm_restart_normalization_samples: Some(100),
+            dry_run: false,
+            elapse_duration_ns: ELAPSE_DURATION_NS,
         }
     }
 }
 
 pub const PREDICTION_POSITION_SIZE: f64 = 0.05;
 pub const PREDICTION_STOP_LOSS_PCT: f64 = 0.005;
+/// Stop distance (see MOMENTUM_STOP_KIND)
+pub const PREDICTION_STOP_KIND: StopKind = StopKind::Percent;
 pub const PREDICTION_TAKE_PROFIT_PCT: f64 = 0.01;
 pub const PREDICTION_CONFIDENCE_THRESHOLD: f64 = 0.001;
 pub const PREDICTION_LEARNING_RATE: f64 = 0.001;
+/// Opposite prediction needed to close an open position; must be >= the entry threshold
+/// (`PREDICTION_CONFIDENCE_THRESHOLD`). None = same as entry (symmetric)
+pub const PREDICTION_EXIT_THRESHOLD: Option<f64> = None;
+/// Also close once the prediction in the held direction decays below this, i.e. the edge has
+/// faded even without a reversal (None = only reversals and stops close)
+pub const PREDICTION_EDGE_EXIT_THRESHOLD: Option<f64> = None;
+/// ε-greedy exploration: each entry decision takes a random direction instead of the model's
+/// signal with probability ε, which is multiplied by the decay after every decision. Uses
+/// PREDICTION_SEED, so explored runs are reproducible (0 = always follow the model)
+pub const PREDICTION_EXPLORATION_EPSILON: f64 = 0.0;
+pub const PREDICTION_EXPLORATION_DECAY: f64 = 0.999;
+pub const PREDICTION_INCLUDE_CVD: bool = true;
+/// Past ticks whose feature vectors are appended to the current one (model input grows by
+/// a factor of lag + 1; 0 = current snapshot only)
+pub const PREDICTION_FEATURE_LAG_DEPTH: usize = 0;
+/// The bid/ask pressure ratio is clamped to [1/R, R] before its log, so a nearly empty side
+/// gives at most ±ln(R) instead of an extreme feature value
+pub const PREDICTION_PRESSURE_RATIO_BOUND: f64 = 100.0;
+/// Skip ticks (no prediction, no training) where either side of the book has fewer than this
+/// many non-empty levels within the 10 scanned, so thin books don't produce garbage features
+pub const PREDICTION_MIN_FEATURE_LEVELS: usize = 1;
+pub const PREDICTION_TIME_IN_FORCE: TimeInForce = TimeInForce::GTC;
+/// Entry price offset in ticks (see MOMENTUM_ENTRY_AGGRESSIVENESS)
+pub const PREDICTION_ENTRY_AGGRESSIVENESS: Option<i64> = None;
+/// Number of price levels the entry is split across (see MOMENTUM_ENTRY_LEVELS)
+pub const PREDICTION_ENTRY_LEVELS: usize = 1;
+/// How `position_size` is read (see MOMENTUM_SIZING_MODE)
+pub const PREDICTION_SIZING_MODE: SizingMode = SizingMode::Quantity;
+/// Entry requotes after a timeout (see MOMENTUM_MAX_REQUOTES)
+pub const PREDICTION_MAX_REQUOTES: usize = 0;
+/// Reset the model, normalization and trade/prediction counters at each file start.
+/// When false the trained model carries over and metrics accumulate across files.
+pub const PREDICTION_RESET_BETWEEN_FILES: bool = false;
+/// `Continuous` runs the selected files as one session: no flatten at file end, the model,
+/// indicators, position and PnL carry over and the GUI keeps its charts (overrides
+/// PREDICTION_RESET_BETWEEN_FILES). `Independent` = each file on its own
+pub const PREDICTION_FILE_MODE: FileMode = FileMode::Independent;
+/// Freeze the model once it has warmed up: no more training samples, batches or normalization
+/// updates, so the rest of the run trades a fixed network (a file reset unfreezes it)
+pub const PREDICTION_FREEZE_AFTER_WARMUP: bool = false;
+/// Weight-init seed of the prediction model (ensemble member i uses seed + i); `--ml-cv` runs
+/// seeds from here upward (None = random init, `--ml-cv` starts at 0)
+pub const PREDICTION_SEED: Option<u64> = None;
+/// Move the stop to break-even (entry + fees) once unrealized PnL reaches this fraction (None = off)
+pub const PREDICTION_BREAKEVEN_TRIGGER_PCT: Option<f64> = None;
+/// Entry confirmation delay in strategy ticks (see MOMENTUM_SIGNAL_DELAY_TICKS)
+pub const PREDICTION_SIGNAL_DELAY_TICKS: usize = 0;
+/// Entry rate limit (see MOMENTUM_MAX_TRADES_PER_MINUTE)
+pub const PREDICTION_MAX_TRADES_PER_MINUTE: Option<usize> = None;
+/// Losing-streak entry halt (see MOMENTUM_MAX_CONSECUTIVE_LOSSES)
+pub const PREDICTION_MAX_CONSECUTIVE_LOSSES: Option<usize> = None;
+/// Re-entry block around the last exit price (see MOMENTUM_NO_TRADE_ZONE_TICKS)
+pub const PREDICTION_NO_TRADE_ZONE_TICKS: Option<f64> = None;
+pub const PREDICTION_NO_TRADE_ZONE_DURATION: usize = 100;
+/// Learning-rate multiplier applied each time a training step is skipped for a NaN/Inf loss
+/// (1.0 = keep the learning rate)
+pub const PREDICTION_BAD_UPDATE_LR_DECAY: f64 = 0.5;
+/// Consecutive NaN/Inf training steps or predictions after which the run is paused with an
+/// error (0 = never pause)
+pub const PREDICTION_MAX_BAD_UPDATES: usize = 10;
+/// Stop training once the pre-update loss of this many consecutive batches hasn't beaten the
+/// best seen; training resumes when the loss degrades past that best (None = always train)
+pub const PREDICTION_EARLY_STOPPING_PATIENCE: Option<usize> = None;
+/// Independently initialized models voting on each prediction; the majority direction is
+/// traded (1 = single model)
+pub const PREDICTION_ENSEMBLE_SIZE: usize = 1;
+/// Regime-change warm restart: fires when directional accuracy over the last
+/// PREDICTION_REGIME_ACCURACY_WINDOW verified predictions drops below the floor, or when realized
+/// volatility jumps past this multiple of its slow baseline (None disables each trigger)
+pub const PREDICTION_REGIME_ACCURACY_FLOOR: Option<f64> = None;
+pub const PREDICTION_REGIME_ACCURACY_WINDOW: usize = 200;
+pub const PREDICTION_REGIME_VOLATILITY_JUMP: Option<f64> = None;
+/// On a warm restart the learning rate is multiplied by this for the next
+/// PREDICTION_WARM_RESTART_BATCHES training batches, and the feature normalization statistics
+/// are cut back to this many effective samples so they re-adapt (None = keep them)
+pub const PREDICTION_WARM_RESTART_LR_MULTIPLIER: f64 = 3.0;
+pub const PREDICTION_WARM_RESTART_BATCHES: usize = 20;
+pub const PREDICTION_WARM_RESTART_NORMALIZATION_SAMPLES: Option<usize> = Some(100);
+/// Signals-only run (see MOMENTUM_DRY_RUN)
+pub const PREDICTION_DRY_RUN: bool = false;
+/// Simulated time per backtest step (see MM_ELAPSE_DURATION_NS)
+pub const PREDICTION_ELAPSE_DURATION_NS: i64 = ELAPSE_DURATION_NS;
+
+// CVD Divergence Strategy Configuration
+#[derive(Debug, Clone)]
+#[allow(dead_code)]
+pub struct CvdConfig {
+    pub lookback_period: usize,
+    pub price_threshold: f64,
+    pub position_size: f64,
+    pub stop_loss_pct: f64,
+    pub take_profit_pct: f64,
+    pub time_in_force: TimeInForce,
+    pub sizing_mode: SizingMode,
+    pub elapse_duration_ns: i64,
+}
+
+impl Default for CvdConfig {
+    fn default() -> Self {
+        Self {
+            lookback_period: 50,
+            price_threshold: 0.0005,
+            position_size: 0.05,
+            stop_loss_pct: 0.005,
+            take_profit_pct: 0.01,
+            time_in_force: TimeInForce::GTC,
+            sizing_mode: SizingMode::Quantity,
+            elapse_duration_ns: ELAPSE_DURATION_NS,
+        }
+    }
+}
+
+pub const CVD_LOOKBACK_PERIOD: usize = 50;
+pub const CVD_PRICE_THRESHOLD: f64 = 0.0005;
+pub const CVD_POSITION_SIZE: f64 = 0.05;
+pub const CVD_STOP_LOSS_PCT: f64 = 0.005;
+pub const CVD_TAKE_PROFIT_PCT: f64 = 0.01;
+pub const CVD_TIME_IN_FORCE: TimeInForce = TimeInForce::GTC;
+/// How `position_size` is read (see MOMENTUM_SIZING_MODE)
+pub const CVD_SIZING_MODE: SizingMode = SizingMode::Quantity;
+/// Simulated time per backtest step (see MM_ELAPSE_DURATION_NS)
+pub const CVD_ELAPSE_DURATION_NS: i64 = ELAPSE_DURATION_NS;
+
+// Portfolio Configuration
+/// Strategy run as one sleeve of the portfolio. Only `Strategy` trait implementations can be
+/// pooled: `MomentumRunner`, `PredictionRunner` and `MarketMakerRunner` drive their own backtest
+/// loop and don't implement it, so they can't be sleeves yet
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PortfolioSleeve {
+    /// CVD divergence with the CVD_* settings above
+    Cvd,
+    /// The template strategy from `strategy::base::example_strategy`
+    Example { position_size: f64, threshold: f64 },
+}
+
+/// Sleeves and the fraction of the portfolio capital each gets; the fractions must sum to 1
+pub const PORTFOLIO_ALLOCATIONS: &[(PortfolioSleeve, f64)] = &[
+    (PortfolioSleeve::Cvd, 0.5),
+    (PortfolioSleeve::Example { position_size: 0.05, threshold: 0.5 }, 0.5),
+];
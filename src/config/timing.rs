@@ -1,8 +1,81 @@
+use crate::common::SECONDS_PER_YEAR_24_7;
+
 /// Time duration to elapse per iteration (100ms in nanoseconds)
 pub const ELAPSE_DURATION_NS: i64 = 100_000_000;
 
+/// Adaptive stepping: after this many consecutive steps with an unchanged top of book the
+/// elapse duration doubles, up to `ADAPTIVE_ELAPSE_MAX_MULTIPLIER` x the runner's base, and
+/// drops back on the next change (0 = fixed stepping)
+pub const ADAPTIVE_ELAPSE_QUIET_TICKS: usize = 0;
+pub const ADAPTIVE_ELAPSE_MAX_MULTIPLIER: i64 = 16;
+
 /// Update strategy every N ticks
 pub const UPDATE_INTERVAL: usize = 10;
 
+/// Runner -> GUI update queue length; when the GUI falls behind, the oldest queued update is
+/// replaced by the newest
+pub const GUI_DATA_CHANNEL_CAPACITY: usize = 256;
+
+/// Also send a GUI update right after every fill, so equity steps line up with the trade
+pub const GUI_UPDATE_ON_FILL: bool = true;
+
+/// Pause the run with an error instead of sending an update whose equity, PnL, position or mid
+/// is NaN/Inf, which would blank the charts (false = send it anyway)
+pub const GUI_PAUSE_ON_NON_FINITE: bool = true;
+
+/// Overlay a buy-and-hold benchmark on the equity chart and show the alpha over it (toggle in settings)
+pub const GUI_SHOW_BUY_AND_HOLD: bool = true;
+
+/// Stream every GUI update of each run to this CSV (timestamp, equity, PnL, position, mid,
+/// drawdown) from startup; recording can also be started from settings (None = off)
+pub const GUI_SERIES_EXPORT_PATH: Option<&str> = None;
+
+/// Resample the exported equity curve (run JSON) and series CSV to one point per this many
+/// simulated seconds, carrying the last value forward, e.g. 60.0 for minute bars (None = every update)
+pub const GUI_EXPORT_RESAMPLE_SECS: Option<f64> = None;
+
+/// Wall-clock window the GUI's realized replay rate (sim seconds per wall second) is measured over
+pub const GUI_REPLAY_RATE_WINDOW_MS: u64 = 1_000;
+
+/// EMA weight of the newest point in the smoothed fill-ratio, hold-time and latency charts, and
+/// whether those charts start out smoothed (both adjustable in settings; the raw series are kept)
+pub const GUI_METRIC_SMOOTHING_ALPHA: f64 = 0.1;
+pub const GUI_SMOOTH_METRIC_CHARTS: bool = false;
+
+/// Default |book imbalance| above which the price chart marks the point (adjustable in settings)
+pub const GUI_IMBALANCE_ALERT_THRESHOLD: f64 = 0.6;
+
+/// "Replay trade" seeks to this many simulated seconds before the selected trade's entry
+pub const GUI_TRADE_REPLAY_LEAD_SECS: f64 = 5.0;
+
 /// Command polling timeout in microseconds
 pub const COMMAND_POLL_TIMEOUT_MICROS: u64 = 1;
+
+/// Default taker `order_timeout_ns`: cancel a resting (unfilled) entry order after this long,
+/// then requote it if requotes are left (1s in nanoseconds)
+pub const PASSIVE_ENTRY_TIMEOUT_NS: i64 = 1_000_000_000;
+
+/// How long taker submit/exit paths block waiting for an order response (100ms in nanoseconds)
+pub const ORDER_RESPONSE_WAIT_NS: i64 = 100_000_000;
+
+/// Elapse steps per loop iteration in run-to-end mode (commands are still polled between batches)
+pub const RUN_TO_END_BATCH: usize = 10_000;
+
+/// Suspend trading once the last market-data update is older than this, resuming when data
+/// arrives again (None = never treat the book as stale)
+pub const MAX_BOOK_STALENESS_NS: Option<i64> = None;
+/// Close open positions (momentum / prediction) or pull quotes (market maker) when the book goes stale
+pub const FLATTEN_ON_STALE_BOOK: bool = false;
+
+/// Time-of-day windows for new entries (momentum / prediction), as `[start, end)` minutes after
+/// UTC midnight, e.g. `&[(9 * 60 + 45, 15 * 60 + 45)]` skips the first and last 15 minutes of a
+/// 09:30-16:00 session. A window with start > end wraps past midnight; exits are never held
+/// back (empty = enter at any time)
+pub const ENTRY_TIME_WINDOWS_UTC: &[(u32, u32)] = &[];
+
+/// How long each end-of-data flatten attempt waits for an order response (100ms in nanoseconds)
+pub const FLATTEN_WAIT_NS: i64 = 100_000_000;
+
+/// Trading seconds in a year for annualized metrics (Sharpe): 24/7 for crypto; for equities use
+/// the session length, e.g. 252.0 * 6.5 * 3600.0
+pub const TRADING_SECONDS_PER_YEAR: f64 = SECONDS_PER_YEAR_24_7;
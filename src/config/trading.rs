@@ -1,8 +1,94 @@
+use hftbacktest::backtest::ExchangeKind;
+use crate::common::{AssetKind, FeeModelKind, LiquidityClassification, MarkPrice, PriceRounding};
+
 pub const TICK_SIZE: f64 = 0.00001;
 pub const LOT_SIZE: f64 = 0.001;
 pub const INITIAL_CAPITAL: f64 = 10000.0;
 
+/// Per-side fee rate used for the runners' own PnL accounting
+pub const ESTIMATED_FEE_RATE: f64 = 0.0001;
+
+/// Maker and taker fee rates of the simulated exchange (negative = rebate). The MM books each
+/// fill at the rate of the side it was classified as
+pub const MAKER_FEE_RATE: f64 = -0.0001;
+pub const TAKER_FEE_RATE: f64 = 0.0004;
+/// How the MM tells maker from taker fills: the exchange's own flag (Engine) or the quote's
+/// price against the touch at submission (Touch)
+pub const FILL_LIQUIDITY_CLASSIFICATION: LiquidityClassification = LiquidityClassification::Engine;
+
+/// Commission model applied on top of `ESTIMATED_FEE_RATE` in the taker runners' PnL
+/// accounting. Their engine charges a flat `ESTIMATED_FEE_RATE` per side, so minimums/tiers
+/// are not simulated there.
+pub const FEE_MODEL: FeeModelKind = FeeModelKind::BpsOnly;
+
+/// Order entry and response latency of the taker runners' engine (ns each way)
+pub const TAKER_ORDER_LATENCY_NS: i64 = 0;
+/// Exponent of the taker runners' `PowerProbQueueFunc3` queue model
+pub const TAKER_QUEUE_POWER: f64 = 3.0;
+
+/// Take-profits only fire once gross profit beats the round-trip fee by this fraction of the
+/// entry price; smaller wins are held (None = take-profit fires at its target regardless)
+pub const MIN_PROFIT_OVER_FEES_PCT: Option<f64> = None;
+
+/// Borrow cost of open shorts in bps of the short notional per simulated day, taken off
+/// equity as it accrues (None = shorts carry no cost)
+pub const SHORT_BORROW_RATE_BPS_PER_DAY: Option<f64> = None;
+
+/// `--reconcile`: position difference (in base units) tolerated before warning
+pub const RECONCILE_POSITION_TOLERANCE: f64 = LOT_SIZE / 2.0;
+/// `--reconcile`: PnL difference tolerated before warning; the engine charges its own
+/// `CommonFees`, so a fee-rate mismatch shows up here too
+pub const RECONCILE_PNL_TOLERANCE_USD: f64 = 1.0;
+
+/// Performance fee accrued on each new equity high (fraction of the gain over the previous
+/// high-water mark); reported equity is net of it. 0 = off
+pub const PERFORMANCE_FEE_PCT: f64 = 0.0;
+
+/// Whole-run profit target and loss limit as fractions of the initial capital: once total
+/// return reaches either, the position is flattened and the run ends (None = off)
+pub const RUN_PROFIT_TARGET_PCT: Option<f64> = None;
+pub const RUN_LOSS_LIMIT_PCT: Option<f64> = None;
+
+/// Price open positions are marked at for unrealized PnL and equity in the market-making,
+/// momentum and prediction runners: Mid, Micro (size-weighted touch) or Last trade
+pub const MARK_PRICE_MODE: MarkPrice = MarkPrice::Mid;
+
+/// Exchange model used by every runner's backtest; see `common::validate_time_in_force`
+/// for the time-in-force values each model supports
+pub const EXCHANGE_KIND: ExchangeKind = ExchangeKind::NoPartialFillExchange;
+
+/// Price impact of large momentum taker orders: one bigger than the touch size is booked at
+/// the VWAP of walking the book up to this many ticks from the touch instead of the touch price
+/// the engine fills it at (None = fill at the touch regardless of size; dry runs use the mid)
+pub const PRICE_IMPACT_DEPTH_TICKS: Option<usize> = None;
+
+/// Book partially filled orders for their executed quantity as it comes in (the rest stays
+/// live); off = only complete fills count. Only matters with `PartialFillExchange`
+pub const BOOK_PARTIAL_FILLS: bool = true;
+
+/// Report the mean/median/p95 simulated time from quote submission to its fill in the
+/// market maker's final stats (queue waiting time, not the configured exchange latency)
+pub const REPORT_FILL_LATENCY: bool = true;
+
+/// Sum the order book into buckets of this many ticks before the prediction features and the
+/// GUI see it, smoothing instruments with very fine ticks (1 = per tick)
+pub const BOOK_BUCKET_TICKS: usize = 1;
+
+/// Contract type and multiplier of the instrument for every runner's backtest and PnL; with
+/// `Inverse` the reported PnL is in the base currency (see `common::AssetKind`)
+pub const ASSET_KIND: AssetKind = AssetKind::Linear { multiplier: 1.0 };
+
+/// How computed quote prices snap to ticks: `Directional` floors bids and ceils asks so a
+/// maker quote never rounds through the opposite side
+pub const ORDER_PRICE_ROUNDING: PriceRounding = PriceRounding::Directional;
+
 pub const PRICE_DECIMAL_PLACES: usize = calculate_decimal_places(TICK_SIZE);
+/// Default factor GUI prices are multiplied by for display (e.g. 1000 for sub-cent instruments);
+/// adjustable in settings
+pub const PRICE_DISPLAY_MULTIPLIER: f64 = 1.0;
+
+/// Start the depth chart in cumulative notional (price × qty) instead of quantity (toggle in settings)
+pub const GUI_DEPTH_CHART_NOTIONAL: bool = false;
 
 const fn calculate_decimal_places(tick_size: f64) -> usize {
     if (tick_size - 0.00001).abs() < 1e-10 { 5 }
@@ -21,3 +107,10 @@ const fn calculate_decimal_places(tick_size: f64) -> usize {
         count
     }
 }
+
+/// End-of-data flatten: ticks added to the crossing price on each retry, and the retry limit
+pub const FLATTEN_STEP_TICKS: i64 = 10;
+pub const FLATTEN_MAX_ATTEMPTS: usize = 10;
+
+/// Number of market trade events buffered between `clear_last_trades` calls
+pub const LAST_TRADES_CAPACITY: usize = 1024;
@@ -1,3 +1,5 @@
+use crate::common::RunStopReason;
+
 /// Strategy control commands using Command Pattern
 #[derive(Debug, Clone)]
 pub enum StrategyCommand {
@@ -14,6 +16,10 @@ pub enum StrategyCommand {
     ChangeFiles(Vec<String>),
     /// Skip current file and move to next (for multi-file backtesting)
     Skip,
+    /// Run the remaining data as fast as possible without GUI updates or delays
+    RunToEnd,
+    /// Run to end until the file's simulated time reaches this many seconds, then pause
+    SeekTo(f64),
     /// Reset strategy state
     #[allow(dead_code)]
     Reset,
@@ -31,11 +37,17 @@ pub enum ControlResponse {
     SpeedChanged(f64),
     /// Files changed
     FilesChanged(Vec<String>),
-    /// Skipped to next file
+    /// Skip acknowledged (the chart is cleared once the next file actually starts)
     Skipped,
+    /// A new data file started within the running backtest
+    NewFileStarted,
     /// Error occurred
-    #[allow(dead_code)]
     Error(String),
+    /// The run hit its whole-run profit target or loss limit and was ended early
+    /// (followed by `Completed`)
+    TargetReached(RunStopReason),
+    /// Notable strategy event for the log (e.g. entries halted by the loss-streak guard)
+    LogEvent(String),
     /// Strategy completed
     Completed,
     /// Thread terminated, ready for new backtest
@@ -43,6 +55,15 @@ pub enum ControlResponse {
     ThreadTerminated,
 }
 
+impl ControlResponse {
+    /// Whether the GUI should drop its chart history on this response. Only `NewFileStarted`
+    /// does; a new run clears the history when its thread is spawned, and speed, state, file
+    /// selection and skip acknowledgements never touch it.
+    pub fn clears_chart(&self) -> bool {
+        matches!(self, ControlResponse::NewFileStarted)
+    }
+}
+
 /// Current control state
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ControlState {
@@ -2,6 +2,7 @@ use crossbeam_channel::{Sender, Receiver, select};
 use std::sync::{Arc, atomic::{AtomicBool, AtomicU64, Ordering}};
 use std::time::Duration;
 use super::commands::{StrategyCommand, ControlResponse, ControlState};
+use crate::common::RunStopReason;
 
 /// Strategy controller that manages execution flow
 /// Follows Single Responsibility Principle - only handles control logic
@@ -18,6 +19,10 @@ pub struct StrategyController {
     should_skip: Arc<AtomicBool>,
     /// Speed multiplier (stored as f64 bits in u64)
     speed_multiplier: Arc<AtomicU64>,
+    /// Run-to-end flag (no GUI updates, no delays until end of data)
+    run_to_end: Arc<AtomicBool>,
+    /// Simulated seconds at which a seek pauses the run (f64 bits, NaN = no seek)
+    seek_target: Arc<AtomicU64>,
 }
 
 impl StrategyController {
@@ -32,6 +37,8 @@ impl StrategyController {
             should_stop: Arc::new(AtomicBool::new(false)),
             should_skip: Arc::new(AtomicBool::new(false)),
             speed_multiplier: Arc::new(AtomicU64::new(1.0f64.to_bits())),
+            run_to_end: Arc::new(AtomicBool::new(false)),
+            seek_target: Arc::new(AtomicU64::new(f64::NAN.to_bits())),
         }
     }
 
@@ -76,6 +83,27 @@ impl StrategyController {
         self.should_skip.store(false, Ordering::Relaxed);
     }
 
+    /// Check if running to end (runners skip GUI updates and loop delays)
+    pub fn is_run_to_end(&self) -> bool {
+        self.run_to_end.load(Ordering::Relaxed)
+    }
+
+    /// Whether a seek has reached `sim_time_secs`: clears it and pauses the run so the user can
+    /// watch from there (false without a pending seek)
+    pub fn reached_seek_target(&self, sim_time_secs: f64) -> bool {
+        let target = f64::from_bits(self.seek_target.load(Ordering::Relaxed));
+        if target.is_nan() || sim_time_secs < target {
+            return false;
+        }
+        self.clear_seek();
+        self.pause_with_log(format!("Seek reached T+{:.1}s", sim_time_secs));
+        true
+    }
+
+    fn clear_seek(&self) {
+        self.seek_target.store(f64::NAN.to_bits(), Ordering::Relaxed);
+    }
+
     /// Check if currently running
     pub fn is_running(&self) -> bool {
         self.state() == ControlState::Running
@@ -106,10 +134,14 @@ impl StrategyController {
                 let _ = self.response_tx.send(ControlResponse::StateChanged(ControlState::Running));
             }
             StrategyCommand::Pause => {
+                self.clear_seek();
+                self.run_to_end.store(false, Ordering::Relaxed);
                 self.state.store(ControlState::Paused as u64, Ordering::Relaxed);
                 let _ = self.response_tx.send(ControlResponse::StateChanged(ControlState::Paused));
             }
             StrategyCommand::Stop => {
+                self.clear_seek();
+                self.run_to_end.store(false, Ordering::Relaxed);
                 self.state.store(ControlState::Stopped as u64, Ordering::Relaxed);
                 self.should_stop.store(true, Ordering::Relaxed);
                 let _ = self.response_tx.send(ControlResponse::StateChanged(ControlState::Stopped));
@@ -132,10 +164,26 @@ impl StrategyController {
                 self.should_skip.store(true, Ordering::Relaxed);
                 let _ = self.response_tx.send(ControlResponse::Skipped);
             }
+            StrategyCommand::RunToEnd => {
+                self.should_stop.store(false, Ordering::Relaxed);
+                self.run_to_end.store(true, Ordering::Relaxed);
+                self.state.store(ControlState::Running as u64, Ordering::Relaxed);
+                let _ = self.response_tx.send(ControlResponse::StateChanged(ControlState::Running));
+            }
+            StrategyCommand::SeekTo(sim_time_secs) => {
+                // Fast-forward like run-to-end; the runner pauses once it gets there
+                self.seek_target.store(sim_time_secs.to_bits(), Ordering::Relaxed);
+                self.should_stop.store(false, Ordering::Relaxed);
+                self.run_to_end.store(true, Ordering::Relaxed);
+                self.state.store(ControlState::Running as u64, Ordering::Relaxed);
+                let _ = self.response_tx.send(ControlResponse::StateChanged(ControlState::Running));
+            }
             StrategyCommand::Reset => {
+                self.clear_seek();
                 self.state.store(ControlState::Paused as u64, Ordering::Relaxed);
                 self.should_stop.store(false, Ordering::Relaxed);
                 self.should_skip.store(false, Ordering::Relaxed);
+                self.run_to_end.store(false, Ordering::Relaxed);
                 self.speed_multiplier.store(1.0f64.to_bits(), Ordering::Relaxed);
                 let _ = self.response_tx.send(ControlResponse::StateChanged(ControlState::Paused));
                 let _ = self.response_tx.send(ControlResponse::SpeedChanged(1.0));
@@ -148,15 +196,39 @@ impl StrategyController {
         }
     }
 
+    /// Pause the run and surface `message` to the GUI (e.g. the strategy can't keep trading safely)
+    pub fn pause_with_error(&self, message: String) {
+        self.run_to_end.store(false, Ordering::Relaxed);
+        self.state.store(ControlState::Paused as u64, Ordering::Relaxed);
+        let _ = self.response_tx.send(ControlResponse::StateChanged(ControlState::Paused));
+        let _ = self.response_tx.send(ControlResponse::Error(message));
+    }
+
+    /// Pause the run and log `message` (e.g. a risk guard wants the user to confirm before continuing)
+    pub fn pause_with_log(&self, message: String) {
+        self.run_to_end.store(false, Ordering::Relaxed);
+        self.state.store(ControlState::Paused as u64, Ordering::Relaxed);
+        let _ = self.response_tx.send(ControlResponse::StateChanged(ControlState::Paused));
+        let _ = self.response_tx.send(ControlResponse::LogEvent(message));
+    }
+
     /// Mark as completed
     pub fn mark_completed(&self) {
         self.state.store(ControlState::Completed as u64, Ordering::Relaxed);
         let _ = self.response_tx.send(ControlResponse::Completed);
     }
 
+    /// End the run early: remaining files are skipped and the GUI is told why before `Completed`
+    pub fn mark_target_reached(&self, reason: RunStopReason) {
+        self.run_to_end.store(false, Ordering::Relaxed);
+        self.should_stop.store(true, Ordering::Relaxed);
+        let _ = self.response_tx.send(ControlResponse::TargetReached(reason));
+        self.mark_completed();
+    }
+
     /// Notify GUI that a new file is starting (clears chart data)
     pub fn notify_new_file(&self) {
-        let _ = self.response_tx.send(ControlResponse::Skipped);
+        let _ = self.response_tx.send(ControlResponse::NewFileStarted);
     }
 
     /// Get clones for sharing with strategy thread
@@ -169,3 +241,48 @@ impl StrategyController {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossbeam_channel::unbounded;
+
+    #[test]
+    fn test_only_new_file_clears_chart() {
+        let (_cmd_tx, cmd_rx) = unbounded();
+        let (response_tx, response_rx) = unbounded();
+        let controller = StrategyController::new(cmd_rx, response_tx);
+
+        controller.handle_command(StrategyCommand::Start);
+        controller.handle_command(StrategyCommand::SetSpeed(4.0));
+        controller.handle_command(StrategyCommand::Pause);
+        controller.handle_command(StrategyCommand::Skip);
+        controller.handle_command(StrategyCommand::ChangeFiles(vec!["a.npz".to_string()]));
+        assert!(response_rx.try_iter().all(|response| !response.clears_chart()));
+
+        controller.notify_new_file();
+        let responses: Vec<_> = response_rx.try_iter().collect();
+        assert_eq!(responses.len(), 1);
+        assert!(responses[0].clears_chart());
+    }
+
+    #[test]
+    fn test_seek_fast_forwards_then_pauses_once_at_target() {
+        let (_cmd_tx, cmd_rx) = unbounded();
+        let (response_tx, _response_rx) = unbounded();
+        let controller = StrategyController::new(cmd_rx, response_tx);
+        assert!(!controller.reached_seek_target(1e9));
+
+        controller.handle_command(StrategyCommand::SeekTo(42.0));
+        assert!(controller.is_running());
+        assert!(controller.is_run_to_end());
+        assert!(!controller.reached_seek_target(41.9));
+
+        assert!(controller.reached_seek_target(42.05));
+        assert_eq!(controller.state(), ControlState::Paused);
+        assert!(!controller.is_run_to_end());
+        // Resuming afterwards plays on normally
+        controller.handle_command(StrategyCommand::Start);
+        assert!(!controller.reached_seek_target(50.0));
+    }
+}
@@ -12,14 +12,67 @@ use config::{
     GAMMA, INITIAL_KAPPA, MAX_INVENTORY, VOLATILITY_THRESHOLD,
     ORDER_SIZE, DEPTH_LEVELS, ORDER_LAYERS,
     PREDICTION_POSITION_SIZE, PREDICTION_STOP_LOSS_PCT, PREDICTION_TAKE_PROFIT_PCT,
-    PREDICTION_CONFIDENCE_THRESHOLD, PREDICTION_LEARNING_RATE
+    PREDICTION_CONFIDENCE_THRESHOLD, PREDICTION_LEARNING_RATE,
+    CVD_LOOKBACK_PERIOD, CVD_PRICE_THRESHOLD, CVD_POSITION_SIZE,
+    CVD_STOP_LOSS_PCT, CVD_TAKE_PROFIT_PCT, PORTFOLIO_ALLOCATIONS
 };
-use strategy::StrategyType;
+use strategy::{StrategyType, DepthDumpRunner, run_seed_cv};
 use ui::launch_monitor_with_respawn;
 
 fn main() -> Result<()> {
     let args: Vec<String> = std::env::args().collect();
-    let mode = args.get(1).map(|s| s.as_str()).unwrap_or("prediction");
+    let reconcile = args.iter().skip(1).any(|arg| arg == "--reconcile");
+    let synthetic = args.iter().skip(1).any(|arg| arg == "--synthetic");
+    let dump_depth = args.iter().position(|arg| arg == "--dump-depth");
+    let log_file = args.iter().position(|arg| arg == "--log-file");
+    let ml_cv = args.iter().position(|arg| arg == "--ml-cv");
+    let signal_log = args.iter().position(|arg| arg == "--signal-log");
+    let mode = args.iter().enumerate().skip(1)
+        .filter(|(i, _)| dump_depth.is_none_or(|flag| *i <= flag || *i > flag + 2))
+        .filter(|(i, _)| log_file.is_none_or(|flag| *i != flag + 1))
+        .filter(|(i, _)| ml_cv.is_none_or(|flag| *i != flag + 1))
+        .filter(|(i, _)| signal_log.is_none_or(|flag| *i != flag + 1))
+        .map(|(_, arg)| arg.as_str())
+        .find(|arg| !arg.starts_with("--"))
+        .unwrap_or("prediction");
+    common::set_reconcile_enabled(reconcile);
+    if let Some(flag) = log_file {
+        let Some(path) = args.get(flag + 1) else {
+            anyhow::bail!("Usage: --log-file <path>");
+        };
+        common::set_log_file(std::path::Path::new(path))?;
+    }
+    if let Some(flag) = signal_log {
+        let Some(path) = args.get(flag + 1) else {
+            anyhow::bail!("Usage: --signal-log <path>");
+        };
+        common::set_signal_log_file(std::path::Path::new(path))?;
+    }
+
+    if let Some(flag) = dump_depth {
+        let (Some(interval), Some(path)) = (args.get(flag + 1), args.get(flag + 2)) else {
+            anyhow::bail!("Usage: --dump-depth <interval_ns> <path>");
+        };
+        let interval_ns: i64 = interval.parse()
+            .map_err(|_| anyhow::anyhow!("Invalid --dump-depth interval: {}", interval))?;
+        let data_file_path = if synthetic { synthetic_data_file()? } else { get_data_file_path() };
+        DepthDumpRunner::new(vec![data_file_path], interval_ns, path.into())?.run()?;
+        common::close_log_file();
+        return Ok(());
+    }
+
+    if let Some(flag) = ml_cv {
+        let Some(n_seeds) = args.get(flag + 1) else {
+            anyhow::bail!("Usage: --ml-cv <n_seeds>");
+        };
+        let n_seeds: u64 = n_seeds.parse()
+            .map_err(|_| anyhow::anyhow!("Invalid --ml-cv seed count: {}", n_seeds))?;
+        let data_file_path = if synthetic { synthetic_data_file()? } else { get_data_file_path() };
+        run_seed_cv(&data_file_path, n_seeds)?;
+        common::close_signal_log_file();
+        common::close_log_file();
+        return Ok(());
+    }
 
     let strategy_type = match mode {
         "mm" | "market-maker" => {
@@ -83,8 +136,40 @@ fn main() -> Result<()> {
                 learning_rate: PREDICTION_LEARNING_RATE,
             }
         }
+        "cvd" => {
+            println!("📊 CVD Divergence Strategy with GUI Monitor\n");
+            println!("Parameters:");
+            println!("  Initial Capital: ${}", INITIAL_CAPITAL);
+            println!("  Lookback Period: {}", CVD_LOOKBACK_PERIOD);
+            println!("  Price Threshold: {} ({:.2}%)", CVD_PRICE_THRESHOLD, CVD_PRICE_THRESHOLD * 100.0);
+            println!("  Position Size: {}", CVD_POSITION_SIZE);
+            println!("  Stop Loss: {:.2}%", CVD_STOP_LOSS_PCT * 100.0);
+            println!("  Take Profit: {:.2}%\n", CVD_TAKE_PROFIT_PCT * 100.0);
+            
+            StrategyType::Cvd {
+                lookback_period: CVD_LOOKBACK_PERIOD,
+                price_threshold: CVD_PRICE_THRESHOLD,
+                position_size: CVD_POSITION_SIZE,
+                stop_loss_pct: CVD_STOP_LOSS_PCT,
+                take_profit_pct: CVD_TAKE_PROFIT_PCT,
+                initial_capital: INITIAL_CAPITAL,
+            }
+        }
+        "portfolio" => {
+            println!("📦 Portfolio with GUI Monitor\n");
+            println!("Parameters:");
+            println!("  Initial Capital: ${}", INITIAL_CAPITAL);
+            for (sleeve, fraction) in PORTFOLIO_ALLOCATIONS {
+                println!("  {:?}: {:.1}%", sleeve, fraction * 100.0);
+            }
+            println!();
+            
+            StrategyType::Portfolio {
+                initial_capital: INITIAL_CAPITAL,
+            }
+        }
         _ => {
-            println!("Usage: cargo run [mode]");
+            println!("Usage: cargo run [mode] [--reconcile] [--synthetic] [--dump-depth <interval_ns> <path>] [--log-file <path>] [--ml-cv <n_seeds>] [--signal-log <path>]");
             println!("  Modes:");
             println!("    mm            - Run market making strategy with GUI monitor");
             println!("    market-maker  - Run market making strategy with GUI monitor");
@@ -92,15 +177,40 @@ fn main() -> Result<()> {
             println!("    predict       - Run ML prediction strategy with GUI monitor (default)");
             println!("    prediction    - Run ML prediction strategy with GUI monitor");
             println!("    ml            - Run ML prediction strategy with GUI monitor");
+            println!("    cvd           - Run CVD divergence strategy with GUI monitor");
+            println!("    portfolio     - Run the PORTFOLIO_ALLOCATIONS strategies on shared capital");
+            println!("  Flags:");
+            println!("    --reconcile   - Compare tracked position/PnL with the engine at file end");
+            println!("    --synthetic   - Run on generated random-walk data instead of a data file");
+            println!("    --dump-depth  - Write the order book every <interval_ns> to <path> as JSON lines (no GUI)");
+            println!("    --log-file    - Also write strategy events to <path> with timestamps and levels");
+            println!("    --ml-cv       - Run the ML strategy once per seed (no GUI) and report accuracy/PnL mean ± std");
+            println!("    --signal-log  - Write every generated signal with its forward return to <path> as CSV");
             return Ok(());
         }
     };
 
-    let data_file_path = get_data_file_path();
+    let data_file_path = if synthetic {
+        synthetic_data_file()?
+    } else {
+        get_data_file_path()
+    };
     
-    launch_monitor_with_respawn(
+    let result = launch_monitor_with_respawn(
         strategy_type,
         INITIAL_CAPITAL,
         data_file_path,
-    )
-}
\ No newline at end of file
+    );
+    common::close_signal_log_file();
+    common::close_log_file();
+    result
+}
+
+/// Generate the default synthetic series into the temp directory and return its path
+fn synthetic_data_file() -> Result<String> {
+    let config = common::SyntheticConfig::default();
+    let path = std::env::temp_dir().join(format!("synthetic_{}.npy", config.seed));
+    common::write_synthetic_file(&config, &path)?;
+    println!("Synthetic data (seed {}, vol {:.0}%): {}", config.seed, config.volatility * 100.0, path.display());
+    Ok(path.to_string_lossy().into_owned())
+}
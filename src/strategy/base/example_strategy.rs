@@ -4,10 +4,11 @@
 
 use anyhow::Result;
 use hftbacktest::backtest::BacktestError;
+use crate::common::OrderIdGenerator;
+use crate::log_info;
 use crate::strategy::base::{Strategy, StrategyState, TickContext, StrategyRunner};
-use crate::ui::PerformanceData;
+use crate::ui::DataSender;
 use crate::controller::StrategyController;
-use crossbeam_channel::Sender;
 use std::sync::Arc;
 
 pub struct ExampleStrategy {
@@ -18,7 +19,7 @@ pub struct ExampleStrategy {
     
     // Internal state (optional)
     last_signal: f64,
-    order_id: u64,
+    order_ids: OrderIdGenerator,
 }
 
 impl ExampleStrategy {
@@ -28,7 +29,7 @@ impl ExampleStrategy {
             threshold,
             initial_capital,
             last_signal: 0.0,
-            order_id: 0,
+            order_ids: OrderIdGenerator::new(),
         }
     }
     
@@ -38,18 +39,13 @@ impl ExampleStrategy {
         position_size: f64,
         threshold: f64,
         initial_capital: f64,
-        sender: Sender<PerformanceData>,
+        sender: DataSender,
         controller: Arc<StrategyController>,
     ) -> Result<()> {
         let strategy = Self::new(position_size, threshold, initial_capital);
         let runner = StrategyRunner::new(strategy, files)?;
         runner.run_with_controller(sender, controller)
     }
-    
-    fn next_order_id(&mut self) -> u64 {
-        self.order_id += 1;
-        self.order_id
-    }
 }
 
 impl Strategy for ExampleStrategy {
@@ -77,7 +73,7 @@ impl Strategy for ExampleStrategy {
         }
         
         if signal < -self.threshold && state.position <= 0.0 {
-            let order_id = self.next_order_id();
+            let order_id = self.order_ids.next_id();
             let buy_price = ctx.best_bid();
             
             ctx.submit_buy_order(buy_price, self.position_size, order_id)?;
@@ -85,7 +81,7 @@ impl Strategy for ExampleStrategy {
         }
         
         if signal > self.threshold && state.position >= 0.0 {
-            let order_id = self.next_order_id();
+            let order_id = self.order_ids.next_id();
             let sell_price = ctx.best_ask();
             
             ctx.submit_sell_order(sell_price, self.position_size, order_id)?;
@@ -99,25 +95,29 @@ impl Strategy for ExampleStrategy {
         Ok(())
     }
     
+    fn set_order_id_namespace(&mut self, namespace: u64) {
+        self.order_ids = OrderIdGenerator::in_namespace(namespace);
+    }
+    
     fn on_file_start(&mut self, file_path: &str) {
-        println!("📂 Starting: {}", file_path);
+        log_info!("📂 Starting: {}", file_path);
         self.last_signal = 0.0;
     }
     
     fn on_file_end(&mut self, state: &StrategyState) {
-        println!("📊 File completed - Trades: {}, PnL: ${:.2}", 
-                 state.num_trades, state.realized_pnl);
+        log_info!("📊 File completed - Trades: {}, PnL: ${:.2}", 
+                  state.num_trades, state.realized_pnl);
     }
     
     fn on_completed(&mut self, state: &StrategyState) {
-        println!("\n╔══════════════════════════════════════╗");
-        println!("║     {} Results      ║", self.name());
-        println!("╠══════════════════════════════════════╣");
-        println!("║ Total PnL:     ${:>18.2} ║", state.realized_pnl);
-        println!("║ Trades:        {:>18}   ║", state.num_trades);
-        println!("║ Win Rate:      {:>17.1}%  ║", state.win_rate());
-        println!("║ Fill Ratio:    {:>17.1}%  ║", state.fill_ratio());
-        println!("╚══════════════════════════════════════╝");
+        log_info!("\n╔══════════════════════════════════════╗");
+        log_info!("║     {} Results      ║", self.name());
+        log_info!("╠══════════════════════════════════════╣");
+        log_info!("║ Total PnL:     ${:>18.2} ║", state.realized_pnl);
+        log_info!("║ Trades:        {:>18}   ║", state.num_trades);
+        log_info!("║ Win Rate:      {:>17.1}%  ║", state.win_rate());
+        log_info!("║ Fill Ratio:    {:>17.1}%  ║", state.fill_ratio());
+        log_info!("╚══════════════════════════════════════╝");
     }
     
     fn update_interval(&self) -> u64 {
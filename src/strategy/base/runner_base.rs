@@ -2,23 +2,63 @@ use anyhow::Result;
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
-use crossbeam_channel::Sender;
 use hftbacktest::{
-    backtest::{Backtest, ExchangeKind, L2AssetBuilder, assettype::LinearAsset,
-        data::DataSource, models::{CommonFees, ConstantLatency, ProbQueueModel, 
+    backtest::{Backtest, L2AssetBuilder,
+        models::{CommonFees, ConstantLatency, ProbQueueModel, 
         PowerProbQueueFunc3, TradingValueFeeModel}},
     prelude::{HashMapMarketDepth, Bot},
-    types::ElapseResult,
+    types::{ElapseResult, Event},
 };
-use crate::common::is_valid_depth;
-use crate::config::{TICK_SIZE, LOT_SIZE, ELAPSE_DURATION_NS, COMMAND_POLL_TIMEOUT_MICROS};
-use crate::ui::PerformanceData;
+use crate::common::{calculate_mid_price, is_valid_depth, AdaptiveElapse, validate_time_in_force, force_flatten, reconcile,
+    FilePreloader, data_source, HighWaterMark, RunTarget, BorrowCost, SignalLog, OrderIdGenerator, FLATTEN_ORDER_ID_NAMESPACE};
+use crate::{log_error, log_info, log_warn};
+use crate::config::{ADAPTIVE_ELAPSE_QUIET_TICKS, ADAPTIVE_ELAPSE_MAX_MULTIPLIER, TICK_SIZE, LOT_SIZE, ASSET_KIND, PERFORMANCE_FEE_PCT, EXCHANGE_KIND, COMMAND_POLL_TIMEOUT_MICROS, LAST_TRADES_CAPACITY, RUN_TO_END_BATCH,
+    ESTIMATED_FEE_RATE, SHORT_BORROW_RATE_BPS_PER_DAY, RUN_PROFIT_TARGET_PCT, RUN_LOSS_LIMIT_PCT, FLATTEN_STEP_TICKS, FLATTEN_MAX_ATTEMPTS, FLATTEN_WAIT_NS,
+    GUI_UPDATE_ON_FILL, GUI_REPLAY_RATE_WINDOW_MS, GUI_PAUSE_ON_NON_FINITE, SIGNAL_LOG_HORIZON_TICKS};
+use crate::ui::{DataSender, PerformanceData, OrderBookLevel, UpdateCadence, ReplayRateMeter};
 use crate::controller::StrategyController;
 use super::{Strategy, StrategyState, TickContext, build_performance_data, extract_orderbook};
 
+/// Send `data` to the GUI, unless its equity, PnL, position or mid is NaN/Inf: then the values
+/// and the touch are logged and the run is paused with an error so the charts stay intact
+pub fn send_gui_update(sender: &DataSender, controller: &StrategyController, data: PerformanceData) {
+    if GUI_PAUSE_ON_NON_FINITE {
+        if let Some(message) = non_finite_report(&data) {
+            log_error!("⚠ {}", message);
+            controller.pause_with_error(message);
+            return;
+        }
+    }
+    sender.send_latest(data);
+}
+
+/// Which of the charted values are NaN/Inf, with the book they were computed on (None = all finite)
+fn non_finite_report(data: &PerformanceData) -> Option<String> {
+    let values = [
+        ("equity", data.equity),
+        ("realized_pnl", data.realized_pnl),
+        ("unrealized_pnl", data.unrealized_pnl),
+        ("position", data.position),
+        ("mid_price", data.mid_price),
+    ];
+    let non_finite: Vec<String> = values.iter()
+        .filter(|(_, value)| !value.is_finite())
+        .map(|(name, value)| format!("{} = {}", name, value))
+        .collect();
+    if non_finite.is_empty() {
+        return None;
+    }
+
+    let touch = |levels: &[OrderBookLevel]| levels.first()
+        .map_or("none".to_string(), |level| format!("{} x {}", level.price, level.quantity));
+    Some(format!("{}: non-finite {} at {:.3}s (best bid {}, best ask {}); run paused",
+                 data.strategy_name, non_finite.join(", "), data.timestamp, touch(&data.bids), touch(&data.asks)))
+}
+
 pub struct StrategyRunner<S: Strategy> {
     strategy: S,
     data_files: Vec<PathBuf>,
+    run_target: RunTarget,
 }
 
 impl<S: Strategy> StrategyRunner<S> {
@@ -27,60 +67,75 @@ impl<S: Strategy> StrategyRunner<S> {
         if data_files.is_empty() {
             anyhow::bail!("No data files provided");
         }
+        validate_time_in_force(&EXCHANGE_KIND, strategy.time_in_force())?;
         
-        println!("Strategy: {}", strategy.name());
-        println!("Using {} file(s):", data_files.len());
+        log_info!("Strategy: {}", strategy.name());
+        log_info!("Using {} file(s):", data_files.len());
         for (i, f) in data_files.iter().enumerate() {
-            println!("  [{}] {}", i + 1, f.display());
+            log_info!("  [{}] {}", i + 1, f.display());
         }
         
-        Ok(Self { strategy, data_files })
+        Ok(Self {
+            strategy,
+            data_files,
+            run_target: RunTarget::new(RUN_PROFIT_TARGET_PCT, RUN_LOSS_LIMIT_PCT),
+        })
     }
 
     pub fn run_with_controller(
         mut self,
-        sender: Sender<PerformanceData>,
+        sender: DataSender,
         controller: Arc<StrategyController>,
     ) -> Result<()> {
         let file_count = self.data_files.len();
         
+        let mut preload: Option<FilePreloader> = None;
+        
         for file_idx in 0..file_count {
             while !controller.is_running() && !controller.should_stop() {
                 controller.process_commands(Duration::from_millis(100));
             }
             
             if controller.should_stop() {
-                println!("\n⏹ Strategy stopped by user");
+                log_info!("\n⏹ Strategy stopped by user");
                 break;
             }
             
             let data_file = self.data_files[file_idx].clone();
+            let preloaded = preload.take().and_then(FilePreloader::wait);
+            // Load the next file in the background while this one runs
+            preload = self.data_files.get(file_idx + 1)
+                .map(|next| FilePreloader::spawn(next.to_string_lossy().into_owned()));
             
             if file_idx > 0 {
                 controller.notify_new_file();
             }
             
-            println!("\n{}", "=".repeat(60));
-            println!("Running {} on file [{}/{}]: {}", 
-                     self.strategy.name(),
-                     file_idx + 1, 
-                     file_count, 
-                     data_file.display());
-            println!("{}\n", "=".repeat(60));
+            log_info!("\n{}", "=".repeat(60));
+            log_info!("Running {} on file [{}/{}]: {}", 
+                      self.strategy.name(),
+                      file_idx + 1, 
+                      file_count, 
+                      data_file.display());
+            log_info!("{}\n", "=".repeat(60));
             
             self.run_single_file(
                 data_file.to_str().unwrap(),
+                preloaded,
                 &sender,
                 &controller,
             )?;
         }
         
+        // Stopped before the next file was needed: discard it; the loader thread is detached
+        drop(preload);
+        
         if !controller.should_stop() {
             controller.mark_completed();
-            println!("\n✅ All files processed successfully!");
+            log_info!("\n✅ All files processed successfully!");
         }
         
-        self.keep_alive_until_close(&controller);
+        keep_alive_until_close(&controller);
         
         Ok(())
     }
@@ -88,30 +143,52 @@ impl<S: Strategy> StrategyRunner<S> {
     fn run_single_file(
         &mut self,
         data_file: &str,
-        sender: &Sender<PerformanceData>,
+        preloaded: Option<Vec<Event>>,
+        sender: &DataSender,
         controller: &StrategyController,
     ) -> Result<()> {
-        println!("Loading data from: {}", data_file);
-        
-        let mut hbt = create_backtest(data_file)?;
+        log_info!("Loading data from: {}", data_file);
         
+        let mut hbt = create_backtest(data_file, preloaded)?;
+        self.run_backtest(&mut hbt, data_file, sender, controller)
+    }
+
+    fn run_backtest(
+        &mut self,
+        hbt: &mut Backtest<HashMapMarketDepth>,
+        data_file: &str,
+        sender: &DataSender,
+        controller: &StrategyController,
+    ) -> Result<()> {
         self.strategy.on_file_start(data_file);
         
         let mut state = StrategyState::new();
+        let mut signal_log = SignalLog::new(self.strategy.name(), SIGNAL_LOG_HORIZON_TICKS);
         let initial_capital = self.strategy.initial_capital();
         let update_interval = self.strategy.update_interval();
+        let mut stepper = AdaptiveElapse::new(
+            self.strategy.elapse_duration_ns(), ADAPTIVE_ELAPSE_QUIET_TICKS, ADAPTIVE_ELAPSE_MAX_MULTIPLIER,
+        );
         let orderbook_depth = self.strategy.orderbook_depth();
+        let tif = self.strategy.time_in_force();
+        let mut performance_fee = HighWaterMark::new(PERFORMANCE_FEE_PCT, initial_capital);
+        let mut borrow_cost = BorrowCost::new(SHORT_BORROW_RATE_BPS_PER_DAY);
         
-        let mut last_gui_update = Instant::now();
+        let mut gui_cadence = UpdateCadence::new(Duration::from_millis(33), GUI_UPDATE_ON_FILL);
+        let mut replay_rate = ReplayRateMeter::new(Duration::from_millis(GUI_REPLAY_RATE_WINDOW_MS));
         let mut last_command_check = Instant::now();
         let command_check_interval = Duration::from_millis(16);
         let mut data_ended = false;
+        // The strategy's own IDs are private to it, so flattens get a range of their own
+        let mut flatten_ids = OrderIdGenerator::in_namespace(FLATTEN_ORDER_ID_NAMESPACE);
 
-        println!("{} started...\n", self.strategy.name());
+        log_info!("{} started...\n", self.strategy.name());
 
         loop {
             if data_ended {
-                println!("\nEnd of data reached!");
+                log_info!("\nEnd of data reached!");
+                flatten_state(hbt, &mut flatten_ids, &mut state)?;
+                reconcile(hbt, self.strategy.name(), state.position, state.realized_pnl + state.unrealized_pnl);
                 self.strategy.on_file_end(&state);
                 break;
             }
@@ -128,34 +205,53 @@ impl<S: Strategy> StrategyRunner<S> {
                 last_command_check = Instant::now();
                 
                 if controller.should_stop() {
-                    println!("\n⏹ Strategy stopped by user");
+                    log_info!("\n⏹ Strategy stopped by user");
                     break;
                 }
             }
             
             let speed = controller.speed_multiplier();
-            let (iterations_per_loop, loop_delay_ms) = calculate_speed_params(speed);
+            let run_to_end = controller.is_run_to_end();
+            let (iterations_per_loop, loop_delay_ms) = loop_params(run_to_end, speed);
             
             for _ in 0..iterations_per_loop {
-                match hbt.elapse(ELAPSE_DURATION_NS) {
+                // A seek stops fast-forwarding here and pauses the run
+                if controller.reached_seek_target(stepper.sim_time_secs()) {
+                    break;
+                }
+                replay_rate.observe(hbt.current_timestamp());
+                match hbt.elapse(stepper.duration_ns()) {
                     Ok(ElapseResult::EndOfData) => {
                         data_ended = true;
                         break;
                     }
                     Ok(_) => {
                         let depth = hbt.depth(0);
-                        if !is_valid_depth(depth) {
+                        let Some(mid_price) = calculate_mid_price(depth) else {
                             continue;
-                        }
+                        };
                         
                         state.update_count += 1;
+                        stepper.observe(depth);
+                        borrow_cost.accrue(state.position, mid_price, hbt.current_timestamp());
+                        signal_log.observe_mid(mid_price);
                         
                         if state.update_count % update_interval == 0 {
-                            let mut ctx = TickContext::new(&mut hbt);
+                            let mut ctx = TickContext::with_time_in_force(hbt, tif);
                             state.mid_price = ctx.mid_price();
                             
                             if let Err(e) = self.strategy.on_tick(&mut ctx, &mut state) {
-                                eprintln!("Strategy error: {:?}", e);
+                                log_error!("Strategy error: {:?}", e);
+                            }
+                            performance_fee.update(state.equity(initial_capital) - borrow_cost.accrued());
+                            if let Some((signal, value)) = self.strategy.take_signal() {
+                                signal_log.record(hbt.current_timestamp(), signal, value, mid_price);
+                            }
+                            hbt.clear_last_trades(Some(0));
+                            // Stop batching so the fill's update carries its own timestamp
+                            gui_cadence.observe_fills(state.total_fills);
+                            if gui_cadence.fill_pending() && !run_to_end {
+                                break;
                             }
                         }
                     }
@@ -165,15 +261,30 @@ impl<S: Strategy> StrategyRunner<S> {
                     }
                 }
             }
+
+            // Whole-run target: flatten and end the run once total return reaches it
+            let equity = state.equity(initial_capital) - borrow_cost.accrued();
+            if let Some(reason) = self.run_target.check(initial_capital, equity) {
+                log_info!("\n🏁 {}", reason);
+                flatten_state(hbt, &mut flatten_ids, &mut state)?;
+                reconcile(hbt, self.strategy.name(), state.position, state.realized_pnl + state.unrealized_pnl);
+                self.strategy.on_file_end(&state);
+                controller.mark_target_reached(reason);
+                break;
+            }
             
+            replay_rate.observe(hbt.current_timestamp());
+
             // Send data to GUI
-            if last_gui_update.elapsed() >= Duration::from_millis(33) {
+            // In run-to-end mode only the final state is sent
+            let gui_due = gui_cadence.due(run_to_end, data_ended);
+            if gui_due {
                 let depth = hbt.depth(0);
                 if is_valid_depth(depth) {
                     let (bids, asks) = extract_orderbook(depth, orderbook_depth);
-                    let sim_time_secs = state.update_count as f64 * (ELAPSE_DURATION_NS as f64 / 1_000_000_000.0);
+                    let sim_time_secs = stepper.sim_time_secs();
                     
-                    let perf_data = build_performance_data(
+                    let mut perf_data = build_performance_data(
                         &state,
                         initial_capital,
                         self.strategy.name(),
@@ -181,37 +292,101 @@ impl<S: Strategy> StrategyRunner<S> {
                         asks,
                         sim_time_secs,
                     );
+                    perf_data.equity = performance_fee.net_equity();
+                    perf_data.performance_fees_accrued = performance_fee.accrued();
+                    perf_data.borrow_costs = borrow_cost.accrued();
+                    perf_data.fill_update = gui_cadence.fill_pending();
+                    perf_data.replay_rate = replay_rate.rate();
                     
-                    let _ = sender.try_send(perf_data);
+                    send_gui_update(sender, controller, perf_data);
                 }
-                last_gui_update = Instant::now();
+                gui_cadence.mark_sent();
             }
             
             if loop_delay_ms > 0 {
                 std::thread::sleep(Duration::from_millis(loop_delay_ms));
-            } else {
+            } else if !run_to_end {
                 std::thread::yield_now();
             }
         }
 
         Ok(())
     }
+}
 
-    fn keep_alive_until_close(&self, controller: &StrategyController) {
-        println!("Backtest finished. Close the window to exit.");
-        
-        loop {
-            if !controller.process_commands(Duration::from_millis(200)) {
-                std::thread::sleep(Duration::from_millis(100));
-                if !controller.process_commands(Duration::from_millis(100)) {
-                    break;
-                }
+/// Keep answering GUI commands after the run until the window goes away
+pub(crate) fn keep_alive_until_close(controller: &StrategyController) {
+    log_info!("Backtest finished. Close the window to exit.");
+    
+    loop {
+        if !controller.process_commands(Duration::from_millis(200)) {
+            std::thread::sleep(Duration::from_millis(100));
+            if !controller.process_commands(Duration::from_millis(100)) {
+                break;
             }
         }
     }
 }
 
-fn calculate_speed_params(speed: f64) -> (usize, u64) {
+/// Liquidate whatever position the strategy left open, booking the PnL into `state`
+pub(crate) fn flatten_state(
+    hbt: &mut Backtest<HashMapMarketDepth>,
+    order_ids: &mut OrderIdGenerator,
+    state: &mut StrategyState,
+) -> Result<()> {
+    if state.position.abs() < LOT_SIZE / 2.0 {
+        return Ok(());
+    }
+
+    log_info!("Flattening remaining position {:.4}...", state.position);
+    let result = force_flatten(
+        hbt, order_ids, state.position, LOT_SIZE,
+        FLATTEN_STEP_TICKS, FLATTEN_MAX_ATTEMPTS, FLATTEN_WAIT_NS,
+    )?;
+    state.total_orders += result.attempts;
+
+    if result.filled_qty > 0.0 {
+        let direction = state.position.signum();
+        let pnl = ASSET_KIND.pnl(state.entry_price, result.avg_price, result.filled_qty) * direction;
+        let fee = (ASSET_KIND.notional(state.entry_price, result.filled_qty)
+            + ASSET_KIND.notional(result.avg_price, result.filled_qty)) * ESTIMATED_FEE_RATE;
+        state.realized_pnl += pnl - fee;
+        state.fees_paid += fee;
+        state.total_fills += 1;
+        state.num_trades += 1;
+        if pnl - fee > 0.0 {
+            state.winning_trades += 1;
+        }
+
+        log_info!("  ✓ Final flatten @ {:.2} qty {:.4} after {} attempt(s) | PnL: {:.4} | Fee: {:.4}",
+                  result.avg_price, result.filled_qty, result.attempts, pnl, fee);
+    }
+
+    if result.is_flat() {
+        state.position = 0.0;
+        state.entry_price = 0.0;
+        state.unrealized_pnl = 0.0;
+        state.stop_price = None;
+        state.take_price = None;
+    } else {
+        state.position = result.remaining;
+        log_warn!("  ✗ Final flatten incomplete: {:.4} still open after {} attempt(s)",
+                  state.position, result.attempts);
+    }
+
+    Ok(())
+}
+
+/// Steps per loop and the sleep after each loop; run-to-end takes large batches and never sleeps
+pub(crate) fn loop_params(run_to_end: bool, speed: f64) -> (usize, u64) {
+    if run_to_end {
+        (RUN_TO_END_BATCH, 0)
+    } else {
+        calculate_speed_params(speed)
+    }
+}
+
+pub(crate) fn calculate_speed_params(speed: f64) -> (usize, u64) {
     if speed >= 100.0 {
         (100, 0)
     } else if speed >= 10.0 {
@@ -223,14 +398,16 @@ fn calculate_speed_params(speed: f64) -> (usize, u64) {
     }
 }
 
-fn create_backtest(data_file: &str) -> Result<Backtest<HashMapMarketDepth>> {
+/// Backtest over `data_file` with the shared engine settings (latency, queue, fees, asset)
+pub fn create_backtest(data_file: &str, preloaded: Option<Vec<Event>>) -> Result<Backtest<HashMapMarketDepth>> {
     let asset = L2AssetBuilder::new()
-        .data(vec![DataSource::File(data_file.to_string())])
-        .exchange(ExchangeKind::NoPartialFillExchange)
+        .data(vec![data_source(data_file, preloaded)])
+        .exchange(EXCHANGE_KIND)
         .latency_model(ConstantLatency::new(50_000, 50_000))
         .fee_model(TradingValueFeeModel::new(CommonFees::new(-0.00005, 0.0007)))
         .queue_model(ProbQueueModel::new(PowerProbQueueFunc3::new(2.0)))
-        .asset_type(LinearAsset::new(1.0))
+        .asset_type(ASSET_KIND)
+        .last_trades_capacity(LAST_TRADES_CAPACITY)
         .depth(|| HashMapMarketDepth::new(TICK_SIZE, LOT_SIZE))
         .build()?;
 
@@ -238,3 +415,165 @@ fn create_backtest(data_file: &str) -> Result<Backtest<HashMapMarketDepth>> {
         .add_asset(asset)
         .build()?)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossbeam_channel::unbounded;
+    use crate::ui::data_channel;
+    use crate::common::test_utils::{backtest_from_events, two_sided_book};
+    use hftbacktest::backtest::BacktestError;
+    use crate::common::RunStopReason;
+    use crate::controller::{ControlResponse, StrategyCommand};
+    use crate::strategy::base::example_strategy::ExampleStrategy;
+
+    #[test]
+    fn test_nan_pnl_pauses_with_error_instead_of_reaching_charts() {
+        let (_cmd_tx, cmd_rx) = unbounded();
+        let (response_tx, response_rx) = unbounded();
+        let controller = StrategyController::new(cmd_rx, response_tx);
+        let (data_tx, data_rx) = data_channel(16);
+
+        let mut data = PerformanceData {
+            strategy_name: "Example".to_string(),
+            equity: 10_000.0,
+            mid_price: 100.0,
+            bids: vec![OrderBookLevel { price: 99.0, quantity: 1.0 }],
+            ..PerformanceData::default()
+        };
+        send_gui_update(&data_tx, &controller, data.clone());
+        assert_eq!(data_rx.try_iter().count(), 1);
+        assert!(response_rx.try_iter().next().is_none());
+
+        data.unrealized_pnl = f64::NAN;
+        data.equity = f64::NAN;
+        send_gui_update(&data_tx, &controller, data);
+
+        // Nothing reaches the GUI (and so its chart history); the run is paused with the values
+        assert!(data_rx.try_iter().next().is_none());
+        assert_eq!(controller.state(), crate::controller::ControlState::Paused);
+        let responses: Vec<_> = response_rx.try_iter().collect();
+        let error = responses.iter().find_map(|response| match response {
+            ControlResponse::Error(message) => Some(message.clone()),
+            _ => None,
+        }).expect("error response");
+        assert!(error.contains("unrealized_pnl = NaN") && error.contains("best bid 99 x 1"), "{}", error);
+    }
+
+    /// Number of GUI updates sent over an hour of data
+    fn run_in_memory(commands: &[StrategyCommand]) -> usize {
+        let end_ts = 3_600_000_000_000;
+        let mut hbt = backtest_from_events(&two_sided_book(99.0, 101.0, 1.0, (1..=60).map(|i| i * end_ts / 60)));
+
+        let (cmd_tx, cmd_rx) = unbounded();
+        let (response_tx, _response_rx) = unbounded();
+        let controller = StrategyController::new(cmd_rx, response_tx);
+        for command in commands {
+            cmd_tx.send(command.clone()).unwrap();
+        }
+
+        let mut runner = StrategyRunner::new(
+            ExampleStrategy::new(0.01, 0.5, 10_000.0), vec!["in-memory".to_string()],
+        ).unwrap();
+        let (data_tx, data_rx) = data_channel(100_000);
+
+        runner.run_backtest(&mut hbt, "in-memory", &data_tx, &controller).unwrap();
+        data_rx.try_iter().count()
+    }
+
+    /// Books a fixed gain every tick without trading
+    struct SteadyGain {
+        ticks: usize,
+    }
+
+    impl Strategy for SteadyGain {
+        fn name(&self) -> &str { "Steady Gain" }
+        fn initial_capital(&self) -> f64 { 10_000.0 }
+        fn on_tick(&mut self, _ctx: &mut TickContext<'_>, state: &mut StrategyState) -> Result<(), BacktestError> {
+            self.ticks += 1;
+            state.realized_pnl += 10.0;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_profit_target_ends_run_with_reason() {
+        let mut hbt = backtest_from_events(&two_sided_book(99.0, 101.0, 1.0, (1..=1_000).map(|i| i * 100_000_000)));
+
+        let (cmd_tx, cmd_rx) = unbounded();
+        let (response_tx, response_rx) = unbounded();
+        let controller = StrategyController::new(cmd_rx, response_tx);
+        cmd_tx.send(StrategyCommand::Start).unwrap();
+        cmd_tx.send(StrategyCommand::SetSpeed(100.0)).unwrap();
+
+        let mut runner = StrategyRunner::new(SteadyGain { ticks: 0 }, vec!["in-memory".to_string()]).unwrap();
+        runner.run_target = RunTarget::new(Some(0.01), None);
+        let (data_tx, _data_rx) = data_channel(16);
+        runner.run_backtest(&mut hbt, "in-memory", &data_tx, &controller).unwrap();
+
+        // +1% after 10 ticks of $10 on $10k; the check runs once per batch of steps
+        assert!(runner.strategy.ticks >= 10 && runner.strategy.ticks < 1_000, "ticks {}", runner.strategy.ticks);
+        assert!(controller.should_stop());
+        let responses: Vec<_> = response_rx.try_iter().collect();
+        let reason = responses.iter().find_map(|response| match response {
+            ControlResponse::TargetReached(reason) => Some(*reason),
+            _ => None,
+        });
+        assert!(matches!(reason, Some(RunStopReason::ProfitTarget { return_pct }) if return_pct >= 0.01));
+        assert!(matches!(responses.last(), Some(ControlResponse::Completed)));
+    }
+
+    #[test]
+    fn test_run_to_end_sends_one_update_without_sleeping() {
+        // Only the final state is sent
+        assert_eq!(run_in_memory(&[StrategyCommand::RunToEnd]), 1);
+
+        // Never sleeps between batches, whatever speed was set before
+        for speed in [0.5, 1.0, 10.0, 100.0] {
+            assert_eq!(loop_params(true, speed), (RUN_TO_END_BATCH, 0));
+        }
+        assert!(loop_params(false, 1.0).1 > 0);
+    }
+
+    /// Spins for `work` on every tick to stand in for an expensive strategy
+    struct BusyTick {
+        work: Duration,
+    }
+
+    impl Strategy for BusyTick {
+        fn name(&self) -> &str { "Busy Tick" }
+        fn initial_capital(&self) -> f64 { 10_000.0 }
+        fn on_tick(&mut self, _ctx: &mut TickContext<'_>, _state: &mut StrategyState) -> Result<(), BacktestError> {
+            let started = Instant::now();
+            while started.elapsed() < self.work {
+                std::hint::spin_loop();
+            }
+            Ok(())
+        }
+    }
+
+    /// Replay rate carried by the final GUI update of a run-to-end pass over 100s of data
+    fn final_replay_rate(work: Duration) -> f64 {
+        let mut hbt = backtest_from_events(&two_sided_book(99.0, 101.0, 1.0, (1..=1_000).map(|i| i * 100_000_000)));
+
+        let (cmd_tx, cmd_rx) = unbounded();
+        let (response_tx, _response_rx) = unbounded();
+        let controller = StrategyController::new(cmd_rx, response_tx);
+        cmd_tx.send(StrategyCommand::RunToEnd).unwrap();
+
+        let mut runner = StrategyRunner::new(BusyTick { work }, vec!["in-memory".to_string()]).unwrap();
+        let (data_tx, data_rx) = data_channel(16);
+        runner.run_backtest(&mut hbt, "in-memory", &data_tx, &controller).unwrap();
+        data_rx.try_iter().last().expect("final update").replay_rate
+    }
+
+    #[test]
+    fn test_heavy_ticks_report_lower_replay_rate() {
+        let trivial = final_replay_rate(Duration::ZERO);
+        let heavy = final_replay_rate(Duration::from_micros(200));
+
+        // ~1000 ticks x 200us is at least 0.2s of wall time for 100s simulated, so at most ~500x
+        assert!(heavy > 0.0 && heavy <= 500.0, "heavy {}", heavy);
+        assert!(heavy < trivial, "heavy {} vs trivial {}", heavy, trivial);
+    }
+}
@@ -1,9 +1,13 @@
 use hftbacktest::{
     backtest::{Backtest, BacktestError},
-    prelude::{HashMapMarketDepth, Bot},
+    prelude::{HashMapMarketDepth, Bot, TimeInForce},
     depth::MarketDepth,
+    types::{Event, OrdType},
 };
 use crate::ui::{PerformanceData, OrderBookLevel};
+use crate::log_info;
+use crate::common::bucketed_book;
+use crate::config::{ELAPSE_DURATION_NS, BOOK_BUCKET_TICKS};
 
 #[derive(Debug, Clone, Default)]
 pub struct StrategyState {
@@ -18,6 +22,11 @@ pub struct StrategyState {
     pub total_orders: usize,
     pub total_fills: usize,
     pub avg_hold_time: f64,
+    /// Trading fees already taken off `realized_pnl`
+    pub fees_paid: f64,
+    /// Stop/take levels shown on the price chart; strategies set them while in a position
+    pub stop_price: Option<f64>,
+    pub take_price: Option<f64>,
 }
 
 impl StrategyState {
@@ -58,23 +67,40 @@ pub trait Strategy: Send {
     ) -> Result<(), BacktestError>;
     
     fn on_file_start(&mut self, _file_path: &str) {}
+
+    /// Called once when the strategy shares a backtest with others (see `PortfolioRunner`); a
+    /// strategy that submits orders must switch its IDs to `OrderIdGenerator::in_namespace`
+    fn set_order_id_namespace(&mut self, _namespace: u64) {}
     
     fn on_file_end(&mut self, _state: &StrategyState) {}
+
+    /// `(signal, value)` generated on the last tick, traded or not, taken by the runner for
+    /// the `--signal-log` stream (see `common::SignalLog`)
+    fn take_signal(&mut self) -> Option<(&'static str, f64)> {
+        None
+    }
     
     fn on_completed(&mut self, state: &StrategyState) {
-        println!("\n=== {} Results ===", self.name());
-        println!("Total PnL: ${:.2}", state.realized_pnl);
-        println!("Trades: {} (Win rate: {:.1}%)", state.num_trades, state.win_rate());
+        log_info!("\n=== {} Results ===", self.name());
+        log_info!("Total PnL: ${:.2}", state.realized_pnl);
+        log_info!("Trades: {} (Win rate: {:.1}%)", state.num_trades, state.win_rate());
     }
     
     fn update_interval(&self) -> u64 { 1 }
     
+    /// Simulated time per backtest step
+    fn elapse_duration_ns(&self) -> i64 { ELAPSE_DURATION_NS }
+    
     fn orderbook_depth(&self) -> usize { 10 }
+    
+    /// Default time-in-force for `TickContext::submit_*` (validated against the exchange model at startup)
+    fn time_in_force(&self) -> TimeInForce { TimeInForce::GTC }
 }
 
 pub struct TickContext<'a> {
     pub hbt: &'a mut Backtest<HashMapMarketDepth>,
     depth_cache: Option<DepthSnapshot>,
+    tif: TimeInForce,
 }
 
 #[derive(Clone)]
@@ -88,12 +114,21 @@ struct DepthSnapshot {
 #[allow(dead_code)]
 impl<'a> TickContext<'a> {
     pub fn new(hbt: &'a mut Backtest<HashMapMarketDepth>) -> Self {
+        Self::with_time_in_force(hbt, TimeInForce::GTC)
+    }
+
+    pub fn with_time_in_force(hbt: &'a mut Backtest<HashMapMarketDepth>, tif: TimeInForce) -> Self {
         Self {
             hbt,
             depth_cache: None,
+            tif,
         }
     }
 
+    pub fn time_in_force(&self) -> TimeInForce {
+        self.tif
+    }
+
     fn ensure_depth_cache(&mut self) {
         if self.depth_cache.is_none() {
             let depth = self.hbt.depth(0);
@@ -145,6 +180,11 @@ impl<'a> TickContext<'a> {
         depth.ask_qty_at_tick(tick)
     }
 
+    /// Market trades seen since the previous on_tick call
+    pub fn last_trades(&self) -> &[Event] {
+        self.hbt.last_trades(0)
+    }
+
     pub fn timestamp_ns(&self) -> i64 {
         self.hbt.current_timestamp()
     }
@@ -155,26 +195,43 @@ impl<'a> TickContext<'a> {
         qty: f64,
         order_id: u64,
     ) -> Result<(), BacktestError> {
-        use hftbacktest::prelude::TimeInForce;
-        use hftbacktest::types::OrdType;
+        self.submit_buy_order_with_tif(price, qty, order_id, self.tif)
+    }
+
+    pub fn submit_sell_order(
+        &mut self,
+        price: f64,
+        qty: f64,
+        order_id: u64,
+    ) -> Result<(), BacktestError> {
+        self.submit_sell_order_with_tif(price, qty, order_id, self.tif)
+    }
+
+    /// IOC orders that can't fill come back `Expired` instead of resting
+    pub fn submit_buy_order_with_tif(
+        &mut self,
+        price: f64,
+        qty: f64,
+        order_id: u64,
+        tif: TimeInForce,
+    ) -> Result<(), BacktestError> {
         self.hbt.submit_buy_order(
             0, order_id, price, qty,
-            TimeInForce::GTC, OrdType::Limit, false
+            tif, OrdType::Limit, false
         )?;
         Ok(())
     }
 
-    pub fn submit_sell_order(
+    pub fn submit_sell_order_with_tif(
         &mut self,
         price: f64,
         qty: f64,
         order_id: u64,
+        tif: TimeInForce,
     ) -> Result<(), BacktestError> {
-        use hftbacktest::prelude::TimeInForce;
-        use hftbacktest::types::OrdType;
         self.hbt.submit_sell_order(
             0, order_id, price, qty,
-            TimeInForce::GTC, OrdType::Limit, false
+            tif, OrdType::Limit, false
         )?;
         Ok(())
     }
@@ -213,45 +270,29 @@ pub fn build_performance_data(
         latency_micros: 100,
         bids,
         asks,
+        stop_price: state.stop_price,
+        take_price: state.take_price,
+        performance_fees_accrued: 0.0,
+        borrow_costs: 0.0,
+        fees_paid: state.fees_paid,
+        attribution: None,
+        spread_capture: None,
+        imbalance: None,
+        fill_update: false,
+        signal_confidence: None,
+        sleeves: Vec::new(),
+        replay_rate: 0.0,
     }
 }
 
+/// GUI book levels per side, best first, bucketed per `BOOK_BUCKET_TICKS`
 pub fn extract_orderbook<MD: MarketDepth>(
     depth: &MD,
     levels: usize,
 ) -> (Vec<OrderBookLevel>, Vec<OrderBookLevel>) {
-    let mut bids = Vec::with_capacity(levels);
-    let mut asks = Vec::with_capacity(levels);
-    
-    let best_bid_tick = depth.best_bid_tick();
-    let best_ask_tick = depth.best_ask_tick();
-    let tick_size = depth.tick_size();
-    
-    if best_bid_tick != i64::MIN {
-        for i in 0..levels {
-            let tick = best_bid_tick - i as i64;
-            let qty = depth.bid_qty_at_tick(tick);
-            if qty > 0.0 {
-                bids.push(OrderBookLevel {
-                    price: tick as f64 * tick_size,
-                    quantity: qty,
-                });
-            }
-        }
-    }
-    
-    if best_ask_tick != i64::MAX {
-        for i in 0..levels {
-            let tick = best_ask_tick + i as i64;
-            let qty = depth.ask_qty_at_tick(tick);
-            if qty > 0.0 {
-                asks.push(OrderBookLevel {
-                    price: tick as f64 * tick_size,
-                    quantity: qty,
-                });
-            }
-        }
-    }
-    
-    (bids, asks)
+    let (bids, asks) = bucketed_book(depth, levels, BOOK_BUCKET_TICKS);
+    let to_levels = |side: Vec<(f64, f64)>| -> Vec<OrderBookLevel> {
+        side.into_iter().map(|(price, quantity)| OrderBookLevel { price, quantity }).collect()
+    };
+    (to_levels(bids), to_levels(asks))
 }
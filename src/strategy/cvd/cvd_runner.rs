@@ -0,0 +1,217 @@
+use anyhow::Result;
+use hftbacktest::backtest::BacktestError;
+use hftbacktest::prelude::{Bot, TimeInForce};
+use std::sync::Arc;
+use crate::common::{CvdTracker, FeeCalculator, FillTracker, OrderIdGenerator, order_quantity};
+use crate::config::{CvdConfig, ASSET_KIND, FEE_MODEL, ESTIMATED_FEE_RATE, LOT_SIZE, ORDER_RESPONSE_WAIT_NS, BOOK_PARTIAL_FILLS};
+use crate::controller::StrategyController;
+use crate::strategy::base::{Strategy, StrategyState, TickContext, StrategyRunner};
+use crate::strategy::momentum::SignalType;
+use crate::ui::DataSender;
+use super::CvdDivergenceIndicator;
+
+/// Trades divergences between cumulative volume delta and price
+pub struct CvdRunner {
+    config: CvdConfig,
+    initial_capital: f64,
+    tracker: CvdTracker,
+    indicator: CvdDivergenceIndicator,
+    order_ids: OrderIdGenerator,
+    fill_tracker: FillTracker,
+    fees: FeeCalculator,
+    /// This tick's divergence signal and CVD change, until the runner takes it
+    last_signal: Option<(&'static str, f64)>,
+}
+
+impl CvdRunner {
+    pub fn new(config: CvdConfig, initial_capital: f64) -> Self {
+        Self {
+            indicator: CvdDivergenceIndicator::new(config.lookback_period, config.price_threshold),
+            config,
+            initial_capital,
+            tracker: CvdTracker::new(),
+            order_ids: OrderIdGenerator::new(),
+            fill_tracker: FillTracker::new(BOOK_PARTIAL_FILLS),
+            fees: FeeCalculator::new(FEE_MODEL, ESTIMATED_FEE_RATE),
+            last_signal: None,
+        }
+    }
+
+    pub fn run_with_files(
+        files: Vec<String>,
+        config: CvdConfig,
+        initial_capital: f64,
+        sender: DataSender,
+        controller: Arc<StrategyController>,
+    ) -> Result<()> {
+        let strategy = Self::new(config, initial_capital);
+        let runner = StrategyRunner::new(strategy, files)?;
+        runner.run_with_controller(sender, controller)
+    }
+
+    /// Submit a marketable order and wait briefly for it; returns the fill price and executed
+    /// quantity. A partial fill that is still working has its rest canceled, since CVD keeps no
+    /// orders open between ticks
+    fn execute(
+        &mut self,
+        ctx: &mut TickContext<'_>,
+        is_buy: bool,
+        qty: f64,
+        state: &mut StrategyState,
+    ) -> Result<Option<(f64, f64)>, BacktestError> {
+        ctx.clear_inactive_orders();
+
+        let order_id = self.order_ids.next_id();
+        if is_buy {
+            let price = ctx.best_ask();
+            ctx.submit_buy_order(price, qty, order_id)?;
+        } else {
+            let price = ctx.best_bid();
+            ctx.submit_sell_order(price, qty, order_id)?;
+        }
+        state.total_orders += 1;
+        let _ = ctx.hbt.wait_order_response(0, order_id, ORDER_RESPONSE_WAIT_NS);
+
+        let Some(fill) = ctx.hbt.orders(0).get(&order_id).map(|order| self.fill_tracker.poll(order)) else {
+            return Ok(None);
+        };
+        if !fill.done {
+            ctx.cancel_order(order_id)?;
+        }
+        if fill.qty <= 0.0 {
+            return Ok(None);
+        }
+        state.total_fills += 1;
+        Ok(Some((fill.price, fill.qty)))
+    }
+
+    fn close_position(
+        &mut self,
+        ctx: &mut TickContext<'_>,
+        state: &mut StrategyState,
+    ) -> Result<(), BacktestError> {
+        let is_long = state.position > 0.0;
+        let qty = state.position.abs();
+
+        if let Some((exit_price, qty)) = self.execute(ctx, !is_long, qty, state)? {
+            let direction = if is_long { 1.0 } else { -1.0 };
+            let pnl = ASSET_KIND.pnl(state.entry_price, exit_price, qty) * direction;
+            let fee = self.fees.charge(ASSET_KIND.notional(state.entry_price, qty))
+                + self.fees.charge(ASSET_KIND.notional(exit_price, qty));
+            state.realized_pnl += pnl - fee;
+            state.fees_paid += fee;
+            state.num_trades += 1;
+            if pnl - fee > 0.0 {
+                state.winning_trades += 1;
+            }
+            // Part-filled exit: the rest stays open at the same entry price
+            state.position -= direction * qty;
+            if state.position.abs() >= LOT_SIZE / 2.0 {
+                state.unrealized_pnl = ASSET_KIND.pnl(state.entry_price, exit_price, state.position);
+                return Ok(());
+            }
+            state.position = 0.0;
+            state.entry_price = 0.0;
+            state.unrealized_pnl = 0.0;
+            state.stop_price = None;
+            state.take_price = None;
+        }
+        Ok(())
+    }
+
+    fn should_close_position(&self, state: &StrategyState, current_price: f64) -> bool {
+        if state.position == 0.0 || state.entry_price <= 0.0 {
+            return false;
+        }
+        let pnl_pct = if state.position > 0.0 {
+            (current_price - state.entry_price) / state.entry_price
+        } else {
+            (state.entry_price - current_price) / state.entry_price
+        };
+        pnl_pct <= -self.config.stop_loss_pct || pnl_pct >= self.config.take_profit_pct
+    }
+}
+
+impl Strategy for CvdRunner {
+    fn name(&self) -> &str {
+        "CVD Divergence"
+    }
+
+    fn initial_capital(&self) -> f64 {
+        self.initial_capital
+    }
+
+    fn set_order_id_namespace(&mut self, namespace: u64) {
+        self.order_ids = OrderIdGenerator::in_namespace(namespace);
+    }
+
+    fn on_file_start(&mut self, _file_path: &str) {
+        self.tracker.reset();
+        self.indicator.reset();
+        self.last_signal = None;
+    }
+
+    fn take_signal(&mut self) -> Option<(&'static str, f64)> {
+        self.last_signal.take()
+    }
+
+    fn on_tick(
+        &mut self,
+        ctx: &mut TickContext<'_>,
+        state: &mut StrategyState,
+    ) -> Result<(), BacktestError> {
+        self.tracker.update_from_events(ctx.last_trades());
+
+        let mid_price = ctx.mid_price();
+        self.indicator.update(mid_price, self.tracker.value());
+        if self.indicator.is_ready() {
+            let cvd_change = self.indicator.cvd_change().unwrap_or(0.0);
+            self.last_signal = Some((self.indicator.generate_signal().label(), cvd_change));
+        }
+
+        if state.position != 0.0 {
+            state.unrealized_pnl = ASSET_KIND.pnl(state.entry_price, mid_price, state.position);
+            if self.should_close_position(state, mid_price) {
+                self.close_position(ctx, state)?;
+            }
+            return Ok(());
+        }
+
+        if !self.indicator.is_ready() {
+            return Ok(());
+        }
+
+        let is_buy = match self.indicator.generate_signal() {
+            SignalType::Long => true,
+            SignalType::Short => false,
+            SignalType::Neutral => return Ok(()),
+        };
+
+        let equity = self.initial_capital + state.realized_pnl;
+        let qty = order_quantity(self.config.position_size, self.config.sizing_mode, mid_price, equity, LOT_SIZE);
+        if qty <= 0.0 {
+            return Ok(());
+        }
+        if let Some((entry_price, qty)) = self.execute(ctx, is_buy, qty, state)? {
+            state.position = if is_buy { qty } else { -qty };
+            state.entry_price = entry_price;
+            let direction = if is_buy { 1.0 } else { -1.0 };
+            state.stop_price = Some(entry_price * (1.0 - direction * self.config.stop_loss_pct));
+            state.take_price = Some(entry_price * (1.0 + direction * self.config.take_profit_pct));
+        }
+
+        Ok(())
+    }
+
+    fn time_in_force(&self) -> TimeInForce {
+        self.config.time_in_force
+    }
+
+    fn update_interval(&self) -> u64 {
+        crate::config::UPDATE_INTERVAL as u64
+    }
+
+    fn elapse_duration_ns(&self) -> i64 {
+        self.config.elapse_duration_ns
+    }
+}
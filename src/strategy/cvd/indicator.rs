@@ -0,0 +1,109 @@
+use std::collections::VecDeque;
+use crate::strategy::momentum::SignalType;
+
+/// CVD / price divergence indicator
+///
+/// Compares the price move over the lookback window with the CVD move over
+/// the same window. Price falling while CVD rises means sellers are being
+/// absorbed (Long); price rising while CVD falls means buyers are (Short).
+pub struct CvdDivergenceIndicator {
+    lookback_period: usize,
+    price_threshold: f64,
+    price_history: VecDeque<f64>,
+    cvd_history: VecDeque<f64>,
+}
+
+impl CvdDivergenceIndicator {
+    pub fn new(lookback_period: usize, price_threshold: f64) -> Self {
+        Self {
+            lookback_period,
+            price_threshold,
+            price_history: VecDeque::with_capacity(lookback_period + 1),
+            cvd_history: VecDeque::with_capacity(lookback_period + 1),
+        }
+    }
+
+    /// Update with the latest mid price and cumulative volume delta
+    pub fn update(&mut self, price: f64, cvd: f64) {
+        self.price_history.push_back(price);
+        self.cvd_history.push_back(cvd);
+
+        if self.price_history.len() > self.lookback_period + 1 {
+            self.price_history.pop_front();
+            self.cvd_history.pop_front();
+        }
+    }
+
+    /// Relative price change over the window
+    pub fn price_change(&self) -> Option<f64> {
+        if self.price_history.len() < 2 {
+            return None;
+        }
+        let first = self.price_history[0];
+        let last = *self.price_history.back().unwrap();
+        Some((last - first) / first)
+    }
+
+    /// CVD change over the window
+    pub fn cvd_change(&self) -> Option<f64> {
+        if self.cvd_history.len() < 2 {
+            return None;
+        }
+        Some(self.cvd_history.back().unwrap() - self.cvd_history[0])
+    }
+
+    /// Generate divergence signal
+    pub fn generate_signal(&self) -> SignalType {
+        let (price_change, cvd_change) = match (self.price_change(), self.cvd_change()) {
+            (Some(p), Some(c)) => (p, c),
+            _ => return SignalType::Neutral,
+        };
+
+        if price_change < -self.price_threshold && cvd_change > 0.0 {
+            SignalType::Long
+        } else if price_change > self.price_threshold && cvd_change < 0.0 {
+            SignalType::Short
+        } else {
+            SignalType::Neutral
+        }
+    }
+
+    /// Check if indicator is ready
+    pub fn is_ready(&self) -> bool {
+        self.price_history.len() > self.lookback_period
+    }
+
+    pub fn reset(&mut self) {
+        self.price_history.clear();
+        self.cvd_history.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bullish_divergence() {
+        let mut indicator = CvdDivergenceIndicator::new(5, 0.001);
+
+        // Price drifts down while buyers keep lifting the offer
+        for i in 0..6 {
+            indicator.update(100.0 - i as f64 * 0.1, i as f64 * 2.0);
+        }
+
+        assert!(indicator.is_ready());
+        assert_eq!(indicator.generate_signal(), SignalType::Long);
+    }
+
+    #[test]
+    fn test_no_divergence_when_aligned() {
+        let mut indicator = CvdDivergenceIndicator::new(5, 0.001);
+
+        for i in 0..6 {
+            indicator.update(100.0 + i as f64 * 0.1, i as f64 * 2.0);
+        }
+
+        assert_eq!(indicator.generate_signal(), SignalType::Neutral);
+    }
+}
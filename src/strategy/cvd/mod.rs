@@ -0,0 +1,5 @@
+pub mod cvd_runner;
+pub mod indicator;
+
+pub use cvd_runner::CvdRunner;
+pub use indicator::CvdDivergenceIndicator;
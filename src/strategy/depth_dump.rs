@@ -0,0 +1,302 @@
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use hftbacktest::{
+    backtest::Backtest,
+    depth::MarketDepth,
+    prelude::{Bot, HashMapMarketDepth},
+    types::ElapseResult,
+};
+use serde::Serialize;
+
+use crate::common::is_valid_depth;
+use crate::{log_info, log_warn};
+use crate::config::{DEPTH_DUMP_LEVELS, DEPTH_DUMP_MAX_FILE_BYTES, DEPTH_DUMP_CONSISTENCY_LEVELS};
+use super::base::{create_backtest, extract_orderbook};
+
+/// One sampled book: `(price, quantity)` per level, best level first
+#[derive(Debug, Serialize)]
+struct DepthSnapshot {
+    timestamp_ns: i64,
+    bids: Vec<(f64, f64)>,
+    asks: Vec<(f64, f64)>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    consistency: Option<BookConsistency>,
+}
+
+/// Internal-consistency report for one reconstructed book, to check that a new data file
+/// loads correctly before running strategies on it
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct BookConsistency {
+    bid_levels: usize,
+    ask_levels: usize,
+    /// Levels not priced strictly worse than the one before them (bids falling, asks rising)
+    ordering_violations: usize,
+    /// Levels holding zero or negative size
+    non_positive_qty: usize,
+    /// Best bid at or above the best ask
+    crossed: bool,
+    /// Summed size of the first `cumulative_levels` levels per side
+    cumulative_levels: usize,
+    cumulative_bid_qty: f64,
+    cumulative_ask_qty: f64,
+}
+
+impl BookConsistency {
+    /// `bids` and `asks` are `(price, quantity)`, best level first
+    fn check(bids: &[(f64, f64)], asks: &[(f64, f64)], cumulative_levels: usize) -> Self {
+        let ordering_violations = bids.windows(2).filter(|pair| pair[1].0 >= pair[0].0).count()
+            + asks.windows(2).filter(|pair| pair[1].0 <= pair[0].0).count();
+        let non_positive_qty = bids.iter().chain(asks).filter(|(_, qty)| *qty <= 0.0).count();
+        let crossed = matches!((bids.first(), asks.first()), (Some(bid), Some(ask)) if bid.0 >= ask.0);
+        let cumulative = |levels: &[(f64, f64)]| levels.iter().take(cumulative_levels).map(|(_, qty)| qty).sum();
+
+        Self {
+            bid_levels: bids.len(),
+            ask_levels: asks.len(),
+            ordering_violations,
+            non_positive_qty,
+            crossed,
+            cumulative_levels,
+            cumulative_bid_qty: cumulative(bids),
+            cumulative_ask_qty: cumulative(asks),
+        }
+    }
+
+    fn is_consistent(&self) -> bool {
+        self.ordering_violations == 0 && self.non_positive_qty == 0 && !self.crossed
+    }
+}
+
+/// `(price, quantity)` per level, best level first
+type PriceLevels = Vec<(f64, f64)>;
+
+/// Up to `levels` ticks per side walked out from the touch, keeping any non-zero size so that
+/// negative quantities left by a bad reconstruction show up in the report
+fn raw_levels<MD: MarketDepth>(depth: &MD, levels: usize) -> (PriceLevels, PriceLevels) {
+    let tick_size = depth.tick_size();
+    let walk = |best_tick: i64, step: i64, qty_at: &dyn Fn(i64) -> f64| -> PriceLevels {
+        (0..levels as i64)
+            .map(|i| best_tick + i * step)
+            .map(|tick| (tick as f64 * tick_size, qty_at(tick)))
+            .filter(|(_, qty)| *qty != 0.0)
+            .collect()
+    };
+    (
+        walk(depth.best_bid_tick(), -1, &|tick| depth.bid_qty_at_tick(tick)),
+        walk(depth.best_ask_tick(), 1, &|tick| depth.ask_qty_at_tick(tick)),
+    )
+}
+
+/// JSON-lines writer that continues in `<stem>.1.<ext>`, `<stem>.2.<ext>`, ... once the
+/// current file reaches `max_bytes`
+struct RotatingWriter {
+    base: PathBuf,
+    max_bytes: u64,
+    written: u64,
+    writer: BufWriter<File>,
+    files: Vec<PathBuf>,
+}
+
+impl RotatingWriter {
+    fn create(base: &Path, max_bytes: u64) -> Result<Self> {
+        Ok(Self {
+            base: base.to_path_buf(),
+            max_bytes: max_bytes.max(1),
+            written: 0,
+            writer: BufWriter::new(File::create(base)?),
+            files: vec![base.to_path_buf()],
+        })
+    }
+
+    fn write_line(&mut self, line: &str) -> Result<()> {
+        if self.written >= self.max_bytes {
+            self.rotate()?;
+        }
+        writeln!(self.writer, "{}", line)?;
+        self.written += line.len() as u64 + 1;
+        Ok(())
+    }
+
+    fn rotate(&mut self) -> Result<()> {
+        self.writer.flush()?;
+        let stem = self.base.file_stem().unwrap_or_default().to_string_lossy();
+        let name = match self.base.extension() {
+            Some(ext) => format!("{}.{}.{}", stem, self.files.len(), ext.to_string_lossy()),
+            None => format!("{}.{}", stem, self.files.len()),
+        };
+        let path = self.base.with_file_name(name);
+        self.writer = BufWriter::new(File::create(&path)?);
+        self.files.push(path);
+        self.written = 0;
+        Ok(())
+    }
+
+    fn finish(mut self) -> Result<Vec<PathBuf>> {
+        self.writer.flush()?;
+        Ok(self.files)
+    }
+}
+
+/// Headless replay that writes the reconstructed order book every `interval_ns` of simulation
+/// time instead of trading on it (`--dump-depth <interval_ns> <path>`)
+pub struct DepthDumpRunner {
+    data_files: Vec<String>,
+    interval_ns: i64,
+    output: PathBuf,
+    levels: usize,
+    max_file_bytes: u64,
+    consistency_levels: Option<usize>,
+}
+
+impl DepthDumpRunner {
+    pub fn new(data_files: Vec<String>, interval_ns: i64, output: PathBuf) -> Result<Self> {
+        if data_files.is_empty() {
+            anyhow::bail!("No data files provided");
+        }
+        if interval_ns <= 0 {
+            anyhow::bail!("Depth dump interval must be positive, got {} ns", interval_ns);
+        }
+        Ok(Self {
+            data_files,
+            interval_ns,
+            output,
+            levels: DEPTH_DUMP_LEVELS,
+            max_file_bytes: DEPTH_DUMP_MAX_FILE_BYTES,
+            consistency_levels: DEPTH_DUMP_CONSISTENCY_LEVELS,
+        })
+    }
+
+    /// Replays every file into the dump; returns the number of snapshots written
+    pub fn run(&self) -> Result<usize> {
+        let mut writer = RotatingWriter::create(&self.output, self.max_file_bytes)?;
+        let mut snapshots = 0;
+        let mut inconsistent = 0;
+        for data_file in &self.data_files {
+            log_info!("Dumping depth from: {}", data_file);
+            let mut hbt = create_backtest(data_file, None)?;
+            let (written, flagged) = dump_backtest(
+                &mut hbt, self.interval_ns, self.levels, self.consistency_levels, &mut writer,
+            )?;
+            snapshots += written;
+            inconsistent += flagged;
+        }
+
+        let files = writer.finish()?;
+        if self.consistency_levels.is_some() {
+            log_info!("Consistency: {} of {} snapshots inconsistent", inconsistent, snapshots);
+        }
+        log_info!("Wrote {} snapshots to {} file(s):", snapshots, files.len());
+        for file in &files {
+            log_info!("  {}", file.display());
+        }
+        Ok(snapshots)
+    }
+}
+
+/// Steps `hbt` by `interval_ns` until the data ends, writing a snapshot after each step with a
+/// valid two-sided book; with `consistency_levels` each snapshot carries a consistency report
+/// and inconsistent ones are printed. Returns the snapshots written and how many were flagged
+fn dump_backtest(
+    hbt: &mut Backtest<HashMapMarketDepth>,
+    interval_ns: i64,
+    levels: usize,
+    consistency_levels: Option<usize>,
+    writer: &mut RotatingWriter,
+) -> Result<(usize, usize)> {
+    let mut snapshots = 0;
+    let mut inconsistent = 0;
+    while let Ok(ElapseResult::Ok) = hbt.elapse(interval_ns) {
+        let depth = hbt.depth(0);
+        if !is_valid_depth(depth) {
+            continue;
+        }
+
+        let timestamp_ns = hbt.current_timestamp();
+        let consistency = consistency_levels.map(|cumulative_levels| {
+            let (raw_bids, raw_asks) = raw_levels(depth, levels);
+            BookConsistency::check(&raw_bids, &raw_asks, cumulative_levels)
+        });
+        if let Some(report) = consistency.as_ref().filter(|report| !report.is_consistent()) {
+            log_warn!("  ⚠ Inconsistent book @ {}: {} ordering violation(s), {} non-positive size(s){}",
+                      timestamp_ns, report.ordering_violations, report.non_positive_qty,
+                      if report.crossed { ", crossed" } else { "" });
+            inconsistent += 1;
+        }
+
+        let (bids, asks) = extract_orderbook(depth, levels);
+        let snapshot = DepthSnapshot {
+            timestamp_ns,
+            bids: bids.iter().map(|level| (level.price, level.quantity)).collect(),
+            asks: asks.iter().map(|level| (level.price, level.quantity)).collect(),
+            consistency,
+        };
+        writer.write_line(&serde_json::to_string(&snapshot)?)?;
+        snapshots += 1;
+    }
+    Ok((snapshots, inconsistent))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hftbacktest::types::{DEPTH_EVENT, BUY_EVENT, SELL_EVENT, EXCH_EVENT, LOCAL_EVENT};
+    use crate::common::test_utils::{backtest_from_events, event};
+
+    #[test]
+    fn test_snapshot_count_matches_interval_and_rotates() {
+        // Two-sided book from 1µs, one update every 1ms up to 105ms
+        let mut events = vec![
+            event(DEPTH_EVENT | BUY_EVENT | EXCH_EVENT | LOCAL_EVENT, 1_000, 99.0, 1.0),
+            event(DEPTH_EVENT | SELL_EVENT | EXCH_EVENT | LOCAL_EVENT, 1_000, 101.0, 1.0),
+        ];
+        for step in 1..=105 {
+            events.push(event(DEPTH_EVENT | BUY_EVENT | EXCH_EVENT | LOCAL_EVENT, step * 1_000_000, 99.0, 1.0 + step as f64));
+        }
+        let path = std::env::temp_dir().join(format!("depth_dump_test_{}.jsonl", std::process::id()));
+        let mut writer = RotatingWriter::create(&path, 200).unwrap();
+
+        let mut hbt = backtest_from_events(&events);
+        let (count, inconsistent) = dump_backtest(&mut hbt, 10_000_000, 5, None, &mut writer).unwrap();
+        let files = writer.finish().unwrap();
+
+        // 105ms of data sampled every 10ms: one snapshot per completed interval
+        assert_eq!(count, 10);
+        assert_eq!(inconsistent, 0);
+        assert!(files.len() > 1);
+        let lines: Vec<String> = files.iter()
+            .flat_map(|file| std::fs::read_to_string(file).unwrap().lines().map(str::to_string).collect::<Vec<_>>())
+            .collect();
+        assert_eq!(lines.len(), count);
+        let first: serde_json::Value = serde_json::from_str(&lines[0]).unwrap();
+        assert_eq!(first["timestamp_ns"], 1_000 + 10_000_000);
+        assert_eq!(first["asks"][0][0], 101.0);
+
+        for file in files {
+            let _ = std::fs::remove_file(file);
+        }
+    }
+
+    #[test]
+    fn test_consistency_check_flags_corrupted_book() {
+        let bids = [(100.0, 2.0), (99.0, 3.0), (98.0, 1.0)];
+        let asks = [(101.0, 1.0), (102.0, 4.0)];
+        let clean = BookConsistency::check(&bids, &asks, 2);
+        assert!(clean.is_consistent());
+        assert_eq!((clean.bid_levels, clean.ask_levels), (3, 2));
+        assert_eq!(clean.cumulative_bid_qty, 5.0);
+        assert_eq!(clean.cumulative_ask_qty, 5.0);
+
+        // Bid levels out of order, a negative size and bids through the best ask
+        let bids = [(101.5, 2.0), (101.6, 1.0), (99.0, -1.0)];
+        let asks = [(101.0, 1.0), (101.0, 0.0)];
+        let corrupted = BookConsistency::check(&bids, &asks, 5);
+        assert!(!corrupted.is_consistent());
+        assert_eq!(corrupted.ordering_violations, 2);
+        assert_eq!(corrupted.non_positive_qty, 2);
+        assert!(corrupted.crossed);
+        assert_eq!(corrupted.cumulative_bid_qty, 2.0);
+    }
+}
@@ -1,33 +1,70 @@
 use anyhow::Result;
 use hftbacktest::{
-    backtest::{Backtest, BacktestError, ExchangeKind, L2AssetBuilder, assettype::LinearAsset,
-        data::DataSource, models::{CommonFees, ConstantLatency, ProbQueueModel, 
+    backtest::{Backtest, BacktestError, L2AssetBuilder,
+        models::{CommonFees, ConstantLatency, ProbQueueModel, 
         PowerProbQueueFunc3, TradingValueFeeModel}},
     prelude::{Bot, HashMapMarketDepth, Status, TimeInForce, OrdType},
     depth::MarketDepth,
-    types::ElapseResult,
+    types::{ElapseResult, Event, Order},
 };
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
-use crossbeam_channel::Sender;
-use crate::common::{calculate_mid_price, is_valid_depth};
-use crate::config::{TICK_SIZE, LOT_SIZE, ELAPSE_DURATION_NS, UPDATE_INTERVAL, COMMAND_POLL_TIMEOUT_MICROS};
-use crate::ui::{PerformanceData, OrderBookLevel};
+use crate::common::{calculate_mid_price, AdaptiveElapse, validate_time_in_force, force_flatten, reconcile,
+    FilePreloader, data_source, load_events, HighWaterMark, SessionClock, PriceRounding, bid_price_tick, ask_price_tick, MarkPricer, BorrowCost, FeeDrag,
+    StaleBookGuard, StaleBookEvent, last_book_update_ns, FillTracker,
+    Liquidity, LiquidityClassification, LiquidityStats, crosses_touch};
+use crate::{log_info, log_warn};
+use crate::config::{ADAPTIVE_ELAPSE_QUIET_TICKS, ADAPTIVE_ELAPSE_MAX_MULTIPLIER, TICK_SIZE, LOT_SIZE, ASSET_KIND, PERFORMANCE_FEE_PCT, SHORT_BORROW_RATE_BPS_PER_DAY, EXCHANGE_KIND, MM_TIME_IN_FORCE, MM_VOLATILITY_MIN_SAMPLES, MM_REQUOTE_ON_PRICE_MOVE, MM_REQUOTE_THRESHOLD_TICKS, MM_IMBALANCE_SENSITIVITY, MM_QUEUE_POWER, MM_MIN_QUOTE_SPREAD_TICKS, MM_ADVERSE_SELECTION_BUFFER_TICKS, MM_INVENTORY_BANDS, MM_CLAMP_CROSSED_QUOTES, MM_WIND_DOWN_FRACTION, MM_LAYER_SPACING_TICKS, ORDER_PRICE_ROUNDING, MARK_PRICE_MODE, LAST_TRADES_CAPACITY, VOLATILITY_EWMA_LAMBDA, MM_ELAPSE_DURATION_NS, UPDATE_INTERVAL, COMMAND_POLL_TIMEOUT_MICROS, RUN_TO_END_BATCH,
+    ESTIMATED_FEE_RATE, MAKER_FEE_RATE, TAKER_FEE_RATE, FILL_LIQUIDITY_CLASSIFICATION, FLATTEN_STEP_TICKS, FLATTEN_MAX_ATTEMPTS, FLATTEN_WAIT_NS, MAX_BOOK_STALENESS_NS, FLATTEN_ON_STALE_BOOK, GUI_UPDATE_ON_FILL, GUI_REPLAY_RATE_WINDOW_MS, BOOK_PARTIAL_FILLS, REPORT_FILL_LATENCY};
+use crate::ui::{DataSender, PerformanceData, UpdateCadence, ReplayRateMeter};
 use crate::controller::StrategyController;
+use crate::strategy::base::{send_gui_update, extract_orderbook};
 use super::{MicroPriceCalculator, OrderBookImbalance, SpreadCalculator,
-    RiskManager, OrderTracker, OrderSide};
+    RiskManager, OrderTracker, OrderSide, OrderManager};
 
 pub struct MarketMakerRunner {
     data_files: Vec<PathBuf>,
     micro_price_calc: MicroPriceCalculator,
+    /// Marks the inventory for unrealized PnL and equity
+    mark_pricer: MarkPricer,
     imbalance_calc: OrderBookImbalance,
     spread_calc: SpreadCalculator,
     risk_manager: RiskManager,
     order_tracker: OrderTracker,
+    /// Inventory/PnL booked from partially filled quotes that are still resting
+    fill_tracker: FillTracker,
+    /// Layer spacing for every quote; also places the one-sided quotes of the wind-down phase
+    order_manager: OrderManager,
     order_size: f64,
     order_layers: usize,
+    imbalance_sensitivity: f64,
+    min_quote_spread_ticks: Option<i64>,
+    /// Minimum edge after costs: half spread >= round-trip fee + this many ticks (None = off)
+    adverse_selection_buffer_ticks: Option<f64>,
+    /// Maker fee of both legs as a fraction of the price (negative = net rebate)
+    round_trip_fee_rate: f64,
+    edge_widenings: usize,
+    /// Pull quotes that would cross the spread back to the touch (see MM_CLAMP_CROSSED_QUOTES)
+    clamp_crossed_quotes: bool,
+    crossed_quote_clamps: usize,
+    /// Maker/taker split of the fills, each booked at its side's fee rate
+    liquidity_classification: LiquidityClassification,
+    liquidity_stats: LiquidityStats,
+    price_rounding: PriceRounding,
+    wind_down_fraction: f64,
+    requote_on_price_move: bool,
+    requote_threshold_ticks: f64,
+    /// Reservation price the live quotes were priced at (None = nothing to compare against)
+    last_quoted_fair: Option<f64>,
     initial_capital: f64,
+    performance_fee: HighWaterMark,
+    /// Carry cost of short inventory
+    borrow_cost: BorrowCost,
+    /// Suspends quoting across data gaps
+    stale_book: StaleBookGuard,
+    tif: TimeInForce,
+    elapse_ns: i64,
     // Metrics tracking
     num_trades: usize,
     winning_trades: usize,
@@ -54,9 +91,9 @@ impl MarketMakerRunner {
         if data_files.is_empty() {
             anyhow::bail!("No data files provided");
         }
-        println!("Using {} file(s):", data_files.len());
+        log_info!("Using {} file(s):", data_files.len());
         for (i, f) in data_files.iter().enumerate() {
-            println!("  [{}] {}", i + 1, f.display());
+            log_info!("  [{}] {}", i + 1, f.display());
         }
         Self::create_runner(data_files, gamma, max_inventory, volatility_threshold, order_size, depth_levels, order_layers, initial_capital)
     }
@@ -71,16 +108,56 @@ impl MarketMakerRunner {
         order_layers: usize,
         initial_capital: f64,
     ) -> Result<Self> {
+        validate_time_in_force(&EXCHANGE_KIND, MM_TIME_IN_FORCE)?;
+        validate_imbalance_sensitivity(MM_IMBALANCE_SENSITIVITY)?;
+        validate_wind_down_fraction(MM_WIND_DOWN_FRACTION)?;
+        if MM_LAYER_SPACING_TICKS.is_nan() || MM_LAYER_SPACING_TICKS < 0.0 {
+            anyhow::bail!("Layer spacing must be non-negative, got {}", MM_LAYER_SPACING_TICKS);
+        }
+        if let Some((soft, hard)) = MM_INVENTORY_BANDS {
+            if soft.is_nan() || soft < 0.0 || hard.is_nan() || hard <= soft {
+                anyhow::bail!("Inventory bands need 0 <= soft < hard, got ({}, {})", soft, hard);
+            }
+        }
+        if MM_REQUOTE_THRESHOLD_TICKS.is_nan() || MM_REQUOTE_THRESHOLD_TICKS < 0.0 {
+            anyhow::bail!("Requote threshold must be non-negative, got {}", MM_REQUOTE_THRESHOLD_TICKS);
+        }
+        
         Ok(Self {
             data_files,
             micro_price_calc: MicroPriceCalculator::new(depth_levels),
+            mark_pricer: MarkPricer::new(MARK_PRICE_MODE),
             imbalance_calc: OrderBookImbalance::new(depth_levels),
             spread_calc: SpreadCalculator::new(gamma),
-            risk_manager: RiskManager::new(max_inventory, volatility_threshold, 60),
-            order_tracker: OrderTracker::new(),
+            risk_manager: RiskManager::new(max_inventory, volatility_threshold, VOLATILITY_EWMA_LAMBDA)
+                .with_volatility_burn_in(MM_VOLATILITY_MIN_SAMPLES)
+                .with_inventory_bands(MM_INVENTORY_BANDS),
+            order_tracker: OrderTracker::new(MM_QUEUE_POWER),
+            fill_tracker: FillTracker::new(BOOK_PARTIAL_FILLS),
+            // GTC so the wind-down quote is allowed to cross the spread
+            order_manager: OrderManager::new(order_layers, MM_LAYER_SPACING_TICKS).with_time_in_force(TimeInForce::GTC),
             order_size,
             order_layers,
+            imbalance_sensitivity: MM_IMBALANCE_SENSITIVITY,
+            min_quote_spread_ticks: MM_MIN_QUOTE_SPREAD_TICKS,
+            adverse_selection_buffer_ticks: MM_ADVERSE_SELECTION_BUFFER_TICKS,
+            round_trip_fee_rate: 2.0 * Liquidity::Maker.fee_rate(MAKER_FEE_RATE, TAKER_FEE_RATE),
+            edge_widenings: 0,
+            clamp_crossed_quotes: MM_CLAMP_CROSSED_QUOTES,
+            crossed_quote_clamps: 0,
+            liquidity_classification: FILL_LIQUIDITY_CLASSIFICATION,
+            liquidity_stats: LiquidityStats::default(),
+            price_rounding: ORDER_PRICE_ROUNDING,
+            wind_down_fraction: MM_WIND_DOWN_FRACTION,
+            requote_on_price_move: MM_REQUOTE_ON_PRICE_MOVE,
+            requote_threshold_ticks: MM_REQUOTE_THRESHOLD_TICKS,
+            last_quoted_fair: None,
             initial_capital,
+            performance_fee: HighWaterMark::new(PERFORMANCE_FEE_PCT, initial_capital),
+            borrow_cost: BorrowCost::new(SHORT_BORROW_RATE_BPS_PER_DAY),
+            stale_book: StaleBookGuard::new(MAX_BOOK_STALENESS_NS, FLATTEN_ON_STALE_BOOK),
+            tif: MM_TIME_IN_FORCE,
+            elapse_ns: MM_ELAPSE_DURATION_NS,
             num_trades: 0,
             winning_trades: 0,
             total_orders: 0,
@@ -90,55 +167,117 @@ impl MarketMakerRunner {
         })
     }
     
+    /// Price skew applied to both sides of the quote: positive imbalance raises bid and ask
+    fn imbalance_adjustment(&self, imbalance: f64, half_spread: f64) -> f64 {
+        imbalance * half_spread * self.imbalance_sensitivity
+    }
+
+    /// Smallest half spread worth quoting around `fair`, in price terms: the round-trip fee per
+    /// unit plus the adverse selection buffer (None with the filter off)
+    fn min_edge(&self, fair: f64, tick_size: f64) -> Option<f64> {
+        let buffer_ticks = self.adverse_selection_buffer_ticks?;
+        Some(self.round_trip_fee_rate * fair + buffer_ticks * tick_size)
+    }
+
+    /// `half_spread`, widened to the minimum edge after costs when it falls short of it
+    fn edge_half_spread(&mut self, half_spread: f64, fair: f64, tick_size: f64) -> f64 {
+        match self.min_edge(fair, tick_size) {
+            Some(min_edge) if half_spread < min_edge => {
+                self.edge_widenings += 1;
+                min_edge
+            }
+            _ => half_spread,
+        }
+    }
+
+    /// Quote ticks for one layer, kept at or outside the touch when a minimum spread is set.
+    /// None when the guarded quotes end up closer together than `min_quote_spread_ticks`.
+    fn quote_ticks(&self, bid_price: f64, ask_price: f64, tick_size: f64, best_bid_tick: i64, best_ask_tick: i64) -> Option<(i64, i64)> {
+        let bid_tick = bid_price_tick(bid_price, tick_size, self.price_rounding);
+        let ask_tick = ask_price_tick(ask_price, tick_size, self.price_rounding);
+        let Some(min_spread_ticks) = self.min_quote_spread_ticks else {
+            return Some((bid_tick, ask_tick));
+        };
+
+        let bid_tick = bid_tick.min(best_bid_tick);
+        let ask_tick = ask_tick.max(best_ask_tick);
+        if ask_tick - bid_tick < min_spread_ticks {
+            return None;
+        }
+        Some((bid_tick, ask_tick))
+    }
+
+    /// Post-only audit of one side's quote tick: a bid at or above the best ask is moved to the
+    /// best bid, an ask at or below the best bid to the best ask, and the clamp is logged and
+    /// counted. Ticks that don't cross (or any tick with the audit off) pass through.
+    fn audit_quote_tick(&mut self, side: OrderSide, tick: i64, layer: usize, best_bid_tick: i64, best_ask_tick: i64) -> i64 {
+        if !self.clamp_crossed_quotes {
+            return tick;
+        }
+        let clamped = match side {
+            OrderSide::Buy if tick >= best_ask_tick => best_bid_tick,
+            OrderSide::Sell if tick <= best_bid_tick => best_ask_tick,
+            _ => return tick,
+        };
+        self.crossed_quote_clamps += 1;
+        log_warn!("  ⚠ Layer {} {:?} quote at tick {} crosses the touch ({} / {}), clamped to {}",
+                  layer + 1, side, tick, best_bid_tick, best_ask_tick, clamped);
+        clamped
+    }
+
     /// Extract order book levels from market depth
-    fn extract_orderbook<MD>(&self, depth: &MD, levels: usize) -> (Vec<OrderBookLevel>, Vec<OrderBookLevel>)
+    /// Whether `fair` has moved beyond the requote threshold from the price the live quotes
+    /// were set at
+    fn fair_price_drifted(&self, fair: f64, tick_size: f64) -> bool {
+        self.requote_on_price_move && self.last_quoted_fair
+            .is_some_and(|quoted| (fair - quoted).abs() > self.requote_threshold_ticks * tick_size)
+    }
+
+    /// Event-driven requote: once the fair price has drifted, cancel every quote so the next
+    /// refill replaces them at the new fair price. Returns whether the quotes were pulled.
+    fn requote_on_drift<MD>(
+        &mut self,
+        hbt: &mut Backtest<MD>,
+        inventory: f64,
+    ) -> Result<bool, BacktestError>
     where
         MD: MarketDepth,
     {
-        let mut bids = Vec::new();
-        let mut asks = Vec::new();
-        
-        let best_bid_tick = depth.best_bid_tick();
-        let best_ask_tick = depth.best_ask_tick();
+        let depth = hbt.depth(0);
         let tick_size = depth.tick_size();
-        
-        if best_bid_tick != i64::MIN {
-            for i in 0..levels {
-                let tick = best_bid_tick - i as i64;
-                let qty = depth.bid_qty_at_tick(tick);
-                if qty > 0.0 {
-                    bids.push(OrderBookLevel {
-                        price: tick as f64 * tick_size,
-                        quantity: qty,
-                    });
-                }
-            }
-        }
-        
-        if best_ask_tick != i64::MAX {
-            for i in 0..levels {
-                let tick = best_ask_tick + i as i64;
-                let qty = depth.ask_qty_at_tick(tick);
-                if qty > 0.0 {
-                    asks.push(OrderBookLevel {
-                        price: tick as f64 * tick_size,
-                        quantity: qty,
-                    });
-                }
-            }
+        let fair = self.spread_calc.calculate_reservation_price(
+            self.micro_price_calc.calculate(depth), inventory, self.risk_manager.calculate_volatility()
+        );
+        if !self.fair_price_drifted(fair, tick_size) {
+            return Ok(false);
         }
-        
-        (bids, asks)
+
+        log_info!("  ↻ Fair price moved {:.5} -> {:.5}, requoting",
+                  self.last_quoted_fair.unwrap_or_default(), fair);
+        self.last_quoted_fair = None;
+        self.order_manager.cancel_all_orders(hbt)?;
+        Ok(true)
+    }
+
+    /// Feed the tick's gross equity to the performance fee's high-water mark
+    fn accrue_performance_fee<MD: MarketDepth>(&mut self, depth: &MD, cash: f64, realized_pnl: f64, inventory: f64) {
+        let Some(mid_price) = calculate_mid_price(depth) else {
+            return;
+        };
+        let mark_price = self.mark_pricer.mark(depth).unwrap_or(mid_price);
+        self.performance_fee.update(cash + realized_pnl + inventory * mark_price - self.borrow_cost.accrued());
     }
 
     /// Run strategy with GUI monitor and Controller
     pub fn run_with_controller(
         &mut self,
-        sender: Sender<PerformanceData>,
+        sender: DataSender,
         controller: Arc<StrategyController>,
     ) -> Result<()> {
         let file_count = self.data_files.len();
         
+        let mut preload: Option<FilePreloader> = None;
+        
         for file_idx in 0..file_count {
             // Wait for start signal if in paused or stopped state
             while !controller.is_running() && !controller.should_stop() {
@@ -146,35 +285,43 @@ impl MarketMakerRunner {
             }
             
             if controller.should_stop() {
-                println!("\n⏹ Strategy stopped by user");
+                log_info!("\n⏹ Strategy stopped by user");
                 break;
             }
             
             let data_file = self.data_files[file_idx].clone();
+            let preloaded = preload.take().and_then(FilePreloader::wait);
+            // Load the next file in the background while this one runs
+            preload = self.data_files.get(file_idx + 1)
+                .map(|next| FilePreloader::spawn(next.to_string_lossy().into_owned()));
             
             // Notify GUI to clear chart data for new file (except first file)
             if file_idx > 0 {
                 controller.notify_new_file();
             }
             
-            println!("\n{}", "=".repeat(60));
-            println!("Running strategy on file [{}/{}]: {}", 
-                     file_idx + 1, 
-                     file_count, 
-                     data_file.display());
-            println!("{}\n", "=".repeat(60));
+            log_info!("\n{}", "=".repeat(60));
+            log_info!("Running strategy on file [{}/{}]: {}", 
+                      file_idx + 1, 
+                      file_count, 
+                      data_file.display());
+            log_info!("{}\n", "=".repeat(60));
             
             self.run_strategy_with_control(
                 data_file.to_str().unwrap(),
+                preloaded,
                 &sender,
                 &controller,
             )?;
         }
         
+        // Stopped before the next file was needed: discard it; the loader thread is detached
+        drop(preload);
+        
         if !controller.should_stop() {
             controller.mark_completed();
-            println!("\n✅ All files processed successfully!");
-            println!("📤 Sent Completed signal to GUI");
+            log_info!("\n✅ All files processed successfully!");
+            log_info!("📤 Sent Completed signal to GUI");
         }
         
         // Keep thread alive to process commands until GUI closes
@@ -184,7 +331,7 @@ impl MarketMakerRunner {
     }
     
     fn keep_alive_until_close(&self, controller: &StrategyController) {
-        println!("Backtest finished. Close the window to exit.");
+        log_info!("Backtest finished. Close the window to exit.");
         
         loop {
             if !controller.process_commands(Duration::from_millis(200)) {
@@ -200,25 +347,50 @@ impl MarketMakerRunner {
     fn run_strategy_with_control(
         &mut self,
         data_file: &str,
-        sender: &Sender<PerformanceData>,
+        preloaded: Option<Vec<Event>>,
+        sender: &DataSender,
         controller: &StrategyController,
     ) -> Result<()> {
-        println!("Loading data from: {}", data_file);
+        log_info!("Loading data from: {}", data_file);
 
-        let mut hbt = self.create_backtest(data_file)?;
+        // The wind-down needs the file's time span up front, so its events are loaded here
+        let (preloaded, session) = if self.wind_down_fraction > 0.0 {
+            let events = match preloaded {
+                Some(events) => events,
+                None => load_events(data_file)?,
+            };
+            let session = SessionClock::from_events(&events);
+            (Some(events), session)
+        } else {
+            (preloaded, None)
+        };
+
+        let mut hbt = self.create_backtest(data_file, preloaded)?;
+        // Each file's equity curve starts from the initial capital again
+        self.performance_fee = HighWaterMark::new(PERFORMANCE_FEE_PCT, self.initial_capital);
+        self.liquidity_stats = LiquidityStats::default();
         
-        println!("Market making strategy started...\n");
+        log_info!("Market making strategy started...\n");
 
         let mut inventory = 0.0;
         let mut realized_pnl = 0.0;
         let cash = self.initial_capital;
         let mut initial_price = 0.0;
         let mut update_count = 0;
+        let mut stepper = AdaptiveElapse::new(self.elapse_ns, ADAPTIVE_ELAPSE_QUIET_TICKS, ADAPTIVE_ELAPSE_MAX_MULTIPLIER);
         let mut initial_orders_placed = false;
+        let mut wind_down_started = false;
+        // Quotes were pulled on a fair-price move; refill on the next step
+        let mut requote_pending = false;
+        self.last_quoted_fair = None;
+        self.borrow_cost.reset();
+        self.stale_book.reset();
+        self.fill_tracker.reset();
 
-        println!("Waiting for market data...\n");
+        log_info!("Waiting for market data...\n");
 
-        let mut last_gui_update = Instant::now();
+        let mut gui_cadence = UpdateCadence::new(Duration::from_millis(33), GUI_UPDATE_ON_FILL);
+        let mut replay_rate = ReplayRateMeter::new(Duration::from_millis(GUI_REPLAY_RATE_WINDOW_MS));
         let mut last_command_check = Instant::now();
         let command_check_interval = Duration::from_millis(16); // ~60Hz command polling
         let mut data_ended = false;
@@ -226,7 +398,7 @@ impl MarketMakerRunner {
         loop {
             // Check if data has ended
             if data_ended {
-                println!("\nEnd of data reached!");
+                log_info!("\nEnd of data reached!");
                 return self.finish_strategy(hbt, inventory, realized_pnl, cash, initial_price);
             }
             
@@ -236,7 +408,7 @@ impl MarketMakerRunner {
                 controller.process_commands(Duration::from_millis(50));
                 
                 if controller.should_stop() {
-                    println!("\n⏹ Strategy stopped by user");
+                    log_info!("\n⏹ Strategy stopped by user");
                     break;
                 }
                 continue;
@@ -248,18 +420,21 @@ impl MarketMakerRunner {
                 last_command_check = Instant::now();
                 
                 if controller.should_stop() {
-                    println!("\n⏹ Strategy stopped by user");
+                    log_info!("\n⏹ Strategy stopped by user");
                     break;
                 }
             }
             
             // Speed adjustment - affects simulation time
             let speed = controller.speed_multiplier();
+            let run_to_end = controller.is_run_to_end();
             
             // Calculate iterations and delay based on speed
             // Base: 1x speed = 1 iteration per 10ms
             // Higher speed: more iterations per loop, less delay
-            let (iterations_per_loop, loop_delay_ms) = if speed >= 100.0 {
+            let (iterations_per_loop, loop_delay_ms) = if run_to_end {
+                (RUN_TO_END_BATCH, 0u64)
+            } else if speed >= 100.0 {
                 // Maximum speed: no delay, many iterations
                 (100, 0u64)
             } else if speed >= 10.0 {
@@ -274,43 +449,86 @@ impl MarketMakerRunner {
             };
             
             for _ in 0..iterations_per_loop {
+                // A seek stops fast-forwarding here and pauses the run
+                if controller.reached_seek_target(stepper.sim_time_secs()) {
+                    break;
+                }
+                replay_rate.observe(hbt.current_timestamp());
                 // Simulate time passing in backtest
-                match hbt.elapse(ELAPSE_DURATION_NS) {
+                match hbt.elapse(stepper.duration_ns()) {
                     Ok(ElapseResult::EndOfData) => {
                         data_ended = true;
                         break;
                     }
                     Ok(_) => {
+                        self.mark_pricer.observe_trades(hbt.last_trades(0));
+                        hbt.clear_last_trades(Some(0));
+                        match self.stale_book.update(last_book_update_ns(&hbt), hbt.current_timestamp()) {
+                            Some(StaleBookEvent::Stale { gap_ns }) => {
+                                log_warn!("  ⚠ No book update for {:.1}s, quoting suspended", gap_ns as f64 / 1e9);
+                                if self.stale_book.flattens() && initial_orders_placed {
+                                    self.order_manager.cancel_all_orders(&mut hbt)?;
+                                    initial_orders_placed = false;
+                                }
+                            }
+                            Some(StaleBookEvent::Resumed { gap_ns }) => {
+                                log_info!("  ✓ Book updates resumed after a {:.1}s gap", gap_ns as f64 / 1e9);
+                            }
+                            None => {}
+                        }
+                        if self.stale_book.is_stale() {
+                            continue;
+                        }
                         let depth = hbt.depth(0);
                         
-                        if !is_valid_depth(depth) {
+                        let Some(mid_price) = calculate_mid_price(depth) else {
                             continue;
-                        }
+                        };
                         
                         update_count += 1;
+                        stepper.observe(depth);
+                        self.risk_manager.update_price(mid_price);
+                        self.borrow_cost.accrue(inventory, mid_price, hbt.current_timestamp());
                         
                         if initial_price == 0.0 {
-                            initial_price = calculate_mid_price(depth);
-                            println!("Initial price set: {:.2}\n", initial_price);
+                            initial_price = mid_price;
+                            log_info!("Initial price set: {:.2}\n", initial_price);
                             
                             let _ = depth;
-                            self.place_initial_orders(&mut hbt)?;
-                            initial_orders_placed = true;
+                            initial_orders_placed = self.place_initial_orders(&mut hbt)?;
                             continue;
                         }
                         
                         if !initial_orders_placed {
                             let _ = depth;
-                            self.place_initial_orders(&mut hbt)?;
-                            initial_orders_placed = true;
+                            initial_orders_placed = self.place_initial_orders(&mut hbt)?;
                             continue;
                         }
                         
-                        if update_count % UPDATE_INTERVAL == 0 {
-                            let _ = depth;
+                        if !wind_down_started && self.requote_on_drift(&mut hbt, inventory)? {
+                            requote_pending = true;
+                            continue;
+                        }
+
+                        if update_count % UPDATE_INTERVAL == 0 || requote_pending {
+                            requote_pending = false;
                             
+                            let wind_down = session.and_then(|clock| {
+                                clock.wind_down_progress(hbt.current_timestamp(), self.wind_down_fraction)
+                            });
+                            if wind_down.is_some() && !wind_down_started {
+                                wind_down_started = true;
+                                log_info!("\n⏳ Wind-down: quoting only to reduce inventory {:.4}\n", inventory);
+                            }
+
                             // Process orders and refill
-                            self.check_and_refill_orders(&mut hbt, &mut inventory, &mut realized_pnl)?;
+                            self.check_and_refill_orders(&mut hbt, &mut inventory, &mut realized_pnl, wind_down)?;
+                            self.accrue_performance_fee(hbt.depth(0), cash, realized_pnl, inventory);
+                            // Stop batching so the fill's update carries its own timestamp
+                            gui_cadence.observe_fills(self.order_tracker.filled_count());
+                            if gui_cadence.fill_pending() && !run_to_end {
+                                break;
+                            }
                         }
                     }
                     Err(_) => {
@@ -320,27 +538,30 @@ impl MarketMakerRunner {
                 }
             }
             
+            replay_rate.observe(hbt.current_timestamp());
+
             // Send data to GUI (throttled to ~30 FPS, non-blocking)
-            if last_gui_update.elapsed() >= Duration::from_millis(33) {
+            // In run-to-end mode only the final state is sent
+            let gui_due = gui_cadence.due(run_to_end, data_ended);
+            if gui_due {
                 let depth_for_data = hbt.depth(0);
-                if is_valid_depth(depth_for_data) {
-                    let mid_price = calculate_mid_price(depth_for_data);
-                    let unrealized_pnl = inventory * (mid_price - initial_price);
-                    let position_value = inventory * mid_price;
+                if let Some(mid_price) = calculate_mid_price(depth_for_data) {
+                    let mark_price = self.mark_pricer.mark(depth_for_data).unwrap_or(mid_price);
+                    let unrealized_pnl = inventory * (mark_price - initial_price);
                     
-                    let (bids, asks) = self.extract_orderbook(depth_for_data, 10);
+                    let (bids, asks) = extract_orderbook(depth_for_data, 10);
                     let avg_hold_time = if self.num_trades > 0 {
                         self.total_hold_time.as_secs_f64() / self.num_trades as f64
                     } else {
                         0.0
                     };
                     
-                    // Use try_send to avoid blocking GUI
+                    // Never blocks the GUI; the newest update wins when the channel is full
                     // timestamp = simulation time in seconds
-                    let sim_time_secs = update_count as f64 * (ELAPSE_DURATION_NS as f64 / 1_000_000_000.0);
-                    let _ = sender.try_send(PerformanceData {
+                    let sim_time_secs = stepper.sim_time_secs();
+                    send_gui_update(sender, controller, PerformanceData {
                         timestamp: sim_time_secs,
-                        equity: cash + realized_pnl + position_value,
+                        equity: self.performance_fee.net_equity(),
                         realized_pnl,
                         unrealized_pnl,
                         position: inventory,
@@ -354,15 +575,27 @@ impl MarketMakerRunner {
                         latency_micros: 100,
                         bids,
                         asks,
+                        stop_price: None,
+                        take_price: None,
+                        performance_fees_accrued: self.performance_fee.accrued(),
+                        borrow_costs: self.borrow_cost.accrued(),
+                        fees_paid: self.liquidity_stats.total_fees(),
+                        attribution: None,
+                        spread_capture: self.order_tracker.spread_capture(),
+                        imbalance: Some(self.imbalance_calc.calculate(depth_for_data)),
+                        fill_update: gui_cadence.fill_pending(),
+                        signal_confidence: None,
+                        sleeves: Vec::new(),
+                        replay_rate: replay_rate.rate(),
                     });
                 }
-                last_gui_update = Instant::now();
+                gui_cadence.mark_sent();
             }
             
             // Apply speed-based delay
             if loop_delay_ms > 0 {
                 std::thread::sleep(Duration::from_millis(loop_delay_ms));
-            } else {
+            } else if !run_to_end {
                 std::thread::yield_now();
             }
         }
@@ -371,14 +604,19 @@ impl MarketMakerRunner {
     }
     
     fn finish_strategy(
-        &self,
-        hbt: Backtest<HashMapMarketDepth>,
-        inventory: f64,
-        realized_pnl: f64,
+        &mut self,
+        mut hbt: Backtest<HashMapMarketDepth>,
+        mut inventory: f64,
+        mut realized_pnl: f64,
         cash: f64,
         initial_price: f64,
     ) -> Result<()> {
+        self.flatten_inventory(&mut hbt, &mut inventory, &mut realized_pnl)?;
         let final_depth = hbt.depth(0);
+        // realized_pnl here is quote cash flow, so the open inventory is marked at mid
+        if let Some(mid_price) = calculate_mid_price(final_depth) {
+            reconcile(&hbt, "Market Making", inventory, realized_pnl + inventory * mid_price);
+        }
         
         self.print_final_stats(
             inventory,
@@ -391,11 +629,57 @@ impl MarketMakerRunner {
         Ok(())
     }
 
+    /// Pull the quotes and liquidate the remaining inventory, crossing the spread more
+    /// aggressively until flat; the flatten price and PnL are reported separately from quote fills
+    fn flatten_inventory<MD>(
+        &mut self,
+        hbt: &mut Backtest<MD>,
+        inventory: &mut f64,
+        realized_pnl: &mut f64,
+    ) -> Result<(), BacktestError>
+    where
+        MD: MarketDepth,
+    {
+        if inventory.abs() < LOT_SIZE / 2.0 {
+            return Ok(());
+        }
+
+        self.order_manager.cancel_all_orders(hbt)?;
+
+        log_info!("Flattening remaining inventory {:.4}...", inventory);
+        let result = force_flatten(
+            hbt, self.order_manager.order_ids(), *inventory, LOT_SIZE,
+            FLATTEN_STEP_TICKS, FLATTEN_MAX_ATTEMPTS, FLATTEN_WAIT_NS,
+        )?;
+
+        if result.filled_qty > 0.0 {
+            let direction = inventory.signum();
+            let proceeds = direction * result.avg_price * result.filled_qty;
+            let fee = result.avg_price * result.filled_qty * ESTIMATED_FEE_RATE;
+            *realized_pnl += proceeds - fee;
+            *inventory -= direction * result.filled_qty;
+            self.liquidity_stats.record(Liquidity::Taker, fee);
+
+            log_info!("  ✓ Final flatten @ {:.2} qty {:.4} after {} attempt(s) | Proceeds: {:+.2} | Fee: {:.4}",
+                      result.avg_price, result.filled_qty, result.attempts, proceeds, fee);
+        }
+
+        if result.is_flat() {
+            *inventory = 0.0;
+        } else {
+            log_warn!("  ✗ Final flatten incomplete: {:.4} still open after {} attempt(s)",
+                      inventory, result.attempts);
+        }
+
+        Ok(())
+    }
+
     fn check_and_refill_orders<MD>(
         &mut self,
         hbt: &mut Backtest<MD>,
         inventory: &mut f64,
         realized_pnl: &mut f64,
+        wind_down: Option<f64>,
     ) -> Result<(), BacktestError>
     where
         MD: MarketDepth,
@@ -403,75 +687,89 @@ impl MarketMakerRunner {
         let depth = hbt.depth(0);
         let tick_size = depth.tick_size();
         
+        let now_ns = hbt.current_timestamp();
         let orders = hbt.orders(0);
         let mut filled_orders = Vec::new();
         let mut expired_orders = Vec::new();
         
         for layer in 0..self.order_layers {
-            let buy_order_id = (layer * 2) as u64;
-            let sell_order_id = (layer * 2 + 1) as u64;
+            let buy_order_id = self.order_manager.slot_order_id(layer, OrderSide::Buy);
+            let sell_order_id = self.order_manager.slot_order_id(layer, OrderSide::Sell);
             
-            if let Some(order) = orders.get(&buy_order_id) {
-                if order.status == Status::Filled {
-                    let fill_price = order.price_tick as f64 * tick_size;
-                    let fill_qty = order.qty;
+            if let Some((buy_order_id, order)) = buy_order_id.and_then(|id| orders.get(&id).map(|order| (id, order))) {
+                // Partial executions are booked as they come; the quote keeps resting until done,
+                // and whatever it executed counts towards the fill stats once it's done
+                let fill = self.fill_tracker.poll(order);
+                if fill.qty > 0.0 {
+                    let fill_price = fill.price;
+                    let fill_qty = fill.qty;
                     
                     *inventory += fill_qty;
                     
                     let cost = fill_price * fill_qty;
-                    let fee = cost * 0.0001;
+                    let (liquidity, fee) = self.book_liquidity(buy_order_id, order, cost);
                     *realized_pnl -= cost;
-                    *realized_pnl += fee;
+                    *realized_pnl -= fee;
                     
-                    filled_orders.push((buy_order_id, OrderSide::Buy, fill_price, fill_qty, layer));
-                    
-                    println!("  ✓ BUY  filled @ {:.2} qty {:.4} | Layer {} | Cost: -{:.2} + Fee: +{:.4}", 
-                             fill_price, fill_qty, layer + 1, cost, fee);
-                    
-                    self.order_tracker.mark_filled(buy_order_id);
-                } else if order.status == Status::Expired || order.status == Status::Canceled {
-                    expired_orders.push((buy_order_id, OrderSide::Buy, layer));
+                    let label = if order.status == Status::Filled { "filled " } else { "partial" };
+                    log_info!("  ✓ BUY  {} @ {:.2} qty {:.4} | Layer {} | Cost: -{:.2} | {:?} Fee: {:+.4}", 
+                              label, fill_price, fill_qty, layer + 1, cost, liquidity, -fee);
+                }
+                if fill.done && fill.total_qty > 0.0 {
+                    filled_orders.push((buy_order_id, OrderSide::Buy, fill.price, fill.total_qty, layer));
+                    self.order_tracker.mark_filled(buy_order_id, fill.total_qty, now_ns);
+                } else if fill.done {
+                    expired_orders.push((OrderSide::Buy, layer));
                 }
             } else {
-                expired_orders.push((buy_order_id, OrderSide::Buy, layer));
+                expired_orders.push((OrderSide::Buy, layer));
             }
             
-            if let Some(order) = orders.get(&sell_order_id) {
-                if order.status == Status::Filled {
-                    let fill_price = order.price_tick as f64 * tick_size;
-                    let fill_qty = order.qty;
+            if let Some((sell_order_id, order)) = sell_order_id.and_then(|id| orders.get(&id).map(|order| (id, order))) {
+                let fill = self.fill_tracker.poll(order);
+                if fill.qty > 0.0 {
+                    let fill_price = fill.price;
+                    let fill_qty = fill.qty;
                     
                     *inventory -= fill_qty;
                     
                     let revenue = fill_price * fill_qty;
-                    let fee = revenue * 0.0001;
+                    let (liquidity, fee) = self.book_liquidity(sell_order_id, order, revenue);
                     *realized_pnl += revenue;
-                    *realized_pnl += fee;
-                    
-                    filled_orders.push((sell_order_id, OrderSide::Sell, fill_price, fill_qty, layer));
+                    *realized_pnl -= fee;
                     
-                    println!("  ✓ SELL filled @ {:.2} qty {:.4} | Layer {} | Revenue: +{:.2} + Fee: +{:.4}", 
-                             fill_price, fill_qty, layer + 1, revenue, fee);
-                    
-                    self.order_tracker.mark_filled(sell_order_id);
-                } else if order.status == Status::Expired || order.status == Status::Canceled {
-                    expired_orders.push((sell_order_id, OrderSide::Sell, layer));
+                    let label = if order.status == Status::Filled { "filled " } else { "partial" };
+                    log_info!("  ✓ SELL {} @ {:.2} qty {:.4} | Layer {} | Revenue: +{:.2} | {:?} Fee: {:+.4}", 
+                              label, fill_price, fill_qty, layer + 1, revenue, liquidity, -fee);
+                }
+                if fill.done && fill.total_qty > 0.0 {
+                    filled_orders.push((sell_order_id, OrderSide::Sell, fill.price, fill.total_qty, layer));
+                    self.order_tracker.mark_filled(sell_order_id, fill.total_qty, now_ns);
+                } else if fill.done {
+                    expired_orders.push((OrderSide::Sell, layer));
                 }
             } else {
-                expired_orders.push((sell_order_id, OrderSide::Sell, layer));
+                expired_orders.push((OrderSide::Sell, layer));
             }
         }
+        // Every closed order was handled above and its slot gets a fresh ID on resubmission
+        hbt.clear_inactive_orders(Some(0));
+        let depth = hbt.depth(0);
         
+        if let Some(progress) = wind_down {
+            return self.wind_down_orders(hbt, *inventory, progress);
+        }
+
         let orders_to_resubmit: Vec<_> = filled_orders.into_iter()
-            .map(|(id, side, _, _, layer)| (id, side, layer, true))
+            .map(|(_, side, _, _, layer)| (side, layer, true))
             .chain(expired_orders.into_iter()
-                .map(|(id, side, layer)| (id, side, layer, false)))
+                .map(|(side, layer)| (side, layer, false)))
             .collect();
         
         if !orders_to_resubmit.is_empty() {
-            if orders_to_resubmit.iter().any(|(_, _, _, filled)| *filled) {
-                println!("  → Refilling {} filled order(s)...", 
-                         orders_to_resubmit.iter().filter(|(_, _, _, f)| *f).count());
+            if orders_to_resubmit.iter().any(|(_, _, filled)| *filled) {
+                log_info!("  → Refilling {} filled order(s)...", 
+                          orders_to_resubmit.iter().filter(|(_, _, f)| *f).count());
             }
             
             let micro_price = self.micro_price_calc.calculate(depth);
@@ -483,46 +781,69 @@ impl MarketMakerRunner {
             );
             
             let fixed_spread = crate::config::FIXED_SPREAD_TICKS * tick_size;
-            let half_spread = fixed_spread / 2.0;
-            let imbalance_adjustment = imbalance * half_spread * 0.1;
+            let half_spread = self.edge_half_spread(fixed_spread / 2.0, reservation_price, tick_size);
+            let imbalance_adjustment = self.imbalance_adjustment(imbalance, half_spread);
             
+            self.last_quoted_fair = Some(reservation_price);
             let adjusted_size = self.risk_manager.adjust_order_size(self.order_size, *inventory);
+            let best_bid_tick = depth.best_bid_tick();
+            let best_ask_tick = depth.best_ask_tick();
             
-            for (order_id, side, layer, _) in orders_to_resubmit {
-                let layer_offset = layer as f64 * 1.0 * tick_size;
-                let layer_size = adjusted_size / (1.0 + layer as f64 * 0.5);
+            for (side, layer, _) in orders_to_resubmit {
+                // Past the hard inventory band the side that would grow the position stays empty
+                let side_multiplier = self.risk_manager.side_size_multiplier(*inventory, side);
+                if side_multiplier <= 0.0 {
+                    continue;
+                }
+                let layer_offset = self.order_manager.layer_offset(layer, tick_size);
+                let layer_size = adjusted_size / (1.0 + layer as f64 * 0.5) * side_multiplier;
+                let bid_price = reservation_price - half_spread - layer_offset + imbalance_adjustment;
+                let ask_price = reservation_price + half_spread + layer_offset - imbalance_adjustment;
+                let Some((bid_tick, ask_tick)) = self.quote_ticks(bid_price, ask_price, tick_size, best_bid_tick, best_ask_tick) else {
+                    log_warn!("  ⏸ Skipping layer {} refill: quote spread below {} tick(s)",
+                              layer + 1, self.min_quote_spread_ticks.unwrap_or_default());
+                    continue;
+                };
+                let (bid_tick, ask_tick) = match side {
+                    OrderSide::Buy => (self.audit_quote_tick(side, bid_tick, layer, best_bid_tick, best_ask_tick), ask_tick),
+                    OrderSide::Sell => (bid_tick, self.audit_quote_tick(side, ask_tick, layer, best_bid_tick, best_ask_tick)),
+                };
+                self.order_tracker.record_quote(bid_tick as f64 * tick_size, ask_tick as f64 * tick_size);
                 
+                let order_id = self.order_manager.assign_slot(layer, side);
                 match side {
                     OrderSide::Buy => {
-                        let bid_price = reservation_price - half_spread - layer_offset + imbalance_adjustment;
-                        let bid_tick = (bid_price / tick_size).round() as i64;
+                        let bid_price = bid_tick as f64 * tick_size;
+                        let queue_ahead = hbt.depth(0).bid_qty_at_tick(bid_tick);
                         
                         if let Ok(_) = hbt.submit_buy_order(
                             0, 
                             order_id, 
-                            bid_tick as f64,
+                            bid_price,
                             layer_size, 
-                            TimeInForce::GTX,
+                            self.tif,
                             OrdType::Limit, 
                             false
                         ) {
-                            self.order_tracker.register_order(order_id, OrderSide::Buy, bid_price, layer_size, layer);
+                            self.order_tracker.register_order(order_id, OrderSide::Buy, bid_price, layer_size, layer, queue_ahead, hbt.current_timestamp());
+                            self.order_tracker.set_crossed_touch(order_id, crosses_touch(true, bid_tick, best_bid_tick, best_ask_tick));
                         }
                     }
                     OrderSide::Sell => {
-                        let ask_price = reservation_price + half_spread + layer_offset - imbalance_adjustment;
-                        let ask_tick = (ask_price / tick_size).round() as i64;
+                        let ask_price = ask_tick as f64 * tick_size;
+                        let queue_ahead = hbt.depth(0).ask_qty_at_tick(ask_tick);
                         
                         if let Ok(_) = hbt.submit_sell_order(
                             0, 
                             order_id, 
-                            ask_tick as f64,
+                            ask_price,
                             layer_size, 
-                            TimeInForce::GTX,
+                            self.tif,
                             OrdType::Limit, 
                             false
                         ) {
-                            self.order_tracker.register_order(order_id, OrderSide::Sell, ask_price, layer_size, layer);
+                            self.order_tracker.register_order(order_id, OrderSide::Sell, ask_price, layer_size, layer, queue_ahead, hbt.current_timestamp());
+                            self.order_tracker.set_crossed_touch(order_id, crosses_touch(false, ask_tick, best_bid_tick, best_ask_tick));
                         }
                     }
                 }
@@ -532,13 +853,72 @@ impl MarketMakerRunner {
         Ok(())
     }
 
-    fn place_initial_orders<MD>(
+    /// Classifies a fill of `notional` as maker or taker and records the fee it pays at that
+    /// side's rate (negative = rebate)
+    fn book_liquidity(&mut self, order_id: u64, order: &Order, notional: f64) -> (Liquidity, f64) {
+        let liquidity = self.liquidity_classification.classify(order, self.order_tracker.crossed_touch(order_id));
+        let fee = notional * liquidity.fee_rate(MAKER_FEE_RATE, TAKER_FEE_RATE);
+        self.liquidity_stats.record(liquidity, fee);
+        (liquidity, fee)
+    }
+
+    /// Wind-down step: pull every quote, then once the cancels are through quote only the side
+    /// that reduces `inventory`. `progress` (0.0 to 1.0) moves that quote from the normal half
+    /// spread to the opposite touch, so what is left gets crossed out before the file ends.
+    fn wind_down_orders<MD>(
         &mut self,
         hbt: &mut Backtest<MD>,
+        inventory: f64,
+        progress: f64,
     ) -> Result<(), BacktestError>
     where
         MD: MarketDepth,
     {
+        self.order_manager.cancel_all_orders(hbt)?;
+        hbt.clear_inactive_orders(Some(0));
+        if !hbt.orders(0).is_empty() || inventory.abs() < LOT_SIZE / 2.0 {
+            return Ok(());
+        }
+
+        let depth = hbt.depth(0);
+        let tick_size = depth.tick_size();
+        let micro_price = self.micro_price_calc.calculate(depth);
+        let volatility = self.risk_manager.calculate_volatility();
+        let reservation_price = self.spread_calc.calculate_reservation_price(
+            micro_price, inventory, volatility
+        );
+
+        let half_spread = crate::config::FIXED_SPREAD_TICKS * tick_size / 2.0;
+        // Offset from the reservation price at which the reducing quote meets the opposite touch
+        let crossing_half_spread = if inventory > 0.0 {
+            depth.best_bid_tick() as f64 * tick_size - reservation_price
+        } else {
+            reservation_price - depth.best_ask_tick() as f64 * tick_size
+        };
+        let half_spread = half_spread + (crossing_half_spread - half_spread) * progress.clamp(0.0, 1.0);
+
+        // Layers shrink as 1 / (1 + 0.5 * layer); together they never exceed the inventory
+        let layer_weight: f64 = (0..self.order_layers).map(|layer| 1.0 / (1.0 + layer as f64 * 0.5)).sum();
+        let order_size = self.order_size.min(inventory.abs() / layer_weight);
+
+        self.order_manager.place_sided_orders(
+            hbt, reservation_price, half_spread, order_size, inventory, LOT_SIZE / 2.0,
+        )
+    }
+
+    /// Places the first quotes once the volatility estimate has burned in; returns whether
+    /// they went out (until then the loop keeps warming up on data)
+    fn place_initial_orders<MD>(
+        &mut self,
+        hbt: &mut Backtest<MD>,
+    ) -> Result<bool, BacktestError>
+    where
+        MD: MarketDepth,
+    {
+        if !self.risk_manager.is_volatility_ready() {
+            return Ok(false);
+        }
+
         let depth = hbt.depth(0);
         let tick_size = depth.tick_size();
         
@@ -550,64 +930,83 @@ impl MarketMakerRunner {
         let imbalance = self.imbalance_calc.calculate(depth);
         
         let fixed_spread = crate::config::FIXED_SPREAD_TICKS * tick_size;
-        let half_spread = fixed_spread / 2.0;
         
         let volatility = self.risk_manager.calculate_volatility();
         let inventory = 0.0;
         let reservation_price = self.spread_calc.calculate_reservation_price(
             micro_price, inventory, volatility
         );
+        let half_spread = self.edge_half_spread(fixed_spread / 2.0, reservation_price, tick_size);
+        if half_spread > fixed_spread / 2.0 {
+            log_info!("    Half spread widened to the minimum edge after costs: {:.6}", half_spread);
+        }
         
-        let imbalance_adjustment = imbalance * half_spread * 0.1;
+        let imbalance_adjustment = self.imbalance_adjustment(imbalance, half_spread);
+        self.last_quoted_fair = Some(reservation_price);
         
-        println!("  Initial Order Submission:");
-        println!("    Market: Bid {:.2} | Ask {:.2} | Spread {:.2}", 
-                 best_bid_price, best_ask_price, market_spread);
-        println!("    Micro Price: {:.2}, Reservation: {:.2}, Fixed Spread: {:.4}", 
-                 micro_price, reservation_price, fixed_spread);
+        log_info!("  Initial Order Submission:");
+        log_info!("    Market: Bid {:.2} | Ask {:.2} | Spread {:.2}", 
+                  best_bid_price, best_ask_price, market_spread);
+        log_info!("    Micro Price: {:.2}, Reservation: {:.2}, Fixed Spread: {:.4}", 
+                  micro_price, reservation_price, fixed_spread);
+        
+        let best_bid_tick = depth.best_bid_tick();
+        let best_ask_tick = depth.best_ask_tick();
         
         for layer in 0..self.order_layers {
-            let layer_offset = layer as f64 * 1.0 * tick_size;
+            let layer_offset = self.order_manager.layer_offset(layer, tick_size);
             let layer_size = self.order_size / (1.0 + layer as f64 * 0.5);
             
             let bid_price = reservation_price - half_spread - layer_offset + imbalance_adjustment;
-            let bid_tick = (bid_price / tick_size).round() as i64;
-            let buy_order_id = (layer * 2) as u64;
+            let ask_price = reservation_price + half_spread + layer_offset - imbalance_adjustment;
+            let Some((bid_tick, ask_tick)) = self.quote_ticks(bid_price, ask_price, tick_size, best_bid_tick, best_ask_tick) else {
+                log_warn!("    ⏸ Skipping layer {}: quote spread below {} tick(s)",
+                          layer + 1, self.min_quote_spread_ticks.unwrap_or_default());
+                continue;
+            };
+            let bid_tick = self.audit_quote_tick(OrderSide::Buy, bid_tick, layer, best_bid_tick, best_ask_tick);
+            let ask_tick = self.audit_quote_tick(OrderSide::Sell, ask_tick, layer, best_bid_tick, best_ask_tick);
+            let bid_price = bid_tick as f64 * tick_size;
+            let ask_price = ask_tick as f64 * tick_size;
+            self.order_tracker.record_quote(bid_price, ask_price);
+            let queue_ahead = hbt.depth(0).bid_qty_at_tick(bid_tick);
+            let buy_order_id = self.order_manager.assign_slot(layer, OrderSide::Buy);
             
             if let Ok(_) = hbt.submit_buy_order(
                 0,
                 buy_order_id,
-                bid_tick as f64,
+                bid_price,
                 layer_size,
-                TimeInForce::GTX,
+                self.tif,
                 OrdType::Limit,
                 false,
             ) {
-                self.order_tracker.register_order(buy_order_id, OrderSide::Buy, bid_price, layer_size, layer);
-                println!("    → BUY  Layer {} @ {:.2} (tick {}) qty {:.4}", 
-                         layer + 1, bid_price, bid_tick, layer_size);
+                self.order_tracker.register_order(buy_order_id, OrderSide::Buy, bid_price, layer_size, layer, queue_ahead, hbt.current_timestamp());
+                self.order_tracker.set_crossed_touch(buy_order_id, crosses_touch(true, bid_tick, best_bid_tick, best_ask_tick));
+                log_info!("    → BUY  Layer {} @ {:.2} (tick {}) qty {:.4}", 
+                          layer + 1, bid_price, bid_tick, layer_size);
             }
             
-            let ask_price = reservation_price + half_spread + layer_offset - imbalance_adjustment;
-            let ask_tick = (ask_price / tick_size).round() as i64;
-            let sell_order_id = (layer * 2 + 1) as u64;
+            let queue_ahead = hbt.depth(0).ask_qty_at_tick(ask_tick);
+            let sell_order_id = self.order_manager.assign_slot(layer, OrderSide::Sell);
             
             if let Ok(_) = hbt.submit_sell_order(
                 0,
                 sell_order_id,
-                ask_tick as f64,
+                ask_price,
                 layer_size,
-                TimeInForce::GTX,
+                self.tif,
                 OrdType::Limit,
                 false,
             ) {
-                self.order_tracker.register_order(sell_order_id, OrderSide::Sell, ask_price, layer_size, layer);
-                println!("    → SELL Layer {} @ {:.2} (tick {}) qty {:.4}", 
-                         layer + 1, ask_price, ask_tick, layer_size);
+                self.order_tracker.register_order(sell_order_id, OrderSide::Sell, ask_price, layer_size, layer, queue_ahead, hbt.current_timestamp());
+                self.order_tracker.set_crossed_touch(sell_order_id, crosses_touch(false, ask_tick, best_bid_tick, best_ask_tick));
+                log_info!("    → SELL Layer {} @ {:.2} (tick {}) qty {:.4}", 
+                          layer + 1, ask_price, ask_tick, layer_size);
             }
         }
         
-        Ok(())
+        Ok(true)
     }
 
     fn print_final_stats(
@@ -618,48 +1017,75 @@ impl MarketMakerRunner {
         initial_price: f64,
         depth: &dyn MarketDepth,
     ) {
-        let tick_size = depth.tick_size();
-        let best_bid = depth.best_bid_tick() as f64 * tick_size;
-        let best_ask = depth.best_ask_tick() as f64 * tick_size;
-        let final_price = (best_bid + best_ask) / 2.0;
+        // One-sided book at the end: fall back to the first mid of the run
+        let final_price = calculate_mid_price(depth).unwrap_or(initial_price);
         
         let inventory_value = inventory * final_price;
-        let portfolio_value = cash + inventory_value;
+        let portfolio_value = cash + inventory_value - self.borrow_cost.accrued();
         
         let return_pct = ((portfolio_value - self.initial_capital) / self.initial_capital) * 100.0;
         let unrealized_pnl = inventory * (final_price - initial_price);
         let total_pnl = realized_pnl + unrealized_pnl;
         
-        println!("\n{}", "=".repeat(60));
-        println!("=== Strategy Complete ===");
-        println!("  Initial Capital: ${:.2}", self.initial_capital);
-        println!("  Final Cash: ${:.2}", cash);
-        println!("  Final Inventory: {:.4} @ ${:.2}", inventory, final_price);
-        println!("  Inventory Value: ${:.2}", inventory_value);
-        println!("  Final Portfolio Value: ${:.2}", portfolio_value);
-        println!("");
-        println!("  Realized PnL: ${:.2}", realized_pnl);
-        println!("  Unrealized PnL: ${:.2}", unrealized_pnl);
-        println!("  Total PnL: ${:.2}", total_pnl);
-        println!("  Total Return: {:.4}%", return_pct);
-        println!("{}", "=".repeat(60));
-    }
-
-    fn create_backtest(&self, data_file: &str) -> Result<Backtest<HashMapMarketDepth>> {
+        log_info!("\n{}", "=".repeat(60));
+        log_info!("=== Strategy Complete ===");
+        log_info!("  Initial Capital: ${:.2}", self.initial_capital);
+        log_info!("  Final Cash: ${:.2}", cash);
+        log_info!("  Final Inventory: {:.4} @ ${:.2}", inventory, final_price);
+        log_info!("  Inventory Value: ${:.2}", inventory_value);
+        log_info!("  Final Portfolio Value: ${:.2}", portfolio_value);
+        log_info!("");
+        log_info!("  Realized PnL: ${:.2}", realized_pnl);
+        log_info!("  Unrealized PnL: ${:.2}", unrealized_pnl);
+        log_info!("  Total PnL: ${:.2}", total_pnl);
+        log_info!("  Borrow Costs: ${:.2}", self.borrow_cost.accrued());
+        let fee_drag = FeeDrag::from_net(portfolio_value - self.initial_capital, self.liquidity_stats.total_fees(), self.initial_capital);
+        log_info!("  Fees Paid: {}", fee_drag.summary());
+        log_info!("  Total Return: {:.4}%", return_pct);
+        if let Some(capture) = self.order_tracker.spread_capture() {
+            log_info!();
+            log_info!("  Spread Captured: {:.5} per unit over {:.4} matched (quoted {:.5})",
+                      capture.captured, capture.matched_qty, capture.quoted);
+        }
+        if self.crossed_quote_clamps > 0 {
+            log_info!("  Crossed Quotes Clamped: {}", self.crossed_quote_clamps);
+        }
+        if self.edge_widenings > 0 {
+            log_info!("  Quotes Widened to Min Edge: {}", self.edge_widenings);
+        }
+        let liquidity = &self.liquidity_stats;
+        if liquidity.maker_fills + liquidity.taker_fills > 0 {
+            log_info!();
+            log_info!("  Maker Fills: {} | Fees Paid: ${:+.4}", liquidity.maker_fills, liquidity.maker_fees);
+            log_info!("  Taker Fills: {} | Fees Paid: ${:+.4}", liquidity.taker_fills, liquidity.taker_fees);
+        }
+        if let Some((expected, realized)) = self.order_tracker.fill_rate_diagnostic() {
+            log_info!();
+            log_info!("  Expected Fill Rate (queue power n={}): {:.1}%", MM_QUEUE_POWER, expected * 100.0);
+            log_info!("  Realized Fill Rate: {:.1}%", realized * 100.0);
+        }
+        if let Some(latency) = self.order_tracker.fill_latency().summary().filter(|_| REPORT_FILL_LATENCY) {
+            log_info!("  Fill Latency: mean {:.3}s | median {:.3}s | p95 {:.3}s ({} fills)",
+                      latency.mean_secs, latency.median_secs, latency.p95_secs, latency.fills);
+        }
+        log_info!("{}", "=".repeat(60));
+    }
+
+    fn create_backtest(&self, data_file: &str, preloaded: Option<Vec<Event>>) -> Result<Backtest<HashMapMarketDepth>> {
         let latency_model = ConstantLatency::new(100_000, 100_000);
-        let asset_type = LinearAsset::new(1.0);
-        let queue_model = ProbQueueModel::new(PowerProbQueueFunc3::new(3.0));
-        let fee_model = TradingValueFeeModel::new(CommonFees::new(-0.0001, 0.0004));
+        let queue_model = ProbQueueModel::new(PowerProbQueueFunc3::new(MM_QUEUE_POWER));
+        let fee_model = TradingValueFeeModel::new(CommonFees::new(MAKER_FEE_RATE, TAKER_FEE_RATE));
 
         let hbt = Backtest::builder()
             .add_asset(
                 L2AssetBuilder::new()
-                    .data(vec![DataSource::File(data_file.to_string())])
+                    .data(vec![data_source(data_file, preloaded)])
                     .latency_model(latency_model)
-                    .asset_type(asset_type)
+                    .asset_type(ASSET_KIND)
                     .fee_model(fee_model)
-                    .exchange(ExchangeKind::NoPartialFillExchange)
+                    .exchange(EXCHANGE_KIND)
                     .queue_model(queue_model)
+                    .last_trades_capacity(LAST_TRADES_CAPACITY)
                     .depth(|| HashMapMarketDepth::new(TICK_SIZE, LOT_SIZE))
                     .build()?,
             )
@@ -668,3 +1094,372 @@ impl MarketMakerRunner {
         Ok(hbt)
     }
 }
+
+fn validate_imbalance_sensitivity(sensitivity: f64) -> Result<()> {
+    if sensitivity.is_nan() || sensitivity < 0.0 {
+        anyhow::bail!("Imbalance sensitivity must be non-negative, got {}", sensitivity);
+    }
+    Ok(())
+}
+
+fn validate_wind_down_fraction(fraction: f64) -> Result<()> {
+    if !(0.0..1.0).contains(&fraction) {
+        anyhow::bail!("Wind-down fraction must be in [0, 1), got {}", fraction);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn runner() -> MarketMakerRunner {
+        MarketMakerRunner::create_runner(
+            vec![PathBuf::from("unused.npz")], 0.001, 5.0, 5.0, 0.01, 20, 2, 10_000.0,
+        ).unwrap()
+    }
+
+    #[test]
+    fn test_higher_sensitivity_skews_quotes_further() {
+        let mut mm = runner();
+        let half_spread = 5.0 * TICK_SIZE;
+
+        mm.imbalance_sensitivity = 0.1;
+        let low = mm.imbalance_adjustment(0.6, half_spread);
+        mm.imbalance_sensitivity = 0.5;
+        let high = mm.imbalance_adjustment(0.6, half_spread);
+
+        assert!(low > 0.0);
+        assert!(high > low);
+        assert!((high / low - 5.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_negative_sensitivity_rejected() {
+        assert!(validate_imbalance_sensitivity(0.0).is_ok());
+        assert!(validate_imbalance_sensitivity(-0.1).is_err());
+        assert!(validate_imbalance_sensitivity(f64::NAN).is_err());
+    }
+
+    #[test]
+    fn test_min_quote_spread_keeps_quotes_outside_touch() {
+        let mut mm = runner();
+        mm.price_rounding = PriceRounding::Nearest;
+        // 1-tick market: best bid tick 10_000, best ask tick 10_001
+        let (best_bid, best_ask) = (10_000, 10_001);
+        // Fair quotes 2 ticks apart straddling the touch
+        let bid_price = 9_999.6 * TICK_SIZE;
+        let ask_price = 10_001.6 * TICK_SIZE;
+
+        assert_eq!(mm.quote_ticks(bid_price, ask_price, TICK_SIZE, best_bid, best_ask), Some((10_000, 10_002)));
+
+        mm.min_quote_spread_ticks = Some(4);
+        assert_eq!(mm.quote_ticks(bid_price, ask_price, TICK_SIZE, best_bid, best_ask), None);
+
+        // Wide enough fair spread is quoted, never inside the touch
+        let wide = mm.quote_ticks(9_998.0 * TICK_SIZE, 10_002.2 * TICK_SIZE, TICK_SIZE, best_bid, best_ask);
+        assert_eq!(wide, Some((9_998, 10_002)));
+        let (bid_tick, ask_tick) = wide.unwrap();
+        assert!(bid_tick <= best_bid && ask_tick >= best_ask);
+    }
+
+    #[test]
+    fn test_spread_below_min_edge_widens_to_it() {
+        let mut mm = runner();
+        let fair = 1.0;
+        let half_spread = 5.0 * TICK_SIZE;
+        // Off by default: the fixed spread is quoted as is
+        mm.adverse_selection_buffer_ticks = None;
+        assert_eq!(mm.edge_half_spread(half_spread, fair, TICK_SIZE), half_spread);
+
+        // 4 bps taker-like round trip + 3 ticks of adverse selection = 43 ticks at a price of 1
+        mm.adverse_selection_buffer_ticks = Some(3.0);
+        mm.round_trip_fee_rate = 0.0004;
+        let min_edge = 0.0004 + 3.0 * TICK_SIZE;
+        assert_eq!(mm.min_edge(fair, TICK_SIZE), Some(min_edge));
+        assert_eq!(mm.edge_half_spread(half_spread, fair, TICK_SIZE), min_edge);
+        assert_eq!(mm.edge_widenings, 1);
+
+        // A spread that already clears the edge is left alone
+        assert_eq!(mm.edge_half_spread(50.0 * TICK_SIZE, fair, TICK_SIZE), 50.0 * TICK_SIZE);
+        assert_eq!(mm.edge_widenings, 1);
+    }
+
+    #[test]
+    fn test_directional_rounding_never_crosses_touch() {
+        let mut mm = runner();
+        mm.price_rounding = PriceRounding::Directional;
+        // 1-tick market; fair quotes land just inside the touch on both sides
+        let (bid_tick, ask_tick) = mm.quote_ticks(10_000.6 * TICK_SIZE, 10_000.4 * TICK_SIZE, TICK_SIZE, 10_000, 10_001).unwrap();
+        assert_eq!((bid_tick, ask_tick), (10_000, 10_001));
+
+        mm.price_rounding = PriceRounding::Nearest;
+        assert_eq!(mm.quote_ticks(10_000.6 * TICK_SIZE, 10_000.4 * TICK_SIZE, TICK_SIZE, 10_000, 10_001), Some((10_001, 10_000)));
+    }
+
+    #[test]
+    fn test_bid_through_best_ask_clamped_to_best_bid() {
+        let mut mm = runner();
+        let (best_bid, best_ask) = (10_000, 10_001);
+
+        assert_eq!(mm.audit_quote_tick(OrderSide::Buy, 10_003, 0, best_bid, best_ask), best_bid);
+        assert_eq!(mm.audit_quote_tick(OrderSide::Sell, 9_999, 0, best_bid, best_ask), best_ask);
+        // Quotes on their own side of the book pass through untouched
+        assert_eq!(mm.audit_quote_tick(OrderSide::Buy, 9_998, 0, best_bid, best_ask), 9_998);
+        assert_eq!(mm.audit_quote_tick(OrderSide::Sell, 10_001, 0, best_bid, best_ask), 10_001);
+        assert_eq!(mm.crossed_quote_clamps, 2);
+
+        mm.clamp_crossed_quotes = false;
+        assert_eq!(mm.audit_quote_tick(OrderSide::Buy, 10_003, 0, best_bid, best_ask), 10_003);
+        assert_eq!(mm.crossed_quote_clamps, 2);
+    }
+
+    #[test]
+    fn test_layer_offsets_scale_with_spacing() {
+        let mut mm = runner();
+        assert_eq!(mm.order_manager.layer_offset(1, TICK_SIZE), MM_LAYER_SPACING_TICKS * TICK_SIZE);
+
+        for spacing in [1.0, 3.0, 5.0] {
+            mm.order_manager = OrderManager::new(3, spacing);
+            assert_eq!(mm.order_manager.layer_offset(0, TICK_SIZE), 0.0);
+            for layer in 1..3 {
+                let offset = mm.order_manager.layer_offset(layer, TICK_SIZE);
+                assert!((offset / TICK_SIZE - layer as f64 * spacing).abs() < 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn test_no_quotes_until_volatility_burned_in() {
+        use crate::common::test_utils::{backtest_from_events, two_sided_book};
+
+        let mut hbt = backtest_from_events(&two_sided_book(0.1, 0.10002, 5.0, [1_000_000]));
+        hbt.elapse(10_000).unwrap();
+
+        let mut mm = runner();
+        mm.risk_manager = RiskManager::new(5.0, 5.0, VOLATILITY_EWMA_LAMBDA).with_volatility_burn_in(3);
+        for price in [0.10001, 0.10002, 0.10001] {
+            mm.risk_manager.update_price(price);
+            assert!(!mm.place_initial_orders(&mut hbt).unwrap());
+            assert!(hbt.orders(0).is_empty());
+        }
+
+        mm.risk_manager.update_price(0.10002);
+        assert!(mm.place_initial_orders(&mut hbt).unwrap());
+        assert!(!hbt.orders(0).is_empty());
+    }
+
+    #[test]
+    fn test_fair_price_move_beyond_threshold_requotes() {
+        use hftbacktest::types::{DEPTH_EVENT, BUY_EVENT, SELL_EVENT, EXCH_EVENT, LOCAL_EVENT};
+        use crate::common::test_utils::{backtest_from_events, event};
+
+        let bid = DEPTH_EVENT | BUY_EVENT | EXCH_EVENT | LOCAL_EVENT;
+        let ask = DEPTH_EVENT | SELL_EVENT | EXCH_EVENT | LOCAL_EVENT;
+        let mut hbt = backtest_from_events(&[
+            event(bid, 1_000, 0.1, 5.0),
+            event(ask, 1_000, 0.10002, 5.0),
+            // Mid up 1 tick
+            event(bid, 20_000, 0.1, 0.0),
+            event(ask, 20_000, 0.10002, 0.0),
+            event(bid, 20_000, 0.10001, 5.0),
+            event(ask, 20_000, 0.10003, 5.0),
+            // Mid up 3 ticks from the first quote
+            event(bid, 40_000, 0.10001, 0.0),
+            event(ask, 40_000, 0.10003, 0.0),
+            event(bid, 40_000, 0.10003, 5.0),
+            event(ask, 40_000, 0.10005, 5.0),
+            event(bid, 1_000_000, 0.10003, 5.0),
+        ]);
+        hbt.elapse(10_000).unwrap();
+
+        let mut mm = runner();
+        mm.risk_manager = RiskManager::new(5.0, 5.0, VOLATILITY_EWMA_LAMBDA);
+        mm.requote_on_price_move = true;
+        mm.requote_threshold_ticks = 2.0;
+        assert!(mm.place_initial_orders(&mut hbt).unwrap());
+        let quoted = mm.last_quoted_fair.unwrap();
+        assert!((quoted - 0.10001).abs() < 1e-9);
+
+        hbt.elapse(20_000).unwrap();
+        assert!(!mm.requote_on_drift(&mut hbt, 0.0).unwrap());
+        assert_eq!(mm.last_quoted_fair, Some(quoted));
+
+        hbt.elapse(20_000).unwrap();
+        assert!(mm.requote_on_drift(&mut hbt, 0.0).unwrap());
+        assert_eq!(mm.last_quoted_fair, None);
+
+        // Off: the same move is ignored
+        mm.requote_on_price_move = false;
+        mm.last_quoted_fair = Some(quoted);
+        assert!(!mm.requote_on_drift(&mut hbt, 0.0).unwrap());
+    }
+
+    #[test]
+    fn test_wind_down_with_long_inventory_only_sells() {
+        use hftbacktest::prelude::Side;
+        use hftbacktest::types::{DEPTH_EVENT, BUY_EVENT, SELL_EVENT, EXCH_EVENT, LOCAL_EVENT};
+        use crate::common::test_utils::{backtest_from_events, event};
+
+        let bid = DEPTH_EVENT | BUY_EVENT | EXCH_EVENT | LOCAL_EVENT;
+        let ask = DEPTH_EVENT | SELL_EVENT | EXCH_EVENT | LOCAL_EVENT;
+        let mut hbt = backtest_from_events(&[
+            event(bid, 1_000, 0.1, 5.0),
+            event(ask, 1_000, 0.10002, 5.0),
+            event(bid, 1_000_000, 0.1, 5.0),
+        ]);
+        hbt.elapse(10_000).unwrap();
+
+        let mut mm = runner();
+        for progress in [0.0, 1.0] {
+            mm.wind_down_orders(&mut hbt, 0.02, progress).unwrap();
+            let orders = hbt.orders(0);
+            assert!(!orders.is_empty());
+            assert!(orders.values().all(|order| order.side == Side::Sell));
+            let max_tick = orders.values().map(|order| order.price_tick).max().unwrap();
+            if progress == 1.0 {
+                // Fully wound down: the first layer sells at the best bid
+                assert!(orders.values().any(|order| order.price_tick == 10_000));
+            } else {
+                assert!(max_tick > 10_002);
+            }
+            assert!(orders.values().map(|order| order.qty).sum::<f64>() <= 0.02 + 1e-9);
+
+            // The next step pulls the resting quotes before requoting
+            hbt.elapse(10_000).unwrap();
+            mm.wind_down_orders(&mut hbt, 0.02, progress).unwrap();
+            hbt.elapse(10_000).unwrap();
+        }
+
+        assert!(validate_wind_down_fraction(0.1).is_ok());
+        assert!(validate_wind_down_fraction(1.0).is_err());
+    }
+
+    #[test]
+    fn test_partial_fill_books_executed_qty_and_keeps_quote_live() {
+        use hftbacktest::backtest::ExchangeKind;
+        use hftbacktest::types::{DEPTH_EVENT, TRADE_EVENT, BUY_EVENT, SELL_EVENT, EXCH_EVENT, LOCAL_EVENT};
+        use crate::common::test_utils::{backtest_from_events_on, event};
+
+        let bid = DEPTH_EVENT | BUY_EVENT | EXCH_EVENT | LOCAL_EVENT;
+        let ask = DEPTH_EVENT | SELL_EVENT | EXCH_EVENT | LOCAL_EVENT;
+        let sell_trade = TRADE_EVENT | SELL_EVENT | EXCH_EVENT | LOCAL_EVENT;
+        let mut hbt = backtest_from_events_on(&[
+            event(bid, 1_000, 0.1, 5.0),
+            event(ask, 1_000, 0.10002, 5.0),
+            // Hits 1 of the 3 resting at the new best bid
+            event(sell_trade, 100_000, 0.10001, 1.0),
+            event(bid, 1_000_000, 0.1, 5.0),
+        ], ExchangeKind::PartialFillExchange);
+        hbt.elapse(10_000).unwrap();
+
+        let mut mm = runner();
+        let order_id = mm.order_manager.assign_slot(0, OrderSide::Buy);
+        hbt.submit_buy_order(0, order_id, 0.10001, 3.0, TimeInForce::GTC, OrdType::Limit, false).unwrap();
+        hbt.elapse(200_000).unwrap();
+        assert_eq!(hbt.orders(0)[&order_id].status, Status::PartiallyFilled);
+
+        let (mut inventory, mut realized_pnl) = (0.0, 0.0);
+        mm.check_and_refill_orders(&mut hbt, &mut inventory, &mut realized_pnl, None).unwrap();
+        assert!((inventory - 1.0).abs() < 1e-9);
+        assert!(realized_pnl < 0.0);
+        // Not resubmitted: the same order keeps working for the rest
+        assert_eq!(mm.order_manager.slot_order_id(0, OrderSide::Buy), Some(order_id));
+        assert_eq!(hbt.orders(0)[&order_id].leaves_qty, 2.0);
+
+        // Nothing new executed, nothing booked twice
+        mm.check_and_refill_orders(&mut hbt, &mut inventory, &mut realized_pnl, None).unwrap();
+        assert!((inventory - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_partial_fill_then_cancel_counts_executed_qty_in_fill_stats() {
+        use hftbacktest::backtest::ExchangeKind;
+        use hftbacktest::types::{DEPTH_EVENT, TRADE_EVENT, BUY_EVENT, SELL_EVENT, EXCH_EVENT, LOCAL_EVENT};
+        use crate::common::test_utils::{backtest_from_events_on, event};
+
+        let bid = DEPTH_EVENT | BUY_EVENT | EXCH_EVENT | LOCAL_EVENT;
+        let ask = DEPTH_EVENT | SELL_EVENT | EXCH_EVENT | LOCAL_EVENT;
+        let sell_trade = TRADE_EVENT | SELL_EVENT | EXCH_EVENT | LOCAL_EVENT;
+        let mut hbt = backtest_from_events_on(&[
+            event(bid, 1_000, 0.1, 5.0),
+            event(ask, 1_000, 0.10002, 5.0),
+            // Hits 1 of the 3 resting at the new best bid
+            event(sell_trade, 100_000, 0.10001, 1.0),
+            event(bid, 1_000_000, 0.1, 5.0),
+        ], ExchangeKind::PartialFillExchange);
+        hbt.elapse(10_000).unwrap();
+
+        let mut mm = runner();
+        let order_id = mm.order_manager.assign_slot(0, OrderSide::Buy);
+        hbt.submit_buy_order(0, order_id, 0.10001, 3.0, TimeInForce::GTC, OrdType::Limit, false).unwrap();
+        mm.order_tracker.register_order(order_id, OrderSide::Buy, 0.10001, 3.0, 0, 0.0, hbt.current_timestamp());
+        hbt.elapse(200_000).unwrap();
+        assert_eq!(hbt.orders(0)[&order_id].status, Status::PartiallyFilled);
+
+        // Canceled with 1 of 3 executed
+        hbt.cancel(0, order_id, false).unwrap();
+        hbt.elapse(10_000).unwrap();
+        assert_eq!(hbt.orders(0)[&order_id].status, Status::Canceled);
+
+        let (mut inventory, mut realized_pnl) = (0.0, 0.0);
+        mm.check_and_refill_orders(&mut hbt, &mut inventory, &mut realized_pnl, None).unwrap();
+        assert!((inventory - 1.0).abs() < 1e-9);
+
+        // The fill stats see the executed 1, like the PnL does
+        assert_eq!(mm.order_tracker.filled_count(), 1);
+        assert_eq!(mm.order_tracker.fill_latency().summary().unwrap().fills, 1);
+        mm.order_tracker.record_fill(OrderSide::Sell, 0.10003, 5.0);
+        assert_eq!(mm.order_tracker.spread_capture().unwrap().matched_qty, 1.0);
+    }
+
+    /// Every resting quote sits at the tick it was audited to, on its own side of the touch
+    fn assert_quotes_rest_at_audited_ticks(mm: &MarketMakerRunner, hbt: &Backtest<HashMapMarketDepth>, expected: usize) {
+        use hftbacktest::prelude::Side;
+
+        let depth = hbt.depth(0);
+        let resting: Vec<_> = hbt.orders(0).values().filter(|order| order.status == Status::New).collect();
+        assert_eq!(resting.len(), expected);
+        for order in resting {
+            let info = mm.order_tracker.order_info(order.order_id).unwrap();
+            assert_eq!(order.price_tick, (info.price / TICK_SIZE).round() as i64);
+            match order.side {
+                Side::Buy => assert!(order.price_tick <= depth.best_bid_tick()),
+                _ => assert!(order.price_tick >= depth.best_ask_tick()),
+            }
+        }
+    }
+
+    #[test]
+    fn test_initial_and_refilled_quotes_rest_at_audited_ticks() {
+        use hftbacktest::types::{DEPTH_EVENT, TRADE_EVENT, BUY_EVENT, SELL_EVENT, EXCH_EVENT, LOCAL_EVENT};
+        use crate::common::test_utils::{backtest_from_events, event};
+
+        let bid = DEPTH_EVENT | BUY_EVENT | EXCH_EVENT | LOCAL_EVENT;
+        let ask = DEPTH_EVENT | SELL_EVENT | EXCH_EVENT | LOCAL_EVENT;
+        let sell_trade = TRADE_EVENT | SELL_EVENT | EXCH_EVENT | LOCAL_EVENT;
+        let mut hbt = backtest_from_events(&[
+            event(bid, 1_000, 0.1, 5.0),
+            event(ask, 1_000, 0.10002, 5.0),
+            // Trades through both resting bids
+            event(sell_trade, 100_000, 0.0998, 100.0),
+            event(bid, 1_000_000, 0.1, 5.0),
+        ]);
+        hbt.elapse(10_000).unwrap();
+
+        let mut mm = runner();
+        mm.risk_manager = RiskManager::new(5.0, 5.0, VOLATILITY_EWMA_LAMBDA);
+        assert!(mm.place_initial_orders(&mut hbt).unwrap());
+        hbt.elapse(10_000).unwrap();
+        assert_quotes_rest_at_audited_ticks(&mm, &hbt, 4);
+
+        hbt.elapse(200_000).unwrap();
+        let (mut inventory, mut realized_pnl) = (0.0, 0.0);
+        mm.check_and_refill_orders(&mut hbt, &mut inventory, &mut realized_pnl, None).unwrap();
+        assert!(inventory > 0.0);
+        hbt.elapse(10_000).unwrap();
+        // Both bids refilled next to the sells still resting
+        assert_quotes_rest_at_audited_ticks(&mm, &hbt, 4);
+    }
+
+}
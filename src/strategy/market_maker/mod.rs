@@ -3,9 +3,11 @@ mod pricing;
 mod spread;
 mod risk_manager;
 mod order_tracker;
+mod order_manager;
 
 pub use market_maker_runner::MarketMakerRunner;
 pub use pricing::{MicroPriceCalculator, OrderBookImbalance};
 pub use spread::SpreadCalculator;
 pub use risk_manager::RiskManager;
 pub use order_tracker::{OrderTracker, OrderSide};
+pub use order_manager::OrderManager;
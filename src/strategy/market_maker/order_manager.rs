@@ -3,11 +3,18 @@ use hftbacktest::{
     backtest::{Backtest, BacktestError},
     depth::MarketDepth,
 };
+use crate::common::{bid_price_tick, ask_price_tick, OrderIdGenerator};
+use super::OrderSide;
+use crate::config::ORDER_PRICE_ROUNDING;
 
 /// 주문 집행 관리
 pub struct OrderManager {
     order_layers: usize,  // 레이어링 개수
     layer_spacing: f64,   // 레이어 간격 (틱 단위)
+    time_in_force: TimeInForce,
+    order_ids: OrderIdGenerator,
+    /// 호가 슬롯별 현재 주문 ID (레이어 * 2 = 매수, 레이어 * 2 + 1 = 매도, None = 미제출)
+    slots: Vec<Option<u64>>,
 }
 
 impl OrderManager {
@@ -15,14 +22,56 @@ impl OrderManager {
         Self {
             order_layers,
             layer_spacing,
+            time_in_force: TimeInForce::GTX,
+            order_ids: OrderIdGenerator::new(),
+            slots: vec![None; order_layers * 2],
         }
     }
 
+    fn slot_index(layer: usize, side: OrderSide) -> usize {
+        match side {
+            OrderSide::Buy => layer * 2,
+            OrderSide::Sell => layer * 2 + 1,
+        }
+    }
+
+    /// 슬롯에 마지막으로 제출된 주문 ID
+    pub fn slot_order_id(&self, layer: usize, side: OrderSide) -> Option<u64> {
+        self.slots.get(Self::slot_index(layer, side)).copied().flatten()
+    }
+
+    /// 슬롯에 새 주문 ID 발급 (이전 주문 ID는 재사용하지 않음)
+    pub fn assign_slot(&mut self, layer: usize, side: OrderSide) -> u64 {
+        let order_id = self.order_ids.next_id();
+        let index = Self::slot_index(layer, side);
+        if index >= self.slots.len() {
+            self.slots.resize(index + 1, None);
+        }
+        self.slots[index] = Some(order_id);
+        order_id
+    }
+
+    /// 슬롯 밖 주문(종료 시 청산)에 쓰는 ID 발급기, 호가 주문과 같은 범위라 겹치지 않음
+    pub fn order_ids(&mut self) -> &mut OrderIdGenerator {
+        &mut self.order_ids
+    }
+
+    /// 주문 유효 조건 변경 (기본 GTX, 스프레드를 건너는 주문은 GTC 필요)
+    pub fn with_time_in_force(mut self, time_in_force: TimeInForce) -> Self {
+        self.time_in_force = time_in_force;
+        self
+    }
+
+    /// 레이어의 가격 오프셋 (첫 레이어 0, 이후 layer_spacing 틱씩 바깥쪽)
+    pub fn layer_offset(&self, layer: usize, tick_size: f64) -> f64 {
+        layer as f64 * self.layer_spacing * tick_size
+    }
+
     /// 양방향 주문 생성 (레이어링 포함)
     /// reservation_price: 재고 리스크 반영한 중간가
     /// spread: 최적 스프레드
     pub fn place_layered_orders<MD>(
-        &self,
+        &mut self,
         hbt: &mut Backtest<MD>,
         reservation_price: f64,
         half_spread: f64,
@@ -38,35 +87,37 @@ impl OrderManager {
         let imbalance_adjustment = imbalance * half_spread * 0.2;  // 최대 20% 조정
         
         for layer in 0..self.order_layers {
-            let layer_offset = layer as f64 * self.layer_spacing * tick_size;
+            let layer_offset = self.layer_offset(layer, tick_size);
             
             // Bid (매수) 주문
             let bid_price = reservation_price - half_spread - layer_offset + imbalance_adjustment;
-            let bid_tick = (bid_price / tick_size).round() as i64;
+            let bid_tick = bid_price_tick(bid_price, tick_size, ORDER_PRICE_ROUNDING);
             
             // Ask (매도) 주문  
             let ask_price = reservation_price + half_spread + layer_offset - imbalance_adjustment;
-            let ask_tick = (ask_price / tick_size).round() as i64;
+            let ask_tick = ask_price_tick(ask_price, tick_size, ORDER_PRICE_ROUNDING);
             
             // 레이어별 수량 감소 (첫 레이어가 가장 큼)
             let layer_size = order_size / (1.0 + layer as f64 * 0.5);
             
             // 실제 주문 제출
+            let buy_order_id = self.assign_slot(layer, OrderSide::Buy);
             hbt.submit_buy_order(
                 0, 
-                (layer * 2) as u64, 
-                bid_tick as f64, 
+                buy_order_id, 
+                bid_tick as f64 * tick_size, 
                 layer_size, 
-                TimeInForce::GTX, 
+                self.time_in_force, 
                 OrdType::Limit, 
                 false
             ).ok();
+            let sell_order_id = self.assign_slot(layer, OrderSide::Sell);
             hbt.submit_sell_order(
                 0, 
-                (layer * 2 + 1) as u64, 
-                ask_tick as f64, 
+                sell_order_id, 
+                ask_tick as f64 * tick_size, 
                 layer_size, 
-                TimeInForce::GTX, 
+                self.time_in_force, 
                 OrdType::Limit, 
                 false
             ).ok();
@@ -81,16 +132,15 @@ impl OrderManager {
         MD: MarketDepth,
     {
         // 모든 주문 취소
-        for layer in 0..self.order_layers {
-            let _ = hbt.cancel(0, (layer * 2) as u64, false);
-            let _ = hbt.cancel(0, (layer * 2 + 1) as u64, false);
+        for order_id in self.slots.iter().flatten() {
+            let _ = hbt.cancel(0, *order_id, false);
         }
         Ok(())
     }
 
     /// 재고 상태에 따라 한쪽 주문만 제출
     pub fn place_sided_orders<MD>(
-        &self,
+        &mut self,
         hbt: &mut Backtest<MD>,
         reservation_price: f64,
         half_spread: f64,
@@ -107,17 +157,18 @@ impl OrderManager {
         if inventory > inventory_threshold {
             // 매도 주문만
             for layer in 0..self.order_layers {
-                let layer_offset = layer as f64 * self.layer_spacing * tick_size;
+                let layer_offset = self.layer_offset(layer, tick_size);
                 let ask_price = reservation_price + half_spread + layer_offset;
-                let ask_tick = (ask_price / tick_size).round() as i64;
+                let ask_tick = ask_price_tick(ask_price, tick_size, ORDER_PRICE_ROUNDING);
                 let layer_size = order_size / (1.0 + layer as f64 * 0.5);
                 
+                let order_id = self.assign_slot(layer, OrderSide::Sell);
                 hbt.submit_sell_order(
                     0, 
-                    (layer * 2 + 1) as u64, 
-                    ask_tick as f64, 
+                    order_id, 
+                    ask_tick as f64 * tick_size, 
                     layer_size, 
-                    TimeInForce::GTX, 
+                    self.time_in_force, 
                     OrdType::Limit, 
                     false
                 ).ok();
@@ -125,17 +176,18 @@ impl OrderManager {
         } else if inventory < -inventory_threshold {
             // 매수 주문만
             for layer in 0..self.order_layers {
-                let layer_offset = layer as f64 * self.layer_spacing * tick_size;
+                let layer_offset = self.layer_offset(layer, tick_size);
                 let bid_price = reservation_price - half_spread - layer_offset;
-                let bid_tick = (bid_price / tick_size).round() as i64;
+                let bid_tick = bid_price_tick(bid_price, tick_size, ORDER_PRICE_ROUNDING);
                 let layer_size = order_size / (1.0 + layer as f64 * 0.5);
                 
+                let order_id = self.assign_slot(layer, OrderSide::Buy);
                 hbt.submit_buy_order(
                     0, 
-                    (layer * 2) as u64, 
-                    bid_tick as f64, 
+                    order_id, 
+                    bid_tick as f64 * tick_size, 
                     layer_size, 
-                    TimeInForce::GTX, 
+                    self.time_in_force, 
                     OrdType::Limit, 
                     false
                 ).ok();
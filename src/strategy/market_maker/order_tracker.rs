@@ -1,4 +1,7 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
+
+use crate::common::FillLatency;
+use crate::ui::SpreadCapture;
 
 /// Order tracking and management (for debugging and PnL calculation)
 #[derive(Debug)]
@@ -7,6 +10,21 @@ pub struct OrderTracker {
     filled_count: u64,
     total_buy_volume: f64,
     total_sell_volume: f64,
+    /// Exponent of the backtest's `PowerProbQueueFunc3`
+    queue_power: f64,
+    submitted_count: u64,
+    /// Sum of the estimated fill probabilities of every submitted order
+    expected_fills: f64,
+    /// Fills not yet matched against the other side, oldest first: (price, qty)
+    unmatched_buys: VecDeque<(f64, f64)>,
+    unmatched_sells: VecDeque<(f64, f64)>,
+    /// Sum of (sell - buy) * qty over matched quantity
+    captured_value: f64,
+    matched_qty: f64,
+    quoted_spread_sum: f64,
+    quote_count: usize,
+    /// 제출부터 체결 종료(완전 체결, 또는 부분 체결 후 취소/만료)까지의 시뮬레이션 시간 분포
+    fill_latency: FillLatency,
 }
 
 #[derive(Debug, Clone)]
@@ -19,6 +37,13 @@ pub struct OrderInfo {
     pub qty: f64,
     #[allow(dead_code)]
     pub layer: usize,
+    /// Displayed quantity ahead of the order at its price when it was submitted
+    #[allow(dead_code)]
+    pub queue_ahead: f64,
+    /// 제출 시점에 반대편 최우선 호가를 넘어선 가격이었는지 (테이커 판정용)
+    pub crossed_touch: bool,
+    /// 제출 시점 (시뮬레이션 ns)
+    pub submitted_ns: i64,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -28,35 +53,138 @@ pub enum OrderSide {
 }
 
 impl OrderTracker {
-    pub fn new() -> Self {
+    pub fn new(queue_power: f64) -> Self {
         Self {
             active_orders: HashMap::new(),
             filled_count: 0,
             total_buy_volume: 0.0,
             total_sell_volume: 0.0,
+            queue_power,
+            submitted_count: 0,
+            expected_fills: 0.0,
+            unmatched_buys: VecDeque::new(),
+            unmatched_sells: VecDeque::new(),
+            captured_value: 0.0,
+            matched_qty: 0.0,
+            quoted_spread_sum: 0.0,
+            quote_count: 0,
+            fill_latency: FillLatency::new(),
+        }
+    }
+
+    /// 호가 한 쌍의 스프레드 기록 (ask - bid)
+    pub fn record_quote(&mut self, bid_price: f64, ask_price: f64) {
+        self.quoted_spread_sum += ask_price - bid_price;
+        self.quote_count += 1;
+    }
+
+    /// 체결을 반대편 미매칭 체결과 FIFO로 짝지어 왕복 스프레드 누적
+    pub fn record_fill(&mut self, side: OrderSide, price: f64, qty: f64) {
+        let (opposite, own) = match side {
+            OrderSide::Buy => (&mut self.unmatched_sells, &mut self.unmatched_buys),
+            OrderSide::Sell => (&mut self.unmatched_buys, &mut self.unmatched_sells),
+        };
+
+        let mut remaining = qty;
+        while remaining > 0.0 {
+            let Some((other_price, other_qty)) = opposite.front_mut() else {
+                break;
+            };
+            let matched = remaining.min(*other_qty);
+            let (buy, sell) = match side {
+                OrderSide::Buy => (price, *other_price),
+                OrderSide::Sell => (*other_price, price),
+            };
+            self.captured_value += (sell - buy) * matched;
+            self.matched_qty += matched;
+            remaining -= matched;
+            *other_qty -= matched;
+            if *other_qty <= 0.0 {
+                opposite.pop_front();
+            }
+        }
+        if remaining > 0.0 {
+            own.push_back((price, remaining));
+        }
+    }
+
+    /// 매칭된 왕복의 단위당 실현 스프레드와 평균 호가 스프레드 (왕복이 없으면 None)
+    pub fn spread_capture(&self) -> Option<SpreadCapture> {
+        if self.matched_qty <= 0.0 {
+            return None;
         }
+        let quoted = if self.quote_count > 0 {
+            self.quoted_spread_sum / self.quote_count as f64
+        } else {
+            0.0
+        };
+        Some(SpreadCapture {
+            captured: self.captured_value / self.matched_qty,
+            quoted,
+            matched_qty: self.matched_qty,
+        })
     }
 
     /// 새 주문 등록
-    pub fn register_order(&mut self, order_id: u64, side: OrderSide, price: f64, qty: f64, layer: usize) {
+    pub fn register_order(&mut self, order_id: u64, side: OrderSide, price: f64, qty: f64, layer: usize, queue_ahead: f64,
+                          submitted_ns: i64) {
+        self.submitted_count += 1;
+        self.expected_fills += power_fill_probability(queue_ahead, qty, self.queue_power);
         self.active_orders.insert(order_id, OrderInfo {
             order_id,
             side,
             price,
             qty,
             layer,
+            queue_ahead,
+            crossed_touch: false,
+            submitted_ns,
         });
     }
 
-    /// 주문 체결 처리
-    pub fn mark_filled(&mut self, order_id: u64) -> Option<OrderInfo> {
+    /// 제출 시점에 반대편 최우선 호가를 넘어섰던 주문으로 표시
+    pub fn set_crossed_touch(&mut self, order_id: u64, crossed_touch: bool) {
+        if let Some(order) = self.active_orders.get_mut(&order_id) {
+            order.crossed_touch = crossed_touch;
+        }
+    }
+
+    /// 등록된 주문의 제출 시점 호가 교차 여부 (미등록 주문이면 None)
+    pub fn crossed_touch(&self, order_id: u64) -> Option<bool> {
+        self.active_orders.get(&order_id).map(|order| order.crossed_touch)
+    }
+
+    /// 지금까지 체결된 주문 수
+    pub fn filled_count(&self) -> usize {
+        self.filled_count as usize
+    }
+
+    /// (expected, realized) fill rate over every submitted order; None before any submission
+    pub fn fill_rate_diagnostic(&self) -> Option<(f64, f64)> {
+        if self.submitted_count == 0 {
+            return None;
+        }
+        let submitted = self.submitted_count as f64;
+        Some((self.expected_fills / submitted, self.filled_count as f64 / submitted))
+    }
+
+    /// 체결된 주문의 제출-체결 지연 분포
+    pub fn fill_latency(&self) -> &FillLatency {
+        &self.fill_latency
+    }
+
+    /// 주문 체결 처리 (`filled_qty`: 체결된 수량으로, 부분 체결 후 끝난 주문은 체결분만,
+    /// `filled_ns`: 체결을 확인한 시뮬레이션 시점)
+    pub fn mark_filled(&mut self, order_id: u64, filled_qty: f64, filled_ns: i64) -> Option<OrderInfo> {
         if let Some(order) = self.active_orders.remove(&order_id) {
             self.filled_count += 1;
+            self.fill_latency.record(order.submitted_ns, filled_ns);
             
             match order.side {
-                OrderSide::Buy => self.total_buy_volume += order.qty,
-                OrderSide::Sell => self.total_sell_volume += order.qty,
+                OrderSide::Buy => self.total_buy_volume += filled_qty,
+                OrderSide::Sell => self.total_sell_volume += filled_qty,
             }
+            self.record_fill(order.side, order.price, filled_qty);
             
             Some(order)
         } else {
@@ -64,6 +192,12 @@ impl OrderTracker {
         }
     }
 
+    /// Registered details of a live order
+    #[allow(dead_code)]
+    pub fn order_info(&self, order_id: u64) -> Option<&OrderInfo> {
+        self.active_orders.get(&order_id)
+    }
+
     /// Check if an order exists
     #[allow(dead_code)]
     pub fn has_order(&self, order_id: u64) -> bool {
@@ -76,3 +210,85 @@ impl OrderTracker {
         self.active_orders.clear();
     }
 }
+
+/// 큐 모델 기반 체결 확률 근사
+///
+/// `ProbQueueModel` + `PowerProbQueueFunc3`가 물량 감소를 앞쪽 큐에 배분하는 비율
+/// `1 - (ahead / (ahead + own))^n`을 주문 제출 시점의 큐 위치로 평가한 값.
+/// 엄밀한 체결 확률은 아니지만 지수 `n` 보정용 기대 체결률 지표로 사용한다.
+pub fn power_fill_probability(queue_ahead: f64, own_qty: f64, power: f64) -> f64 {
+    let total = queue_ahead.max(0.0) + own_qty;
+    if total <= 0.0 {
+        return 1.0;
+    }
+    1.0 - (queue_ahead.max(0.0) / total).powf(power)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_power_fill_probability_with_known_queue_ahead() {
+        // 앞에 3, 내 주문 1, n=3: 1 - 0.75^3
+        assert!((power_fill_probability(3.0, 1.0, 3.0) - 0.578125).abs() < 1e-12);
+        // 큐 맨 앞이면 확실
+        assert_eq!(power_fill_probability(0.0, 1.0, 3.0), 1.0);
+        // 지수가 클수록 같은 큐 위치에서 체결 기대치가 높음
+        assert!(power_fill_probability(3.0, 1.0, 5.0) > power_fill_probability(3.0, 1.0, 2.0));
+    }
+
+    #[test]
+    fn test_round_trip_reports_captured_spread() {
+        let mut tracker = OrderTracker::new(3.0);
+        assert!(tracker.spread_capture().is_none());
+
+        tracker.record_quote(99.75, 100.75);
+        tracker.register_order(0, OrderSide::Buy, 100.0, 1.0, 0, 0.0, 0);
+        tracker.register_order(1, OrderSide::Sell, 100.5, 1.0, 0, 0.0, 0);
+        tracker.mark_filled(0, 1.0, 0);
+        assert!(tracker.spread_capture().is_none());
+        tracker.mark_filled(1, 1.0, 0);
+
+        let capture = tracker.spread_capture().unwrap();
+        assert!((capture.captured - 0.5).abs() < 1e-12);
+        assert!((capture.quoted - 1.0).abs() < 1e-12);
+        assert_eq!(capture.matched_qty, 1.0);
+
+        // A partial match leaves the rest queued for the next opposite fill
+        tracker.record_fill(OrderSide::Sell, 101.0, 2.0);
+        tracker.record_fill(OrderSide::Buy, 100.0, 1.0);
+        let capture = tracker.spread_capture().unwrap();
+        assert!((capture.captured - 0.75).abs() < 1e-12);
+        assert_eq!(capture.matched_qty, 2.0);
+    }
+
+    #[test]
+    fn test_fill_rate_diagnostic() {
+        let mut tracker = OrderTracker::new(3.0);
+        assert!(tracker.fill_rate_diagnostic().is_none());
+
+        tracker.register_order(0, OrderSide::Buy, 99.0, 1.0, 0, 0.0, 0);
+        tracker.register_order(1, OrderSide::Sell, 101.0, 1.0, 0, 3.0, 0);
+        tracker.mark_filled(0, 1.0, 0);
+
+        let (expected, realized) = tracker.fill_rate_diagnostic().unwrap();
+        assert!((expected - (1.0 + 0.578125) / 2.0).abs() < 1e-12);
+        assert!((realized - 0.5).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_fill_latency_measured_from_submission() {
+        let mut tracker = OrderTracker::new(3.0);
+        tracker.register_order(0, OrderSide::Buy, 99.0, 1.0, 0, 0.0, 5_000_000_000);
+        tracker.register_order(1, OrderSide::Sell, 101.0, 1.0, 0, 0.0, 5_000_000_000);
+        assert!(tracker.fill_latency().summary().is_none());
+
+        // 제출 2초 뒤 체결
+        tracker.mark_filled(0, 1.0, 7_000_000_000);
+        let latency = tracker.fill_latency().summary().unwrap();
+        assert_eq!(latency.fills, 1);
+        assert_eq!(latency.mean_secs, 2.0);
+        assert_eq!(latency.median_secs, 2.0);
+    }
+}
@@ -1,49 +1,89 @@
-use std::collections::VecDeque;
+use crate::common::EwmaVolatility;
+use super::OrderSide;
 
 pub struct RiskManager {
     pub max_inventory: f64,
     #[allow(dead_code)]
     volatility_threshold: f64,
-    price_history: VecDeque<f64>,
-    #[allow(dead_code)]
-    volatility_window: usize,
+    volatility: EwmaVolatility,
+    /// Returns the volatility estimate needs before it's trusted for quoting
+    min_volatility_samples: usize,
+    last_price: Option<f64>,
+    /// |inventory| where the inventory-increasing side starts shrinking, and where it stops
+    soft_inventory: Option<f64>,
+    hard_inventory: Option<f64>,
 }
 
 impl RiskManager {
-    pub fn new(max_inventory: f64, volatility_threshold: f64, volatility_window: usize) -> Self {
+    pub fn new(max_inventory: f64, volatility_threshold: f64, volatility_lambda: f64) -> Self {
         Self {
             max_inventory,
             volatility_threshold,
-            price_history: VecDeque::with_capacity(volatility_window),
-            volatility_window,
+            volatility: EwmaVolatility::new(volatility_lambda),
+            min_volatility_samples: 0,
+            last_price: None,
+            soft_inventory: None,
+            hard_inventory: None,
         }
     }
 
+    pub fn with_volatility_burn_in(mut self, min_samples: usize) -> Self {
+        self.min_volatility_samples = min_samples;
+        self
+    }
+
+    /// `(soft, hard)` inventory bands for `side_size_multiplier` (None = no bands)
+    pub fn with_inventory_bands(mut self, bands: Option<(f64, f64)>) -> Self {
+        self.soft_inventory = bands.map(|(soft, _)| soft);
+        self.hard_inventory = bands.map(|(_, hard)| hard);
+        self
+    }
+
+    /// Size factor for quotes on `side`: 1 within the soft band, falling linearly to 0 at the hard
+    /// band for the side that would grow |inventory|; the reducing side is always 1
+    pub fn side_size_multiplier(&self, inventory: f64, side: OrderSide) -> f64 {
+        let (Some(soft), Some(hard)) = (self.soft_inventory, self.hard_inventory) else {
+            return 1.0;
+        };
+        let increases = match side {
+            OrderSide::Buy => inventory > 0.0,
+            OrderSide::Sell => inventory < 0.0,
+        };
+        let exposure = inventory.abs();
+        if !increases || exposure <= soft {
+            return 1.0;
+        }
+        if exposure >= hard {
+            return 0.0;
+        }
+        1.0 - (exposure - soft) / (hard - soft)
+    }
+
+    /// Whether enough returns have been seen for `calculate_volatility` to be meaningful
+    pub fn is_volatility_ready(&self) -> bool {
+        self.volatility.samples() >= self.min_volatility_samples
+    }
+
     #[allow(dead_code)]
     pub fn is_position_safe(&self, inventory: f64) -> bool {
         inventory.abs() < self.max_inventory
     }
 
-    #[allow(dead_code)]
     pub fn update_price(&mut self, price: f64) {
-        if self.price_history.len() >= self.volatility_window {
-            self.price_history.pop_front();
+        if let Some(last_price) = self.last_price {
+            if last_price > 0.0 {
+                self.volatility.update((price - last_price) / last_price);
+            }
         }
-        self.price_history.push_back(price);
+        self.last_price = Some(price);
     }
 
+    /// Volatility in price units (EWMA return volatility scaled by the latest price)
     pub fn calculate_volatility(&self) -> f64 {
-        if self.price_history.len() < 2 {
-            return 0.0;
+        match self.last_price {
+            Some(price) => self.volatility.value() * price,
+            None => 0.0,
         }
-
-        let mean = self.price_history.iter().sum::<f64>() / self.price_history.len() as f64;
-        let variance = self.price_history
-            .iter()
-            .map(|&price| (price - mean).powi(2))
-            .sum::<f64>() / self.price_history.len() as f64;
-        
-        variance.sqrt()
     }
 
     #[allow(dead_code)]
@@ -61,3 +101,43 @@ impl RiskManager {
         base_size * (1.0 - inventory_ratio * 0.5)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_volatility_ready_after_burn_in_returns() {
+        let mut risk = RiskManager::new(5.0, 5.0, 0.94).with_volatility_burn_in(3);
+        // The first price only sets the reference, each later one adds a return
+        for (i, price) in [100.0, 100.1, 99.9].into_iter().enumerate() {
+            risk.update_price(price);
+            assert!(!risk.is_volatility_ready(), "ready after {} price(s)", i + 1);
+        }
+        risk.update_price(100.2);
+        assert!(risk.is_volatility_ready());
+        assert!(risk.calculate_volatility() > 0.0);
+
+        assert!(RiskManager::new(5.0, 5.0, 0.94).is_volatility_ready());
+    }
+
+    #[test]
+    fn test_inventory_bands_shrink_then_stop_the_increasing_side() {
+        let risk = RiskManager::new(5.0, 5.0, 0.94).with_inventory_bands(Some((2.0, 4.0)));
+
+        // Within the soft band both sides quote in full
+        assert_eq!(risk.side_size_multiplier(1.5, OrderSide::Buy), 1.0);
+        assert_eq!(risk.side_size_multiplier(-2.0, OrderSide::Sell), 1.0);
+        // Between the bands the increasing side shrinks linearly
+        assert_eq!(risk.side_size_multiplier(3.0, OrderSide::Buy), 0.5);
+        let short = risk.side_size_multiplier(-3.5, OrderSide::Sell);
+        assert!(short > 0.0 && short < 1.0);
+        // At and past the hard band it stops, while the reducing side stays full
+        assert_eq!(risk.side_size_multiplier(4.0, OrderSide::Buy), 0.0);
+        assert_eq!(risk.side_size_multiplier(-6.0, OrderSide::Sell), 0.0);
+        assert_eq!(risk.side_size_multiplier(4.0, OrderSide::Sell), 1.0);
+        assert_eq!(risk.side_size_multiplier(-6.0, OrderSide::Buy), 1.0);
+
+        assert_eq!(RiskManager::new(5.0, 5.0, 0.94).side_size_multiplier(10.0, OrderSide::Buy), 1.0);
+    }
+}
@@ -1,10 +1,16 @@
 pub mod base;
+pub mod cvd;
 pub mod market_maker;
 pub mod momentum;
 pub mod prediction;
+pub mod portfolio;
+pub mod depth_dump;
 mod strategy_type;
 
+pub use cvd::CvdRunner;
 pub use market_maker::MarketMakerRunner;
 pub use momentum::MomentumRunner;
-pub use prediction::PredictionRunner;
+pub use prediction::{PredictionRunner, run_seed_cv};
+pub use portfolio::PortfolioRunner;
 pub use strategy_type::StrategyType;
+pub use depth_dump::DepthDumpRunner;
@@ -7,18 +7,35 @@ pub enum SignalType {
     Neutral,
 }
 
+impl SignalType {
+    /// Column value in the `--signal-log` CSV
+    pub fn label(&self) -> &'static str {
+        match self {
+            SignalType::Long => "long",
+            SignalType::Short => "short",
+            SignalType::Neutral => "neutral",
+        }
+    }
+}
+
 /// Momentum indicator calculator
+///
+/// With a `short_lookback` the indicator runs in dual-lookback mode: the short-window return,
+/// projected onto the long window, must clear the threshold while its per-step rate exceeds the
+/// long window's, so it reacts to acceleration rather than only to the level of momentum.
 pub struct MomentumIndicator {
     lookback_period: usize,
+    short_lookback: Option<usize>,
     price_history: VecDeque<f64>,
     returns_history: VecDeque<f64>,
     momentum_threshold: f64,
 }
 
 impl MomentumIndicator {
-    pub fn new(lookback_period: usize, momentum_threshold: f64) -> Self {
+    pub fn new(lookback_period: usize, short_lookback: Option<usize>, momentum_threshold: f64) -> Self {
         Self {
             lookback_period,
+            short_lookback: short_lookback.map(|short| short.clamp(1, lookback_period)),
             price_history: VecDeque::with_capacity(lookback_period + 1),
             returns_history: VecDeque::with_capacity(lookback_period),
             momentum_threshold,
@@ -59,6 +76,19 @@ impl MomentumIndicator {
         Some((last_price - first_price) / first_price)
     }
 
+    /// Return over the last `short_lookback` steps (None outside dual-lookback mode)
+    pub fn calculate_short_momentum(&self) -> Option<f64> {
+        let short = self.short_lookback?;
+        if self.price_history.len() < short + 1 {
+            return None;
+        }
+
+        let reference = self.price_history[self.price_history.len() - 1 - short];
+        let last_price = *self.price_history.back().unwrap();
+
+        Some((last_price - reference) / reference)
+    }
+
     /// Calculate average return
     #[allow(dead_code)]
     pub fn calculate_average_return(&self) -> Option<f64> {
@@ -72,6 +102,10 @@ impl MomentumIndicator {
 
     /// Generate momentum signal
     pub fn generate_signal(&self) -> SignalType {
+        if let Some(short) = self.short_lookback {
+            return self.generate_dual_signal(short);
+        }
+
         let momentum = match self.calculate_momentum() {
             Some(m) => m,
             None => return SignalType::Neutral,
@@ -86,6 +120,26 @@ impl MomentumIndicator {
         }
     }
 
+    fn generate_dual_signal(&self, short: usize) -> SignalType {
+        let (short_momentum, long_momentum) = match (self.calculate_short_momentum(), self.calculate_momentum()) {
+            (Some(short_momentum), Some(long_momentum)) => (short_momentum, long_momentum),
+            _ => return SignalType::Neutral,
+        };
+
+        let long_steps = (self.price_history.len() - 1) as f64;
+        let short_rate = short_momentum / short as f64;
+        let long_rate = long_momentum / long_steps;
+        let projected = short_rate * long_steps;
+
+        if projected > self.momentum_threshold && short_rate > long_rate {
+            SignalType::Long
+        } else if projected < -self.momentum_threshold && short_rate < long_rate {
+            SignalType::Short
+        } else {
+            SignalType::Neutral
+        }
+    }
+
     /// Check if indicator is ready
     pub fn is_ready(&self) -> bool {
         self.price_history.len() >= self.lookback_period
@@ -96,6 +150,27 @@ impl MomentumIndicator {
         self.calculate_momentum().unwrap_or(0.0)
     }
 
+    /// Entry size multiple for strength-scaled sizing: |momentum| / threshold, capped at
+    /// `max_multiple` (None = fixed sizing, always 1)
+    pub fn size_multiplier(&self, max_multiple: Option<f64>) -> f64 {
+        match max_multiple {
+            Some(max_multiple) if self.momentum_threshold > 0.0 => {
+                (self.get_momentum().abs() / self.momentum_threshold).min(max_multiple)
+            }
+            _ => 1.0,
+        }
+    }
+
+    /// Signal strength in [0, 1]: |momentum| / (`scale` x threshold), so a move just past the
+    /// threshold scores about 1 / `scale` and one `scale` times the threshold or more scores 1
+    pub fn confidence(&self, scale: f64) -> f64 {
+        let full_confidence = scale * self.momentum_threshold;
+        if full_confidence <= 0.0 {
+            return 1.0;
+        }
+        (self.get_momentum().abs() / full_confidence).clamp(0.0, 1.0)
+    }
+
     /// Calculate price volatility (standard deviation)
     #[allow(dead_code)]
     pub fn calculate_volatility(&self) -> Option<f64> {
@@ -119,7 +194,7 @@ mod tests {
 
     #[test]
     fn test_momentum_calculation() {
-        let mut indicator = MomentumIndicator::new(5, 0.01);
+        let mut indicator = MomentumIndicator::new(5, None, 0.01);
         
         let prices = vec![100.0, 101.0, 102.0, 103.0, 104.0, 105.0];
         
@@ -133,7 +208,7 @@ mod tests {
 
     #[test]
     fn test_signal_generation() {
-        let mut indicator = MomentumIndicator::new(5, 0.01);
+        let mut indicator = MomentumIndicator::new(5, None, 0.01);
         
         // Upward trend
         for i in 0..6 {
@@ -143,4 +218,57 @@ mod tests {
         let signal = indicator.generate_signal();
         assert_eq!(signal, SignalType::Long);
     }
+
+    #[test]
+    fn test_size_scales_with_momentum_strength_up_to_cap() {
+        let mut indicator = MomentumIndicator::new(5, None, 0.01);
+        // (102 - 100) / 100 = 0.02 = 2x the threshold
+        for price in [100.0, 100.4, 100.8, 101.2, 101.6, 102.0] {
+            indicator.update(price);
+        }
+
+        assert!((indicator.size_multiplier(Some(5.0)) - 2.0).abs() < 1e-9);
+        assert!((indicator.size_multiplier(Some(1.5)) - 1.5).abs() < 1e-9);
+        assert_eq!(indicator.size_multiplier(None), 1.0);
+
+        let base_size = 0.05;
+        let size = base_size * indicator.size_multiplier(Some(5.0));
+        assert!((size - 2.0 * base_size).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_confidence_grows_with_momentum_up_to_one() {
+        let mut indicator = MomentumIndicator::new(5, None, 0.01);
+        // (101.1 - 100) / 100 = 0.011, just past the threshold
+        for price in [100.0, 100.2, 100.4, 100.6, 100.8, 101.1] {
+            indicator.update(price);
+        }
+        assert_eq!(indicator.generate_signal(), SignalType::Long);
+        assert!((indicator.confidence(3.0) - 0.011 / 0.03).abs() < 1e-9);
+
+        for price in [102.0, 104.0, 106.0, 108.0, 110.0] {
+            indicator.update(price);
+        }
+        assert_eq!(indicator.confidence(3.0), 1.0);
+    }
+
+    #[test]
+    fn test_dual_lookback_fires_earlier_on_acceleration() {
+        fn first_long_signal(mut indicator: MomentumIndicator) -> Option<usize> {
+            // Flat, then accelerating: p = 100 + 0.01 * k^2
+            let prices = std::iter::repeat_n(100.0, 25)
+                .chain((1..30).map(|k| 100.0 + 0.01 * (k * k) as f64));
+            for (i, price) in prices.enumerate() {
+                indicator.update(price);
+                if indicator.is_ready() && indicator.generate_signal() == SignalType::Long {
+                    return Some(i);
+                }
+            }
+            None
+        }
+
+        let single = first_long_signal(MomentumIndicator::new(20, None, 0.002)).unwrap();
+        let dual = first_long_signal(MomentumIndicator::new(20, Some(5), 0.002)).unwrap();
+        assert!(dual < single, "dual {} vs single {}", dual, single);
+    }
 }
@@ -1,20 +1,29 @@
 use anyhow::Result;
 use hftbacktest::{
-    backtest::{Backtest, BacktestError, ExchangeKind, L2AssetBuilder, assettype::LinearAsset,
-        data::DataSource, models::{CommonFees, ConstantLatency, ProbQueueModel, 
+    backtest::{Backtest, BacktestError, L2AssetBuilder,
+        models::{CommonFees, ConstantLatency, ProbQueueModel, 
         PowerProbQueueFunc3, TradingValueFeeModel}},
     prelude::{Bot, HashMapMarketDepth, Status, TimeInForce, OrdType},
     depth::MarketDepth,
-    types::ElapseResult,
+    types::{ElapseResult, Event},
 };
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
-use crossbeam_channel::Sender;
-use crate::common::{calculate_mid_price, is_valid_depth};
-use crate::config::{TICK_SIZE, LOT_SIZE, ELAPSE_DURATION_NS, UPDATE_INTERVAL, COMMAND_POLL_TIMEOUT_MICROS};
-use crate::ui::{PerformanceData, OrderBookLevel};
+use crate::common::{calculate_mid_price, AdaptiveElapse, validate_time_in_force,
+    entry_price_tick, entry_ladder, summarize_ladder_fills, FeeCalculator, FeeDrag, order_quantity, SizingMode, force_flatten, reconcile,
+    FilePreloader, data_source, HighWaterMark, SignalDelay, TradeThrottle, AtrTracker, StopKind, validate_stop_kind,
+    RunTarget, RunStopReason, MarkPricer, validate_scale_out_levels, SignalLog, NoTradeZone, OrderIdGenerator, BorrowCost, StaleBookGuard, StaleBookEvent,
+    last_book_update_ns, EntryWindows, LossStreakHalt, FillTracker, impact_fill_price, crosses_touch};
+use crate::{log_info, log_warn};
+use crate::config::{ADAPTIVE_ELAPSE_QUIET_TICKS, ADAPTIVE_ELAPSE_MAX_MULTIPLIER, TICK_SIZE, LOT_SIZE, ASSET_KIND, PERFORMANCE_FEE_PCT, RUN_PROFIT_TARGET_PCT, RUN_LOSS_LIMIT_PCT, EXCHANGE_KIND, FEE_MODEL, ESTIMATED_FEE_RATE, MIN_PROFIT_OVER_FEES_PCT, SHORT_BORROW_RATE_BPS_PER_DAY, MOMENTUM_TIME_IN_FORCE,
+    MOMENTUM_SHORT_LOOKBACK_PERIOD, MOMENTUM_REQUIRE_TIMEFRAME_CONFIRMATION, MOMENTUM_CONFIRMATION_LOOKBACK_PERIOD,
+    MOMENTUM_ENTRY_AGGRESSIVENESS, MOMENTUM_ENTRY_LEVELS, ENTRY_LEVEL_SPACING_TICKS, MOMENTUM_SIZING_MODE, MOMENTUM_SIZE_SCALING_MAX, MOMENTUM_CONFIDENCE_SCALE, MOMENTUM_MIN_CONFIDENCE, MOMENTUM_ELAPSE_DURATION_NS, UPDATE_INTERVAL, COMMAND_POLL_TIMEOUT_MICROS, PASSIVE_ENTRY_TIMEOUT_NS, ORDER_RESPONSE_WAIT_NS, MOMENTUM_MAX_REQUOTES, MOMENTUM_SIGNAL_DELAY_TICKS, MOMENTUM_MIN_HOLD_TICKS, MOMENTUM_REQUIRE_SIGNAL_TRANSITION, MOMENTUM_MAX_TRADES_PER_MINUTE, MOMENTUM_MAX_CONSECUTIVE_LOSSES, MOMENTUM_NO_TRADE_ZONE_TICKS, MOMENTUM_NO_TRADE_ZONE_DURATION, MOMENTUM_STOP_KIND, MOMENTUM_TAKE_PROFIT_VOL_K, MOMENTUM_SCALE_OUT_LEVELS, MOMENTUM_SCALE_OUT_TRAIL_PCT, MARK_PRICE_MODE, LAST_TRADES_CAPACITY, ATR_BAR_TICKS, ATR_PERIOD, MOMENTUM_DRY_RUN, PRICE_IMPACT_DEPTH_TICKS, RUN_TO_END_BATCH,
+    FLATTEN_STEP_TICKS, FLATTEN_MAX_ATTEMPTS, FLATTEN_WAIT_NS, MAX_BOOK_STALENESS_NS, FLATTEN_ON_STALE_BOOK, ENTRY_TIME_WINDOWS_UTC, GUI_UPDATE_ON_FILL, GUI_REPLAY_RATE_WINDOW_MS, BOOK_PARTIAL_FILLS,
+    SIGNAL_LOG_HORIZON_TICKS, TAKER_ORDER_LATENCY_NS, TAKER_QUEUE_POWER};
+use crate::ui::{DataSender, PerformanceData, SideAttribution, UpdateCadence, ReplayRateMeter};
 use crate::controller::StrategyController;
+use crate::strategy::base::{send_gui_update, extract_orderbook};
 use super::{MomentumIndicator, SignalType};
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -24,17 +33,68 @@ enum PositionState {
     Short,
 }
 
+/// Entry orders that haven't been confirmed filled yet (passive entries can rest).
+/// Multi-level entries track one child order per price level.
+#[derive(Debug, Clone)]
+struct PendingEntry {
+    /// Orders of the current attempt
+    order_ids: Vec<u64>,
+    side: PositionState,
+    submitted_at: i64,
+    target_qty: f64,
+    /// Fills carried over from earlier (requoted) attempts
+    filled_qty: f64,
+    filled_notional: f64,
+    filled_orders: usize,
+    requotes: usize,
+    /// Book-walk VWAP the entry is booked at when it crossed bigger than the touch
+    impact_price: Option<f64>,
+}
+
+/// Exit order that filled partially and is still working
+#[derive(Debug, Clone)]
+struct PendingExit {
+    order_id: u64,
+    side: PositionState,
+    submitted_at: i64,
+    /// Quantity closed (and PnL booked) by this order so far
+    filled_qty: f64,
+    net_pnl: f64,
+    /// Book-walk VWAP the exit is booked at when it was bigger than the touch
+    impact_price: Option<f64>,
+}
+
 pub struct MomentumRunner {
     data_files: Vec<PathBuf>,
     momentum_indicator: MomentumIndicator,
+    /// Longer-lookback indicator entries must agree with (None = no confirmation)
+    confirmation_indicator: Option<MomentumIndicator>,
     #[allow(dead_code)]
     lookback_period: usize,
     #[allow(dead_code)]
     momentum_threshold: f64,
     position_size: f64,
     stop_loss_pct: f64,
+    stop_kind: StopKind,
+    atr: AtrTracker,
     take_profit_pct: f64,
+    /// Scales the take-profit with the ATR at entry (None = always take_profit_pct)
+    take_profit_vol_k: Option<f64>,
+    /// Take-profit of the open position, fixed when it was opened
+    entry_take_profit_pct: f64,
+    /// (profit_pct, fraction_to_close) exits in ascending order (empty = close everything at
+    /// take_profit_pct)
+    scale_out_levels: Vec<(f64, f64)>,
+    scale_out_trail_pct: f64,
+    /// Levels fired for the open position and the size it was opened with
+    scale_outs_fired: usize,
+    scale_out_base_qty: f64,
+    /// Best price since the first scale-out, which the trailing stop follows
+    trail_peak: Option<f64>,
     initial_capital: f64,
+    performance_fee: HighWaterMark,
+    run_target: RunTarget,
+    mark_pricer: MarkPricer,
     position_state: PositionState,
     entry_price: f64,
     position_qty: f64,
@@ -43,10 +103,57 @@ pub struct MomentumRunner {
     winning_trades: usize,
     total_orders: usize,
     total_fills: usize,
+    attribution: SideAttribution,
     #[allow(dead_code)]
     position_entry_time: Option<Instant>,
     total_hold_time: Duration,
-    next_order_id: u64,
+    order_ids: OrderIdGenerator,
+    tif: TimeInForce,
+    elapse_ns: i64,
+    entry_aggressiveness: Option<i64>,
+    entry_levels: usize,
+    order_timeout_ns: i64,
+    max_requotes: usize,
+    signal_delay: SignalDelay<SignalType>,
+    /// Every step's momentum signal for `--signal-log`
+    signal_log: SignalLog,
+    /// Ticks before a position may close on anything but the hard stop (0 = off)
+    min_hold_ticks: usize,
+    /// Strategy ticks run so far, and the one the open position was entered on
+    strategy_ticks: usize,
+    entry_tick: usize,
+    /// Enter only on a signal that changed since the last entry
+    require_signal_transition: bool,
+    /// Previous tick's signal, and the latest one that was a fresh transition and hasn't been
+    /// entered on yet
+    previous_signal: SignalType,
+    armed_signal: Option<SignalType>,
+    entry_throttle: TradeThrottle,
+    entry_windows: EntryWindows,
+    loss_streak: LossStreakHalt,
+    no_trade_zone: NoTradeZone,
+    borrow_cost: BorrowCost,
+    /// Suspends trading across data gaps
+    stale_book: StaleBookGuard,
+    /// Book-walk depth for taker orders bigger than the touch (None = fill at the touch)
+    price_impact_ticks: Option<usize>,
+    dry_run: bool,
+    sizing_mode: SizingMode,
+    /// Equity as of the last strategy tick, the base for FractionOfEquity sizing
+    sizing_equity: f64,
+    /// Cap on the momentum-strength entry size multiple (None = fixed size)
+    size_scaling_max: Option<f64>,
+    /// Momentum (in thresholds) at which signal confidence reaches 1
+    confidence_scale: f64,
+    /// Entries whose signal confidence is below this are skipped
+    min_confidence: f64,
+    pending_entry: Option<PendingEntry>,
+    pending_exit: Option<PendingExit>,
+    /// Books exits as they execute (see BOOK_PARTIAL_FILLS)
+    fill_tracker: FillTracker,
+    fees: FeeCalculator,
+    /// Take-profit must beat the round-trip fee by this much (None = off)
+    min_profit_over_fees: Option<f64>,
 }
 
 impl MomentumRunner {
@@ -63,9 +170,9 @@ impl MomentumRunner {
         if data_files.is_empty() {
             anyhow::bail!("No data files provided");
         }
-        println!("Using {} file(s):", data_files.len());
+        log_info!("Using {} file(s):", data_files.len());
         for (i, f) in data_files.iter().enumerate() {
-            println!("  [{}] {}", i + 1, f.display());
+            log_info!("  [{}] {}", i + 1, f.display());
         }
         Self::create_runner(data_files, lookback_period, momentum_threshold, position_size, stop_loss_pct, take_profit_pct, initial_capital)
     }
@@ -79,15 +186,36 @@ impl MomentumRunner {
         take_profit_pct: f64,
         initial_capital: f64,
     ) -> Result<Self> {
+        validate_time_in_force(&EXCHANGE_KIND, MOMENTUM_TIME_IN_FORCE)?;
+        validate_stop_kind(MOMENTUM_STOP_KIND)?;
+        validate_scale_out_levels(MOMENTUM_SCALE_OUT_LEVELS, MOMENTUM_SCALE_OUT_TRAIL_PCT)?;
+        if let Some(k) = MOMENTUM_TAKE_PROFIT_VOL_K.filter(|k| !k.is_finite() || *k < 0.0) {
+            anyhow::bail!("Take-profit volatility scale must be finite and non-negative, got {}", k);
+        }
+        
         Ok(Self {
             data_files,
-            momentum_indicator: MomentumIndicator::new(lookback_period, momentum_threshold),
+            momentum_indicator: MomentumIndicator::new(lookback_period, MOMENTUM_SHORT_LOOKBACK_PERIOD, momentum_threshold),
+            confirmation_indicator: MOMENTUM_REQUIRE_TIMEFRAME_CONFIRMATION
+                .then(|| MomentumIndicator::new(MOMENTUM_CONFIRMATION_LOOKBACK_PERIOD, None, momentum_threshold)),
             lookback_period,
             momentum_threshold,
             position_size,
             stop_loss_pct,
+            stop_kind: MOMENTUM_STOP_KIND,
+            atr: AtrTracker::new(ATR_BAR_TICKS, ATR_PERIOD),
             take_profit_pct,
+            take_profit_vol_k: MOMENTUM_TAKE_PROFIT_VOL_K,
+            entry_take_profit_pct: take_profit_pct,
+            scale_out_levels: MOMENTUM_SCALE_OUT_LEVELS.to_vec(),
+            scale_out_trail_pct: MOMENTUM_SCALE_OUT_TRAIL_PCT,
+            scale_outs_fired: 0,
+            scale_out_base_qty: 0.0,
+            trail_peak: None,
             initial_capital,
+            performance_fee: HighWaterMark::new(PERFORMANCE_FEE_PCT, initial_capital),
+            run_target: RunTarget::new(RUN_PROFIT_TARGET_PCT, RUN_LOSS_LIMIT_PCT),
+            mark_pricer: MarkPricer::new(MARK_PRICE_MODE),
             position_state: PositionState::Flat,
             entry_price: 0.0,
             position_qty: 0.0,
@@ -95,61 +223,55 @@ impl MomentumRunner {
             winning_trades: 0,
             total_orders: 0,
             total_fills: 0,
+            attribution: SideAttribution::default(),
             position_entry_time: None,
             total_hold_time: Duration::ZERO,
-            next_order_id: 1,
+            order_ids: OrderIdGenerator::new(),
+            tif: MOMENTUM_TIME_IN_FORCE,
+            elapse_ns: MOMENTUM_ELAPSE_DURATION_NS,
+            entry_aggressiveness: MOMENTUM_ENTRY_AGGRESSIVENESS,
+            entry_levels: MOMENTUM_ENTRY_LEVELS,
+            order_timeout_ns: PASSIVE_ENTRY_TIMEOUT_NS,
+            max_requotes: MOMENTUM_MAX_REQUOTES,
+            signal_delay: SignalDelay::new(MOMENTUM_SIGNAL_DELAY_TICKS, SignalType::Neutral),
+            signal_log: SignalLog::new("Momentum", SIGNAL_LOG_HORIZON_TICKS),
+            min_hold_ticks: MOMENTUM_MIN_HOLD_TICKS,
+            strategy_ticks: 0,
+            entry_tick: 0,
+            require_signal_transition: MOMENTUM_REQUIRE_SIGNAL_TRANSITION,
+            previous_signal: SignalType::Neutral,
+            armed_signal: None,
+            entry_throttle: TradeThrottle::new(MOMENTUM_MAX_TRADES_PER_MINUTE),
+            entry_windows: EntryWindows::new(ENTRY_TIME_WINDOWS_UTC),
+            loss_streak: LossStreakHalt::new(MOMENTUM_MAX_CONSECUTIVE_LOSSES),
+            no_trade_zone: NoTradeZone::new(MOMENTUM_NO_TRADE_ZONE_TICKS, MOMENTUM_NO_TRADE_ZONE_DURATION),
+            borrow_cost: BorrowCost::new(SHORT_BORROW_RATE_BPS_PER_DAY),
+            stale_book: StaleBookGuard::new(MAX_BOOK_STALENESS_NS, FLATTEN_ON_STALE_BOOK),
+            price_impact_ticks: PRICE_IMPACT_DEPTH_TICKS,
+            dry_run: MOMENTUM_DRY_RUN,
+            sizing_mode: MOMENTUM_SIZING_MODE,
+            sizing_equity: initial_capital,
+            size_scaling_max: MOMENTUM_SIZE_SCALING_MAX,
+            confidence_scale: MOMENTUM_CONFIDENCE_SCALE,
+            min_confidence: MOMENTUM_MIN_CONFIDENCE,
+            pending_entry: None,
+            pending_exit: None,
+            fill_tracker: FillTracker::new(BOOK_PARTIAL_FILLS),
+            fees: FeeCalculator::new(FEE_MODEL, ESTIMATED_FEE_RATE),
+            min_profit_over_fees: MIN_PROFIT_OVER_FEES_PCT,
         })
     }
     
-    /// Extract order book levels from market depth
-    fn extract_orderbook<MD>(&self, depth: &MD, levels: usize) -> (Vec<OrderBookLevel>, Vec<OrderBookLevel>)
-    where
-        MD: MarketDepth,
-    {
-        let mut bids = Vec::new();
-        let mut asks = Vec::new();
-        
-        let best_bid_tick = depth.best_bid_tick();
-        let best_ask_tick = depth.best_ask_tick();
-        let tick_size = depth.tick_size();
-        
-        if best_bid_tick != i64::MIN {
-            for i in 0..levels {
-                let tick = best_bid_tick - i as i64;
-                let qty = depth.bid_qty_at_tick(tick);
-                if qty > 0.0 {
-                    bids.push(OrderBookLevel {
-                        price: tick as f64 * tick_size,
-                        quantity: qty,
-                    });
-                }
-            }
-        }
-        
-        if best_ask_tick != i64::MAX {
-            for i in 0..levels {
-                let tick = best_ask_tick + i as i64;
-                let qty = depth.ask_qty_at_tick(tick);
-                if qty > 0.0 {
-                    asks.push(OrderBookLevel {
-                        price: tick as f64 * tick_size,
-                        quantity: qty,
-                    });
-                }
-            }
-        }
-        
-        (bids, asks)
-    }
-
     /// Run strategy with GUI monitor and Controller
     pub fn run_with_controller(
         &mut self,
-        sender: Sender<PerformanceData>,
+        sender: DataSender,
         controller: Arc<StrategyController>,
     ) -> Result<()> {
         let file_count = self.data_files.len();
         
+        let mut preload: Option<FilePreloader> = None;
+        
         for file_idx in 0..file_count {
             // Wait for start signal if in paused or stopped state
             while !controller.is_running() && !controller.should_stop() {
@@ -157,34 +279,42 @@ impl MomentumRunner {
             }
             
             if controller.should_stop() {
-                println!("\n⏹ Strategy stopped by user");
+                log_info!("\n⏹ Strategy stopped by user");
                 break;
             }
             
             let data_file = self.data_files[file_idx].clone();
+            let preloaded = preload.take().and_then(FilePreloader::wait);
+            // Load the next file in the background while this one runs
+            preload = self.data_files.get(file_idx + 1)
+                .map(|next| FilePreloader::spawn(next.to_string_lossy().into_owned()));
             
             // Notify GUI to clear chart data for new file (except first file)
             if file_idx > 0 {
                 controller.notify_new_file();
             }
             
-            println!("\n{}", "=".repeat(60));
-            println!("Running momentum strategy on file [{}/{}]: {}", 
-                     file_idx + 1, 
-                     file_count, 
-                     data_file.display());
-            println!("{}\n", "=".repeat(60));
+            log_info!("\n{}", "=".repeat(60));
+            log_info!("Running momentum strategy on file [{}/{}]: {}", 
+                      file_idx + 1, 
+                      file_count, 
+                      data_file.display());
+            log_info!("{}\n", "=".repeat(60));
             
             self.run_strategy_with_control(
                 data_file.to_str().unwrap(),
+                preloaded,
                 &sender,
                 &controller,
             )?;
         }
         
+        // Stopped before the next file was needed: discard it; the loader thread is detached
+        drop(preload);
+        
         if !controller.should_stop() {
             controller.mark_completed();
-            println!("\n✅ All files processed successfully!");
+            log_info!("\n✅ All files processed successfully!");
         }
         
         // Keep thread alive until GUI closes
@@ -195,7 +325,7 @@ impl MomentumRunner {
     
     /// Keep thread alive until GUI window closes
     fn keep_alive_until_close(&self, controller: &StrategyController) {
-        println!("Backtest finished. Close the window to exit.");
+        log_info!("Backtest finished. Close the window to exit.");
         
         loop {
             if !controller.process_commands(Duration::from_millis(200)) {
@@ -210,27 +340,44 @@ impl MomentumRunner {
     fn run_strategy_with_control(
         &mut self,
         data_file: &str,
-        sender: &Sender<PerformanceData>,
+        preloaded: Option<Vec<Event>>,
+        sender: &DataSender,
         controller: &StrategyController,
     ) -> Result<()> {
-        println!("Loading data from: {}", data_file);
+        log_info!("Loading data from: {}", data_file);
 
-        let mut hbt = self.create_backtest(data_file)?;
+        let mut hbt = self.create_backtest(data_file, preloaded)?;
+        // Each file's equity curve starts from the initial capital again
+        self.performance_fee = HighWaterMark::new(PERFORMANCE_FEE_PCT, self.initial_capital);
+        self.fees.reset_total_fees();
         
-        println!("Momentum strategy started...\n");
+        log_info!("Momentum strategy started...\n");
 
         let mut realized_pnl = 0.0;
         let cash = self.initial_capital;
         let mut update_count = 0;
+        let mut stepper = AdaptiveElapse::new(self.elapse_ns, ADAPTIVE_ELAPSE_QUIET_TICKS, ADAPTIVE_ELAPSE_MAX_MULTIPLIER);
 
-        println!("Waiting for market data...\n");
+        log_info!("Waiting for market data...\n");
 
         // Initialize position state
         self.position_state = PositionState::Flat;
+        self.pending_entry = None;
+        self.pending_exit = None;
+        self.fill_tracker.reset();
+        self.signal_delay.reset();
+        self.signal_log.flush();
+        self.entry_throttle.reset();
+        self.no_trade_zone.reset();
+        self.atr.reset();
+        self.borrow_cost.reset();
+        self.stale_book.reset();
+        self.mark_pricer.reset();
         self.entry_price = 0.0;
         self.position_qty = 0.0;
 
-        let mut last_gui_update = Instant::now();
+        let mut gui_cadence = UpdateCadence::new(Duration::from_millis(33), GUI_UPDATE_ON_FILL);
+        let mut replay_rate = ReplayRateMeter::new(Duration::from_millis(GUI_REPLAY_RATE_WINDOW_MS));
         let mut last_command_check = Instant::now();
         let command_check_interval = Duration::from_millis(16); // ~60Hz command polling
         let mut data_ended = false;
@@ -238,11 +385,12 @@ impl MomentumRunner {
         loop {
             // Check if data has ended
             if data_ended {
-                println!("\nEnd of data reached!");
+                log_info!("\nEnd of data reached!");
                 if self.position_state != PositionState::Flat {
-                    println!("Closing remaining position...");
-                    let _ = self.close_position(&mut hbt, &mut realized_pnl)?;
+                    log_info!("Closing remaining position...");
+                    self.flatten_remaining(&mut hbt, &mut realized_pnl)?;
                 }
+                self.reconcile_with_engine(&hbt, realized_pnl);
                 let final_depth = hbt.depth(0);
                 self.print_final_stats(realized_pnl, cash, final_depth);
                 return Ok(());
@@ -254,11 +402,17 @@ impl MomentumRunner {
                 controller.process_commands(Duration::from_millis(50));
                 
                 if controller.should_stop() {
-                    println!("\n⏹ Strategy stopped by user");
+                    log_info!("\n⏹ Strategy stopped by user");
                     break;
                 }
                 continue;
             }
+
+            // The loss-streak halt paused the run; resuming it lifts the halt
+            if self.loss_streak.is_halted() {
+                self.loss_streak.resume();
+                log_info!("▶ Loss streak cleared, entries resumed");
+            }
             
             // Process commands at fixed interval when running
             if last_command_check.elapsed() >= command_check_interval {
@@ -266,16 +420,19 @@ impl MomentumRunner {
                 last_command_check = Instant::now();
                 
                 if controller.should_stop() {
-                    println!("\n⏹ Strategy stopped by user");
+                    log_info!("\n⏹ Strategy stopped by user");
                     break;
                 }
             }
             
             // Speed adjustment - affects simulation time
             let speed = controller.speed_multiplier();
+            let run_to_end = controller.is_run_to_end();
             
             // Calculate iterations and delay based on speed
-            let (iterations_per_loop, loop_delay_ms) = if speed >= 100.0 {
+            let (iterations_per_loop, loop_delay_ms) = if run_to_end {
+                (RUN_TO_END_BATCH, 0u64)
+            } else if speed >= 100.0 {
                 (100, 0u64)
             } else if speed >= 10.0 {
                 ((speed / 10.0).ceil() as usize, 1)
@@ -286,28 +443,52 @@ impl MomentumRunner {
             };
             
             for _ in 0..iterations_per_loop {
-                match hbt.elapse(ELAPSE_DURATION_NS) {
+                // A seek stops fast-forwarding here and pauses the run
+                if controller.reached_seek_target(stepper.sim_time_secs()) {
+                    break;
+                }
+                replay_rate.observe(hbt.current_timestamp());
+                match hbt.elapse(stepper.duration_ns()) {
                     Ok(ElapseResult::EndOfData) => {
                         data_ended = true;
                         break;
                     }
                     Ok(_) => {
+                        self.mark_pricer.observe_trades(hbt.last_trades(0));
+                        hbt.clear_last_trades(Some(0));
                         let depth = hbt.depth(0);
                         
-                        if !is_valid_depth(depth) {
+                        let Some(mid_price) = calculate_mid_price(depth) else {
                             continue;
-                        }
+                        };
                         
                         update_count += 1;
+                        stepper.observe(depth);
                         
-                        let mid_price = calculate_mid_price(depth);
+                        self.borrow_cost.accrue(self.signed_position(), mid_price, hbt.current_timestamp());
                         
                         // Update momentum indicator
                         self.momentum_indicator.update(mid_price);
+                        self.signal_log.observe_mid(mid_price);
+                        if self.signal_log.is_enabled() && self.momentum_indicator.is_ready() {
+                            let signal = self.momentum_indicator.generate_signal();
+                            self.signal_log.record(hbt.current_timestamp(), signal.label(),
+                                                   self.momentum_indicator.get_momentum(), mid_price);
+                        }
+                        if let Some(confirmation) = self.confirmation_indicator.as_mut() {
+                            confirmation.update(mid_price);
+                        }
+                        self.atr.update(mid_price);
 
                         if update_count % UPDATE_INTERVAL == 0 {
                             // Execute strategy logic
                             self.execute_strategy(&mut hbt, &mut realized_pnl)?;
+                            self.accrue_performance_fee(hbt.depth(0), cash, realized_pnl);
+                            // Stop batching so the fill's update carries its own timestamp
+                            gui_cadence.observe_fills(self.total_fills);
+                            if gui_cadence.fill_pending() && !run_to_end {
+                                break;
+                            }
                         }
                     }
                     Err(_) => {
@@ -316,32 +497,54 @@ impl MomentumRunner {
                     }
                 }
             }
+
+            // Losing streak: pause the run with entries halted until the user resumes
+            if let Some(notice) = self.loss_streak.take_halt_notice() {
+                log_warn!("\n⏸ {}", notice);
+                controller.pause_with_log(notice);
+            }
+
+            // Whole-run target: flatten and end the run once total return reaches it
+            if let Some(reason) = self.run_target_reached(hbt.depth(0), realized_pnl) {
+                log_info!("\n🏁 {}", reason);
+                if self.position_state != PositionState::Flat {
+                    self.flatten_remaining(&mut hbt, &mut realized_pnl)?;
+                }
+                self.reconcile_with_engine(&hbt, realized_pnl);
+                self.print_final_stats(realized_pnl, cash, hbt.depth(0));
+                controller.mark_target_reached(reason);
+                return Ok(());
+            }
             
+            replay_rate.observe(hbt.current_timestamp());
+
             // Send data to GUI (throttled to ~30 FPS)
-            if last_gui_update.elapsed() >= Duration::from_millis(33) {
+            // In run-to-end mode only the final state is sent
+            let gui_due = gui_cadence.due(run_to_end, data_ended);
+            if gui_due {
                 let depth_for_data = hbt.depth(0);
-                if is_valid_depth(depth_for_data) {
-                    let mid_price = calculate_mid_price(depth_for_data);
-                    
-                    let (position_value, unrealized_pnl) = self.calculate_position_metrics(mid_price);
-                    let (bids, asks) = self.extract_orderbook(depth_for_data, 10);
+                if let Some(mid_price) = calculate_mid_price(depth_for_data) {
+                    let (_, unrealized_pnl) =
+                        self.calculate_position_metrics(self.mark_pricer.mark(depth_for_data).unwrap_or(mid_price));
+                    let (bids, asks) = extract_orderbook(depth_for_data, 10);
                     let avg_hold_time = if self.num_trades > 0 {
                         self.total_hold_time.as_secs_f64() / self.num_trades as f64
                     } else {
                         0.0
                     };
                     
-                    // Use try_send to avoid blocking GUI
+                    // Never blocks the GUI; the newest update wins when the channel is full
                     // timestamp = simulation time in seconds
-                    let sim_time_secs = update_count as f64 * (ELAPSE_DURATION_NS as f64 / 1_000_000_000.0);
-                    let _ = sender.try_send(PerformanceData {
+                    let sim_time_secs = stepper.sim_time_secs();
+                    let (stop_price, take_price) = self.exit_levels();
+                    send_gui_update(sender, controller, PerformanceData {
                         timestamp: sim_time_secs,
-                        equity: cash + realized_pnl + position_value,
+                        equity: self.performance_fee.net_equity(),
                         realized_pnl,
                         unrealized_pnl,
                         position: self.position_qty,
                         mid_price,
-                        strategy_name: "Momentum".to_string(),
+                        strategy_name: if self.dry_run { "Momentum (Dry Run)" } else { "Momentum" }.to_string(),
                         num_trades: self.num_trades,
                         winning_trades: self.winning_trades,
                         total_fills: self.total_fills,
@@ -350,25 +553,39 @@ impl MomentumRunner {
                         latency_micros: 100,
                         bids,
                         asks,
+                        stop_price,
+                        take_price,
+                        performance_fees_accrued: self.performance_fee.accrued(),
+                        borrow_costs: self.borrow_cost.accrued(),
+                        fees_paid: self.fees.total_fees(),
+                        attribution: Some(self.attribution),
+                        spread_capture: None,
+                        imbalance: None,
+                        fill_update: gui_cadence.fill_pending(),
+                        signal_confidence: self.momentum_indicator.is_ready()
+                            .then(|| self.momentum_indicator.confidence(self.confidence_scale)),
+                        sleeves: Vec::new(),
+                        replay_rate: replay_rate.rate(),
                     });
                 }
-                last_gui_update = Instant::now();
+                gui_cadence.mark_sent();
             }
             
             // Apply speed-based delay
             if loop_delay_ms > 0 {
                 std::thread::sleep(Duration::from_millis(loop_delay_ms));
-            } else {
+            } else if !run_to_end {
                 std::thread::yield_now();
             }
         }
 
         // Close remaining position
         if self.position_state != PositionState::Flat {
-            println!("\nClosing remaining position...");
-            let _ = self.close_position(&mut hbt, &mut realized_pnl)?;
+            log_info!("\nClosing remaining position...");
+            self.flatten_remaining(&mut hbt, &mut realized_pnl)?;
         }
 
+        self.reconcile_with_engine(&hbt, realized_pnl);
         let final_depth = hbt.depth(0);
         self.print_final_stats(realized_pnl, cash, final_depth);
 
@@ -383,52 +600,71 @@ impl MomentumRunner {
     where
         MD: MarketDepth,
     {
+        self.no_trade_zone.tick();
+        self.strategy_ticks += 1;
+        self.sizing_equity = self.initial_capital + *realized_pnl - self.borrow_cost.accrued();
+        if self.check_stale_book(hbt, realized_pnl)? {
+            return Ok(());
+        }
         if !self.momentum_indicator.is_ready() {
             return Ok(());
         }
 
+        // Don't act on new signals while an entry order is still working
+        if self.pending_entry.is_some() {
+            return self.check_pending_entry(hbt);
+        }
+        // Likewise for an exit that filled partially
+        if let Some(exit) = self.pending_exit.take() {
+            return self.resolve_exit(hbt, exit, realized_pnl);
+        }
+
         let depth = hbt.depth(0);
-        let mid_price = calculate_mid_price(depth);
+        let Some(mid_price) = calculate_mid_price(depth) else {
+            return Ok(());
+        };
 
-        // Check exit conditions (stop-loss or take-profit)
-        if self.position_state != PositionState::Flat {
-            if self.should_close_position(mid_price) {
-                println!("  Closing position due to stop loss or take profit");
-                return self.close_position(hbt, realized_pnl);
-            }
+        // Check exit conditions (stop-loss, trailing stop, take-profit or scale-out)
+        if self.position_state != PositionState::Flat && self.check_exit(hbt, mid_price, realized_pnl)? {
+            return Ok(());
         }
 
         // Generate signals based on momentum
         let signal = self.momentum_indicator.generate_signal();
         let momentum_value = self.momentum_indicator.get_momentum();
+        self.observe_signal(signal);
 
         match self.position_state {
             PositionState::Flat => {
-                // Enter new position based on signal
-                match signal {
+                let size = self.position_size * self.momentum_indicator.size_multiplier(self.size_scaling_max);
+                // Enter new position based on signal, once it has held for the confirmation delay
+                let confirmed = self.signal_delay.confirm(self.gate_confidence(self.confirm_timeframes(signal)));
+                match self.gate_transition(confirmed) {
                     SignalType::Long => {
-                        println!("  🟢 LONG signal detected | Momentum: {:.4}", momentum_value);
-                        self.open_long_position(hbt)?;
+                        log_info!("  🟢 LONG signal detected | Momentum: {:.4}", momentum_value);
+                        self.open_long_position(hbt, size)?;
                     }
                     SignalType::Short => {
-                        println!("  🔴 SHORT signal detected | Momentum: {:.4}", momentum_value);
-                        self.open_short_position(hbt)?;
+                        log_info!("  🔴 SHORT signal detected | Momentum: {:.4}", momentum_value);
+                        self.open_short_position(hbt, size)?;
                     }
                     SignalType::Neutral => {}
                 }
             }
             PositionState::Long => {
+                self.signal_delay.reset();
                 // Close long position on opposite signal
-                if signal == SignalType::Short {
-                    println!("  ⚠️  Reverse signal detected, closing LONG position");
-                    self.close_position(hbt, realized_pnl)?;
+                if signal == SignalType::Short && self.min_hold_elapsed() {
+                    log_warn!("  ⚠️  Reverse signal detected, closing LONG position");
+                    self.close_position(hbt, self.position_qty, realized_pnl)?;
                 }
             }
             PositionState::Short => {
+                self.signal_delay.reset();
                 // Close short position on opposite signal
-                if signal == SignalType::Long {
-                    println!("  ⚠️  Reverse signal detected, closing SHORT position");
-                    self.close_position(hbt, realized_pnl)?;
+                if signal == SignalType::Long && self.min_hold_elapsed() {
+                    log_warn!("  ⚠️  Reverse signal detected, closing SHORT position");
+                    self.close_position(hbt, self.position_qty, realized_pnl)?;
                 }
             }
         }
@@ -436,184 +672,492 @@ impl MomentumRunner {
         Ok(())
     }
 
+    /// Place the exit `should_close_position` calls for, if any; returns whether one was placed.
+    /// A partial close fires the next scale-out level and arms the trailing stop
+    fn check_exit<MD>(
+        &mut self,
+        hbt: &mut Backtest<MD>,
+        mid_price: f64,
+        realized_pnl: &mut f64,
+    ) -> Result<bool, BacktestError>
+    where
+        MD: MarketDepth,
+    {
+        self.update_trail_peak(mid_price);
+        let Some(qty) = self.should_close_position(mid_price) else {
+            return Ok(false);
+        };
+
+        if qty < self.position_qty {
+            self.scale_outs_fired += 1;
+            self.trail_peak.get_or_insert(mid_price);
+            log_info!("  Scaling out {:.4} at level {}/{}", qty, self.scale_outs_fired, self.scale_out_levels.len());
+        } else {
+            log_info!("  Closing position due to stop loss or take profit");
+        }
+        self.close_position(hbt, qty, realized_pnl)?;
+        Ok(true)
+    }
+
+    /// Logs data gaps and reports whether trading is suspended; closes the position when the
+    /// book goes stale if configured
+    fn check_stale_book<MD>(
+        &mut self,
+        hbt: &mut Backtest<MD>,
+        realized_pnl: &mut f64,
+    ) -> Result<bool, BacktestError>
+    where
+        MD: MarketDepth,
+    {
+        match self.stale_book.update(last_book_update_ns(hbt), hbt.current_timestamp()) {
+            Some(StaleBookEvent::Stale { gap_ns }) => {
+                log_warn!("  ⚠ No book update for {:.1}s, trading suspended", gap_ns as f64 / 1e9);
+                if self.stale_book.flattens() && self.position_state != PositionState::Flat {
+                    log_info!("  Closing position on stale book");
+                    self.close_position(hbt, self.position_qty, realized_pnl)?;
+                }
+            }
+            Some(StaleBookEvent::Resumed { gap_ns }) => {
+                log_info!("  ✓ Book updates resumed after a {:.1}s gap", gap_ns as f64 / 1e9);
+            }
+            None => {}
+        }
+        Ok(self.stale_book.is_stale())
+    }
+
+    /// Entry signal after the longer-timeframe check: Neutral unless the confirmation indicator
+    /// is ready and signals the same direction
+    fn confirm_timeframes(&self, signal: SignalType) -> SignalType {
+        match &self.confirmation_indicator {
+            Some(confirmation) if !confirmation.is_ready() || confirmation.generate_signal() != signal => {
+                SignalType::Neutral
+            }
+            _ => signal,
+        }
+    }
+
+    /// Arm the signal when it differs from the previous tick's
+    fn observe_signal(&mut self, signal: SignalType) {
+        if signal != self.previous_signal {
+            self.armed_signal = Some(signal);
+        }
+        self.previous_signal = signal;
+    }
+
+    /// Entry signal after the fresh-transition check: Neutral when required and the signal has
+    /// persisted since the last entry
+    fn gate_transition(&self, signal: SignalType) -> SignalType {
+        if self.require_signal_transition && self.armed_signal != Some(signal) {
+            return SignalType::Neutral;
+        }
+        signal
+    }
+
+    /// Entry signal after the confidence check: Neutral when confidence is below the minimum
+    fn gate_confidence(&self, signal: SignalType) -> SignalType {
+        if signal == SignalType::Neutral || self.min_confidence <= 0.0 {
+            return signal;
+        }
+        let confidence = self.momentum_indicator.confidence(self.confidence_scale);
+        if confidence < self.min_confidence {
+            log_warn!("  ⏸ {:?} signal skipped: confidence {:.2} below {:.2}", signal, confidence, self.min_confidence);
+            return SignalType::Neutral;
+        }
+        signal
+    }
+
     fn open_long_position<MD>(
         &mut self,
         hbt: &mut Backtest<MD>,
+        size: f64,
     ) -> Result<(), BacktestError>
     where
         MD: MarketDepth,
     {
+        self.open_position(hbt, PositionState::Long, size)
+    }
+
+    fn open_short_position<MD>(
+        &mut self,
+        hbt: &mut Backtest<MD>,
+        size: f64,
+    ) -> Result<(), BacktestError>
+    where
+        MD: MarketDepth,
+    {
+        self.open_position(hbt, PositionState::Short, size)
+    }
+
+    /// `size` is `position_size`, scaled by momentum strength when that's enabled
+    fn open_position<MD>(
+        &mut self,
+        hbt: &mut Backtest<MD>,
+        side: PositionState,
+        size: f64,
+    ) -> Result<(), BacktestError>
+    where
+        MD: MarketDepth,
+    {
+        let now = hbt.current_timestamp();
+        if !self.entry_throttle.allows_entry(now) {
+            log_warn!("  ⏸ Entry throttled: max {} trades per minute reached",
+                      MOMENTUM_MAX_TRADES_PER_MINUTE.unwrap_or_default());
+            return Ok(());
+        }
+        if !self.loss_streak.allows_entry() {
+            log_warn!("  ⏸ Entry skipped: halted after a losing streak, waiting for resume");
+            return Ok(());
+        }
+        if !self.entry_windows.allows_entry(now) {
+            log_warn!("  ⏸ Entry skipped: outside the allowed time-of-day windows");
+            return Ok(());
+        }
+        let Some(mid_price) = calculate_mid_price(hbt.depth(0)) else {
+            return Ok(());
+        };
+        if self.no_trade_zone.blocks(mid_price, TICK_SIZE) {
+            log_warn!("  ⏸ Entry skipped: {:.5} is inside the no-trade zone around the last exit", mid_price);
+            return Ok(());
+        }
+
         // Clear any pending orders first
         hbt.clear_inactive_orders(Some(0));
         
+        let qty = order_quantity(size, self.sizing_mode, mid_price, self.sizing_equity, LOT_SIZE);
+        if qty <= 0.0 {
+            return Ok(());
+        }
+        self.entry_throttle.record_entry(now);
+        // The next entry needs a new transition
+        self.armed_signal = None;
+        if self.dry_run {
+            self.open_dry_run(side, mid_price, qty);
+            return Ok(());
+        }
+        let impact_price = self.entry_impact_price(hbt.depth(0), side == PositionState::Long, qty);
+        let order_ids = self.submit_entry_orders(hbt, side == PositionState::Long, qty)?;
+
+        self.pending_entry = Some(PendingEntry {
+            order_ids,
+            side,
+            submitted_at: hbt.current_timestamp(),
+            target_qty: qty,
+            filled_qty: 0.0,
+            filled_notional: 0.0,
+            filled_orders: 0,
+            requotes: 0,
+            impact_price,
+        });
+        self.check_pending_entry(hbt)
+    }
+
+    /// Submit `qty` as an entry ladder priced off the current book and wait briefly for the
+    /// response; returns the submitted order IDs
+    fn submit_entry_orders<MD>(
+        &mut self,
+        hbt: &mut Backtest<MD>,
+        is_buy: bool,
+        qty: f64,
+    ) -> Result<Vec<u64>, BacktestError>
+    where
+        MD: MarketDepth,
+    {
         let depth = hbt.depth(0);
         let tick_size = depth.tick_size();
-        let best_ask_tick = depth.best_ask_tick();
-        let best_ask_price = best_ask_tick as f64 * tick_size;
-        
-        let order_id = self.next_order_id;
-        self.next_order_id += 1;
+        let price_tick = entry_price_tick(
+            depth.best_bid_tick(), depth.best_ask_tick(), is_buy, self.entry_aggressiveness,
+        );
+        let ladder = entry_ladder(
+            price_tick, is_buy, qty, self.entry_levels, ENTRY_LEVEL_SPACING_TICKS, LOT_SIZE,
+        );
         
-        hbt.submit_buy_order(
-            0,
-            order_id,
-            best_ask_price,
-            self.position_size,
-            TimeInForce::GTC,
-            OrdType::Limit,
-            false,
-        )?;
-        self.total_orders += 1;
+        let mut order_ids = Vec::with_capacity(ladder.len());
+        for (tick, qty) in ladder {
+            let price = tick as f64 * tick_size;
+            let order_id = self.order_ids.next_id();
+            
+            if is_buy {
+                hbt.submit_buy_order(0, order_id, price, qty, self.tif, OrdType::Limit, false)?;
+            } else {
+                hbt.submit_sell_order(0, order_id, price, qty, self.tif, OrdType::Limit, false)?;
+            }
+            self.total_orders += 1;
+            order_ids.push(order_id);
+        }
 
-        // Short timeout to avoid blocking - 100ms
-        let _ = hbt.wait_order_response(0, order_id, 100_000_000);
+        if let Some(&last_id) = order_ids.last() {
+            let _ = hbt.wait_order_response(0, last_id, ORDER_RESPONSE_WAIT_NS);
+        }
+        Ok(order_ids)
+    }
 
-        let orders = hbt.orders(0);
-        if let Some(order) = orders.get(&order_id) {
-            if order.status == Status::Filled {
-                self.entry_price = order.price_tick as f64 * tick_size;
-                self.position_qty = order.qty;
-                self.position_state = PositionState::Long;                self.total_fills += 1;                
-                println!("    ✓ Opened LONG @ {:.2} qty {:.4}", self.entry_price, self.position_qty);
+    /// Resolve the outstanding entry orders: cancel whatever is still resting after
+    /// `order_timeout_ns`; once none are working, requote the unfilled rest (up to
+    /// `max_requotes` times) or open the position with the aggregated fills (or stay flat if
+    /// nothing filled)
+    fn check_pending_entry<MD>(
+        &mut self,
+        hbt: &mut Backtest<MD>,
+    ) -> Result<(), BacktestError>
+    where
+        MD: MarketDepth,
+    {
+        let pending = match &self.pending_entry {
+            Some(pending) => pending.clone(),
+            None => return Ok(()),
+        };
+        let label = if pending.side == PositionState::Long { "LONG" } else { "SHORT" };
+
+        let fills = summarize_ladder_fills(hbt.orders(0), &pending.order_ids);
+
+        if !fills.working.is_empty() {
+            if hbt.current_timestamp() - pending.submitted_at > self.order_timeout_ns {
+                for order_id in fills.working {
+                    hbt.cancel(0, order_id, false)?;
+                }
+            }
+            return Ok(());
+        }
+
+        let filled_qty = pending.filled_qty + fills.filled_qty;
+        let filled_notional = pending.filled_notional + fills.avg_price * fills.filled_qty;
+        let filled_orders = pending.filled_orders + fills.filled_orders;
+        let remaining = pending.target_qty - filled_qty;
+
+        if remaining >= LOT_SIZE / 2.0 && pending.requotes < self.max_requotes {
+            log_info!("    ↻ Requoting {} entry ({}/{}) qty {:.4}",
+                      label, pending.requotes + 1, self.max_requotes, remaining);
+            let order_ids = self.submit_entry_orders(hbt, pending.side == PositionState::Long, remaining)?;
+            self.pending_entry = Some(PendingEntry {
+                order_ids,
+                submitted_at: hbt.current_timestamp(),
+                filled_qty,
+                filled_notional,
+                filled_orders,
+                requotes: pending.requotes + 1,
+                ..pending
+            });
+            return Ok(());
+        }
+
+        self.pending_entry = None;
+        self.total_fills += filled_orders;
+
+        if filled_qty > 0.0 {
+            self.entry_price = pending.impact_price.unwrap_or(filled_notional / filled_qty);
+            self.position_qty = filled_qty;
+            self.position_state = pending.side;
+            self.on_position_opened();
+            
+            log_info!("    ✓ Opened {} @ {:.2} qty {:.4}", label, self.entry_price, self.position_qty);
+        } else {
+            match pending.order_ids.first().and_then(|order_id| hbt.orders(0).get(order_id)) {
+                Some(order) => log_warn!("    ✗ {} entry not filled ({:?})", label, order.status),
+                None => log_warn!("    ✗ {} entry not filled", label),
             }
         }
 
         Ok(())
     }
 
-    fn open_short_position<MD>(
+    /// Liquidate whatever is left, crossing the spread more aggressively until the position
+    /// is confirmed flat; the flatten price and PnL are reported separately from normal exits
+    fn flatten_remaining<MD>(
         &mut self,
         hbt: &mut Backtest<MD>,
+        realized_pnl: &mut f64,
     ) -> Result<(), BacktestError>
     where
         MD: MarketDepth,
     {
-        // Clear any pending orders first
-        hbt.clear_inactive_orders(Some(0));
-        
-        let depth = hbt.depth(0);
-        let tick_size = depth.tick_size();
-        let best_bid_tick = depth.best_bid_tick();
-        let best_bid_price = best_bid_tick as f64 * tick_size;
-        
-        let order_id = self.next_order_id;
-        self.next_order_id += 1;
-        
-        hbt.submit_sell_order(
-            0,
-            order_id,
-            best_bid_price,
-            self.position_size,
-            TimeInForce::GTC,
-            OrdType::Limit,
-            false,
+        let direction = match self.position_state {
+            PositionState::Long => 1.0,
+            PositionState::Short => -1.0,
+            PositionState::Flat => return Ok(()),
+        };
+        if self.dry_run {
+            return self.close_position(hbt, self.position_qty, realized_pnl);
+        }
+
+        // Resting entry orders would change the position underneath the flatten
+        if let Some(pending) = self.pending_entry.take() {
+            for order_id in pending.order_ids {
+                let _ = hbt.cancel(0, order_id, false);
+            }
+        }
+        if let Some(exit) = self.pending_exit.take() {
+            let _ = hbt.cancel(0, exit.order_id, false);
+        }
+
+        let result = force_flatten(
+            hbt, &mut self.order_ids, direction * self.position_qty, LOT_SIZE,
+            FLATTEN_STEP_TICKS, FLATTEN_MAX_ATTEMPTS, FLATTEN_WAIT_NS,
         )?;
-        self.total_orders += 1;
+        self.total_orders += result.attempts;
 
-        // Short timeout to avoid blocking - 100ms
-        let _ = hbt.wait_order_response(0, order_id, 100_000_000);
+        if result.filled_qty > 0.0 {
+            let pnl = ASSET_KIND.pnl(self.entry_price, result.avg_price, result.filled_qty) * direction;
+            let fee = self.fees.charge(ASSET_KIND.notional(self.entry_price, result.filled_qty))
+                + self.fees.charge(ASSET_KIND.notional(result.avg_price, result.filled_qty));
+            *realized_pnl += pnl - fee;
+            self.total_fills += 1;
+            self.record_close(self.position_state, result.avg_price, pnl - fee);
+            
+            log_info!("    ✓ Final flatten @ {:.2} qty {:.4} after {} attempt(s) | PnL: {:.4} | Fee: {:.4}",
+                      result.avg_price, result.filled_qty, result.attempts, pnl, fee);
+        }
 
-        let orders = hbt.orders(0);
-        if let Some(order) = orders.get(&order_id) {
-            if order.status == Status::Filled {
-                self.entry_price = order.price_tick as f64 * tick_size;
-                self.position_qty = order.qty;
-                self.position_state = PositionState::Short;
-                self.total_fills += 1;
-                
-                println!("    ✓ Opened SHORT @ {:.2} qty {:.4}", self.entry_price, self.position_qty);
-            }
+        if result.is_flat() {
+            self.position_state = PositionState::Flat;
+            self.entry_price = 0.0;
+            self.position_qty = 0.0;
+        } else {
+            self.position_qty = result.remaining.abs();
+            log_warn!("    ✗ Final flatten incomplete: {:.4} still open after {} attempt(s)",
+                      self.position_qty, result.attempts);
         }
 
         Ok(())
     }
 
+    /// Exit `qty` of the position (all of it, or one scale-out level)
     fn close_position<MD>(
         &mut self,
         hbt: &mut Backtest<MD>,
+        qty: f64,
         realized_pnl: &mut f64,
     ) -> Result<(), BacktestError>
     where
         MD: MarketDepth,
     {
+        if self.dry_run {
+            // One-sided book: keep the position and retry on a later tick
+            if let Some(mid_price) = calculate_mid_price(hbt.depth(0)) {
+                self.close_dry_run(mid_price, qty, realized_pnl);
+            }
+            return Ok(());
+        }
+
         // Clear any pending orders first
         hbt.clear_inactive_orders(Some(0));
         
         let depth = hbt.depth(0);
         let tick_size = depth.tick_size();
+        let side = self.position_state;
+        let order_id = self.order_ids.next_id();
+        let impact_price = self.price_impact_ticks
+            .and_then(|max_ticks| impact_fill_price(depth, side == PositionState::Short, qty, max_ticks));
 
-        match self.position_state {
+        match side {
             PositionState::Long => {
-                let best_bid_tick = depth.best_bid_tick();
-                let best_bid_price = best_bid_tick as f64 * tick_size;
-                
-                let order_id = self.next_order_id;
-                self.next_order_id += 1;
-                
+                let best_bid_price = depth.best_bid_tick() as f64 * tick_size;
                 hbt.submit_sell_order(
                     0,
                     order_id,
                     best_bid_price,
-                    self.position_qty,
-                    TimeInForce::GTC,
+                    qty,
+                    self.tif,
                     OrdType::Limit,
                     false,
                 )?;
-                self.total_orders += 1;
-
-                // Short timeout to avoid blocking - 100ms
-                let _ = hbt.wait_order_response(0, order_id, 100_000_000);
-
-                let orders = hbt.orders(0);
-                if let Some(order) = orders.get(&order_id) {
-                    if order.status == Status::Filled {
-                        let exit_price = order.price_tick as f64 * tick_size;
-                        let pnl = (exit_price - self.entry_price) * self.position_qty;
-                        let fee = (exit_price * self.position_qty + self.entry_price * self.position_qty) * 0.0001;
-                        *realized_pnl += pnl - fee;
-                        self.total_fills += 1;
-                        
-                        println!("    ✓ Closed LONG @ {:.2} | PnL: {:.2} | Fee: {:.2}", 
-                                 exit_price, pnl, fee);
-                    }
-                }
             }
             PositionState::Short => {
-                let best_ask_tick = depth.best_ask_tick();
-                let best_ask_price = best_ask_tick as f64 * tick_size;
-                
-                let order_id = self.next_order_id;
-                self.next_order_id += 1;
-                
+                let best_ask_price = depth.best_ask_tick() as f64 * tick_size;
                 hbt.submit_buy_order(
                     0,
                     order_id,
                     best_ask_price,
-                    self.position_qty,
-                    TimeInForce::GTC,
+                    qty,
+                    self.tif,
                     OrdType::Limit,
                     false,
                 )?;
-                self.total_orders += 1;
-
-                // Short timeout to avoid blocking - 100ms
-                let _ = hbt.wait_order_response(0, order_id, 100_000_000);
-
-                let orders = hbt.orders(0);
-                if let Some(order) = orders.get(&order_id) {
-                    if order.status == Status::Filled {
-                        let exit_price = order.price_tick as f64 * tick_size;
-                        let pnl = (self.entry_price - exit_price) * self.position_qty;
-                        let fee = (exit_price * self.position_qty + self.entry_price * self.position_qty) * 0.0001;
-                        *realized_pnl += pnl - fee;
-                        self.total_fills += 1;
-                        
-                        println!("    ✓ Closed SHORT @ {:.2} | PnL: {:.2} | Fee: {:.2}", 
-                                 exit_price, pnl, fee);
-                    }
+            }
+            PositionState::Flat => return Ok(()),
+        }
+        self.total_orders += 1;
+        let _ = hbt.wait_order_response(0, order_id, ORDER_RESPONSE_WAIT_NS);
+
+        let exit = PendingExit {
+            order_id,
+            side,
+            submitted_at: hbt.current_timestamp(),
+            filled_qty: 0.0,
+            net_pnl: 0.0,
+            impact_price,
+        };
+        self.resolve_exit(hbt, exit, realized_pnl)
+    }
+
+    /// Book what the exit order executed since it was last checked. A complete fill closes
+    /// the position (or, for a scale-out, the part it was sized for); an exit that is still
+    /// working, filled or not, stays in `pending_exit` (canceled after `order_timeout_ns`); an
+    /// exit that ended short of the full quantity leaves the rest of the position open
+    fn resolve_exit<MD>(
+        &mut self,
+        hbt: &mut Backtest<MD>,
+        mut exit: PendingExit,
+        realized_pnl: &mut f64,
+    ) -> Result<(), BacktestError>
+    where
+        MD: MarketDepth,
+    {
+        let (direction, label) = if exit.side == PositionState::Long { (1.0, "LONG") } else { (-1.0, "SHORT") };
+        let (fill, status) = match hbt.orders(0).get(&exit.order_id) {
+            Some(order) => (self.fill_tracker.poll(order), order.status),
+            None => {
+                // Nothing more can be booked for it, so the position stays open
+                log_warn!("    ✗ {} exit order {} no longer tracked, {:.4} still open",
+                          label, exit.order_id, self.position_qty);
+                return Ok(());
+            }
+        };
+
+        if fill.qty > 0.0 {
+            let qty = fill.qty.min(self.position_qty);
+            let fill_price = exit.impact_price.unwrap_or(fill.price);
+            let pnl = ASSET_KIND.pnl(self.entry_price, fill_price, qty) * direction;
+            let fee = self.fees.charge(ASSET_KIND.notional(self.entry_price, qty))
+                + self.fees.charge(ASSET_KIND.notional(fill_price, qty));
+            *realized_pnl += pnl - fee;
+            self.total_fills += 1;
+            self.position_qty -= qty;
+            exit.filled_qty += qty;
+            exit.net_pnl += pnl - fee;
+
+            let action = if status == Status::Filled { "Closed" } else { "Partially closed" };
+            log_info!("    ✓ {} {} @ {:.2} qty {:.4} | PnL: {:.2} | Fee: {:.2}",
+                      action, label, fill_price, qty, pnl, fee);
+        }
+
+        match status {
+            Status::Filled => {
+                self.record_close(exit.side, fill.price, exit.net_pnl);
+                if self.position_qty >= LOT_SIZE / 2.0 {
+                    log_info!("    ↘ Scaled out of {}, {:.4} still open", label, self.position_qty);
+                    return Ok(());
                 }
             }
-            PositionState::Flat => {}
+            _ if fill.done => {
+                if exit.filled_qty > 0.0 {
+                    // The executed part is a closed trade of its own; the rest stays open
+                    self.record_close(exit.side, fill.price, exit.net_pnl);
+                    log_warn!("    ✗ {} exit ended part-filled ({:?}), {:.4} still open",
+                              label, status, self.position_qty);
+                } else {
+                    // Unfilled IOC exits are dropped by the exchange, so the position is still open
+                    log_warn!("    ✗ {} exit not filled ({:?})", label, status);
+                }
+                return Ok(());
+            }
+            // Still working, part-filled or not: the position is only closed once it fills
+            _ => {
+                if hbt.current_timestamp() - exit.submitted_at > self.order_timeout_ns {
+                    hbt.cancel(0, exit.order_id, false)?;
+                }
+                self.pending_exit = Some(exit);
+                return Ok(());
+            }
         }
 
         self.position_state = PositionState::Flat;
@@ -623,58 +1167,291 @@ impl MomentumRunner {
         Ok(())
     }
 
+    /// Book-walk VWAP for an entry that crosses the spread with more than the touch size
+    /// (None = no impact model, a passive entry, or it fits in the touch)
+    fn entry_impact_price<MD: MarketDepth>(&self, depth: &MD, is_buy: bool, qty: f64) -> Option<f64> {
+        let max_ticks = self.price_impact_ticks?;
+        let price_tick = entry_price_tick(
+            depth.best_bid_tick(), depth.best_ask_tick(), is_buy, self.entry_aggressiveness,
+        );
+        crosses_touch(is_buy, price_tick, depth.best_bid_tick(), depth.best_ask_tick())
+            .then(|| impact_fill_price(depth, is_buy, qty, max_ticks))
+            .flatten()
+    }
+
+    /// Dry run: take the position at the mid price without submitting anything
+    fn open_dry_run(&mut self, side: PositionState, mid_price: f64, qty: f64) {
+        let label = if side == PositionState::Long { "LONG" } else { "SHORT" };
+        self.total_orders += 1;
+        self.total_fills += 1;
+        self.entry_price = mid_price;
+        self.position_qty = qty;
+        self.position_state = side;
+        self.on_position_opened();
+
+        log_info!("    [Dry Run] Would open {} @ {:.2} qty {:.4}", label, mid_price, qty);
+    }
+
+    /// Dry run: close `qty` of the hypothetical position at the mid price, fees included
+    fn close_dry_run(&mut self, mid_price: f64, qty: f64, realized_pnl: &mut f64) {
+        let (direction, label) = match self.position_state {
+            PositionState::Long => (1.0, "LONG"),
+            PositionState::Short => (-1.0, "SHORT"),
+            PositionState::Flat => return,
+        };
+        let qty = qty.min(self.position_qty);
+        let pnl = ASSET_KIND.pnl(self.entry_price, mid_price, qty) * direction;
+        let fee = self.fees.charge(ASSET_KIND.notional(self.entry_price, qty))
+            + self.fees.charge(ASSET_KIND.notional(mid_price, qty));
+        *realized_pnl += pnl - fee;
+        self.total_orders += 1;
+        self.total_fills += 1;
+        self.record_close(self.position_state, mid_price, pnl - fee);
+        self.position_qty -= qty;
+
+        if self.position_qty >= LOT_SIZE / 2.0 {
+            log_info!("    [Dry Run] Would scale out of {} @ {:.2} qty {:.4} | PnL: {:.2} | Fee: {:.2}",
+                      label, mid_price, qty, pnl, fee);
+            return;
+        }
+        log_info!("    [Dry Run] Would close {} @ {:.2} | PnL: {:.2} | Fee: {:.2}",
+                  label, mid_price, pnl, fee);
+
+        self.position_state = PositionState::Flat;
+        self.entry_price = 0.0;
+        self.position_qty = 0.0;
+    }
+
+    /// Add a closed trade's net PnL (after fees) to its direction's bucket and anchor the
+    /// no-trade zone at its exit price
+    fn record_close(&mut self, side: PositionState, exit_price: f64, net_pnl: f64) {
+        self.no_trade_zone.record_exit(exit_price);
+        self.loss_streak.record_trade(net_pnl);
+        match side {
+            PositionState::Long => {
+                self.attribution.long_trades += 1;
+                self.attribution.long_pnl += net_pnl;
+            }
+            PositionState::Short => {
+                self.attribution.short_trades += 1;
+                self.attribution.short_pnl += net_pnl;
+            }
+            PositionState::Flat => {}
+        }
+    }
+
+    /// `--reconcile`: compare the tracked position/PnL with the engine's state at file end
+    fn reconcile_with_engine<MD>(&self, hbt: &Backtest<MD>, realized_pnl: f64)
+    where
+        MD: MarketDepth,
+    {
+        // The engine never sees dry-run trades, so there is nothing to compare against
+        if self.dry_run {
+            return;
+        }
+        let Some(mid_price) = calculate_mid_price(hbt.depth(0)) else {
+            log_warn!("⚠️  [Momentum] Reconcile skipped: one-sided book at file end");
+            return;
+        };
+        let (_, unrealized_pnl) = self.calculate_position_metrics(mid_price);
+        let signed_position = match self.position_state {
+            PositionState::Long => self.position_qty,
+            PositionState::Short => -self.position_qty,
+            PositionState::Flat => 0.0,
+        };
+        reconcile(hbt, "Momentum", signed_position, realized_pnl + unrealized_pnl);
+    }
+
     /// Calculate position metrics (position_value, unrealized_pnl)
     fn calculate_position_metrics(&self, mid_price: f64) -> (f64, f64) {
         match self.position_state {
             PositionState::Long => {
                 let position_value = self.position_qty * mid_price;
-                let unrealized_pnl = (mid_price - self.entry_price) * self.position_qty;
+                let unrealized_pnl = ASSET_KIND.pnl(self.entry_price, mid_price, self.position_qty);
                 (position_value, unrealized_pnl)
             }
             PositionState::Short => {
                 let position_value = -self.position_qty * mid_price;
-                let unrealized_pnl = (self.entry_price - mid_price) * self.position_qty;
+                let unrealized_pnl = -ASSET_KIND.pnl(self.entry_price, mid_price, self.position_qty);
                 (position_value, unrealized_pnl)
             }
             PositionState::Flat => (0.0, 0.0),
         }
     }
 
-    fn should_close_position(&self, current_price: f64) -> bool {
+    /// Feed the tick's gross equity to the performance fee's high-water mark
+    fn accrue_performance_fee<MD: MarketDepth>(&mut self, depth: &MD, cash: f64, realized_pnl: f64) {
+        let Some(mid_price) = calculate_mid_price(depth) else {
+            return;
+        };
+        let (position_value, _) = self.calculate_position_metrics(self.mark_pricer.mark(depth).unwrap_or(mid_price));
+        self.performance_fee.update(cash + realized_pnl + position_value - self.borrow_cost.accrued());
+    }
+
+    /// Whole-run profit target / loss limit hit at the current mark
+    fn run_target_reached<MD: MarketDepth>(&self, depth: &MD, realized_pnl: f64) -> Option<RunStopReason> {
+        let mark_price = calculate_mid_price(depth).and(self.mark_pricer.mark(depth))?;
+        let (_, unrealized_pnl) = self.calculate_position_metrics(mark_price);
+        let equity = self.initial_capital + realized_pnl + unrealized_pnl - self.borrow_cost.accrued();
+        self.run_target.check(self.initial_capital, equity)
+    }
+
+    /// Position in base units, negative when short
+    fn signed_position(&self) -> f64 {
+        match self.position_state {
+            PositionState::Long => self.position_qty,
+            PositionState::Short => -self.position_qty,
+            PositionState::Flat => 0.0,
+        }
+    }
+
+    /// Stop-loss and take-profit prices for the open position
+    fn exit_levels(&self) -> (Option<f64>, Option<f64>) {
         if self.entry_price == 0.0 {
-            return false;
+            return (None, None);
         }
 
+        let stop_pct = self.stop_pct();
+        // With scale-outs the next unfired level is the take; none is left once all have fired
+        let take_pct = if self.scale_out_levels.is_empty() {
+            Some(self.entry_take_profit_pct)
+        } else {
+            self.scale_out_levels.get(self.scale_outs_fired).map(|&(profit_pct, _)| profit_pct)
+        };
+        let trailing = self.trailing_stop_price();
         match self.position_state {
-            PositionState::Long => {
-                let pnl_pct = (current_price - self.entry_price) / self.entry_price;
-                pnl_pct <= -self.stop_loss_pct || pnl_pct >= self.take_profit_pct
-            }
-            PositionState::Short => {
-                let pnl_pct = (self.entry_price - current_price) / self.entry_price;
-                pnl_pct <= -self.stop_loss_pct || pnl_pct >= self.take_profit_pct
+            PositionState::Long => (
+                Some(trailing.unwrap_or(f64::MIN).max(self.entry_price * (1.0 - stop_pct))),
+                take_pct.map(|pct| self.entry_price * (1.0 + pct)),
+            ),
+            PositionState::Short => (
+                Some(trailing.unwrap_or(f64::MAX).min(self.entry_price * (1.0 + stop_pct))),
+                take_pct.map(|pct| self.entry_price * (1.0 - pct)),
+            ),
+            PositionState::Flat => (None, None),
+        }
+    }
+
+    /// Fresh per-position exit state: scale-out levels, trailing stop and the minimum hold
+    fn on_position_opened(&mut self) {
+        self.scale_outs_fired = 0;
+        self.scale_out_base_qty = self.position_qty;
+        self.trail_peak = None;
+        self.entry_tick = self.strategy_ticks;
+        self.entry_take_profit_pct = self.dynamic_take_profit_pct();
+    }
+
+    /// `take_profit_pct * (1 + k * ATR / entry price)`, or the base target without a scale or
+    /// a warmed-up ATR
+    fn dynamic_take_profit_pct(&self) -> f64 {
+        match (self.take_profit_vol_k, self.atr.value()) {
+            (Some(k), Some(atr)) if self.entry_price > 0.0 => {
+                self.take_profit_pct * (1.0 + k * atr / self.entry_price)
             }
-            PositionState::Flat => false,
+            _ => self.take_profit_pct,
         }
     }
 
-    fn create_backtest(&self, data_file: &str) -> Result<Backtest<HashMapMarketDepth>> {
-        let latency_model = ConstantLatency::new(0, 0);
-        let queue_model = ProbQueueModel::new(PowerProbQueueFunc3::new(3.0));
-        let asset_type = LinearAsset::new(1.0);
-        let fee_model = TradingValueFeeModel::new(CommonFees::new(-0.00005, 0.0007));
+    /// Whether the open position has been held for `min_hold_ticks`
+    fn min_hold_elapsed(&self) -> bool {
+        self.strategy_ticks - self.entry_tick >= self.min_hold_ticks
+    }
+
+    /// Follow the best price once the trailing stop is armed
+    fn update_trail_peak(&mut self, current_price: f64) {
+        let Some(peak) = self.trail_peak else {
+            return;
+        };
+        self.trail_peak = match self.position_state {
+            PositionState::Long => Some(peak.max(current_price)),
+            PositionState::Short => Some(peak.min(current_price)),
+            PositionState::Flat => None,
+        };
+    }
+
+    /// Trailing stop for what's left after the first scale-out (None until then)
+    fn trailing_stop_price(&self) -> Option<f64> {
+        let peak = self.trail_peak?;
+        match self.position_state {
+            PositionState::Long => Some(peak * (1.0 - self.scale_out_trail_pct)),
+            PositionState::Short => Some(peak * (1.0 + self.scale_out_trail_pct)),
+            PositionState::Flat => None,
+        }
+    }
+
+    /// Stop distance from entry as a fraction of the entry price, per `stop_kind`
+    fn stop_pct(&self) -> f64 {
+        self.stop_kind.stop_pct(self.entry_price, self.stop_loss_pct, TICK_SIZE, self.atr.value())
+    }
+
+    /// Quantity to exit at `current_price`: the whole position on a stop, trailing stop or
+    /// take-profit, the next level's fraction of the opened size on a scale-out (None = hold).
+    /// Inside the minimum hold only the stop-loss counts
+    fn should_close_position(&self, current_price: f64) -> Option<f64> {
+        if self.entry_price == 0.0 {
+            return None;
+        }
+
+        let stop_pct = self.stop_pct();
+        let pnl_pct = match self.position_state {
+            PositionState::Long => (current_price - self.entry_price) / self.entry_price,
+            PositionState::Short => (self.entry_price - current_price) / self.entry_price,
+            PositionState::Flat => return None,
+        };
+        let trailing_stop_hit = match (self.trailing_stop_price(), self.position_state) {
+            (Some(stop), PositionState::Long) => current_price <= stop,
+            (Some(stop), PositionState::Short) => current_price >= stop,
+            _ => false,
+        };
+        if pnl_pct <= -stop_pct {
+            return Some(self.position_qty);
+        }
+        // Only the hard stop may close inside the minimum hold
+        if !self.min_hold_elapsed() {
+            return None;
+        }
+        if trailing_stop_hit {
+            return Some(self.position_qty);
+        }
+
+        // A take-profit that fees would turn into a loss is held instead
+        let clears_fees = self.fees.clears_round_trip(pnl_pct, self.min_profit_over_fees);
+        if self.scale_out_levels.is_empty() {
+            return (pnl_pct >= self.entry_take_profit_pct && clears_fees).then_some(self.position_qty);
+        }
+        let &(profit_pct, fraction) = self.scale_out_levels.get(self.scale_outs_fired)?;
+        if pnl_pct < profit_pct || !clears_fees {
+            return None;
+        }
+        let qty = ((self.scale_out_base_qty * fraction / LOT_SIZE).round() * LOT_SIZE)
+            .max(LOT_SIZE)
+            .min(self.position_qty);
+        // A remainder too small to trade goes out with this level
+        if self.position_qty - qty < LOT_SIZE / 2.0 {
+            Some(self.position_qty)
+        } else {
+            Some(qty)
+        }
+    }
+
+    fn create_backtest(&self, data_file: &str, preloaded: Option<Vec<Event>>) -> Result<Backtest<HashMapMarketDepth>> {
+        let latency_model = ConstantLatency::new(TAKER_ORDER_LATENCY_NS, TAKER_ORDER_LATENCY_NS);
+        let queue_model = ProbQueueModel::new(PowerProbQueueFunc3::new(TAKER_QUEUE_POWER));
+        // The same flat rate the runner's own PnL charges, so `--reconcile` compares like with like
+        let fee_model = TradingValueFeeModel::new(CommonFees::new(ESTIMATED_FEE_RATE, ESTIMATED_FEE_RATE));
 
         let hbt = Backtest::builder()
             .add_asset(
                 L2AssetBuilder::new()
                     .data(vec![
-                        DataSource::File(data_file.to_string())
+                        data_source(data_file, preloaded)
                     ])
                     .latency_model(latency_model)
                     .queue_model(queue_model)
-                    .asset_type(asset_type)
+                    .asset_type(ASSET_KIND)
                     .fee_model(fee_model)
-                    .exchange(ExchangeKind::NoPartialFillExchange)
+                    .exchange(EXCHANGE_KIND)
+                    .last_trades_capacity(LAST_TRADES_CAPACITY)
                     .depth(|| HashMapMarketDepth::new(TICK_SIZE, LOT_SIZE))
                     .build()?,
             )
@@ -687,10 +1464,8 @@ impl MomentumRunner {
     where
         MD: MarketDepth,
     {
-        let tick_size = depth.tick_size();
-        let best_bid = depth.best_bid_tick() as f64 * tick_size;
-        let best_ask = depth.best_ask_tick() as f64 * tick_size;
-        let mid_price = (best_bid + best_ask) / 2.0;
+        // One-sided book: carry whatever is still open at its entry price
+        let mid_price = calculate_mid_price(depth).unwrap_or(self.entry_price);
 
         let position_value = match self.position_state {
             PositionState::Long => self.position_qty * mid_price,
@@ -698,16 +1473,669 @@ impl MomentumRunner {
             PositionState::Flat => 0.0,
         };
 
-        let total_equity = cash + realized_pnl + position_value;
-
-        println!("\n{}", "=".repeat(60));
-        println!("Final Statistics:");
-        println!("{}", "=".repeat(60));
-        println!("Initial Capital: ${:.2}", cash);
-        println!("Realized PnL: ${:.2}", realized_pnl);
-        println!("Final Position Value: ${:.2}", position_value);
-        println!("Total Equity: ${:.2}", total_equity);
-        println!("Total Return: {:.2}%", (total_equity - cash) / cash * 100.0);
-        println!("{}", "=".repeat(60));
+        let total_equity = cash + realized_pnl + position_value - self.borrow_cost.accrued();
+
+        log_info!("\n{}", "=".repeat(60));
+        log_info!("Final Statistics:");
+        log_info!("{}", "=".repeat(60));
+        log_info!("Initial Capital: ${:.2}", cash);
+        log_info!("Realized PnL: ${:.2}", realized_pnl);
+        log_info!("Final Position Value: ${:.2}", position_value);
+        log_info!("Borrow Costs: ${:.2}", self.borrow_cost.accrued());
+        log_info!("Fees Paid: {}", FeeDrag::from_net(total_equity - cash, self.fees.total_fees(), cash).summary());
+        log_info!("Total Equity: ${:.2}", total_equity);
+        log_info!("Total Return: {:.2}%", (total_equity - cash) / cash * 100.0);
+        log_info!("Long:  {} trade(s), PnL ${:.2}", self.attribution.long_trades, self.attribution.long_pnl);
+        log_info!("Short: {} trade(s), PnL ${:.2}", self.attribution.short_trades, self.attribution.short_pnl);
+        log_info!("{}", "=".repeat(60));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hftbacktest::types::{
+        DEPTH_EVENT, BUY_EVENT, SELL_EVENT, EXCH_EVENT, LOCAL_EVENT,
+        EXCH_ASK_DEPTH_EVENT, LOCAL_ASK_DEPTH_EVENT, EXCH_BID_DEPTH_EVENT, LOCAL_BID_DEPTH_EVENT,
+    };
+    use crate::common::test_utils::{backtest_from_events, event, two_sided_book};
+    use crate::common::FeeModelKind;
+
+    /// Runner with the defaults every test starts from: lookback 10, threshold 0.001, size 0.05,
+    /// 1% stop and 2% take-profit
+    fn test_runner() -> MomentumRunner {
+        runner_with_momentum(10, 0.001)
+    }
+
+    /// `test_runner` with another momentum window and threshold
+    fn runner_with_momentum(lookback_period: usize, momentum_threshold: f64) -> MomentumRunner {
+        MomentumRunner::create_runner(
+            vec![PathBuf::from("unused.npz")], lookback_period, momentum_threshold, 0.05, 0.01, 0.02, 10_000.0,
+        ).unwrap()
+    }
+
+    #[test]
+    fn test_unfilled_ioc_entry_leaves_position_flat() {
+        // The local book still shows an ask at 100.0 that's already gone at the exchange,
+        // so an IOC buy at the local best ask can't fill.
+        let events = vec![
+            event(DEPTH_EVENT | BUY_EVENT | EXCH_EVENT | LOCAL_EVENT, 1_000, 99.0, 1.0),
+            event(EXCH_ASK_DEPTH_EVENT, 1_000, 101.0, 1.0),
+            event(LOCAL_ASK_DEPTH_EVENT, 1_000, 100.0, 1.0),
+            event(DEPTH_EVENT | BUY_EVENT | EXCH_EVENT | LOCAL_EVENT, 10_000_000_000, 99.0, 1.0),
+        ];
+        let mut hbt = backtest_from_events(&events);
+        hbt.elapse(1_000_000).unwrap();
+
+        let mut runner = test_runner();
+        runner.tif = TimeInForce::IOC;
+
+        runner.open_long_position(&mut hbt, runner.position_size).unwrap();
+
+        let order = hbt.orders(0).values().next().unwrap();
+        assert_eq!(order.status, Status::Expired);
+        assert_eq!(runner.position_state, PositionState::Flat);
+        assert_eq!(runner.position_qty, 0.0);
+        assert_eq!(runner.total_fills, 0);
+        assert_eq!(hbt.position(0), 0.0);
+        assert!(runner.pending_entry.is_none());
+    }
+
+    #[test]
+    fn test_resting_exit_keeps_position_until_it_fills() {
+        // The local book still shows a bid at 100.0 that's gone at the exchange, so the exit
+        // sell rests until a bid at 100.0 comes back at 2s
+        let events = vec![
+            event(EXCH_BID_DEPTH_EVENT, 1_000, 99.0, 1.0),
+            event(LOCAL_BID_DEPTH_EVENT, 1_000, 100.0, 1.0),
+            event(DEPTH_EVENT | SELL_EVENT | EXCH_EVENT | LOCAL_EVENT, 1_000, 101.0, 1.0),
+            event(DEPTH_EVENT | BUY_EVENT | EXCH_EVENT | LOCAL_EVENT, 2_000_000_000, 100.0, 1.0),
+            event(DEPTH_EVENT | BUY_EVENT | EXCH_EVENT | LOCAL_EVENT, 10_000_000_000, 99.0, 1.0),
+        ];
+        let mut hbt = backtest_from_events(&events);
+        hbt.elapse(1_000_000).unwrap();
+
+        let mut runner = test_runner();
+        runner.position_state = PositionState::Long;
+        runner.entry_price = 99.0;
+        runner.position_qty = 0.05;
+        let mut realized_pnl = 0.0;
+
+        runner.close_position(&mut hbt, 0.05, &mut realized_pnl).unwrap();
+        assert_eq!(runner.position_state, PositionState::Long);
+        assert_eq!(runner.position_qty, 0.05);
+        assert!(runner.pending_exit.is_some());
+        assert_eq!(realized_pnl, 0.0);
+
+        hbt.elapse(2_000_000_000).unwrap();
+        let exit = runner.pending_exit.take().unwrap();
+        runner.resolve_exit(&mut hbt, exit, &mut realized_pnl).unwrap();
+        assert_eq!(runner.position_state, PositionState::Flat);
+        assert!((hbt.position(0) + 0.05).abs() < 1e-9);
+        assert_eq!(runner.attribution.long_trades, 1);
+        assert!(realized_pnl > 0.0);
+    }
+
+    #[test]
+    fn test_entry_larger_than_touch_books_book_walk_vwap() {
+        let events = vec![
+            event(DEPTH_EVENT | BUY_EVENT | EXCH_EVENT | LOCAL_EVENT, 1_000, 99.99, 1.0),
+            event(DEPTH_EVENT | SELL_EVENT | EXCH_EVENT | LOCAL_EVENT, 1_000, 100.0, 0.02),
+            event(DEPTH_EVENT | SELL_EVENT | EXCH_EVENT | LOCAL_EVENT, 1_000, 100.01, 0.02),
+            event(DEPTH_EVENT | SELL_EVENT | EXCH_EVENT | LOCAL_EVENT, 1_000, 100.03, 1.0),
+            event(DEPTH_EVENT | BUY_EVENT | EXCH_EVENT | LOCAL_EVENT, 10_000_000_000, 99.99, 1.0),
+        ];
+        let mut hbt = backtest_from_events(&events);
+        hbt.elapse(1_000_000).unwrap();
+
+        let mut runner = test_runner();
+        runner.price_impact_ticks = Some(10_000);
+        runner.open_long_position(&mut hbt, 0.05).unwrap();
+
+        // The engine fills all 0.05 at the 100.00 ask; booked at 0.02 @ 100.00 + 0.02 @ 100.01
+        // + 0.01 @ 100.03
+        assert_eq!(runner.position_state, PositionState::Long);
+        let vwap = (0.02 * 100.0 + 0.02 * 100.01 + 0.01 * 100.03) / 0.05;
+        assert!((runner.entry_price - vwap).abs() < 1e-9);
+        assert!((runner.entry_price - 100.01).abs() < 1e-9);
+        assert!(runner.entry_price > 100.0);
+
+        // Within the touch the engine's price stands
+        assert_eq!(runner.entry_impact_price(hbt.depth(0), true, 0.01), None);
+    }
+
+    #[test]
+    fn test_zero_offset_entry_rests_at_best_bid() {
+        let mut hbt = backtest_from_events(&two_sided_book(99.0, 101.0, 1.0, [10_000_000_000]));
+        hbt.elapse(1_000_000).unwrap();
+
+        let mut runner = test_runner();
+        runner.entry_aggressiveness = Some(0);
+
+        runner.open_long_position(&mut hbt, runner.position_size).unwrap();
+
+        let order = hbt.orders(0).values().next().unwrap();
+        assert_eq!(order.status, Status::New);
+        assert_eq!(order.price_tick, (99.0 / TICK_SIZE).round() as i64);
+        assert_eq!(runner.position_state, PositionState::Flat);
+        assert!(runner.pending_entry.is_some());
+        assert_eq!(hbt.position(0), 0.0);
+    }
+
+    #[test]
+    fn test_exit_levels_follow_position_side() {
+        let mut runner = test_runner();
+        assert_eq!(runner.exit_levels(), (None, None));
+
+        runner.entry_price = 100.0;
+        runner.position_state = PositionState::Long;
+        let (stop, take) = runner.exit_levels();
+        assert!((stop.unwrap() - 99.0).abs() < 1e-9);
+        assert!((take.unwrap() - 102.0).abs() < 1e-9);
+
+        runner.position_state = PositionState::Short;
+        let (stop, take) = runner.exit_levels();
+        assert!((stop.unwrap() - 101.0).abs() < 1e-9);
+        assert!((take.unwrap() - 98.0).abs() < 1e-9);
+
+        // Tick stop sits a fixed number of ticks above a short entry
+        runner.stop_kind = StopKind::Ticks(50.0);
+        let (stop, _) = runner.exit_levels();
+        assert!((stop.unwrap() - (100.0 + 50.0 * TICK_SIZE)).abs() < 1e-9);
+        assert!(runner.should_close_position(100.0 + 51.0 * TICK_SIZE).is_some());
+        assert!(runner.should_close_position(100.0 + 49.0 * TICK_SIZE).is_none());
+    }
+
+    #[test]
+    fn test_multi_level_entry_splits_target_size() {
+        let mut hbt = backtest_from_events(&two_sided_book(99.0, 101.0, 1.0, [10_000_000_000]));
+        hbt.elapse(1_000_000).unwrap();
+
+        let mut runner = test_runner();
+        runner.entry_aggressiveness = Some(0);
+        runner.entry_levels = 3;
+
+        runner.open_long_position(&mut hbt, runner.position_size).unwrap();
+
+        let orders = hbt.orders(0);
+        assert_eq!(orders.len(), 3);
+        let total_qty: f64 = orders.values().map(|order| order.qty).sum();
+        assert!((total_qty - runner.position_size).abs() < 1e-9);
+        assert_eq!(runner.total_orders, 3);
+        assert_eq!(runner.pending_entry.as_ref().unwrap().order_ids.len(), 3);
+        assert_eq!(runner.position_state, PositionState::Flat);
+    }
+
+    #[test]
+    fn test_unfilled_entry_is_canceled_and_requoted_up_to_limit() {
+        let mut hbt = backtest_from_events(&two_sided_book(99.0, 101.0, 1.0, [10_000_000_000]));
+        hbt.elapse(1_000_000).unwrap();
+
+        let mut runner = test_runner();
+        // Join the bid; nothing trades, so every attempt times out
+        runner.entry_aggressiveness = Some(0);
+        runner.order_timeout_ns = 10_000_000;
+        runner.max_requotes = 2;
+
+        runner.open_long_position(&mut hbt, runner.position_size).unwrap();
+        assert_eq!(runner.total_orders, 1);
+
+        for _ in 0..10 {
+            hbt.elapse(20_000_000).unwrap();
+            runner.check_pending_entry(&mut hbt).unwrap();
+        }
+
+        assert_eq!(runner.total_orders, 3);
+        assert!(runner.pending_entry.is_none());
+        assert_eq!(runner.position_state, PositionState::Flat);
+        assert!(hbt.orders(0).values().all(|order| order.status == Status::Canceled));
+        assert_eq!(hbt.position(0), 0.0);
+    }
+
+    #[test]
+    fn test_dry_run_counts_orders_without_submitting() {
+        let mut hbt = backtest_from_events(&two_sided_book(99.0, 101.0, 1.0, [10_000_000_000]));
+        hbt.elapse(1_000_000).unwrap();
+
+        let mut runner = test_runner();
+        runner.dry_run = true;
+
+        runner.open_long_position(&mut hbt, runner.position_size).unwrap();
+        assert_eq!(runner.total_orders, 1);
+        assert_eq!(runner.position_state, PositionState::Long);
+        assert!((runner.entry_price - 100.0).abs() < 1e-9);
+
+        let mut realized_pnl = 0.0;
+        runner.close_position(&mut hbt, runner.position_qty, &mut realized_pnl).unwrap();
+        assert_eq!(runner.total_orders, 2);
+        assert_eq!(runner.position_state, PositionState::Flat);
+        // Flat at mid, so only the modeled fees are lost
+        assert!(realized_pnl <= 0.0);
+
+        assert!(hbt.orders(0).is_empty());
+        assert_eq!(hbt.position(0), 0.0);
+    }
+
+    #[test]
+    fn test_take_profit_below_round_trip_fee_does_not_close() {
+        let mut runner = test_runner();
+        runner.take_profit_pct = 0.0005;
+        runner.entry_take_profit_pct = 0.0005;
+        runner.fees = FeeCalculator::new(FeeModelKind::BpsOnly, 0.0007);
+        runner.position_state = PositionState::Long;
+        runner.entry_price = 100.0;
+
+        // +6bps clears the 5bps target
+        assert!(runner.should_close_position(100.06).is_some());
+        runner.min_profit_over_fees = Some(0.0);
+        assert!(runner.should_close_position(100.06).is_none());
+        // Stops are unaffected
+        assert!(runner.should_close_position(98.9).is_some());
+    }
+
+    #[test]
+    fn test_trading_suspended_during_data_gap_and_resumes_after() {
+        let events = vec![
+            event(DEPTH_EVENT | BUY_EVENT | EXCH_EVENT | LOCAL_EVENT, 1_000, 99.0, 1.0),
+            event(DEPTH_EVENT | SELL_EVENT | EXCH_EVENT | LOCAL_EVENT, 1_000, 101.0, 1.0),
+            // 5s gap in the data
+            event(DEPTH_EVENT | BUY_EVENT | EXCH_EVENT | LOCAL_EVENT, 5_000_000_000, 99.0, 1.0),
+            event(DEPTH_EVENT | BUY_EVENT | EXCH_EVENT | LOCAL_EVENT, 10_000_000_000, 99.0, 1.0),
+        ];
+        let mut hbt = backtest_from_events(&events);
+        hbt.elapse(1_000_000).unwrap();
+
+        let mut runner = runner_with_momentum(5, 0.001);
+        runner.dry_run = true;
+        runner.stale_book = StaleBookGuard::new(Some(1_000_000_000), false);
+        for price in [100.0, 100.5, 101.0, 101.5, 102.0, 102.5] {
+            runner.momentum_indicator.update(price);
+        }
+        assert_eq!(runner.momentum_indicator.generate_signal(), SignalType::Long);
+
+        let mut realized_pnl = 0.0;
+        hbt.elapse(2_000_000_000).unwrap();
+        runner.execute_strategy(&mut hbt, &mut realized_pnl).unwrap();
+        assert!(runner.stale_book.is_stale());
+        assert_eq!(runner.position_state, PositionState::Flat);
+
+        // Data arrives again at 5s
+        hbt.elapse(3_000_000_000).unwrap();
+        runner.execute_strategy(&mut hbt, &mut realized_pnl).unwrap();
+        assert!(!runner.stale_book.is_stale());
+        assert_eq!(runner.position_state, PositionState::Long);
+    }
+
+    #[test]
+    fn test_entry_suppressed_when_timeframes_disagree() {
+        let mut hbt = backtest_from_events(&two_sided_book(99.0, 101.0, 1.0, [10_000_000_000]));
+        hbt.elapse(1_000_000).unwrap();
+
+        let mut runner = runner_with_momentum(5, 0.001);
+        runner.dry_run = true;
+        runner.confirmation_indicator = Some(MomentumIndicator::new(20, None, 0.001));
+
+        // Long sell-off, then a short bounce: short lookback is long, long lookback is short
+        let prices = (0..16).map(|i| 110.0 - i as f64).chain([95.5, 96.0, 96.5, 97.0, 97.5]);
+        for price in prices {
+            runner.momentum_indicator.update(price);
+            runner.confirmation_indicator.as_mut().unwrap().update(price);
+        }
+        assert_eq!(runner.momentum_indicator.generate_signal(), SignalType::Long);
+
+        let mut realized_pnl = 0.0;
+        runner.execute_strategy(&mut hbt, &mut realized_pnl).unwrap();
+        assert_eq!(runner.position_state, PositionState::Flat);
+        assert_eq!(runner.total_orders, 0);
+
+        // Same signal without confirmation enters
+        runner.confirmation_indicator = None;
+        runner.execute_strategy(&mut hbt, &mut realized_pnl).unwrap();
+        assert_eq!(runner.position_state, PositionState::Long);
+    }
+
+    #[test]
+    fn test_low_confidence_signal_gated_out() {
+        let mut hbt = backtest_from_events(&two_sided_book(99.0, 101.0, 1.0, [10_000_000_000]));
+        hbt.elapse(1_000_000).unwrap();
+
+        let mut runner = runner_with_momentum(5, 0.01);
+        runner.dry_run = true;
+        runner.confidence_scale = 3.0;
+        runner.min_confidence = 0.8;
+        // Momentum 0.011 barely clears the 0.01 threshold: confidence ~0.37
+        for price in [100.0, 100.2, 100.4, 100.6, 100.8, 101.1] {
+            runner.momentum_indicator.update(price);
+        }
+        assert_eq!(runner.momentum_indicator.generate_signal(), SignalType::Long);
+        assert!(runner.momentum_indicator.confidence(3.0) < 0.4);
+
+        let mut realized_pnl = 0.0;
+        runner.execute_strategy(&mut hbt, &mut realized_pnl).unwrap();
+        assert_eq!(runner.position_state, PositionState::Flat);
+
+        runner.min_confidence = 0.3;
+        runner.execute_strategy(&mut hbt, &mut realized_pnl).unwrap();
+        assert_eq!(runner.position_state, PositionState::Long);
+    }
+
+    #[test]
+    fn test_no_trade_zone_blocks_reentry_near_exit_only() {
+        let events = vec![
+            event(DEPTH_EVENT | BUY_EVENT | EXCH_EVENT | LOCAL_EVENT, 1_000, 99.0, 1.0),
+            event(DEPTH_EVENT | SELL_EVENT | EXCH_EVENT | LOCAL_EVENT, 1_000, 101.0, 1.0),
+            // Book moves up: mid 100 -> 105
+            event(DEPTH_EVENT | BUY_EVENT | EXCH_EVENT | LOCAL_EVENT, 5_000_000, 99.0, 0.0),
+            event(DEPTH_EVENT | SELL_EVENT | EXCH_EVENT | LOCAL_EVENT, 5_000_000, 101.0, 0.0),
+            event(DEPTH_EVENT | BUY_EVENT | EXCH_EVENT | LOCAL_EVENT, 5_000_000, 104.0, 1.0),
+            event(DEPTH_EVENT | SELL_EVENT | EXCH_EVENT | LOCAL_EVENT, 5_000_000, 106.0, 1.0),
+            event(DEPTH_EVENT | BUY_EVENT | EXCH_EVENT | LOCAL_EVENT, 10_000_000_000, 104.0, 1.0),
+        ];
+        let mut hbt = backtest_from_events(&events);
+        hbt.elapse(1_000_000).unwrap();
+
+        let mut runner = test_runner();
+        runner.dry_run = true;
+        runner.no_trade_zone = NoTradeZone::new(Some(10.0), 100);
+
+        let mut realized_pnl = 0.0;
+        runner.open_long_position(&mut hbt, runner.position_size).unwrap();
+        runner.close_position(&mut hbt, runner.position_qty, &mut realized_pnl).unwrap();
+
+        // Same price as the exit: suppressed
+        runner.open_long_position(&mut hbt, runner.position_size).unwrap();
+        assert_eq!(runner.position_state, PositionState::Flat);
+        assert_eq!(runner.total_orders, 2);
+
+        // Well outside the zone: proceeds
+        hbt.elapse(10_000_000).unwrap();
+        runner.open_long_position(&mut hbt, runner.position_size).unwrap();
+        assert_eq!(runner.position_state, PositionState::Long);
+        assert!((runner.entry_price - 105.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_entries_over_rate_limit_blocked_but_exits_proceed() {
+        let mut hbt = backtest_from_events(&two_sided_book(99.0, 101.0, 1.0, [100_000_000_000]));
+        hbt.elapse(1_000_000).unwrap();
+
+        let mut runner = test_runner();
+        runner.dry_run = true;
+        runner.entry_throttle = TradeThrottle::new(Some(2));
+        let mut realized_pnl = 0.0;
+
+        for _ in 0..2 {
+            runner.open_long_position(&mut hbt, runner.position_size).unwrap();
+            assert_eq!(runner.position_state, PositionState::Long);
+            runner.close_position(&mut hbt, runner.position_qty, &mut realized_pnl).unwrap();
+            assert_eq!(runner.position_state, PositionState::Flat);
+        }
+
+        // Third entry inside the same minute is skipped
+        runner.open_short_position(&mut hbt, runner.position_size).unwrap();
+        assert_eq!(runner.position_state, PositionState::Flat);
+        assert_eq!(runner.total_orders, 4);
+
+        // A minute later entries resume, and the exit is never throttled
+        hbt.elapse(60_000_000_000).unwrap();
+        runner.open_short_position(&mut hbt, runner.position_size).unwrap();
+        assert_eq!(runner.position_state, PositionState::Short);
+        runner.entry_throttle = TradeThrottle::new(Some(0));
+        runner.close_position(&mut hbt, runner.position_qty, &mut realized_pnl).unwrap();
+        assert_eq!(runner.position_state, PositionState::Flat);
+    }
+
+    #[test]
+    fn test_entry_outside_time_window_is_skipped() {
+        let mut hbt = backtest_from_events(&two_sided_book(99.0, 101.0, 1.0, [100_000_000_000]));
+        hbt.elapse(1_000_000).unwrap();
+
+        let mut runner = test_runner();
+        runner.dry_run = true;
+        // Entries only during the first minute after midnight
+        runner.entry_windows = EntryWindows::new(&[(0, 1)]);
+        let mut realized_pnl = 0.0;
+
+        runner.open_long_position(&mut hbt, runner.position_size).unwrap();
+        assert_eq!(runner.position_state, PositionState::Long);
+
+        // The exit goes through outside the window, the next entry doesn't
+        hbt.elapse(60_000_000_000).unwrap();
+        runner.close_position(&mut hbt, runner.position_qty, &mut realized_pnl).unwrap();
+        assert_eq!(runner.position_state, PositionState::Flat);
+        runner.open_short_position(&mut hbt, runner.position_size).unwrap();
+        assert_eq!(runner.position_state, PositionState::Flat);
+        assert_eq!(runner.total_orders, 2);
+    }
+
+    #[test]
+    fn test_losing_streak_halts_entries_until_resumed() {
+        let mut hbt = backtest_from_events(&two_sided_book(99.0, 101.0, 1.0, [100_000_000_000]));
+        hbt.elapse(1_000_000).unwrap();
+
+        let mut runner = test_runner();
+        runner.dry_run = true;
+        runner.loss_streak = LossStreakHalt::new(Some(2));
+        let mut realized_pnl = 0.0;
+
+        // Round trips at an unchanged mid lose the fees
+        for _ in 0..2 {
+            runner.open_long_position(&mut hbt, runner.position_size).unwrap();
+            runner.close_position(&mut hbt, runner.position_qty, &mut realized_pnl).unwrap();
+        }
+        assert!(runner.loss_streak.take_halt_notice().is_some());
+        runner.open_long_position(&mut hbt, runner.position_size).unwrap();
+        assert_eq!(runner.position_state, PositionState::Flat);
+
+        runner.loss_streak.resume();
+        runner.open_long_position(&mut hbt, runner.position_size).unwrap();
+        assert_eq!(runner.position_state, PositionState::Long);
+    }
+
+    #[test]
+    fn test_closed_trades_attributed_by_direction() {
+        let mut hbt = backtest_from_events(&two_sided_book(99.0, 101.0, 1.0, [10_000_000_000]));
+        hbt.elapse(1_000_000).unwrap();
+
+        let mut runner = test_runner();
+        runner.dry_run = true;
+        let mut realized_pnl = 0.0;
+
+        // Long from 95 closed at mid 100: winner
+        runner.position_state = PositionState::Long;
+        runner.entry_price = 95.0;
+        runner.position_qty = 0.05;
+        runner.close_position(&mut hbt, runner.position_qty, &mut realized_pnl).unwrap();
+
+        // Short from 95 closed at mid 100: loser
+        runner.position_state = PositionState::Short;
+        runner.entry_price = 95.0;
+        runner.position_qty = 0.05;
+        runner.close_position(&mut hbt, runner.position_qty, &mut realized_pnl).unwrap();
+
+        let attribution = runner.attribution;
+        assert_eq!(attribution.long_trades, 1);
+        assert_eq!(attribution.short_trades, 1);
+        assert!(attribution.long_pnl > 0.0);
+        assert!(attribution.short_pnl < 0.0);
+        assert!((attribution.long_pnl + attribution.short_pnl - realized_pnl).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_higher_volatility_at_entry_widens_take_profit() {
+        let mut runner = runner_with_momentum(5, 0.001);
+        runner.take_profit_vol_k = Some(100.0);
+        runner.position_state = PositionState::Long;
+        runner.entry_price = 100.0;
+        runner.position_qty = 0.1;
+
+        // ATR not warmed up yet: the base target
+        runner.on_position_opened();
+        assert_eq!(runner.entry_take_profit_pct, 0.02);
+
+        let take_after = |runner: &mut MomentumRunner, swing: f64| {
+            runner.atr = AtrTracker::new(2, 3);
+            for i in 0..6 {
+                runner.atr.update(if i % 2 == 0 { 100.0 } else { 100.0 + swing });
+            }
+            runner.on_position_opened();
+            runner.entry_take_profit_pct
+        };
+        let quiet = take_after(&mut runner, 0.1);
+        let volatile = take_after(&mut runner, 1.0);
+        assert!(quiet > 0.02);
+        assert!(volatile > quiet);
+        // ATR 1.0 on a 100 entry: 0.02 * (1 + 100 * 0.01)
+        assert!((volatile - 0.04).abs() < 1e-9);
+
+        // The target is held for the trade even as the ATR moves
+        runner.atr.reset();
+        assert!(runner.should_close_position(103.0).is_none());
+        assert_eq!(runner.should_close_position(104.0), Some(0.1));
+    }
+
+    #[test]
+    fn test_first_scale_out_level_closes_fraction_and_trails_the_rest() {
+        let mut hbt = backtest_from_events(&two_sided_book(99.0, 101.0, 1.0, [10_000_000_000]));
+        hbt.elapse(1_000_000).unwrap();
+
+        let mut runner = test_runner();
+        runner.position_size = 0.1;
+        runner.stop_loss_pct = 0.05;
+        runner.scale_out_levels = vec![(0.01, 0.4), (0.03, 0.3)];
+        runner.scale_out_trail_pct = 0.005;
+        let mut realized_pnl = 0.0;
+
+        // Long 0.1 from 98: mid 100 is +2%, past the first level only
+        runner.position_state = PositionState::Long;
+        runner.entry_price = 98.0;
+        runner.position_qty = 0.1;
+        runner.on_position_opened();
+        assert!(runner.check_exit(&mut hbt, 100.0, &mut realized_pnl).unwrap());
+
+        // The sell of 0.04 crosses the bid; the rest stays open
+        assert_eq!(runner.position_state, PositionState::Long);
+        assert!((runner.position_qty - 0.06).abs() < 1e-9);
+        assert!((hbt.position(0) + 0.04).abs() < 1e-9);
+        assert_eq!(runner.scale_outs_fired, 1);
+        assert_eq!(runner.attribution.long_trades, 1);
+        assert!(realized_pnl > 0.0);
+
+        // The first level doesn't fire again, the second isn't reached and the trail holds
+        assert!(!runner.check_exit(&mut hbt, 100.2, &mut realized_pnl).unwrap());
+        assert_eq!(runner.trail_peak, Some(100.2));
+        let (stop, take) = runner.exit_levels();
+        assert!((stop.unwrap() - 100.2 * 0.995).abs() < 1e-9);
+        assert!((take.unwrap() - 98.0 * 1.03).abs() < 1e-9);
+
+        // Falling back through the trailing stop closes the remainder
+        assert_eq!(runner.should_close_position(99.6), Some(runner.position_qty));
+        runner.dry_run = true;
+        assert!(runner.check_exit(&mut hbt, 99.6, &mut realized_pnl).unwrap());
+        assert_eq!(runner.position_state, PositionState::Flat);
+
+        assert!(validate_scale_out_levels(&[(0.02, 0.5), (0.01, 0.5)], 0.005).is_err());
+        assert!(validate_scale_out_levels(&[(0.01, 0.7), (0.02, 0.5)], 0.005).is_err());
+    }
+
+    #[test]
+    fn test_reversal_inside_min_hold_is_ignored_until_hold_elapses() {
+        let mut hbt = backtest_from_events(&two_sided_book(99.0, 101.0, 1.0, [10_000_000_000]));
+        hbt.elapse(1_000_000).unwrap();
+
+        let mut runner = runner_with_momentum(5, 0.001);
+        runner.dry_run = true;
+        runner.min_hold_ticks = 3;
+        for price in [100.0, 100.5, 101.0, 101.5, 102.0, 102.5] {
+            runner.momentum_indicator.update(price);
+        }
+        let mut realized_pnl = 0.0;
+        runner.execute_strategy(&mut hbt, &mut realized_pnl).unwrap();
+        assert_eq!(runner.position_state, PositionState::Long);
+
+        for price in [102.0, 101.5, 101.0, 100.5, 100.0, 99.5] {
+            runner.momentum_indicator.update(price);
+        }
+        assert_eq!(runner.momentum_indicator.generate_signal(), SignalType::Short);
+
+        // One tick after entry the reversal is ignored, and so is a take-profit
+        runner.execute_strategy(&mut hbt, &mut realized_pnl).unwrap();
+        assert_eq!(runner.position_state, PositionState::Long);
+        assert!(runner.should_close_position(103.0).is_none());
+        // The hard stop still protects the position
+        assert!(runner.should_close_position(98.0).is_some());
+
+        runner.execute_strategy(&mut hbt, &mut realized_pnl).unwrap();
+        assert_eq!(runner.position_state, PositionState::Long);
+        // Held for three ticks: the same signal closes it
+        runner.execute_strategy(&mut hbt, &mut realized_pnl).unwrap();
+        assert_eq!(runner.position_state, PositionState::Flat);
+    }
+
+    #[test]
+    fn test_persistent_signal_does_not_reenter_after_stop_out() {
+        let mut hbt = backtest_from_events(&two_sided_book(99.0, 101.0, 1.0, [10_000_000_000]));
+        hbt.elapse(1_000_000).unwrap();
+
+        let mut runner = runner_with_momentum(5, 0.001);
+        runner.dry_run = true;
+        runner.require_signal_transition = true;
+        for price in [100.0, 100.5, 101.0, 101.5, 102.0, 102.5] {
+            runner.momentum_indicator.update(price);
+        }
+        let mut realized_pnl = 0.0;
+        runner.execute_strategy(&mut hbt, &mut realized_pnl).unwrap();
+        assert_eq!(runner.position_state, PositionState::Long);
+
+        // Stopped out 2% below entry while the signal is still Long
+        runner.entry_price = 102.0;
+        runner.execute_strategy(&mut hbt, &mut realized_pnl).unwrap();
+        assert_eq!(runner.position_state, PositionState::Flat);
+        for _ in 0..3 {
+            runner.execute_strategy(&mut hbt, &mut realized_pnl).unwrap();
+            assert_eq!(runner.position_state, PositionState::Flat);
+        }
+
+        // The signal goes Neutral, then Long again: a fresh transition enters
+        for _ in 0..6 {
+            runner.momentum_indicator.update(102.5);
+        }
+        assert_eq!(runner.momentum_indicator.generate_signal(), SignalType::Neutral);
+        runner.execute_strategy(&mut hbt, &mut realized_pnl).unwrap();
+        assert_eq!(runner.position_state, PositionState::Flat);
+        for price in [103.0, 103.5, 104.0, 104.5, 105.0] {
+            runner.momentum_indicator.update(price);
+        }
+        assert_eq!(runner.momentum_indicator.generate_signal(), SignalType::Long);
+        runner.execute_strategy(&mut hbt, &mut realized_pnl).unwrap();
+        assert_eq!(runner.position_state, PositionState::Long);
+    }
+
+    #[test]
+    fn test_log_file_records_open_and_close() {
+        let mut hbt = backtest_from_events(&two_sided_book(99.0, 101.0, 1.0, [10_000_000_000]));
+        hbt.elapse(1_000_000).unwrap();
+
+        let mut runner = test_runner();
+        runner.dry_run = true;
+        let path = std::env::temp_dir().join(format!("momentum_log_test_{}.log", std::process::id()));
+        crate::common::set_log_file(&path).unwrap();
+
+        let mut realized_pnl = 0.0;
+        runner.open_long_position(&mut hbt, runner.position_size).unwrap();
+        runner.close_position(&mut hbt, runner.position_qty, &mut realized_pnl).unwrap();
+        crate::common::close_log_file();
+
+        // Other tests log concurrently, so only look for this run's lines
+        let log = std::fs::read_to_string(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+        let open = log.lines().find(|line| line.contains("[Dry Run] Would open LONG")).expect("open line");
+        let close = log.lines().find(|line| line.contains("[Dry Run] Would close LONG")).expect("close line");
+        for line in [open, close] {
+            // <timestamp> INFO  <message>
+            let (timestamp, rest) = line.split_once(' ').unwrap();
+            assert!(timestamp.ends_with('Z') && timestamp.contains('T'), "{}", line);
+            assert!(rest.starts_with("INFO  [Dry Run]"), "{}", line);
+        }
     }
 }
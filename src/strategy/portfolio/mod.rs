@@ -0,0 +1,3 @@
+pub mod portfolio_runner;
+
+pub use portfolio_runner::PortfolioRunner;
@@ -0,0 +1,439 @@
+use anyhow::{bail, Result};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use hftbacktest::{
+    backtest::Backtest,
+    prelude::{HashMapMarketDepth, Bot},
+    types::{ElapseResult, Event},
+};
+use crate::common::{calculate_mid_price, is_valid_depth, validate_time_in_force, reconcile, FilePreloader, SignalLog,
+    OrderIdGenerator, FLATTEN_ORDER_ID_NAMESPACE};
+use crate::{log_error, log_info};
+use crate::config::{EXCHANGE_KIND, COMMAND_POLL_TIMEOUT_MICROS, RUN_TO_END_BATCH, GUI_UPDATE_ON_FILL, GUI_REPLAY_RATE_WINDOW_MS,
+    SIGNAL_LOG_HORIZON_TICKS};
+use crate::controller::{ControlState, StrategyController};
+use crate::strategy::base::{
+    send_gui_update,
+    Strategy, StrategyState, TickContext, build_performance_data, extract_orderbook,
+    create_backtest, flatten_state, calculate_speed_params, keep_alive_until_close,
+};
+use crate::ui::{DataSender, SleeveBreakdown, UpdateCadence, ReplayRateMeter};
+
+/// One strategy of the portfolio with its share of the capital and its own books
+struct Sleeve {
+    strategy: Box<dyn Strategy>,
+    capital: f64,
+    state: StrategyState,
+}
+
+impl Sleeve {
+    fn equity(&self) -> f64 {
+        self.state.equity(self.capital)
+    }
+}
+
+/// Runs several `Strategy` implementations on the same backtest ticks with a shared capital
+/// pool. Each sleeve gets a fraction of the capital, its own order-ID namespace and its own
+/// `StrategyState`, so positions and PnL are tracked per strategy while the engine sees the
+/// combined book; the GUI gets the combined totals plus the per-sleeve split.
+///
+/// The portfolio is exempt from the performance fee (`PERFORMANCE_FEE_PCT`), so the combined
+/// equity stays exactly the sum of the sleeves' equity.
+pub struct PortfolioRunner {
+    sleeves: Vec<Sleeve>,
+    total_capital: f64,
+    data_files: Vec<PathBuf>,
+}
+
+impl PortfolioRunner {
+    pub fn new(files: Vec<String>, total_capital: f64) -> Result<Self> {
+        let data_files: Vec<PathBuf> = files.into_iter().map(PathBuf::from).collect();
+        if data_files.is_empty() {
+            bail!("No data files provided");
+        }
+        if total_capital <= 0.0 {
+            bail!("Portfolio capital must be positive, got {}", total_capital);
+        }
+
+        Ok(Self {
+            sleeves: Vec::new(),
+            total_capital,
+            data_files,
+        })
+    }
+
+    /// Add a sleeve with `fraction` of the total capital; `build` gets the sleeve's capital
+    pub fn with_sleeve<F>(mut self, fraction: f64, build: F) -> Result<Self>
+    where
+        F: FnOnce(f64) -> Box<dyn Strategy>,
+    {
+        if fraction <= 0.0 || fraction > 1.0 {
+            bail!("Sleeve fraction must be in (0, 1], got {}", fraction);
+        }
+        let allocated: f64 = self.sleeves.iter().map(|sleeve| sleeve.capital).sum::<f64>() / self.total_capital;
+        if allocated + fraction > 1.0 + 1e-9 {
+            bail!("Portfolio allocations exceed 100% ({:.1}% + {:.1}%)", allocated * 100.0, fraction * 100.0);
+        }
+
+        let capital = self.total_capital * fraction;
+        let mut strategy = build(capital);
+        validate_time_in_force(&EXCHANGE_KIND, strategy.time_in_force())?;
+        // Namespace 0 is the standalone range; sleeves start at 1
+        strategy.set_order_id_namespace(self.sleeves.len() as u64 + 1);
+        self.sleeves.push(Sleeve {
+            strategy,
+            capital,
+            state: StrategyState::new(),
+        });
+        Ok(self)
+    }
+
+    /// Every dollar must be assigned so the combined equity is exactly the sum of the sleeves
+    fn validate_allocations(&self) -> Result<()> {
+        if self.sleeves.is_empty() {
+            bail!("Portfolio has no sleeves");
+        }
+        let allocated: f64 = self.sleeves.iter().map(|sleeve| sleeve.capital).sum();
+        if (allocated - self.total_capital).abs() > self.total_capital * 1e-9 {
+            bail!("Portfolio allocations must sum to 100%, got {:.1}%", allocated / self.total_capital * 100.0);
+        }
+        Ok(())
+    }
+
+    pub fn combined_equity(&self) -> f64 {
+        self.sleeves.iter().map(Sleeve::equity).sum()
+    }
+
+    /// Sum of all sleeve books (stop/take levels are per sleeve, so none are shown)
+    fn combined_state(&self) -> StrategyState {
+        let mut combined = StrategyState::new();
+        for sleeve in &self.sleeves {
+            let state = &sleeve.state;
+            combined.realized_pnl += state.realized_pnl;
+            combined.unrealized_pnl += state.unrealized_pnl;
+            combined.position += state.position;
+            combined.num_trades += state.num_trades;
+            combined.winning_trades += state.winning_trades;
+            combined.total_orders += state.total_orders;
+            combined.total_fills += state.total_fills;
+            combined.fees_paid += state.fees_paid;
+            combined.update_count = combined.update_count.max(state.update_count);
+            combined.mid_price = state.mid_price;
+        }
+        combined
+    }
+
+    pub fn sleeve_breakdown(&self) -> Vec<SleeveBreakdown> {
+        self.sleeves.iter()
+            .map(|sleeve| SleeveBreakdown {
+                strategy_name: sleeve.strategy.name().to_string(),
+                capital: sleeve.capital,
+                equity: sleeve.equity(),
+                position: sleeve.state.position,
+                num_trades: sleeve.state.num_trades,
+            })
+            .collect()
+    }
+
+    pub fn run_with_controller(
+        mut self,
+        sender: DataSender,
+        controller: Arc<StrategyController>,
+    ) -> Result<()> {
+        self.validate_allocations()?;
+
+        log_info!("Portfolio: ${:.2} across {} strategies", self.total_capital, self.sleeves.len());
+        for sleeve in &self.sleeves {
+            log_info!("  {} - ${:.2} ({:.1}%)", sleeve.strategy.name(), sleeve.capital,
+                      sleeve.capital / self.total_capital * 100.0);
+        }
+
+        let file_count = self.data_files.len();
+        let mut preload: Option<FilePreloader> = None;
+
+        for file_idx in 0..file_count {
+            while !controller.is_running() && !controller.should_stop() {
+                controller.process_commands(Duration::from_millis(100));
+            }
+
+            if controller.should_stop() {
+                log_info!("\n⏹ Strategy stopped by user");
+                break;
+            }
+
+            let data_file = self.data_files[file_idx].clone();
+            let preloaded = preload.take().and_then(FilePreloader::wait);
+            preload = self.data_files.get(file_idx + 1)
+                .map(|next| FilePreloader::spawn(next.to_string_lossy().into_owned()));
+
+            if file_idx > 0 {
+                controller.notify_new_file();
+            }
+
+            log_info!("\n{}", "=".repeat(60));
+            log_info!("Running Portfolio on file [{}/{}]: {}", file_idx + 1, file_count, data_file.display());
+            log_info!("{}\n", "=".repeat(60));
+
+            self.run_single_file(data_file.to_str().unwrap(), preloaded, &sender, &controller)?;
+        }
+
+        drop(preload);
+
+        if !controller.should_stop() {
+            controller.mark_completed();
+            for sleeve in &mut self.sleeves {
+                sleeve.strategy.on_completed(&sleeve.state);
+            }
+            log_info!("\n=== Portfolio Results ===");
+            log_info!("Combined Equity: ${:.2} ({:+.2}%)", self.combined_equity(),
+                      (self.combined_equity() / self.total_capital - 1.0) * 100.0);
+            log_info!("\n✅ All files processed successfully!");
+        }
+
+        keep_alive_until_close(&controller);
+
+        Ok(())
+    }
+
+    fn run_single_file(
+        &mut self,
+        data_file: &str,
+        preloaded: Option<Vec<Event>>,
+        sender: &DataSender,
+        controller: &StrategyController,
+    ) -> Result<()> {
+        log_info!("Loading data from: {}", data_file);
+
+        let mut hbt = create_backtest(data_file, preloaded)?;
+        self.run_backtest(&mut hbt, data_file, sender, controller)
+    }
+
+    fn run_backtest(
+        &mut self,
+        hbt: &mut Backtest<HashMapMarketDepth>,
+        data_file: &str,
+        sender: &DataSender,
+        controller: &StrategyController,
+    ) -> Result<()> {
+        for sleeve in &mut self.sleeves {
+            sleeve.strategy.on_file_start(data_file);
+            sleeve.state = StrategyState::new();
+        }
+        let mut signal_logs: Vec<SignalLog> = self.sleeves.iter()
+            .map(|sleeve| SignalLog::new(sleeve.strategy.name(), SIGNAL_LOG_HORIZON_TICKS))
+            .collect();
+
+        // The finest step any sleeve asks for; coarser sleeves still see every step
+        let elapse_ns = self.sleeves.iter().map(|sleeve| sleeve.strategy.elapse_duration_ns()).min().unwrap_or(1);
+        let orderbook_depth = self.sleeves.iter().map(|sleeve| sleeve.strategy.orderbook_depth()).max().unwrap_or(10);
+        let mut update_count: u64 = 0;
+        let mut sim_time_ns: i64 = 0;
+
+        let mut gui_cadence = UpdateCadence::new(Duration::from_millis(33), GUI_UPDATE_ON_FILL);
+        let mut replay_rate = ReplayRateMeter::new(Duration::from_millis(GUI_REPLAY_RATE_WINDOW_MS));
+        let mut last_command_check = Instant::now();
+        let command_check_interval = Duration::from_millis(16);
+        let mut data_ended = false;
+
+        log_info!("Portfolio started...\n");
+
+        loop {
+            if data_ended {
+                log_info!("\nEnd of data reached!");
+                self.flatten_sleeves(hbt)?;
+                let combined = self.combined_state();
+                reconcile(hbt, "Portfolio", combined.position, combined.realized_pnl + combined.unrealized_pnl);
+                for sleeve in &mut self.sleeves {
+                    sleeve.strategy.on_file_end(&sleeve.state);
+                }
+                break;
+            }
+
+            while controller.state() == ControlState::Paused {
+                controller.process_commands(Duration::from_millis(50));
+                if controller.should_stop() {
+                    return Ok(());
+                }
+            }
+
+            if last_command_check.elapsed() >= command_check_interval {
+                controller.process_commands(Duration::from_micros(COMMAND_POLL_TIMEOUT_MICROS));
+                last_command_check = Instant::now();
+
+                if controller.should_stop() {
+                    log_info!("\n⏹ Strategy stopped by user");
+                    break;
+                }
+            }
+
+            let run_to_end = controller.is_run_to_end();
+            let (iterations_per_loop, loop_delay_ms) = if run_to_end {
+                (RUN_TO_END_BATCH, 0)
+            } else {
+                calculate_speed_params(controller.speed_multiplier())
+            };
+
+            for _ in 0..iterations_per_loop {
+                // A seek stops fast-forwarding here and pauses the run
+                if controller.reached_seek_target(sim_time_ns as f64 / 1e9) {
+                    break;
+                }
+                replay_rate.observe(hbt.current_timestamp());
+                match hbt.elapse(elapse_ns) {
+                    Ok(ElapseResult::EndOfData) | Err(_) => {
+                        data_ended = true;
+                        break;
+                    }
+                    Ok(_) => {
+                        sim_time_ns += elapse_ns;
+                        if !is_valid_depth(hbt.depth(0)) {
+                            continue;
+                        }
+                        update_count += 1;
+                        let mid_price = calculate_mid_price(hbt.depth(0));
+
+                        for (sleeve, signal_log) in self.sleeves.iter_mut().zip(&mut signal_logs) {
+                            if let Some(mid_price) = mid_price {
+                                signal_log.observe_mid(mid_price);
+                            }
+                            sleeve.state.update_count = update_count;
+                            if !update_count.is_multiple_of(sleeve.strategy.update_interval()) {
+                                continue;
+                            }
+                            let mut ctx = TickContext::with_time_in_force(hbt, sleeve.strategy.time_in_force());
+                            sleeve.state.mid_price = ctx.mid_price();
+                            if let Err(e) = sleeve.strategy.on_tick(&mut ctx, &mut sleeve.state) {
+                                log_error!("{} error: {:?}", sleeve.strategy.name(), e);
+                            }
+                            if let (Some((signal, value)), Some(mid_price)) = (sleeve.strategy.take_signal(), mid_price) {
+                                signal_log.record(hbt.current_timestamp(), signal, value, mid_price);
+                            }
+                        }
+                        // Every sleeve saw this step's trades; drop them only after the last one
+                        hbt.clear_last_trades(Some(0));
+
+                        gui_cadence.observe_fills(self.sleeves.iter().map(|sleeve| sleeve.state.total_fills).sum());
+                        if gui_cadence.fill_pending() && !run_to_end {
+                            break;
+                        }
+                    }
+                }
+            }
+
+            replay_rate.observe(hbt.current_timestamp());
+
+            if gui_cadence.due(run_to_end, data_ended) {
+                let depth = hbt.depth(0);
+                if is_valid_depth(depth) {
+                    let (bids, asks) = extract_orderbook(depth, orderbook_depth);
+                    let mut perf_data = build_performance_data(
+                        &self.combined_state(),
+                        self.total_capital,
+                        "Portfolio",
+                        bids,
+                        asks,
+                        sim_time_ns as f64 / 1e9,
+                    );
+                    perf_data.fill_update = gui_cadence.fill_pending();
+                    perf_data.sleeves = self.sleeve_breakdown();
+                    perf_data.replay_rate = replay_rate.rate();
+                    send_gui_update(sender, controller, perf_data);
+                }
+                gui_cadence.mark_sent();
+            }
+
+            if loop_delay_ms > 0 {
+                std::thread::sleep(Duration::from_millis(loop_delay_ms));
+            } else if !run_to_end {
+                std::thread::yield_now();
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Close each sleeve's position on its own so its PnL lands in its own books
+    fn flatten_sleeves(&mut self, hbt: &mut Backtest<HashMapMarketDepth>) -> Result<()> {
+        // One range for every sleeve's flatten, clear of the sleeves' own namespaces
+        let mut flatten_ids = OrderIdGenerator::in_namespace(FLATTEN_ORDER_ID_NAMESPACE);
+        for sleeve in &mut self.sleeves {
+            flatten_state(hbt, &mut flatten_ids, &mut sleeve.state)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossbeam_channel::unbounded;
+    use hftbacktest::backtest::BacktestError;
+    use crate::common::test_utils::{backtest_from_events, two_sided_book};
+    use crate::controller::StrategyCommand;
+    use crate::ui::data_channel;
+
+    /// Books `pnl_per_tick` every tick without trading
+    struct FixedPnl {
+        name: &'static str,
+        capital: f64,
+        pnl_per_tick: f64,
+    }
+
+    impl Strategy for FixedPnl {
+        fn name(&self) -> &str { self.name }
+        fn initial_capital(&self) -> f64 { self.capital }
+        fn on_tick(&mut self, _ctx: &mut TickContext<'_>, state: &mut StrategyState) -> Result<(), BacktestError> {
+            state.realized_pnl += self.pnl_per_tick;
+            state.unrealized_pnl = -self.pnl_per_tick / 2.0;
+            Ok(())
+        }
+    }
+
+    fn fixed(name: &'static str, pnl_per_tick: f64) -> impl FnOnce(f64) -> Box<dyn Strategy> {
+        move |capital| Box::new(FixedPnl { name, capital, pnl_per_tick })
+    }
+
+    #[test]
+    fn test_combined_equity_is_sum_of_sleeves() {
+        let mut hbt = backtest_from_events(&two_sided_book(99.0, 101.0, 1.0, (1..=100).map(|i| i * 100_000_000)));
+
+        let (cmd_tx, cmd_rx) = unbounded();
+        let (response_tx, _response_rx) = unbounded();
+        let controller = StrategyController::new(cmd_rx, response_tx);
+        cmd_tx.send(StrategyCommand::RunToEnd).unwrap();
+
+        let mut portfolio = PortfolioRunner::new(vec!["in-memory".to_string()], 10_000.0).unwrap()
+            .with_sleeve(0.7, fixed("Winner", 4.0)).unwrap()
+            .with_sleeve(0.3, fixed("Loser", -1.0)).unwrap();
+        portfolio.validate_allocations().unwrap();
+        let (data_tx, data_rx) = data_channel(16);
+        portfolio.run_backtest(&mut hbt, "in-memory", &data_tx, &controller).unwrap();
+
+        let breakdown = portfolio.sleeve_breakdown();
+        assert_eq!(breakdown.len(), 2);
+        assert_eq!((breakdown[0].capital, breakdown[1].capital), (7_000.0, 3_000.0));
+        assert!(breakdown[0].equity > 7_000.0 && breakdown[1].equity < 3_000.0);
+        let sum: f64 = breakdown.iter().map(|sleeve| sleeve.equity).sum();
+        assert!((portfolio.combined_equity() - sum).abs() < 1e-9);
+
+        // What the GUI gets agrees with the sleeves it carries
+        let last = data_rx.try_iter().last().unwrap();
+        assert_eq!(last.strategy_name, "Portfolio");
+        assert!((last.equity - last.sleeves.iter().map(|sleeve| sleeve.equity).sum::<f64>()).abs() < 1e-9);
+        assert!((last.equity - sum).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_allocations_must_cover_the_capital_exactly() {
+        let over = PortfolioRunner::new(vec!["in-memory".to_string()], 10_000.0).unwrap()
+            .with_sleeve(0.6, fixed("A", 0.0)).unwrap()
+            .with_sleeve(0.6, fixed("B", 0.0));
+        assert!(over.is_err());
+
+        let under = PortfolioRunner::new(vec!["in-memory".to_string()], 10_000.0).unwrap()
+            .with_sleeve(0.6, fixed("A", 0.0)).unwrap();
+        assert!(under.validate_allocations().is_err());
+        assert!(PortfolioRunner::new(vec!["in-memory".to_string()], 10_000.0).unwrap().validate_allocations().is_err());
+    }
+}
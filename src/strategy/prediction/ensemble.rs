@@ -0,0 +1,205 @@
+use anyhow::{bail, Result};
+use super::orderbook_features::{OrderBookFeatures, FeatureConfig};
+use super::price_predictor::{PricePredictor, PredictionSignal};
+
+/// 여러 `PricePredictor`의 다수결 앙상블
+///
+/// 멤버마다 가중치가 따로 초기화되므로 개별 모델의 잡음이 상쇄된다.
+/// 멤버가 하나면 단일 모델과 같은 결과를 낸다.
+pub struct PredictorEnsemble {
+    members: Vec<PricePredictor>,
+}
+
+/// 과반수 방향과 그 방향에 투표한 멤버들의 평균 예측값 (과반이 없으면 전체 평균과 Neutral)
+pub fn majority_vote(votes: &[(f64, PredictionSignal)]) -> (f64, PredictionSignal) {
+    for signal in [PredictionSignal::Up, PredictionSignal::Down, PredictionSignal::Neutral] {
+        let agreeing: Vec<f64> = votes.iter()
+            .filter(|(_, vote)| *vote == signal)
+            .map(|(prediction, _)| *prediction)
+            .collect();
+        if agreeing.len() * 2 > votes.len() {
+            return (agreeing.iter().sum::<f64>() / agreeing.len() as f64, signal);
+        }
+    }
+
+    let mean = votes.iter().map(|(prediction, _)| prediction).sum::<f64>() / votes.len().max(1) as f64;
+    (mean, PredictionSignal::Neutral)
+}
+
+#[allow(dead_code)]
+impl PredictorEnsemble {
+    /// `size`개의 멤버로 앙상블 생성 (임계값은 모든 멤버 공통)
+    pub fn new(size: usize, entry_threshold: f64, exit_threshold: f64, feature_config: &FeatureConfig) -> Result<Self> {
+        if size == 0 {
+            bail!("Ensemble size must be at least 1");
+        }
+        let members = (0..size)
+            .map(|_| PricePredictor::new(entry_threshold, exit_threshold, feature_config))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self { members })
+    }
+
+    /// 이미 만든 모델들로 앙상블 구성
+    pub fn from_members(members: Vec<PricePredictor>) -> Result<Self> {
+        if members.is_empty() {
+            bail!("Ensemble needs at least one member");
+        }
+        Ok(Self { members })
+    }
+
+    /// 모든 멤버에 조기 종료 설정
+    pub fn with_early_stopping(self, patience: Option<usize>) -> Self {
+        Self {
+            members: self.members.into_iter().map(|member| member.with_early_stopping(patience)).collect(),
+        }
+    }
+
+    /// 멤버 i를 `seed + i`로 초기화 (None이면 무작위 초기화 유지)
+    pub fn with_seed(mut self, seed: Option<u64>) -> Result<Self> {
+        if let Some(seed) = seed {
+            for (i, member) in self.members.iter_mut().enumerate() {
+                member.reseed(seed.wrapping_add(i as u64))?;
+            }
+        }
+        Ok(self)
+    }
+
+    pub fn size(&self) -> usize {
+        self.members.len()
+    }
+
+    /// 모든 멤버로 예측 후 다수결
+    pub fn predict(&mut self, features: &OrderBookFeatures) -> Result<(f64, PredictionSignal)> {
+        let votes = self.members.iter_mut()
+            .map(|member| member.predict(features))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(majority_vote(&votes))
+    }
+
+    /// 청산 임계값은 멤버 공통이므로 첫 멤버 기준
+    pub fn is_exit_signal(&self, prediction: f64, is_long: bool) -> bool {
+        self.members[0].is_exit_signal(prediction, is_long)
+    }
+
+    pub fn add_training_sample(&mut self, features: &OrderBookFeatures, price_change_pct: f64) {
+        for member in &mut self.members {
+            member.add_training_sample(features, price_change_pct);
+        }
+    }
+
+    /// 모든 멤버 배치 학습, 평균 손실 반환 (한 멤버라도 실패하면 에러)
+    pub fn train_batch(&mut self, batch_size: usize, learning_rate: f64) -> Result<f64> {
+        let mut total_loss = 0.0;
+        let mut first_error = None;
+        for member in &mut self.members {
+            match member.train_batch(batch_size, learning_rate) {
+                Ok(loss) => total_loss += loss,
+                Err(e) => {
+                    first_error.get_or_insert(e);
+                }
+            }
+        }
+        match first_error {
+            Some(e) => Err(e),
+            None => Ok(total_loss / self.members.len() as f64),
+        }
+    }
+
+    pub fn record_prediction(&mut self, prediction: f64, actual: f64) {
+        for member in &mut self.members {
+            member.record_prediction(prediction, actual);
+        }
+    }
+
+    /// 모든 멤버 웜 리스타트 (학습률 부스트 + 정규화 윈도 축소)
+    pub fn warm_restart(&mut self, lr_multiplier: f64, batches: usize, normalization_samples: Option<usize>) {
+        for member in &mut self.members {
+            member.boost_learning_rate(lr_multiplier, batches);
+            if let Some(max_samples) = normalization_samples {
+                member.shrink_normalization_window(max_samples);
+            }
+        }
+    }
+
+    /// 멤버 평균 정확도
+    pub fn get_accuracy(&self) -> f64 {
+        self.members.iter().map(PricePredictor::get_accuracy).sum::<f64>() / self.members.len() as f64
+    }
+
+    /// 학습한 멤버들의 평균 마지막 손실
+    pub fn last_loss(&self) -> Option<f64> {
+        let losses: Vec<f64> = self.members.iter().filter_map(PricePredictor::last_loss).collect();
+        if losses.is_empty() {
+            return None;
+        }
+        Some(losses.iter().sum::<f64>() / losses.len() as f64)
+    }
+
+    /// 가장 오래 비정상 업데이트가 이어진 멤버 기준
+    pub fn consecutive_bad_updates(&self) -> usize {
+        self.members.iter().map(PricePredictor::consecutive_bad_updates).max().unwrap_or(0)
+    }
+
+    /// 모든 멤버가 조기 종료됐는지
+    pub fn is_training_halted(&self) -> bool {
+        self.members.iter().all(PricePredictor::is_training_halted)
+    }
+
+    /// 멤버 전체에서 조기 종료로 건너뛴 배치 수
+    pub fn early_stopped_batches(&self) -> usize {
+        self.members.iter().map(PricePredictor::early_stopped_batches).sum()
+    }
+
+    /// 모든 멤버 고정
+    pub fn freeze(&mut self) {
+        for member in &mut self.members {
+            member.freeze();
+        }
+    }
+
+    /// 멤버는 함께 고정되므로 첫 멤버 기준
+    pub fn is_frozen(&self) -> bool {
+        self.members[0].is_frozen()
+    }
+
+    /// 학습 샘플은 모든 멤버에 같이 쌓이므로 첫 멤버 기준
+    pub fn get_training_samples(&self) -> usize {
+        self.members[0].get_training_samples()
+    }
+
+    pub fn is_ready(&self) -> bool {
+        self.members.iter().all(PricePredictor::is_ready)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::orderbook_features::sample_features;
+
+    #[test]
+    fn test_majority_direction_wins_two_to_one() {
+        let (prediction, signal) = majority_vote(&[
+            (0.002, PredictionSignal::Up),
+            (-0.004, PredictionSignal::Down),
+            (0.004, PredictionSignal::Up),
+        ]);
+        assert_eq!(signal, PredictionSignal::Up);
+        // 평균은 과반 멤버만으로 계산해 방향과 부호가 일치
+        assert!((prediction - 0.003).abs() < 1e-12);
+
+        // 1-1-1이면 과반 없음
+        let (_, signal) = majority_vote(&[
+            (0.002, PredictionSignal::Up),
+            (-0.002, PredictionSignal::Down),
+            (0.0, PredictionSignal::Neutral),
+        ]);
+        assert_eq!(signal, PredictionSignal::Neutral);
+
+        let mut ensemble = PredictorEnsemble::new(3, 0.001, 0.001, &FeatureConfig::default()).unwrap();
+        assert_eq!(ensemble.size(), 3);
+        let features = sample_features(0.1);
+        assert!(ensemble.predict(&features).is_ok());
+        assert!(PredictorEnsemble::new(0, 0.001, 0.001, &FeatureConfig::default()).is_err());
+    }
+}
@@ -0,0 +1,99 @@
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use super::PredictionSignal;
+
+/// ε-greedy 탐색: 진입 결정마다 확률 ε로 모델 신호 대신 무작위 방향으로 진입하고, ε는 결정마다 감쇠
+///
+/// 모델은 진입 여부와 무관하게 모든 예측의 실제 가격 변화로 학습하므로, 탐색 진입으로 겪은
+/// 구간도 그대로 학습 샘플이 된다.
+#[derive(Debug, Clone)]
+pub struct Exploration {
+    initial_epsilon: f64,
+    epsilon: f64,
+    decay: f64,
+    seed: Option<u64>,
+    rng: StdRng,
+    /// 무작위 방향으로 바꾼 결정 수
+    explored: usize,
+}
+
+impl Exploration {
+    /// `decay`: 결정마다 ε에 곱하는 계수 (1 = 감쇠 없음), 시드가 없으면 무작위
+    pub fn new(epsilon: f64, decay: f64, seed: Option<u64>) -> Self {
+        let epsilon = epsilon.clamp(0.0, 1.0);
+        Self {
+            initial_epsilon: epsilon,
+            epsilon,
+            decay: decay.clamp(0.0, 1.0),
+            seed,
+            rng: Self::rng(seed),
+            explored: 0,
+        }
+    }
+
+    fn rng(seed: Option<u64>) -> StdRng {
+        seed.map_or_else(StdRng::from_entropy, StdRng::seed_from_u64)
+    }
+
+    /// 진입 결정 하나: 확률 ε로 무작위 Up/Down, 아니면 모델 신호 그대로 (ε = 0이면 난수도 쓰지 않음)
+    pub fn choose(&mut self, signal: PredictionSignal) -> PredictionSignal {
+        if self.epsilon <= 0.0 {
+            return signal;
+        }
+        let explore = self.rng.gen_bool(self.epsilon);
+        self.epsilon *= self.decay;
+        if !explore {
+            return signal;
+        }
+        self.explored += 1;
+        if self.rng.gen_bool(0.5) { PredictionSignal::Up } else { PredictionSignal::Down }
+    }
+
+    pub fn epsilon(&self) -> f64 {
+        self.epsilon
+    }
+
+    pub fn explored(&self) -> usize {
+        self.explored
+    }
+
+    /// 초기 ε와 난수열로 되돌림
+    pub fn reset(&mut self) {
+        self.epsilon = self.initial_epsilon;
+        self.rng = Self::rng(self.seed);
+        self.explored = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_full_epsilon_picks_seeded_random_directions_and_zero_follows_model() {
+        let choices = |exploration: &mut Exploration| -> Vec<PredictionSignal> {
+            (0..40).map(|_| exploration.choose(PredictionSignal::Neutral)).collect()
+        };
+
+        let mut exploration = Exploration::new(1.0, 1.0, Some(7));
+        let first = choices(&mut exploration);
+        assert!(first.iter().all(|&signal| signal != PredictionSignal::Neutral));
+        assert!(first.contains(&PredictionSignal::Up) && first.contains(&PredictionSignal::Down));
+        assert_eq!(exploration.explored(), 40);
+        // Same seed, same directions
+        assert_eq!(choices(&mut Exploration::new(1.0, 1.0, Some(7))), first);
+        exploration.reset();
+        assert_eq!(choices(&mut exploration), first);
+
+        let mut greedy = Exploration::new(0.0, 1.0, Some(7));
+        for signal in [PredictionSignal::Up, PredictionSignal::Down, PredictionSignal::Neutral] {
+            assert_eq!(greedy.choose(signal), signal);
+        }
+        assert_eq!(greedy.explored(), 0);
+
+        // ε shrinks by the decay factor per decision
+        let mut decaying = Exploration::new(0.5, 0.9, Some(7));
+        decaying.choose(PredictionSignal::Neutral);
+        decaying.choose(PredictionSignal::Neutral);
+        assert!((decaying.epsilon() - 0.5 * 0.81).abs() < 1e-12);
+    }
+}
@@ -1,7 +1,15 @@
 pub mod orderbook_features;
 pub mod price_predictor;
+pub mod ensemble;
+pub mod regime;
+pub mod exploration;
 pub mod prediction_runner;
+pub mod seed_cv;
 
-pub use orderbook_features::OrderBookFeatureExtractor;
-pub use price_predictor::{PricePredictor, PredictionSignal};
+pub use orderbook_features::{OrderBookFeatureExtractor, FeatureConfig};
+pub use price_predictor::PredictionSignal;
+pub use ensemble::PredictorEnsemble;
+pub use regime::RegimeChangeDetector;
+pub use exploration::Exploration;
 pub use prediction_runner::PredictionRunner;
+pub use seed_cv::run_seed_cv;
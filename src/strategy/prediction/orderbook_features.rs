@@ -1,4 +1,7 @@
 use std::collections::VecDeque;
+use hftbacktest::types::Event;
+use crate::common::{CvdTracker, EwmaVolatility};
+use crate::config::{VOLATILITY_EWMA_LAMBDA, PREDICTION_PRESSURE_RATIO_BOUND};
 
 /// 오더북에서 ML 모델용 특성(feature)을 추출하는 모듈
 /// 
@@ -8,6 +11,8 @@ use std::collections::VecDeque;
 /// 3. 압력 지표: bid/ask 압력, 누적 압력
 /// 4. 변동성 지표: 가격 변동 표준편차
 /// 5. 시계열 특성: 이전 가격 변화율
+/// 6. 주문 흐름: 누적 거래량 델타 (CVD, 선택)
+/// 7. 지연 특성: 이전 K틱의 특성 벡터 (lag_depth, 선택)
 
 /// 오더북 레벨 정보
 #[derive(Debug, Clone, Copy)]
@@ -16,6 +21,30 @@ pub struct Level {
     pub quantity: f64,
 }
 
+/// 특성 선택 설정
+#[derive(Debug, Clone)]
+pub struct FeatureConfig {
+    /// 누적 거래량 델타(CVD) 특성 포함 여부
+    pub include_cvd: bool,
+    /// 변동성 EWMA 감쇠 계수 λ
+    pub volatility_lambda: f64,
+    /// 현재 벡터 뒤에 이어 붙일 과거 틱 수 K (입력 차원 = 단일 스냅샷 × (K+1), 0 = 현재만)
+    pub lag_depth: usize,
+    /// 로그 전에 bid/ask 압력 비율을 [1/R, R]로 제한하는 R (1 이상)
+    pub pressure_ratio_bound: f64,
+}
+
+impl Default for FeatureConfig {
+    fn default() -> Self {
+        Self {
+            include_cvd: false,
+            volatility_lambda: VOLATILITY_EWMA_LAMBDA,
+            lag_depth: 0,
+            pressure_ratio_bound: PREDICTION_PRESSURE_RATIO_BOUND,
+        }
+    }
+}
+
 /// 추출된 특성 벡터
 #[derive(Debug, Clone)]
 #[allow(dead_code)]
@@ -38,18 +67,29 @@ pub struct OrderBookFeatures {
     pub pressure_ratio: f64,
     /// 최근 가격 변화율 (%)
     pub price_change_pct: f64,
-    /// 변동성 (표준편차)
+    /// 변동성 (수익률 EWMA 표준편차, bps)
     pub volatility: f64,
     /// 수량 가중 스프레드
     pub volume_weighted_spread: f64,
     /// 거래 강도 지표
     pub trade_intensity: f64,
+    /// 누적 거래량 델타 (FeatureConfig::include_cvd일 때만 Some)
+    pub cvd: Option<f64>,
+    /// 1~K틱 전 스냅샷 벡터를 최근 순으로 이어 붙인 값 (lag_depth = 0이면 비어 있음)
+    pub lagged: Vec<f64>,
 }
 
 impl OrderBookFeatures {
-    /// 특성 벡터를 f64 배열로 변환 (모델 입력용)
+    /// 특성 벡터를 f64 배열로 변환 (모델 입력용, 지연 특성 포함)
     pub fn to_vec(&self) -> Vec<f64> {
-        vec![
+        let mut values = self.snapshot_vec();
+        values.extend_from_slice(&self.lagged);
+        values
+    }
+
+    /// 현재 틱의 특성만 담은 벡터
+    pub fn snapshot_vec(&self) -> Vec<f64> {
+        let mut values = vec![
             self.spread_bps,
             self.imbalance_level1,
             self.imbalance_multi_level,
@@ -58,12 +98,16 @@ impl OrderBookFeatures {
             self.volatility,
             self.volume_weighted_spread,
             self.trade_intensity,
-        ]
+        ];
+        if let Some(cvd) = self.cvd {
+            values.push(cvd);
+        }
+        values
     }
 
-    /// 특성 차원 수
-    pub fn feature_dim() -> usize {
-        8
+    /// 특성 차원 수 (지연 특성 포함)
+    pub fn feature_dim(config: &FeatureConfig) -> usize {
+        (8 + usize::from(config.include_cvd)) * (config.lag_depth + 1)
     }
 }
 
@@ -79,20 +123,45 @@ pub struct OrderBookFeatureExtractor {
     history_size: usize,
     /// 마지막 mid price
     last_mid_price: Option<f64>,
+    /// 수익률 EWMA 변동성
+    volatility: EwmaVolatility,
+    /// 특성 선택 설정
+    config: FeatureConfig,
+    /// 체결 기반 CVD 누적기
+    cvd_tracker: CvdTracker,
+    /// 최근 lag_depth개 스냅샷 벡터 (링 버퍼, 오래된 것부터)
+    lag_buffer: VecDeque<Vec<f64>>,
 }
 
 #[allow(dead_code)]
 impl OrderBookFeatureExtractor {
     pub fn new(depth_levels: usize, history_size: usize) -> Self {
+        Self::with_config(depth_levels, history_size, FeatureConfig::default())
+    }
+
+    pub fn with_config(depth_levels: usize, history_size: usize, config: FeatureConfig) -> Self {
         Self {
             depth_levels,
             price_history: VecDeque::with_capacity(history_size),
             volume_history: VecDeque::with_capacity(history_size),
             history_size,
             last_mid_price: None,
+            volatility: EwmaVolatility::new(config.volatility_lambda),
+            lag_buffer: VecDeque::with_capacity(config.lag_depth),
+            config,
+            cvd_tracker: CvdTracker::new(),
         }
     }
 
+    pub fn config(&self) -> &FeatureConfig {
+        &self.config
+    }
+
+    /// 백테스트에서 받은 체결 이벤트 반영 (오더북 레벨만으로는 체결 방향을 알 수 없음)
+    pub fn record_trades(&mut self, trades: &[Event]) {
+        self.cvd_tracker.update_from_events(trades);
+    }
+
     /// 오더북 데이터로부터 특성 추출
     pub fn extract(&mut self, bids: &[Level], asks: &[Level]) -> Option<OrderBookFeatures> {
         if bids.is_empty() || asks.is_empty() {
@@ -132,8 +201,10 @@ impl OrderBookFeatureExtractor {
         // 압력 지표
         let bid_pressure: f64 = bids.iter().take(levels_to_use).map(|l| l.quantity).sum();
         let ask_pressure: f64 = asks.iter().take(levels_to_use).map(|l| l.quantity).sum();
-        let pressure_ratio = if ask_pressure > 0.0 {
-            (bid_pressure / ask_pressure).ln() // log ratio for symmetry
+        let pressure_bound = self.config.pressure_ratio_bound.max(1.0);
+        let pressure_ratio = if bid_pressure + ask_pressure > 0.0 {
+            // log ratio for symmetry, clamped so a near-empty side stays within ±ln(R)
+            (bid_pressure / ask_pressure).clamp(1.0 / pressure_bound, pressure_bound).ln()
         } else {
             0.0
         };
@@ -173,7 +244,7 @@ impl OrderBookFeatureExtractor {
         // 히스토리 업데이트
         self.update_history(mid_price, current_total_volume);
 
-        Some(OrderBookFeatures {
+        let mut features = OrderBookFeatures {
             mid_price,
             spread_bps,
             weighted_mid_price,
@@ -186,36 +257,47 @@ impl OrderBookFeatureExtractor {
             volatility,
             volume_weighted_spread,
             trade_intensity,
-        })
+            cvd: self.config.include_cvd.then(|| self.cvd_tracker.value()),
+            lagged: Vec::new(),
+        };
+        features.lagged = self.stack_lags(features.snapshot_vec());
+
+        Some(features)
     }
 
-    /// 변동성 계산 (가격 변화의 표준편차)
-    fn calculate_volatility(&self) -> f64 {
-        if self.price_history.len() < 2 {
-            return 0.0;
+    /// 1~K틱 전 스냅샷을 최근 순으로 이어 붙이고 현재 스냅샷을 버퍼에 추가.
+    /// 히스토리가 K틱보다 짧으면 가장 오래된 스냅샷(없으면 현재)으로 채운다.
+    fn stack_lags(&mut self, snapshot: Vec<f64>) -> Vec<f64> {
+        let lag_depth = self.config.lag_depth;
+        if lag_depth == 0 {
+            return Vec::new();
         }
 
-        // 수익률 계산
-        let returns: Vec<f64> = self.price_history
-            .iter()
-            .zip(self.price_history.iter().skip(1))
-            .map(|(prev, curr)| (curr - prev) / prev)
+        let padding = self.lag_buffer.front().unwrap_or(&snapshot);
+        let lagged = self.lag_buffer.iter().rev()
+            .chain(std::iter::repeat(padding))
+            .take(lag_depth)
+            .flatten()
+            .copied()
             .collect();
 
-        if returns.is_empty() {
-            return 0.0;
+        self.lag_buffer.push_back(snapshot);
+        if self.lag_buffer.len() > lag_depth {
+            self.lag_buffer.pop_front();
         }
+        lagged
+    }
 
-        let mean = returns.iter().sum::<f64>() / returns.len() as f64;
-        let variance = returns.iter()
-            .map(|r| (r - mean).powi(2))
-            .sum::<f64>() / returns.len() as f64;
-
-        variance.sqrt() * 10000.0 // bps 단위로 변환
+    /// 변동성 계산 (가격 변화의 EWMA 표준편차)
+    fn calculate_volatility(&self) -> f64 {
+        self.volatility.value() * 10000.0 // bps 단위로 변환
     }
 
     /// 히스토리 업데이트
     fn update_history(&mut self, mid_price: f64, total_volume: f64) {
+        if let Some(last_price) = self.last_mid_price {
+            self.volatility.update((mid_price - last_price) / last_price);
+        }
         self.last_mid_price = Some(mid_price);
         
         self.price_history.push_back(mid_price);
@@ -245,6 +327,30 @@ impl OrderBookFeatureExtractor {
         self.price_history.clear();
         self.volume_history.clear();
         self.last_mid_price = None;
+        self.volatility.reset();
+        self.cvd_tracker.reset();
+        self.lag_buffer.clear();
+    }
+}
+
+/// 테스트용 고정 특성 (1차 불균형 `imbalance`, 다층 불균형은 그 절반)
+#[cfg(test)]
+pub(crate) fn sample_features(imbalance: f64) -> OrderBookFeatures {
+    OrderBookFeatures {
+        mid_price: 100.0,
+        spread_bps: 5.0,
+        weighted_mid_price: 100.0,
+        imbalance_level1: imbalance,
+        imbalance_multi_level: imbalance / 2.0,
+        bid_pressure: 1000.0,
+        ask_pressure: 900.0,
+        pressure_ratio: 0.1,
+        price_change_pct: 0.01,
+        volatility: 10.0,
+        volume_weighted_spread: 5.0,
+        trade_intensity: 0.02,
+        cvd: None,
+        lagged: Vec::new(),
     }
 }
 
@@ -273,5 +379,50 @@ mod tests {
         assert!((features.mid_price - 100.5).abs() < 0.01);
         assert!(features.spread_bps > 0.0);
         assert!(features.imbalance_level1.abs() <= 1.0);
+        assert_eq!(features.to_vec().len(), OrderBookFeatures::feature_dim(&FeatureConfig::default()));
+    }
+
+    #[test]
+    fn test_cvd_feature() {
+        use hftbacktest::types::{EXCH_BUY_TRADE_EVENT, EXCH_SELL_TRADE_EVENT};
+
+        let config = FeatureConfig { include_cvd: true, ..FeatureConfig::default() };
+        let mut extractor = OrderBookFeatureExtractor::with_config(5, 100, config.clone());
+
+        let trade = |ev, qty| Event {
+            ev, exch_ts: 0, local_ts: 0, px: 100.0, qty, order_id: 0, ival: 0, fval: 0.0,
+        };
+        extractor.record_trades(&[
+            trade(EXCH_BUY_TRADE_EVENT, 3.0),
+            trade(EXCH_SELL_TRADE_EVENT, 1.0),
+            trade(EXCH_BUY_TRADE_EVENT, 2.0),
+        ]);
+
+        let bids = vec![Level { price: 100.0, quantity: 10.0 }];
+        let asks = vec![Level { price: 101.0, quantity: 15.0 }];
+        let features = extractor.extract(&bids, &asks).unwrap();
+
+        assert_eq!(features.cvd, Some(4.0));
+        assert_eq!(features.to_vec().len(), OrderBookFeatures::feature_dim(&config));
+        assert_eq!(*features.to_vec().last().unwrap(), 4.0);
+    }
+
+    #[test]
+    fn test_near_empty_ask_side_keeps_pressure_ratio_bounded() {
+        let config = FeatureConfig { pressure_ratio_bound: 10.0, ..FeatureConfig::default() };
+        let mut extractor = OrderBookFeatureExtractor::with_config(5, 100, config);
+
+        let bids = vec![Level { price: 100.0, quantity: 50.0 }];
+        let asks = vec![Level { price: 101.0, quantity: 1e-12 }];
+        let features = extractor.extract(&bids, &asks).unwrap();
+        assert!(features.pressure_ratio.is_finite());
+        assert!((features.pressure_ratio - 10.0_f64.ln()).abs() < 1e-12);
+
+        // Symmetric on the other side, and untouched inside the bound
+        let features = extractor.extract(&asks, &bids).unwrap();
+        assert!((features.pressure_ratio + 10.0_f64.ln()).abs() < 1e-12);
+        let asks = vec![Level { price: 101.0, quantity: 25.0 }];
+        let features = extractor.extract(&bids, &asks).unwrap();
+        assert!((features.pressure_ratio - 2.0_f64.ln()).abs() < 1e-12);
     }
 }
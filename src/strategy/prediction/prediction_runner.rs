@@ -1,22 +1,31 @@
 use anyhow::Result;
 use hftbacktest::{
-    backtest::{Backtest, BacktestError, ExchangeKind, L2AssetBuilder, assettype::LinearAsset,
-        data::DataSource, models::{CommonFees, ConstantLatency, ProbQueueModel, 
+    backtest::{Backtest, BacktestError, L2AssetBuilder,
+        models::{CommonFees, ConstantLatency, ProbQueueModel, 
         PowerProbQueueFunc3, TradingValueFeeModel}},
     prelude::{Bot, HashMapMarketDepth, Status, TimeInForce, OrdType},
     depth::MarketDepth,
-    types::ElapseResult,
+    types::{ElapseResult, Event},
 };
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use std::collections::VecDeque;
-use crossbeam_channel::Sender;
-use crate::common::{calculate_mid_price, is_valid_depth};
-use crate::config::{TICK_SIZE, LOT_SIZE, ELAPSE_DURATION_NS, UPDATE_INTERVAL, COMMAND_POLL_TIMEOUT_MICROS};
-use crate::ui::{PerformanceData, OrderBookLevel};
-use crate::controller::StrategyController;
-use super::{OrderBookFeatureExtractor, PricePredictor, PredictionSignal};
+use crate::common::{calculate_mid_price, AdaptiveElapse, validate_time_in_force,
+    entry_price_tick, entry_ladder, summarize_ladder_fills, FeeCalculator, FeeDrag, bucketed_book, order_quantity, SizingMode, force_flatten, reconcile,
+    FilePreloader, data_source, HighWaterMark, SignalDelay, TradeThrottle, AtrTracker, StopKind, validate_stop_kind,
+    RunTarget, RunStopReason, MarkPricer, SignalLog, NoTradeZone, OrderIdGenerator, BorrowCost, StaleBookGuard, StaleBookEvent,
+    last_book_update_ns, EntryWindows, LossStreakHalt, FillTracker, FileMode};
+use crate::{log_error, log_info, log_warn};
+use crate::config::{ADAPTIVE_ELAPSE_QUIET_TICKS, ADAPTIVE_ELAPSE_MAX_MULTIPLIER, TICK_SIZE, LOT_SIZE, ASSET_KIND, PERFORMANCE_FEE_PCT, RUN_PROFIT_TARGET_PCT, RUN_LOSS_LIMIT_PCT, EXCHANGE_KIND, PREDICTION_TIME_IN_FORCE, PREDICTION_ELAPSE_DURATION_NS, UPDATE_INTERVAL, COMMAND_POLL_TIMEOUT_MICROS,
+    LAST_TRADES_CAPACITY, PREDICTION_INCLUDE_CVD, PREDICTION_FEATURE_LAG_DEPTH, PREDICTION_MIN_FEATURE_LEVELS, PREDICTION_ENTRY_AGGRESSIVENESS, PASSIVE_ENTRY_TIMEOUT_NS, ORDER_RESPONSE_WAIT_NS, PREDICTION_MAX_REQUOTES, PREDICTION_SIGNAL_DELAY_TICKS, PREDICTION_MAX_TRADES_PER_MINUTE, PREDICTION_MAX_CONSECUTIVE_LOSSES, PREDICTION_NO_TRADE_ZONE_TICKS, PREDICTION_NO_TRADE_ZONE_DURATION, PREDICTION_BAD_UPDATE_LR_DECAY, PREDICTION_MAX_BAD_UPDATES, PREDICTION_EARLY_STOPPING_PATIENCE, PREDICTION_ENSEMBLE_SIZE, PREDICTION_REGIME_ACCURACY_FLOOR, PREDICTION_REGIME_ACCURACY_WINDOW, PREDICTION_REGIME_VOLATILITY_JUMP, PREDICTION_WARM_RESTART_LR_MULTIPLIER, PREDICTION_WARM_RESTART_BATCHES, PREDICTION_WARM_RESTART_NORMALIZATION_SAMPLES, PREDICTION_STOP_KIND, MARK_PRICE_MODE, ATR_BAR_TICKS, ATR_PERIOD, PREDICTION_DRY_RUN,
+    PREDICTION_RESET_BETWEEN_FILES, PREDICTION_FILE_MODE, PREDICTION_FREEZE_AFTER_WARMUP, PREDICTION_SEED, PREDICTION_BREAKEVEN_TRIGGER_PCT, PREDICTION_EXIT_THRESHOLD, PREDICTION_EDGE_EXIT_THRESHOLD, PREDICTION_EXPLORATION_EPSILON, PREDICTION_EXPLORATION_DECAY, ESTIMATED_FEE_RATE,
+    SIGNAL_LOG_HORIZON_TICKS, PREDICTION_ENTRY_LEVELS, ENTRY_LEVEL_SPACING_TICKS, FEE_MODEL, MIN_PROFIT_OVER_FEES_PCT, SHORT_BORROW_RATE_BPS_PER_DAY, PREDICTION_SIZING_MODE, RUN_TO_END_BATCH,
+    FLATTEN_STEP_TICKS, FLATTEN_MAX_ATTEMPTS, FLATTEN_WAIT_NS, MAX_BOOK_STALENESS_NS, FLATTEN_ON_STALE_BOOK, ENTRY_TIME_WINDOWS_UTC, GUI_UPDATE_ON_FILL, GUI_REPLAY_RATE_WINDOW_MS, BOOK_PARTIAL_FILLS, BOOK_BUCKET_TICKS, TAKER_ORDER_LATENCY_NS, TAKER_QUEUE_POWER};
+use crate::ui::{DataSender, PerformanceData, UpdateCadence, ReplayRateMeter, data_channel};
+use crate::controller::{StrategyController, StrategyCommand};
+use crate::strategy::base::{send_gui_update, extract_orderbook};
+use super::{OrderBookFeatureExtractor, FeatureConfig, PredictorEnsemble, PredictionSignal, RegimeChangeDetector, Exploration};
 use super::orderbook_features::Level;
 
 /// 예측 기반 거래를 위한 1초 후 가격 예측 정보
@@ -36,6 +45,34 @@ enum PositionState {
     Short,
 }
 
+/// 체결 확인 전인 진입 주문 (패시브 진입은 호가에 대기할 수 있음)
+/// 분할 진입 시 가격 레벨별 자식 주문을 모두 추적
+#[derive(Debug, Clone)]
+struct PendingEntry {
+    /// 현재 시도의 주문
+    order_ids: Vec<u64>,
+    side: PositionState,
+    submitted_at: i64,
+    target_qty: f64,
+    /// 이전(재호가 전) 시도에서 누적된 체결분
+    filled_qty: f64,
+    filled_notional: f64,
+    filled_orders: usize,
+    requotes: usize,
+}
+
+/// 부분 체결 후 아직 대기 중인 청산 주문
+#[derive(Debug, Clone)]
+struct PendingExit {
+    order_id: u64,
+    side: PositionState,
+    submitted_at: i64,
+    /// 이 주문으로 지금까지 청산된 수량과 손익
+    filled_qty: f64,
+    gross_pnl: f64,
+    net_pnl: f64,
+}
+
 /// 오더북 기반 1초 후 가격 예측 전략 Runner
 /// 
 /// 전략 로직:
@@ -47,9 +84,17 @@ enum PositionState {
 pub struct PredictionRunner {
     data_files: Vec<PathBuf>,
     feature_extractor: OrderBookFeatureExtractor,
-    predictor: PricePredictor,
+    predictor: PredictorEnsemble,
+    /// 장세 변화 감지 시 웜 리스타트
+    regime_detector: RegimeChangeDetector,
+    warm_restarts: usize,
     position_size: f64,
     initial_capital: f64,
+    performance_fee: HighWaterMark,
+    /// 전체 실행 목표 수익/손실 한도
+    run_target: RunTarget,
+    /// 미실현 손익/평가금액 기준 가격
+    mark_pricer: MarkPricer,
     position_state: PositionState,
     entry_price: f64,
     position_qty: f64,
@@ -58,27 +103,111 @@ pub struct PredictionRunner {
     prediction_horizon_ns: i64, // 1초 = 1_000_000_000ns
     pending_predictions: VecDeque<PricePredictionData>,
     min_prediction_confidence: f64,
+    exit_threshold: f64,
+    /// 보유 방향 예측이 이 값 아래로 약해지면 청산 (None이면 끔)
+    edge_exit_threshold: Option<f64>,
+    /// 진입 결정의 ε-greedy 무작위 탐색
+    exploration: Exploration,
     
     // 학습 관련
     learning_rate: f64,
     warmup_samples: usize,
     is_warmed_up: bool,
+    /// 웜업이 끝나면 모델 고정 (이후 학습 없음)
+    freeze_after_warmup: bool,
+    /// NaN/Inf로 학습을 건너뛸 때마다 학습률에 곱하는 계수
+    bad_update_lr_decay: f64,
+    /// 연속 비정상 업데이트가 이 횟수에 도달하면 거래 중단 (0 = 중단하지 않음)
+    max_bad_updates: usize,
+    /// Controller에 전달할 모델 오류 (전달 후 거래 일시정지)
+    model_fault: Option<String>,
     
     // 리스크 관리
     stop_loss_pct: f64,
+    /// 손절 거리 방식 (비율/틱/ATR 배수)
+    stop_kind: StopKind,
+    atr: AtrTracker,
     take_profit_pct: f64,
     max_position_time_ns: i64,
     position_entry_time: i64,
+    breakeven_trigger_pct: Option<f64>,
+    breakeven_armed: bool,
+    
+    // 주문 설정
+    tif: TimeInForce,
+    elapse_ns: i64,
+    entry_aggressiveness: Option<i64>,
+    /// 진입 주문을 나눌 가격 레벨 수
+    entry_levels: usize,
+    order_timeout_ns: i64,
+    max_requotes: usize,
+    /// 진입 신호가 N틱 유지될 때만 진입
+    signal_delay: SignalDelay<PredictionSignal>,
+    /// `--signal-log`용 매 예측 기록 (진입 여부와 무관)
+    signal_log: SignalLog,
+    entry_throttle: TradeThrottle,
+    entry_windows: EntryWindows,
+    loss_streak: LossStreakHalt,
+    no_trade_zone: NoTradeZone,
+    /// 숏 포지션 차입 비용 누적
+    borrow_cost: BorrowCost,
+    /// 데이터 공백 동안 거래 중단
+    stale_book: StaleBookGuard,
+    /// 신호만 기록: 주문 없이 중간가로 가상 체결
+    dry_run: bool,
+    sizing_mode: SizingMode,
+    /// 마지막 전략 틱 기준 자산 (FractionOfEquity 사이징 기준)
+    sizing_equity: f64,
+    pending_entry: Option<PendingEntry>,
+    pending_exit: Option<PendingExit>,
+    /// 체결분 누적 반영 (BOOK_PARTIAL_FILLS 참고)
+    fill_tracker: FillTracker,
+    /// 수수료 계산 (누적 거래대금 기반 구간 요율 포함)
+    fees: FeeCalculator,
+    /// 익절은 왕복 수수료를 이만큼 넘는 수익에서만 실행 (None = 필터 없음)
+    min_profit_over_fees: Option<f64>,
+    
+    // 파일 간 모델/지표 초기화 여부
+    reset_between_files: bool,
+    /// 여러 파일을 하나의 세션으로 이어서 실행할지 여부
+    file_mode: FileMode,
+    /// 현재 파일 뒤에 실행할 파일이 있는지
+    has_next_file: bool,
+    /// 이전 파일에서 넘어온 실현 손익 (Continuous 모드, 넘어온 게 없으면 None)
+    carried_pnl: Option<f64>,
+    /// 현재 파일이 이전 파일의 포지션을 이어받았는지 (엔진 대조 불가)
+    carried_in: bool,
     
     // 메트릭
     num_trades: usize,
     winning_trades: usize,
     total_orders: usize,
+    /// 진입/청산 주문 ID 발급 (중복 없음)
+    order_ids: OrderIdGenerator,
     total_fills: usize,
     total_hold_time: Duration,
     prediction_accuracy: f64,
     total_predictions: usize,
     correct_predictions: usize,
+    /// 마지막으로 추출한 다단계 오더북 불균형 (GUI 표시용)
+    last_imbalance: Option<f64>,
+    /// 특성 추출에 필요한 한쪽 최소 실레벨 수와, 부족해서 건너뛴 틱 수
+    min_feature_levels: usize,
+    thin_book_ticks: usize,
+    /// 가중치 초기화 시드 (None이면 무작위)
+    seed: Option<u64>,
+    /// 마지막 파일 종료 시 (예측 정확도, 최종 손익)
+    last_result: Option<(f64, f64)>,
+    /// 모델 오류 시 일시정지 대신 실행 종료 (GUI 없이 돌릴 때)
+    stop_on_model_fault: bool,
+}
+
+/// 설정대로 앙상블 생성 후 시드 적용
+fn build_predictor(min_prediction_confidence: f64, exit_threshold: f64, feature_config: &FeatureConfig,
+                   seed: Option<u64>) -> Result<PredictorEnsemble> {
+    PredictorEnsemble::new(PREDICTION_ENSEMBLE_SIZE, min_prediction_confidence, exit_threshold, feature_config)?
+        .with_early_stopping(PREDICTION_EARLY_STOPPING_PATIENCE)
+        .with_seed(seed)
 }
 
 impl PredictionRunner {
@@ -95,9 +224,9 @@ impl PredictionRunner {
         if data_files.is_empty() {
             anyhow::bail!("No data files provided");
         }
-        println!("Using {} file(s):", data_files.len());
+        log_info!("Using {} file(s):", data_files.len());
         for (i, f) in data_files.iter().enumerate() {
-            println!("  [{}] {}", i + 1, f.display());
+            log_info!("  [{}] {}", i + 1, f.display());
         }
         Self::create_runner(data_files, position_size, stop_loss_pct, take_profit_pct, initial_capital, min_prediction_confidence, learning_rate)
     }
@@ -111,35 +240,94 @@ impl PredictionRunner {
         min_prediction_confidence: f64,
         learning_rate: f64,
     ) -> Result<Self> {
-        let predictor = PricePredictor::new(min_prediction_confidence)?;
+        validate_time_in_force(&EXCHANGE_KIND, PREDICTION_TIME_IN_FORCE)?;
+        validate_stop_kind(PREDICTION_STOP_KIND)?;
+        let feature_config = FeatureConfig {
+            include_cvd: PREDICTION_INCLUDE_CVD,
+            lag_depth: PREDICTION_FEATURE_LAG_DEPTH,
+            ..FeatureConfig::default()
+        };
+        let exit_threshold = PREDICTION_EXIT_THRESHOLD.unwrap_or(min_prediction_confidence);
+        let predictor = build_predictor(min_prediction_confidence, exit_threshold, &feature_config, PREDICTION_SEED)?;
 
         Ok(Self {
             data_files,
-            feature_extractor: OrderBookFeatureExtractor::new(10, 100),
+            feature_extractor: OrderBookFeatureExtractor::with_config(10, 100, feature_config),
             predictor,
+            regime_detector: RegimeChangeDetector::new(PREDICTION_REGIME_ACCURACY_FLOOR, PREDICTION_REGIME_ACCURACY_WINDOW,
+                                                       PREDICTION_REGIME_VOLATILITY_JUMP),
+            warm_restarts: 0,
             position_size,
             initial_capital,
+            performance_fee: HighWaterMark::new(PERFORMANCE_FEE_PCT, initial_capital),
+            run_target: RunTarget::new(RUN_PROFIT_TARGET_PCT, RUN_LOSS_LIMIT_PCT),
+            mark_pricer: MarkPricer::new(MARK_PRICE_MODE),
             position_state: PositionState::Flat,
             entry_price: 0.0,
             position_qty: 0.0,
             prediction_horizon_ns: 1_000_000_000,
             pending_predictions: VecDeque::with_capacity(100),
             min_prediction_confidence,
+            exit_threshold,
+            edge_exit_threshold: PREDICTION_EDGE_EXIT_THRESHOLD,
+            exploration: Exploration::new(PREDICTION_EXPLORATION_EPSILON, PREDICTION_EXPLORATION_DECAY, PREDICTION_SEED),
             learning_rate,
             warmup_samples: 1000,
             is_warmed_up: false,
+            freeze_after_warmup: PREDICTION_FREEZE_AFTER_WARMUP,
+            bad_update_lr_decay: PREDICTION_BAD_UPDATE_LR_DECAY,
+            max_bad_updates: PREDICTION_MAX_BAD_UPDATES,
+            model_fault: None,
             stop_loss_pct,
+            stop_kind: PREDICTION_STOP_KIND,
+            atr: AtrTracker::new(ATR_BAR_TICKS, ATR_PERIOD),
             take_profit_pct,
             max_position_time_ns: 5_000_000_000,
             position_entry_time: 0,
+            breakeven_trigger_pct: PREDICTION_BREAKEVEN_TRIGGER_PCT,
+            breakeven_armed: false,
+            tif: PREDICTION_TIME_IN_FORCE,
+            elapse_ns: PREDICTION_ELAPSE_DURATION_NS,
+            entry_aggressiveness: PREDICTION_ENTRY_AGGRESSIVENESS,
+            entry_levels: PREDICTION_ENTRY_LEVELS,
+            order_timeout_ns: PASSIVE_ENTRY_TIMEOUT_NS,
+            max_requotes: PREDICTION_MAX_REQUOTES,
+            signal_delay: SignalDelay::new(PREDICTION_SIGNAL_DELAY_TICKS, PredictionSignal::Neutral),
+            signal_log: SignalLog::new("ML Prediction", SIGNAL_LOG_HORIZON_TICKS),
+            entry_throttle: TradeThrottle::new(PREDICTION_MAX_TRADES_PER_MINUTE),
+            entry_windows: EntryWindows::new(ENTRY_TIME_WINDOWS_UTC),
+            loss_streak: LossStreakHalt::new(PREDICTION_MAX_CONSECUTIVE_LOSSES),
+            no_trade_zone: NoTradeZone::new(PREDICTION_NO_TRADE_ZONE_TICKS, PREDICTION_NO_TRADE_ZONE_DURATION),
+            borrow_cost: BorrowCost::new(SHORT_BORROW_RATE_BPS_PER_DAY),
+            stale_book: StaleBookGuard::new(MAX_BOOK_STALENESS_NS, FLATTEN_ON_STALE_BOOK),
+            dry_run: PREDICTION_DRY_RUN,
+            sizing_mode: PREDICTION_SIZING_MODE,
+            sizing_equity: initial_capital,
+            pending_entry: None,
+            pending_exit: None,
+            fill_tracker: FillTracker::new(BOOK_PARTIAL_FILLS),
+            fees: FeeCalculator::new(FEE_MODEL, ESTIMATED_FEE_RATE),
+            min_profit_over_fees: MIN_PROFIT_OVER_FEES_PCT,
+            reset_between_files: PREDICTION_RESET_BETWEEN_FILES,
+            file_mode: PREDICTION_FILE_MODE,
+            has_next_file: false,
+            carried_pnl: None,
+            carried_in: false,
             num_trades: 0,
             winning_trades: 0,
             total_orders: 0,
+            order_ids: OrderIdGenerator::new(),
             total_fills: 0,
             total_hold_time: Duration::ZERO,
             prediction_accuracy: 0.0,
             total_predictions: 0,
             correct_predictions: 0,
+            last_imbalance: None,
+            min_feature_levels: PREDICTION_MIN_FEATURE_LEVELS,
+            thin_book_ticks: 0,
+            seed: PREDICTION_SEED,
+            last_result: None,
+            stop_on_model_fault: false,
         })
     }
 
@@ -148,91 +336,61 @@ impl PredictionRunner {
     where
         MD: MarketDepth,
     {
-        let mut bids = Vec::new();
-        let mut asks = Vec::new();
-        
-        let best_bid_tick = depth.best_bid_tick();
-        let best_ask_tick = depth.best_ask_tick();
-        let tick_size = depth.tick_size();
-        
-        if best_bid_tick != i64::MIN {
-            for i in 0..count {
-                let tick = best_bid_tick - i as i64;
-                let qty = depth.bid_qty_at_tick(tick);
-                if qty > 0.0 {
-                    bids.push(Level {
-                        price: tick as f64 * tick_size,
-                        quantity: qty,
-                    });
-                }
-            }
-        }
-        
-        if best_ask_tick != i64::MAX {
-            for i in 0..count {
-                let tick = best_ask_tick + i as i64;
-                let qty = depth.ask_qty_at_tick(tick);
-                if qty > 0.0 {
-                    asks.push(Level {
-                        price: tick as f64 * tick_size,
-                        quantity: qty,
-                    });
-                }
-            }
-        }
-        
-        (bids, asks)
+        let (bids, asks) = bucketed_book(depth, count, BOOK_BUCKET_TICKS);
+        let to_levels = |side: Vec<(f64, f64)>| -> Vec<Level> {
+            side.into_iter().map(|(price, quantity)| Level { price, quantity }).collect()
+        };
+        (to_levels(bids), to_levels(asks))
     }
 
-    /// UI용 오더북 레벨 추출
-    fn extract_orderbook<MD>(&self, depth: &MD, levels: usize) -> (Vec<OrderBookLevel>, Vec<OrderBookLevel>)
-    where
-        MD: MarketDepth,
-    {
-        let mut bids = Vec::new();
-        let mut asks = Vec::new();
-        
-        let best_bid_tick = depth.best_bid_tick();
-        let best_ask_tick = depth.best_ask_tick();
-        let tick_size = depth.tick_size();
-        
-        if best_bid_tick != i64::MIN {
-            for i in 0..levels {
-                let tick = best_bid_tick - i as i64;
-                let qty = depth.bid_qty_at_tick(tick);
-                if qty > 0.0 {
-                    bids.push(OrderBookLevel {
-                        price: tick as f64 * tick_size,
-                        quantity: qty,
-                    });
-                }
-            }
-        }
-        
-        if best_ask_tick != i64::MAX {
-            for i in 0..levels {
-                let tick = best_ask_tick + i as i64;
-                let qty = depth.ask_qty_at_tick(tick);
-                if qty > 0.0 {
-                    asks.push(OrderBookLevel {
-                        price: tick as f64 * tick_size,
-                        quantity: qty,
-                    });
-                }
-            }
+    /// 양쪽 모두 `min_feature_levels`개 이상의 실제 레벨이 있어야 특성 추출
+    fn has_min_feature_levels(&self, bids: &[Level], asks: &[Level]) -> bool {
+        bids.len() >= self.min_feature_levels && asks.len() >= self.min_feature_levels
+    }
+
+    /// 가중치를 `seed`로 다시 초기화 (파일 간 모델 초기화에도 같은 시드 사용)
+    pub fn with_seed(mut self, seed: u64) -> Result<Self> {
+        let feature_config = self.feature_extractor.config().clone();
+        self.seed = Some(seed);
+        self.predictor = build_predictor(self.min_prediction_confidence, self.exit_threshold, &feature_config, self.seed)?;
+        self.exploration = Exploration::new(PREDICTION_EXPLORATION_EPSILON, PREDICTION_EXPLORATION_DECAY, self.seed);
+        Ok(self)
+    }
+
+    /// 마지막 파일의 (예측 정확도 0~1, 최종 자산 - 초기 자본), 실행 전이면 None
+    pub fn last_result(&self) -> Option<(f64, f64)> {
+        self.last_result
+    }
+
+    /// GUI 없이 모든 파일을 끝까지 실행 (모델 오류는 에러로 반환)
+    pub fn run_headless(&mut self) -> Result<()> {
+        let (cmd_tx, cmd_rx) = crossbeam_channel::unbounded();
+        let (response_tx, _response_rx) = crossbeam_channel::unbounded();
+        let controller = StrategyController::new(cmd_rx, response_tx);
+        cmd_tx.send(StrategyCommand::RunToEnd)?;
+        controller.process_commands(Duration::ZERO);
+        // 받는 쪽이 없으므로 최신 업데이트 하나만 유지
+        let (sender, _data_rx) = data_channel(1);
+
+        self.stop_on_model_fault = true;
+        let file_count = self.data_files.len();
+        for (file_idx, data_file) in self.data_files.clone().into_iter().enumerate() {
+            self.has_next_file = file_idx + 1 < file_count;
+            self.run_strategy_with_control(&data_file.to_string_lossy(), None, &sender, &controller)?;
         }
-        
-        (bids, asks)
+        Ok(())
     }
 
     /// Controller를 통한 전략 실행
     pub fn run_with_controller(
         &mut self,
-        sender: Sender<PerformanceData>,
+        sender: DataSender,
         controller: Arc<StrategyController>,
     ) -> Result<()> {
         let file_count = self.data_files.len();
         
+        let mut preload: Option<FilePreloader> = None;
+        
         for file_idx in 0..file_count {
             // Wait for start signal if in paused or stopped state
             while !controller.is_running() && !controller.should_stop() {
@@ -240,34 +398,43 @@ impl PredictionRunner {
             }
             
             if controller.should_stop() {
-                println!("\n⏹ Strategy stopped by user");
+                log_info!("\n⏹ Strategy stopped by user");
                 break;
             }
             
             let data_file = self.data_files[file_idx].clone();
+            let preloaded = preload.take().and_then(FilePreloader::wait);
+            // Load the next file in the background while this one runs
+            preload = self.data_files.get(file_idx + 1)
+                .map(|next| FilePreloader::spawn(next.to_string_lossy().into_owned()));
             
-            // Notify GUI to clear chart data for new file (except first file)
-            if file_idx > 0 {
+            // Notify GUI to clear chart data for new file (except first file and continued sessions)
+            if file_idx > 0 && self.carried_pnl.is_none() {
                 controller.notify_new_file();
             }
             
-            println!("\n{}", "=".repeat(60));
-            println!("Running ML Prediction strategy on file [{}/{}]: {}", 
-                     file_idx + 1, 
-                     file_count, 
-                     data_file.display());
-            println!("{}\n", "=".repeat(60));
+            log_info!("\n{}", "=".repeat(60));
+            log_info!("Running ML Prediction strategy on file [{}/{}]: {}", 
+                      file_idx + 1, 
+                      file_count, 
+                      data_file.display());
+            log_info!("{}\n", "=".repeat(60));
             
+            self.has_next_file = file_idx + 1 < file_count;
             self.run_strategy_with_control(
                 data_file.to_str().unwrap(),
+                preloaded,
                 &sender,
                 &controller,
             )?;
         }
         
+        // Stopped before the next file was needed: discard it; the loader thread is detached
+        drop(preload);
+        
         if !controller.should_stop() {
             controller.mark_completed();
-            println!("\n✅ All files processed successfully!");
+            log_info!("\n✅ All files processed successfully!");
         }
         
         // Keep thread alive until GUI closes
@@ -278,7 +445,7 @@ impl PredictionRunner {
     
     /// Keep thread alive until GUI window closes
     fn keep_alive_until_close(&self, controller: &StrategyController) {
-        println!("Backtest finished. Close the window to exit.");
+        log_info!("Backtest finished. Close the window to exit.");
         
         loop {
             if !controller.process_commands(Duration::from_millis(200)) {
@@ -290,31 +457,87 @@ impl PredictionRunner {
         }
     }
 
+    /// 파일 시작 시 상태 초기화
+    ///
+    /// 포지션 상태는 항상 초기화한다. `reset_between_files`가 true이면 모델(가중치, 정규화 통계),
+    /// 특성 히스토리, 거래/예측 카운터도 함께 초기화하여 파일별로 독립된 지표를 얻는다.
+    /// false이면 학습된 모델과 카운터가 파일 간에 누적된다.
+    /// 이전 파일에서 상태를 넘겨받았으면(Continuous) 이전 엔진의 미체결 주문만 정리하고 나머지는 유지한다.
+    fn reset_for_new_file(&mut self) -> Result<()> {
+        self.pending_entry = None;
+        self.pending_exit = None;
+        self.fill_tracker.reset();
+        // 파일이 바뀌면 이전 파일의 미드로 선행 수익률을 잴 수 없음
+        self.signal_log.flush();
+        if self.carried_pnl.is_some() {
+            return Ok(());
+        }
+
+        self.position_state = PositionState::Flat;
+        self.signal_delay.reset();
+        self.entry_throttle.reset();
+        self.no_trade_zone.reset();
+        self.atr.reset();
+        self.borrow_cost.reset();
+        self.stale_book.reset();
+        self.mark_pricer.reset();
+        self.entry_price = 0.0;
+        self.position_qty = 0.0;
+        self.is_warmed_up = false;
+        self.last_imbalance = None;
+
+        if self.reset_between_files {
+            let feature_config = self.feature_extractor.config().clone();
+            self.predictor = build_predictor(self.min_prediction_confidence, self.exit_threshold, &feature_config, self.seed)?;
+            self.feature_extractor.reset();
+            self.pending_predictions.clear();
+            self.regime_detector.reset();
+            self.exploration.reset();
+
+            self.num_trades = 0;
+            self.winning_trades = 0;
+            self.total_orders = 0;
+            self.total_fills = 0;
+            self.total_hold_time = Duration::ZERO;
+            self.prediction_accuracy = 0.0;
+            self.total_predictions = 0;
+            self.correct_predictions = 0;
+            self.fees.reset();
+        }
+
+        Ok(())
+    }
+
     /// 단일 파일에 대한 전략 실행 (Controller 사용)
     fn run_strategy_with_control(
         &mut self,
         data_file: &str,
-        sender: &Sender<PerformanceData>,
+        preloaded: Option<Vec<Event>>,
+        sender: &DataSender,
         controller: &StrategyController,
     ) -> Result<()> {
-        println!("Loading data from: {}", data_file);
+        log_info!("Loading data from: {}", data_file);
 
-        let mut hbt = self.create_backtest(data_file)?;
+        let mut hbt = self.create_backtest(data_file, preloaded)?;
+        // Each file's equity curve starts from the initial capital again (unless it continues the last one)
+        if self.carried_pnl.is_none() {
+            self.performance_fee = HighWaterMark::new(PERFORMANCE_FEE_PCT, self.initial_capital);
+            self.fees.reset_total_fees();
+        }
         
-        println!("ML Prediction strategy started...\n");
-        println!("🔬 Warming up model with {} samples...\n", self.warmup_samples);
+        log_info!("ML Prediction strategy started...\n");
+        log_info!("🔬 Warming up model with {} samples...\n", self.warmup_samples);
 
-        let mut realized_pnl = 0.0;
         let cash = self.initial_capital;
         let mut update_count = 0;
+        let mut stepper = AdaptiveElapse::new(self.elapse_ns, ADAPTIVE_ELAPSE_QUIET_TICKS, ADAPTIVE_ELAPSE_MAX_MULTIPLIER);
 
-        // Reset state
-        self.position_state = PositionState::Flat;
-        self.entry_price = 0.0;
-        self.position_qty = 0.0;
-        self.is_warmed_up = false;
+        self.reset_for_new_file()?;
+        self.carried_in = self.carried_pnl.is_some();
+        let mut realized_pnl = self.carried_pnl.take().unwrap_or(0.0);
 
-        let mut last_gui_update = Instant::now();
+        let mut gui_cadence = UpdateCadence::new(Duration::from_millis(33), GUI_UPDATE_ON_FILL);
+        let mut replay_rate = ReplayRateMeter::new(Duration::from_millis(GUI_REPLAY_RATE_WINDOW_MS));
         let mut last_command_check = Instant::now();
         let command_check_interval = Duration::from_millis(16); // ~60Hz command polling
         let mut current_time_ns: i64 = 0;
@@ -323,13 +546,8 @@ impl PredictionRunner {
         loop {
             // Check if data has ended
             if data_ended {
-                println!("\nEnd of data reached!");
-                if self.position_state != PositionState::Flat {
-                    println!("Closing remaining position...");
-                    let _ = self.close_position(&mut hbt, &mut realized_pnl)?;
-                }
-                let final_depth = hbt.depth(0);
-                self.print_final_stats(realized_pnl, cash, final_depth);
+                log_info!("\nEnd of data reached!");
+                self.finish_file(&mut hbt, realized_pnl, cash)?;
                 return Ok(());
             }
             
@@ -339,11 +557,17 @@ impl PredictionRunner {
                 controller.process_commands(Duration::from_millis(50));
                 
                 if controller.should_stop() {
-                    println!("\n⏹ Strategy stopped by user");
+                    log_info!("\n⏹ Strategy stopped by user");
                     break;
                 }
                 continue;
             }
+
+            // 연속 손실로 멈춘 실행을 사용자가 재개하면 진입 제한 해제
+            if self.loss_streak.is_halted() {
+                self.loss_streak.resume();
+                log_info!("▶ 연속 손실 카운트 초기화, 진입 재개");
+            }
             
             // Process commands at fixed interval when running
             if last_command_check.elapsed() >= command_check_interval {
@@ -351,16 +575,19 @@ impl PredictionRunner {
                 last_command_check = Instant::now();
                 
                 if controller.should_stop() {
-                    println!("\n⏹ Strategy stopped by user");
+                    log_info!("\n⏹ Strategy stopped by user");
                     break;
                 }
             }
             
             // Speed adjustment - affects simulation time
             let speed = controller.speed_multiplier();
+            let run_to_end = controller.is_run_to_end();
             
             // Calculate iterations and delay based on speed
-            let (iterations_per_loop, loop_delay_ms) = if speed >= 100.0 {
+            let (iterations_per_loop, loop_delay_ms) = if run_to_end {
+                (RUN_TO_END_BATCH, 0u64)
+            } else if speed >= 100.0 {
                 (100, 0u64)
             } else if speed >= 10.0 {
                 ((speed / 10.0).ceil() as usize, 1)
@@ -371,38 +598,65 @@ impl PredictionRunner {
             };
             
             for _ in 0..iterations_per_loop {
-                match hbt.elapse(ELAPSE_DURATION_NS) {
+                // A seek stops fast-forwarding here and pauses the run
+                if controller.reached_seek_target(stepper.sim_time_secs()) {
+                    break;
+                }
+                replay_rate.observe(hbt.current_timestamp());
+                match hbt.elapse(stepper.duration_ns()) {
                     Ok(ElapseResult::EndOfData) => {
                         data_ended = true;
                         break;
                     }
                     Ok(_) => {
-                        current_time_ns += ELAPSE_DURATION_NS;
+                        current_time_ns += stepper.duration_ns();
+                        
+                        // 체결 이벤트는 유효하지 않은 depth 구간에서도 누적
+                        self.feature_extractor.record_trades(hbt.last_trades(0));
+                        self.mark_pricer.observe_trades(hbt.last_trades(0));
+                        hbt.clear_last_trades(Some(0));
+                        
                         let depth = hbt.depth(0);
                         
-                        if !is_valid_depth(depth) {
+                        let Some(mid_price) = calculate_mid_price(depth) else {
                             continue;
-                        }
+                        };
                         
                         update_count += 1;
+                        stepper.observe(depth);
                         
-                        let mid_price = calculate_mid_price(depth);
+                        self.atr.update(mid_price);
+                        self.borrow_cost.accrue(self.signed_position(), mid_price, current_time_ns);
+                        self.signal_log.observe_mid(mid_price);
                         
                         // Feature extraction
                         let (bids, asks) = self.extract_levels(depth, 10);
+                        if !self.has_min_feature_levels(&bids, &asks) {
+                            self.thin_book_ticks += 1;
+                            continue;
+                        }
                         
                         if let Some(features) = self.feature_extractor.extract(&bids, &asks) {
+                            self.last_imbalance = Some(features.imbalance_multi_level);
+                            if self.regime_detector.observe_volatility(features.volatility) {
+                                self.warm_restart("실현 변동성 급등");
+                            }
                             // Validate past predictions and learn
                             self.validate_and_learn_predictions(mid_price, current_time_ns);
                             
                             // Make new prediction
-                            if let Ok((prediction, signal)) = self.predictor.predict(&features) {
+                            let predicted = self.predictor.predict(&features);
+                            if predicted.is_err() {
+                                self.check_model_health();
+                            }
+                            if let Ok((prediction, signal)) = predicted {
                                 // Record prediction
                                 self.pending_predictions.push_back(PricePredictionData {
                                     mid_price,
                                     predicted_change: prediction,
                                     timestamp: current_time_ns,
                                 });
+                                self.signal_log.record(current_time_ns, signal.label(), prediction, mid_price);
                                 
                                 // Remove old predictions
                                 while self.pending_predictions.len() > 100 {
@@ -410,14 +664,17 @@ impl PredictionRunner {
                                 }
                                 
                                 // Warmup check
-                                if !self.is_warmed_up && self.predictor.get_training_samples() >= self.warmup_samples {
-                                    self.is_warmed_up = true;
-                                    println!("\n🚀 Model warmed up! Starting trading...\n");
-                                }
+                                self.check_warmup();
                                 
                                 // Execute trade (only after warmup)
                                 if self.is_warmed_up && update_count % UPDATE_INTERVAL == 0 {
                                     self.execute_strategy(&mut hbt, &mut realized_pnl, signal, prediction, current_time_ns)?;
+                                    self.accrue_performance_fee(hbt.depth(0), cash, realized_pnl);
+                                    // 체결 직후 업데이트가 체결 시각을 갖도록 배치 중단
+                                    gui_cadence.observe_fills(self.total_fills);
+                                    if gui_cadence.fill_pending() && !run_to_end {
+                                        break;
+                                    }
                                 }
                             }
                         }
@@ -427,16 +684,50 @@ impl PredictionRunner {
                         break;
                     }
                 }
+
+                if self.model_fault.is_some() {
+                    break;
+                }
+            }
+
+            // 연속 손실 한도 도달 시 진입을 멈추고 실행 일시정지
+            if let Some(notice) = self.loss_streak.take_halt_notice() {
+                log_warn!("\n⏸ {}", notice);
+                controller.pause_with_log(notice);
             }
 
+            // 전체 실행 목표 도달 시 포지션을 청산하고 실행 종료
+            if let Some(reason) = self.run_target_reached(hbt.depth(0), realized_pnl) {
+                log_info!("\n🏁 {}", reason);
+                if self.position_state != PositionState::Flat {
+                    self.flatten_remaining(&mut hbt, &mut realized_pnl)?;
+                }
+                self.reconcile_with_engine(&hbt, realized_pnl);
+                self.print_final_stats(realized_pnl, cash, hbt.depth(0));
+                controller.mark_target_reached(reason);
+                return Ok(());
+            }
+
+            // 모델이 계속 NaN/Inf를 내면 더 거래하지 않고 일시정지
+            if let Some(message) = self.model_fault.take() {
+                log_error!("⚠ {}", message);
+                if self.stop_on_model_fault {
+                    anyhow::bail!(message);
+                }
+                controller.pause_with_error(message);
+            }
+
+            replay_rate.observe(hbt.current_timestamp());
+
             // GUI 업데이트 (throttled to ~30 FPS)
-            if last_gui_update.elapsed() >= Duration::from_millis(33) {
+            // In run-to-end mode only the final state is sent
+            let gui_due = gui_cadence.due(run_to_end, data_ended);
+            if gui_due {
                 let depth_for_data = hbt.depth(0);
-                if is_valid_depth(depth_for_data) {
-                    let mid_price = calculate_mid_price(depth_for_data);
-                    
-                    let (position_value, unrealized_pnl) = self.calculate_position_metrics(mid_price);
-                    let (bids, asks) = self.extract_orderbook(depth_for_data, 10);
+                if let Some(mid_price) = calculate_mid_price(depth_for_data) {
+                    let (_, unrealized_pnl) =
+                        self.calculate_position_metrics(self.mark_pricer.mark(depth_for_data).unwrap_or(mid_price));
+                    let (bids, asks) = extract_orderbook(depth_for_data, 10);
                     let avg_hold_time = if self.num_trades > 0 {
                         self.total_hold_time.as_secs_f64() / self.num_trades as f64
                     } else {
@@ -445,17 +736,18 @@ impl PredictionRunner {
                     
                     self.prediction_accuracy = self.predictor.get_accuracy();
                     
-                    // Use try_send to avoid blocking GUI
+                    // Never blocks the GUI; the newest update wins when the channel is full
                     // timestamp = simulation time in seconds
-                    let sim_time_secs = update_count as f64 * (ELAPSE_DURATION_NS as f64 / 1_000_000_000.0);
-                    let _ = sender.try_send(PerformanceData {
+                    let sim_time_secs = stepper.sim_time_secs();
+                    let (stop_price, take_price) = self.exit_levels();
+                    send_gui_update(sender, controller, PerformanceData {
                         timestamp: sim_time_secs,
-                        equity: cash + realized_pnl + position_value,
+                        equity: self.performance_fee.net_equity(),
                         realized_pnl,
                         unrealized_pnl,
                         position: self.position_qty,
                         mid_price,
-                        strategy_name: format!("ML Prediction (Acc: {:.1}%)", self.prediction_accuracy * 100.0),
+                        strategy_name: self.display_name(),
                         num_trades: self.num_trades,
                         winning_trades: self.winning_trades,
                         total_fills: self.total_fills,
@@ -464,31 +756,93 @@ impl PredictionRunner {
                         latency_micros: 100,
                         bids,
                         asks,
+                        stop_price,
+                        take_price,
+                        performance_fees_accrued: self.performance_fee.accrued(),
+                        borrow_costs: self.borrow_cost.accrued(),
+                        fees_paid: self.fees.total_fees(),
+                        attribution: None,
+                        spread_capture: None,
+                        imbalance: self.last_imbalance,
+                        fill_update: gui_cadence.fill_pending(),
+                        signal_confidence: None,
+                        sleeves: Vec::new(),
+                        replay_rate: replay_rate.rate(),
                     });
                 }
-                last_gui_update = Instant::now();
+                gui_cadence.mark_sent();
             }
             
             // Apply speed-based delay
             if loop_delay_ms > 0 {
                 std::thread::sleep(Duration::from_millis(loop_delay_ms));
-            } else {
+            } else if !run_to_end {
                 std::thread::yield_now();
             }
         }
 
         // 남은 포지션 청산
         if self.position_state != PositionState::Flat {
-            println!("\nClosing remaining position...");
-            let _ = self.close_position(&mut hbt, &mut realized_pnl)?;
+            log_info!("\nClosing remaining position...");
+            self.flatten_remaining(&mut hbt, &mut realized_pnl)?;
         }
 
+        self.reconcile_with_engine(&hbt, realized_pnl);
         let final_depth = hbt.depth(0);
         self.print_final_stats(realized_pnl, cash, final_depth);
 
         Ok(())
     }
 
+    /// GUI 표시용 전략 이름 (워밍업 중에는 학습 손실과 방향 정확도 표시)
+    fn display_name(&self) -> String {
+        let status = if self.is_warmed_up {
+            format!("Acc: {:.1}%", self.prediction_accuracy * 100.0)
+        } else {
+            let loss = self.predictor.last_loss().map_or("n/a".to_string(), |loss| format!("{:.6}", loss));
+            let accuracy = if self.total_predictions > 0 {
+                self.correct_predictions as f64 / self.total_predictions as f64 * 100.0
+            } else {
+                0.0
+            };
+            format!("Warming up — loss: {}, acc: {:.1}%", loss, accuracy)
+        };
+        format!("ML Prediction ({}){}", status, if self.dry_run { " (Dry Run)" } else { "" })
+    }
+
+    /// 학습 샘플이 충분하면 웜업 완료 (설정 시 모델 고정)
+    fn check_warmup(&mut self) {
+        if self.is_warmed_up || self.predictor.get_training_samples() < self.warmup_samples {
+            return;
+        }
+        self.is_warmed_up = true;
+        log_info!("\n🚀 Model warmed up! Starting trading...\n");
+        if self.freeze_after_warmup {
+            self.predictor.freeze();
+            log_info!("  🧊 모델 고정: 이후 학습 없이 현재 가중치로 예측\n");
+        }
+    }
+
+    /// 연속 비정상 업데이트가 한도에 도달한 시점에 한 번 거래 중단을 요청
+    fn check_model_health(&mut self) {
+        let consecutive = self.predictor.consecutive_bad_updates();
+        if self.max_bad_updates > 0 && consecutive == self.max_bad_updates {
+            self.model_fault = Some(format!(
+                "Prediction model produced {} consecutive non-finite updates (learning rate now {:e}); trading paused",
+                consecutive, self.learning_rate
+            ));
+        }
+    }
+
+    /// 장세 변화: 일정 배치 동안 학습률을 올리고 정규화 통계를 줄여 빠르게 재적응
+    fn warm_restart(&mut self, reason: &str) {
+        self.warm_restarts += 1;
+        self.predictor.warm_restart(PREDICTION_WARM_RESTART_LR_MULTIPLIER, PREDICTION_WARM_RESTART_BATCHES,
+                                    PREDICTION_WARM_RESTART_NORMALIZATION_SAMPLES);
+        log_info!("  🔄 웜 리스타트 ({}): {}배치 동안 학습률 x{}",
+                  reason, PREDICTION_WARM_RESTART_BATCHES, PREDICTION_WARM_RESTART_LR_MULTIPLIER);
+    }
+
     /// 과거 예측 검증 및 온라인 학습
     fn validate_and_learn_predictions(&mut self, current_mid_price: f64, current_time_ns: i64) {
         // 1초 전 예측 찾기
@@ -498,24 +852,33 @@ impl PredictionRunner {
                 
                 // 방향 정확도 체크
                 self.total_predictions += 1;
-                if (pred.predicted_change > 0.0 && actual_change > 0.0) ||
-                   (pred.predicted_change < 0.0 && actual_change < 0.0) {
+                let hit = (pred.predicted_change > 0.0 && actual_change > 0.0) ||
+                    (pred.predicted_change < 0.0 && actual_change < 0.0);
+                if hit {
                     self.correct_predictions += 1;
                 }
+                let regime_changed = self.regime_detector.record_outcome(hit);
                 
                 // 예측 기록 (정확도 추적용)
                 self.predictor.record_prediction(pred.predicted_change, actual_change);
                 
                 // 특성 재추출 후 학습 (실제 구현에서는 저장된 특성 사용)
                 // 여기서는 간단히 버퍼에 있는 데이터로 배치 학습
-                if self.predictor.get_training_samples() >= 64 && 
+                if !self.predictor.is_frozen() && self.predictor.get_training_samples() >= 64 && 
                    self.pending_predictions.len() % 32 == 0 {
                     if let Err(e) = self.predictor.train_batch(32, self.learning_rate) {
-                        eprintln!("Training error: {}", e);
+                        log_error!("Training error: {}", e);
+                        if self.predictor.consecutive_bad_updates() > 0 {
+                            self.learning_rate *= self.bad_update_lr_decay;
+                        }
+                        self.check_model_health();
                     }
                 }
                 
                 self.pending_predictions.pop_front();
+                if regime_changed {
+                    self.warm_restart("예측 정확도 하한 미달");
+                }
             } else {
                 break;
             }
@@ -534,20 +897,38 @@ impl PredictionRunner {
     where
         MD: MarketDepth,
     {
+        self.no_trade_zone.tick();
+        self.sizing_equity = self.initial_capital + *realized_pnl - self.borrow_cost.accrued();
+        if self.check_stale_book(hbt, realized_pnl)? {
+            return Ok(());
+        }
+        // 진입 주문이 아직 처리 중이면 새 신호는 무시
+        if self.pending_entry.is_some() {
+            return self.check_pending_entry(hbt, current_time_ns);
+        }
+        // 부분 체결된 청산 주문도 마찬가지
+        if let Some(exit) = self.pending_exit.take() {
+            return self.resolve_exit(hbt, exit, realized_pnl);
+        }
+
         let depth = hbt.depth(0);
-        let mid_price = calculate_mid_price(depth);
+        let Some(mid_price) = calculate_mid_price(depth) else {
+            return Ok(());
+        };
 
         // 포지션 종료 조건 체크
         if self.position_state != PositionState::Flat {
+            self.update_breakeven(mid_price);
+            
             // Stop-loss / Take-profit 체크
             if self.should_close_position(mid_price) {
-                println!("  💔 Closing due to stop-loss/take-profit");
+                log_info!("  💔 Closing due to stop-loss/take-profit");
                 return self.close_position(hbt, realized_pnl);
             }
             
             // 최대 보유 시간 초과
             if current_time_ns - self.position_entry_time > self.max_position_time_ns {
-                println!("  ⏰ Closing due to max hold time");
+                log_info!("  ⏰ Closing due to max hold time");
                 return self.close_position(hbt, realized_pnl);
             }
         }
@@ -555,27 +936,43 @@ impl PredictionRunner {
         // 신호 기반 거래
         match self.position_state {
             PositionState::Flat => {
+                // 확인 지연: 신호가 유지된 경우에만 진입 (탐색 시 무작위 방향)
+                let confirmed = self.signal_delay.confirm(signal);
+                let signal = self.exploration.choose(confirmed);
+                if signal != confirmed {
+                    log_info!("  🎲 Exploring: {:?} instead of the model's {:?} (ε = {:.4})",
+                              signal, confirmed, self.exploration.epsilon());
+                }
                 match signal {
                     PredictionSignal::Up => {
-                        println!("  🔮 Predicted UP ({:.4}%) - Opening LONG", prediction * 100.0);
+                        log_info!("  🔮 Predicted UP ({:.4}%) - Opening LONG", prediction * 100.0);
                         self.open_long_position(hbt, current_time_ns)?;
                     }
                     PredictionSignal::Down => {
-                        println!("  🔮 Predicted DOWN ({:.4}%) - Opening SHORT", prediction * 100.0);
+                        log_info!("  🔮 Predicted DOWN ({:.4}%) - Opening SHORT", prediction * 100.0);
                         self.open_short_position(hbt, current_time_ns)?;
                     }
                     PredictionSignal::Neutral => {}
                 }
             }
+            // 청산은 진입보다 강한 반대 예측(exit_threshold)을 요구
             PositionState::Long => {
-                if signal == PredictionSignal::Down {
-                    println!("  ⚠️  Signal reversed, closing LONG");
+                self.signal_delay.reset();
+                if self.predictor.is_exit_signal(prediction, true) {
+                    log_warn!("  ⚠️  Signal reversed ({:.4}%), closing LONG", prediction * 100.0);
+                    self.close_position(hbt, realized_pnl)?;
+                } else if self.edge_faded(prediction, true) {
+                    log_info!("  📉 Edge faded ({:.4}%), closing LONG", prediction * 100.0);
                     self.close_position(hbt, realized_pnl)?;
                 }
             }
             PositionState::Short => {
-                if signal == PredictionSignal::Up {
-                    println!("  ⚠️  Signal reversed, closing SHORT");
+                self.signal_delay.reset();
+                if self.predictor.is_exit_signal(prediction, false) {
+                    log_warn!("  ⚠️  Signal reversed ({:.4}%), closing SHORT", prediction * 100.0);
+                    self.close_position(hbt, realized_pnl)?;
+                } else if self.edge_faded(prediction, false) {
+                    log_info!("  📉 Edge faded ({:.4}%), closing SHORT", prediction * 100.0);
                     self.close_position(hbt, realized_pnl)?;
                 }
             }
@@ -584,89 +981,281 @@ impl PredictionRunner {
         Ok(())
     }
 
+    /// 보유 방향 예측이 `edge_exit_threshold` 아래로 약해졌는지 (반전 없이 엣지 소멸)
+    fn edge_faded(&self, prediction: f64, is_long: bool) -> bool {
+        let edge = if is_long { prediction } else { -prediction };
+        self.edge_exit_threshold.is_some_and(|threshold| edge < threshold)
+    }
+
+    /// 데이터 공백 로그 및 거래 중단 여부 반환 (설정 시 공백 시작에 포지션 청산)
+    fn check_stale_book<MD>(
+        &mut self,
+        hbt: &mut Backtest<MD>,
+        realized_pnl: &mut f64,
+    ) -> Result<bool, BacktestError>
+    where
+        MD: MarketDepth,
+    {
+        match self.stale_book.update(last_book_update_ns(hbt), hbt.current_timestamp()) {
+            Some(StaleBookEvent::Stale { gap_ns }) => {
+                log_warn!("  ⚠ No book update for {:.1}s, trading suspended", gap_ns as f64 / 1e9);
+                if self.stale_book.flattens() && self.position_state != PositionState::Flat {
+                    log_info!("  Closing position on stale book");
+                    self.close_position(hbt, realized_pnl)?;
+                }
+            }
+            Some(StaleBookEvent::Resumed { gap_ns }) => {
+                log_info!("  ✓ Book updates resumed after a {:.1}s gap", gap_ns as f64 / 1e9);
+            }
+            None => {}
+        }
+        Ok(self.stale_book.is_stale())
+    }
+
     fn open_long_position<MD>(
         &mut self,
         hbt: &mut Backtest<MD>,
         current_time_ns: i64,
     ) -> Result<(), BacktestError>
+    where
+        MD: MarketDepth,
+    {
+        self.open_position(hbt, PositionState::Long, current_time_ns)
+    }
+
+    fn open_short_position<MD>(
+        &mut self,
+        hbt: &mut Backtest<MD>,
+        current_time_ns: i64,
+    ) -> Result<(), BacktestError>
+    where
+        MD: MarketDepth,
+    {
+        self.open_position(hbt, PositionState::Short, current_time_ns)
+    }
+
+    fn open_position<MD>(
+        &mut self,
+        hbt: &mut Backtest<MD>,
+        side: PositionState,
+        current_time_ns: i64,
+    ) -> Result<(), BacktestError>
+    where
+        MD: MarketDepth,
+    {
+        if !self.entry_throttle.allows_entry(current_time_ns) {
+            log_warn!("  ⏸ 진입 제한: 분당 최대 {}회 도달", PREDICTION_MAX_TRADES_PER_MINUTE.unwrap_or_default());
+            return Ok(());
+        }
+        if !self.loss_streak.allows_entry() {
+            log_warn!("  ⏸ 진입 생략: 연속 손실 한도 도달, 재개 대기 중");
+            return Ok(());
+        }
+        if !self.entry_windows.allows_entry(current_time_ns) {
+            log_warn!("  ⏸ 진입 생략: 허용된 진입 시간대가 아님");
+            return Ok(());
+        }
+
+        let Some(mid_price) = calculate_mid_price(hbt.depth(0)) else {
+            return Ok(());
+        };
+        if self.no_trade_zone.blocks(mid_price, TICK_SIZE) {
+            log_warn!("  ⏸ 진입 생략: {:.5}가 직전 청산가 주변 no-trade zone 안에 있음", mid_price);
+            return Ok(());
+        }
+        let qty = order_quantity(self.position_size, self.sizing_mode, mid_price, self.sizing_equity, LOT_SIZE);
+        if qty <= 0.0 {
+            return Ok(());
+        }
+        self.entry_throttle.record_entry(current_time_ns);
+        if self.dry_run {
+            self.open_dry_run(side, mid_price, qty, current_time_ns);
+            return Ok(());
+        }
+        let order_ids = self.submit_entry_orders(hbt, side == PositionState::Long, qty)?;
+
+        self.pending_entry = Some(PendingEntry {
+            order_ids,
+            side,
+            submitted_at: current_time_ns,
+            target_qty: qty,
+            filled_qty: 0.0,
+            filled_notional: 0.0,
+            filled_orders: 0,
+            requotes: 0,
+        });
+        self.check_pending_entry(hbt, current_time_ns)
+    }
+
+    /// 현재 호가 기준으로 `qty`를 진입 래더로 제출하고 잠시 응답 대기, 제출한 주문 ID 반환
+    fn submit_entry_orders<MD>(
+        &mut self,
+        hbt: &mut Backtest<MD>,
+        is_buy: bool,
+        qty: f64,
+    ) -> Result<Vec<u64>, BacktestError>
     where
         MD: MarketDepth,
     {
         let depth = hbt.depth(0);
         let tick_size = depth.tick_size();
-        let best_ask_tick = depth.best_ask_tick();
-        let best_ask_price = best_ask_tick as f64 * tick_size;
+        let price_tick = entry_price_tick(
+            depth.best_bid_tick(), depth.best_ask_tick(), is_buy, self.entry_aggressiveness,
+        );
+        let ladder = entry_ladder(
+            price_tick, is_buy, qty, self.entry_levels, ENTRY_LEVEL_SPACING_TICKS, LOT_SIZE,
+        );
         
-        let order_id = 100 + self.total_orders as u64;
-        hbt.submit_buy_order(
-            0,
-            order_id,
-            best_ask_price,
-            self.position_size,
-            TimeInForce::GTC,
-            OrdType::Limit,
-            false,
-        )?;
-        self.total_orders += 1;
+        let mut order_ids = Vec::with_capacity(ladder.len());
+        for (tick, qty) in ladder {
+            let price = tick as f64 * tick_size;
+            let order_id = self.order_ids.next_id();
+            if is_buy {
+                hbt.submit_buy_order(0, order_id, price, qty, self.tif, OrdType::Limit, false)?;
+            } else {
+                hbt.submit_sell_order(0, order_id, price, qty, self.tif, OrdType::Limit, false)?;
+            }
+            self.total_orders += 1;
+            order_ids.push(order_id);
+        }
 
-        // Short timeout to avoid blocking - 100ms
-        let _ = hbt.wait_order_response(0, order_id, 100_000_000);
-
-        let orders = hbt.orders(0);
-        if let Some(order) = orders.get(&order_id) {
-            if order.status == Status::Filled {
-                self.entry_price = order.price_tick as f64 * tick_size;
-                self.position_qty = order.qty;
-                self.position_state = PositionState::Long;
-                self.position_entry_time = current_time_ns;
-                self.total_fills += 1;
-                
-                println!("    ✓ Opened LONG @ {:.6} qty {:.4}", self.entry_price, self.position_qty);
+        if let Some(&last_id) = order_ids.last() {
+            let _ = hbt.wait_order_response(0, last_id, ORDER_RESPONSE_WAIT_NS);
+        }
+        Ok(order_ids)
+    }
+
+    /// 미확정 진입 주문 처리: `order_timeout_ns`가 지나면 남은 주문 취소 요청,
+    /// 살아있는 주문이 없으면 미체결분을 재호가(`max_requotes`회까지)하거나
+    /// 체결분을 합산해 포지션 반영 (체결이 없으면 진입 취소)
+    fn check_pending_entry<MD>(
+        &mut self,
+        hbt: &mut Backtest<MD>,
+        current_time_ns: i64,
+    ) -> Result<(), BacktestError>
+    where
+        MD: MarketDepth,
+    {
+        let pending = match &self.pending_entry {
+            Some(pending) => pending.clone(),
+            None => return Ok(()),
+        };
+        let label = if pending.side == PositionState::Long { "LONG" } else { "SHORT" };
+
+        let fills = summarize_ladder_fills(hbt.orders(0), &pending.order_ids);
+
+        if !fills.working.is_empty() {
+            if current_time_ns - pending.submitted_at > self.order_timeout_ns {
+                for order_id in fills.working {
+                    hbt.cancel(0, order_id, false)?;
+                }
+            }
+            return Ok(());
+        }
+
+        let filled_qty = pending.filled_qty + fills.filled_qty;
+        let filled_notional = pending.filled_notional + fills.avg_price * fills.filled_qty;
+        let filled_orders = pending.filled_orders + fills.filled_orders;
+        let remaining = pending.target_qty - filled_qty;
+
+        if remaining >= LOT_SIZE / 2.0 && pending.requotes < self.max_requotes {
+            log_info!("    ↻ Requoting {} entry ({}/{}) qty {:.4}",
+                      label, pending.requotes + 1, self.max_requotes, remaining);
+            let order_ids = self.submit_entry_orders(hbt, pending.side == PositionState::Long, remaining)?;
+            self.pending_entry = Some(PendingEntry {
+                order_ids,
+                submitted_at: current_time_ns,
+                filled_qty,
+                filled_notional,
+                filled_orders,
+                requotes: pending.requotes + 1,
+                ..pending
+            });
+            return Ok(());
+        }
+
+        self.pending_entry = None;
+        self.total_fills += filled_orders;
+
+        if filled_qty > 0.0 {
+            self.entry_price = filled_notional / filled_qty;
+            self.position_qty = filled_qty;
+            self.position_state = pending.side;
+            self.position_entry_time = current_time_ns;
+            self.breakeven_armed = false;
+            
+            log_info!("    ✓ Opened {} @ {:.6} qty {:.4}", label, self.entry_price, self.position_qty);
+        } else {
+            match pending.order_ids.first().and_then(|order_id| hbt.orders(0).get(order_id)) {
+                Some(order) => log_warn!("    ✗ {} entry not filled ({:?})", label, order.status),
+                None => log_warn!("    ✗ {} entry not filled", label),
             }
         }
 
         Ok(())
     }
 
-    fn open_short_position<MD>(
+    /// 남은 포지션 강제 청산: 확인될 때까지 점점 더 공격적인 가격으로 스프레드를 가로지름
+    /// (청산가와 손익은 일반 청산과 구분해 출력)
+    fn flatten_remaining<MD>(
         &mut self,
         hbt: &mut Backtest<MD>,
-        current_time_ns: i64,
+        realized_pnl: &mut f64,
     ) -> Result<(), BacktestError>
     where
         MD: MarketDepth,
     {
-        let depth = hbt.depth(0);
-        let tick_size = depth.tick_size();
-        let best_bid_tick = depth.best_bid_tick();
-        let best_bid_price = best_bid_tick as f64 * tick_size;
-        
-        let order_id = 200 + self.total_orders as u64;
-        hbt.submit_sell_order(
-            0,
-            order_id,
-            best_bid_price,
-            self.position_size,
-            TimeInForce::GTC,
-            OrdType::Limit,
-            false,
+        let direction = match self.position_state {
+            PositionState::Long => 1.0,
+            PositionState::Short => -1.0,
+            PositionState::Flat => return Ok(()),
+        };
+        if self.dry_run {
+            return self.close_position(hbt, realized_pnl);
+        }
+
+        // Resting entry orders would change the position underneath the flatten
+        if let Some(pending) = self.pending_entry.take() {
+            for order_id in pending.order_ids {
+                let _ = hbt.cancel(0, order_id, false);
+            }
+        }
+        if let Some(exit) = self.pending_exit.take() {
+            let _ = hbt.cancel(0, exit.order_id, false);
+        }
+
+        let result = force_flatten(
+            hbt, &mut self.order_ids, direction * self.position_qty, LOT_SIZE,
+            FLATTEN_STEP_TICKS, FLATTEN_MAX_ATTEMPTS, FLATTEN_WAIT_NS,
         )?;
-        self.total_orders += 1;
+        self.total_orders += result.attempts;
 
-        // Short timeout to avoid blocking - 100ms
-        let _ = hbt.wait_order_response(0, order_id, 100_000_000);
-
-        let orders = hbt.orders(0);
-        if let Some(order) = orders.get(&order_id) {
-            if order.status == Status::Filled {
-                self.entry_price = order.price_tick as f64 * tick_size;
-                self.position_qty = order.qty;
-                self.position_state = PositionState::Short;
-                self.position_entry_time = current_time_ns;
-                self.total_fills += 1;
-                
-                println!("    ✓ Opened SHORT @ {:.6} qty {:.4}", self.entry_price, self.position_qty);
+        if result.filled_qty > 0.0 {
+            let pnl = ASSET_KIND.pnl(self.entry_price, result.avg_price, result.filled_qty) * direction;
+            let fee = self.fees.charge(ASSET_KIND.notional(self.entry_price, result.filled_qty))
+                + self.fees.charge(ASSET_KIND.notional(result.avg_price, result.filled_qty));
+            *realized_pnl += pnl - fee;
+            self.no_trade_zone.record_exit(result.avg_price);
+            self.loss_streak.record_trade(pnl - fee);
+            self.total_fills += 1;
+            self.num_trades += 1;
+            if pnl > 0.0 {
+                self.winning_trades += 1;
             }
+            
+            log_info!("    ✓ Final flatten @ {:.6} qty {:.4} after {} attempt(s) | PnL: {:.4} | Fee: {:.4}",
+                      result.avg_price, result.filled_qty, result.attempts, pnl, fee);
+        }
+
+        if result.is_flat() {
+            self.position_state = PositionState::Flat;
+            self.entry_price = 0.0;
+            self.position_qty = 0.0;
+            self.breakeven_armed = false;
+        } else {
+            self.position_qty = result.remaining.abs();
+            log_warn!("    ✗ Final flatten incomplete: {:.4} still open after {} attempt(s)",
+                      self.position_qty, result.attempts);
         }
 
         Ok(())
@@ -680,147 +1269,365 @@ impl PredictionRunner {
     where
         MD: MarketDepth,
     {
+        if self.dry_run {
+            // 한쪽 호가가 비었으면 포지션 유지, 다음 틱에 다시 시도
+            if let Some(mid_price) = calculate_mid_price(hbt.depth(0)) {
+                self.close_dry_run(mid_price, realized_pnl);
+            }
+            return Ok(());
+        }
+
         let depth = hbt.depth(0);
         let tick_size = depth.tick_size();
+        let side = self.position_state;
+        let order_id = self.order_ids.next_id();
 
-        match self.position_state {
+        match side {
             PositionState::Long => {
-                let best_bid_tick = depth.best_bid_tick();
-                let best_bid_price = best_bid_tick as f64 * tick_size;
-                
-                let order_id = 300 + self.total_orders as u64;
+                let best_bid_price = depth.best_bid_tick() as f64 * tick_size;
                 hbt.submit_sell_order(
                     0,
                     order_id,
                     best_bid_price,
                     self.position_qty,
-                    TimeInForce::GTC,
+                    self.tif,
                     OrdType::Limit,
                     false,
                 )?;
-                self.total_orders += 1;
-
-                // Short timeout to avoid blocking - 100ms
-                let _ = hbt.wait_order_response(0, order_id, 100_000_000);
-
-                let orders = hbt.orders(0);
-                if let Some(order) = orders.get(&order_id) {
-                    if order.status == Status::Filled {
-                        let exit_price = order.price_tick as f64 * tick_size;
-                        let pnl = (exit_price - self.entry_price) * self.position_qty;
-                        let fee = (exit_price * self.position_qty + self.entry_price * self.position_qty) * 0.0001;
-                        *realized_pnl += pnl - fee;
-                        self.total_fills += 1;
-                        
-                        self.num_trades += 1;
-                        if pnl > 0.0 {
-                            self.winning_trades += 1;
-                        }
-                        
-                        println!("    ✓ Closed LONG @ {:.6} | PnL: {:.4} | Fee: {:.4}", 
-                                 exit_price, pnl, fee);
-                    }
-                }
             }
             PositionState::Short => {
-                let best_ask_tick = depth.best_ask_tick();
-                let best_ask_price = best_ask_tick as f64 * tick_size;
-                
-                let order_id = 400 + self.total_orders as u64;
+                let best_ask_price = depth.best_ask_tick() as f64 * tick_size;
                 hbt.submit_buy_order(
                     0,
                     order_id,
                     best_ask_price,
                     self.position_qty,
-                    TimeInForce::GTC,
+                    self.tif,
                     OrdType::Limit,
                     false,
                 )?;
-                self.total_orders += 1;
-
-                // Short timeout to avoid blocking - 100ms
-                let _ = hbt.wait_order_response(0, order_id, 100_000_000);
-
-                let orders = hbt.orders(0);
-                if let Some(order) = orders.get(&order_id) {
-                    if order.status == Status::Filled {
-                        let exit_price = order.price_tick as f64 * tick_size;
-                        let pnl = (self.entry_price - exit_price) * self.position_qty;
-                        let fee = (exit_price * self.position_qty + self.entry_price * self.position_qty) * 0.0001;
-                        *realized_pnl += pnl - fee;
-                        self.total_fills += 1;
-                        
-                        self.num_trades += 1;
-                        if pnl > 0.0 {
-                            self.winning_trades += 1;
-                        }
-                        
-                        println!("    ✓ Closed SHORT @ {:.6} | PnL: {:.4} | Fee: {:.4}", 
-                                 exit_price, pnl, fee);
-                    }
+            }
+            PositionState::Flat => return Ok(()),
+        }
+        self.total_orders += 1;
+        let _ = hbt.wait_order_response(0, order_id, ORDER_RESPONSE_WAIT_NS);
+
+        let exit = PendingExit {
+            order_id,
+            side,
+            submitted_at: hbt.current_timestamp(),
+            filled_qty: 0.0,
+            gross_pnl: 0.0,
+            net_pnl: 0.0,
+        };
+        self.resolve_exit(hbt, exit, realized_pnl)
+    }
+
+    /// 청산 주문의 새 체결분을 반영
+    ///
+    /// 전량 체결이면 포지션 종료, 체결 여부와 관계없이 아직 대기 중이면 `pending_exit`에 남기고
+    /// (`order_timeout_ns` 경과 시 취소), 일부만 체결되고 끝났으면 나머지 포지션은 유지한다.
+    fn resolve_exit<MD>(
+        &mut self,
+        hbt: &mut Backtest<MD>,
+        mut exit: PendingExit,
+        realized_pnl: &mut f64,
+    ) -> Result<(), BacktestError>
+    where
+        MD: MarketDepth,
+    {
+        let (direction, label) = if exit.side == PositionState::Long { (1.0, "LONG") } else { (-1.0, "SHORT") };
+        let (fill, status) = match hbt.orders(0).get(&exit.order_id) {
+            Some(order) => (self.fill_tracker.poll(order), order.status),
+            None => {
+                // 더 이상 체결을 반영할 수 없으므로 포지션은 유지
+                log_warn!("    ✗ {} exit order {} no longer tracked, {:.4} still open",
+                          label, exit.order_id, self.position_qty);
+                return Ok(());
+            }
+        };
+
+        if fill.qty > 0.0 {
+            let qty = fill.qty.min(self.position_qty);
+            let pnl = ASSET_KIND.pnl(self.entry_price, fill.price, qty) * direction;
+            let fee = self.fees.charge(ASSET_KIND.notional(self.entry_price, qty))
+                + self.fees.charge(ASSET_KIND.notional(fill.price, qty));
+            *realized_pnl += pnl - fee;
+            self.total_fills += 1;
+            self.position_qty -= qty;
+            exit.filled_qty += qty;
+            exit.gross_pnl += pnl;
+            exit.net_pnl += pnl - fee;
+
+            let action = if status == Status::Filled { "Closed" } else { "Partially closed" };
+            log_info!("    ✓ {} {} @ {:.6} qty {:.4} | PnL: {:.4} | Fee: {:.4}",
+                      action, label, fill.price, qty, pnl, fee);
+        }
+
+        match status {
+            Status::Filled => self.record_exit_trade(&exit, fill.price),
+            _ if fill.done => {
+                if exit.filled_qty > 0.0 {
+                    // 체결된 부분은 별도 거래로 기록하고 나머지 포지션은 유지
+                    self.record_exit_trade(&exit, fill.price);
+                    log_warn!("    ✗ {} exit ended part-filled ({:?}), {:.4} still open",
+                              label, status, self.position_qty);
+                } else {
+                    // Unfilled IOC exits are dropped by the exchange, so the position is still open
+                    log_warn!("    ✗ {} exit not filled ({:?})", label, status);
                 }
+                return Ok(());
+            }
+            // 부분 체결 여부와 관계없이 아직 대기 중이면 전량 체결될 때까지 포지션 유지
+            _ => {
+                if hbt.current_timestamp() - exit.submitted_at > self.order_timeout_ns {
+                    hbt.cancel(0, exit.order_id, false)?;
+                }
+                self.pending_exit = Some(exit);
+                return Ok(());
             }
-            PositionState::Flat => {}
         }
 
         self.position_state = PositionState::Flat;
         self.entry_price = 0.0;
         self.position_qty = 0.0;
+        self.breakeven_armed = false;
+
+        Ok(())
+    }
+
+    /// 청산 주문 하나를 거래 1건으로 집계
+    fn record_exit_trade(&mut self, exit: &PendingExit, exit_price: f64) {
+        self.no_trade_zone.record_exit(exit_price);
+        self.loss_streak.record_trade(exit.net_pnl);
+        self.num_trades += 1;
+        if exit.gross_pnl > 0.0 {
+            self.winning_trades += 1;
+        }
+    }
+
+    /// 드라이런 진입: 주문 없이 중간가로 포지션 설정
+    fn open_dry_run(&mut self, side: PositionState, mid_price: f64, qty: f64, current_time_ns: i64) {
+        let label = if side == PositionState::Long { "LONG" } else { "SHORT" };
+        self.total_orders += 1;
+        self.total_fills += 1;
+        self.entry_price = mid_price;
+        self.position_qty = qty;
+        self.position_state = side;
+        self.position_entry_time = current_time_ns;
+        self.breakeven_armed = false;
+
+        log_info!("    [Dry Run] Would open {} @ {:.6} qty {:.4}", label, mid_price, qty);
+    }
+
+    /// 드라이런 청산: 가상 포지션을 중간가로 청산 (수수료 포함)
+    fn close_dry_run(&mut self, mid_price: f64, realized_pnl: &mut f64) {
+        let (direction, label) = match self.position_state {
+            PositionState::Long => (1.0, "LONG"),
+            PositionState::Short => (-1.0, "SHORT"),
+            PositionState::Flat => return,
+        };
+        let pnl = ASSET_KIND.pnl(self.entry_price, mid_price, self.position_qty) * direction;
+        let fee = self.fees.charge(ASSET_KIND.notional(self.entry_price, self.position_qty))
+            + self.fees.charge(ASSET_KIND.notional(mid_price, self.position_qty));
+        *realized_pnl += pnl - fee;
+        self.no_trade_zone.record_exit(mid_price);
+        self.loss_streak.record_trade(pnl - fee);
+        self.total_orders += 1;
+        self.total_fills += 1;
+        self.num_trades += 1;
+        if pnl > 0.0 {
+            self.winning_trades += 1;
+        }
+
+        log_info!("    [Dry Run] Would close {} @ {:.6} | PnL: {:.4} | Fee: {:.4}",
+                  label, mid_price, pnl, fee);
+
+        self.position_state = PositionState::Flat;
+        self.entry_price = 0.0;
+        self.position_qty = 0.0;
+        self.breakeven_armed = false;
+    }
 
+    /// 데이터 끝: Continuous 모드에서 다음 파일이 있으면 포지션과 손익을 넘기고, 아니면 청산 후 대조
+    fn finish_file<MD>(&mut self, hbt: &mut Backtest<MD>, mut realized_pnl: f64, cash: f64) -> Result<(), BacktestError>
+    where
+        MD: MarketDepth,
+    {
+        if self.file_mode.carries_over(self.has_next_file) {
+            log_info!("Carrying {:?} position and ${:.2} realized PnL into the next file", self.position_state, realized_pnl);
+            self.carried_pnl = Some(realized_pnl);
+        } else {
+            if self.position_state != PositionState::Flat {
+                log_info!("Closing remaining position...");
+                self.flatten_remaining(hbt, &mut realized_pnl)?;
+            }
+            self.reconcile_with_engine(hbt, realized_pnl);
+        }
+        self.print_final_stats(realized_pnl, cash, hbt.depth(0));
         Ok(())
     }
 
+    /// `--reconcile`: compare the tracked position/PnL with the engine's state at file end
+    fn reconcile_with_engine<MD>(&self, hbt: &Backtest<MD>, realized_pnl: f64)
+    where
+        MD: MarketDepth,
+    {
+        // The engine never sees dry-run trades, so there is nothing to compare against
+        if self.dry_run {
+            return;
+        }
+        // This file's engine started flat while the tracked position and PnL came from earlier files
+        if self.carried_in {
+            log_info!("[Prediction] Reconcile skipped: the session continued from a previous file");
+            return;
+        }
+        let Some(mid_price) = calculate_mid_price(hbt.depth(0)) else {
+            log_warn!("⚠️  [Prediction] Reconcile skipped: one-sided book at file end");
+            return;
+        };
+        let (_, unrealized_pnl) = self.calculate_position_metrics(mid_price);
+        let signed_position = match self.position_state {
+            PositionState::Long => self.position_qty,
+            PositionState::Short => -self.position_qty,
+            PositionState::Flat => 0.0,
+        };
+        reconcile(hbt, "Prediction", signed_position, realized_pnl + unrealized_pnl);
+    }
+
     fn calculate_position_metrics(&self, mid_price: f64) -> (f64, f64) {
         match self.position_state {
             PositionState::Long => {
                 let position_value = self.position_qty * mid_price;
-                let unrealized_pnl = (mid_price - self.entry_price) * self.position_qty;
+                let unrealized_pnl = ASSET_KIND.pnl(self.entry_price, mid_price, self.position_qty);
                 (position_value, unrealized_pnl)
             }
             PositionState::Short => {
                 let position_value = -self.position_qty * mid_price;
-                let unrealized_pnl = (self.entry_price - mid_price) * self.position_qty;
+                let unrealized_pnl = -ASSET_KIND.pnl(self.entry_price, mid_price, self.position_qty);
                 (position_value, unrealized_pnl)
             }
             PositionState::Flat => (0.0, 0.0),
         }
     }
 
-    fn should_close_position(&self, current_price: f64) -> bool {
+    /// 현재 포지션 수익률 (진입가 대비)
+    fn position_pnl_pct(&self, current_price: f64) -> f64 {
         if self.entry_price == 0.0 {
-            return false;
+            return 0.0;
         }
 
         match self.position_state {
-            PositionState::Long => {
-                let pnl_pct = (current_price - self.entry_price) / self.entry_price;
-                pnl_pct <= -self.stop_loss_pct || pnl_pct >= self.take_profit_pct
-            }
-            PositionState::Short => {
-                let pnl_pct = (self.entry_price - current_price) / self.entry_price;
-                pnl_pct <= -self.stop_loss_pct || pnl_pct >= self.take_profit_pct
-            }
-            PositionState::Flat => false,
+            PositionState::Long => (current_price - self.entry_price) / self.entry_price,
+            PositionState::Short => (self.entry_price - current_price) / self.entry_price,
+            PositionState::Flat => 0.0,
+        }
+    }
+
+    /// 수익률이 breakeven_trigger_pct를 넘으면 손절선을 본전(진입가 + 수수료)으로 이동
+    fn update_breakeven(&mut self, current_price: f64) {
+        let trigger = match self.breakeven_trigger_pct {
+            Some(trigger) => trigger,
+            None => return,
+        };
+
+        if !self.breakeven_armed
+            && self.position_state != PositionState::Flat
+            && self.position_pnl_pct(current_price) >= trigger
+        {
+            self.breakeven_armed = true;
+            log_info!("  🔒 Break-even stop armed");
+        }
+    }
+
+    /// 전략 틱마다 총자산을 성과보수 고점(high-water mark)에 반영
+    fn accrue_performance_fee<MD: MarketDepth>(&mut self, depth: &MD, cash: f64, realized_pnl: f64) {
+        let Some(mid_price) = calculate_mid_price(depth) else {
+            return;
+        };
+        let (position_value, _) = self.calculate_position_metrics(self.mark_pricer.mark(depth).unwrap_or(mid_price));
+        self.performance_fee.update(cash + realized_pnl + position_value - self.borrow_cost.accrued());
+    }
+
+    /// 현재 평가 기준으로 전체 실행 목표 수익/손실 한도에 도달했는지
+    fn run_target_reached<MD: MarketDepth>(&self, depth: &MD, realized_pnl: f64) -> Option<RunStopReason> {
+        let mark_price = calculate_mid_price(depth).and(self.mark_pricer.mark(depth))?;
+        let (_, unrealized_pnl) = self.calculate_position_metrics(mark_price);
+        let equity = self.initial_capital + realized_pnl + unrealized_pnl - self.borrow_cost.accrued();
+        self.run_target.check(self.initial_capital, equity)
+    }
+
+    /// 부호 있는 포지션 수량 (숏 = 음수)
+    fn signed_position(&self) -> f64 {
+        match self.position_state {
+            PositionState::Long => self.position_qty,
+            PositionState::Short => -self.position_qty,
+            PositionState::Flat => 0.0,
         }
     }
 
-    fn create_backtest(&self, data_file: &str) -> Result<Backtest<HashMapMarketDepth>> {
-        let latency_model = ConstantLatency::new(0, 0);
-        let queue_model = ProbQueueModel::new(PowerProbQueueFunc3::new(3.0));
-        let asset_type = LinearAsset::new(1.0);
-        let fee_model = TradingValueFeeModel::new(CommonFees::new(-0.00005, 0.0007));
+    /// 손절/익절 가격 (본전 손절이 활성화되면 손절가가 진입가 + 수수료로 이동)
+    fn exit_levels(&self) -> (Option<f64>, Option<f64>) {
+        if self.entry_price == 0.0 {
+            return (None, None);
+        }
+
+        let stop_pct = self.stop_pct();
+
+        match self.position_state {
+            PositionState::Long => (
+                Some(self.entry_price * (1.0 + stop_pct)),
+                Some(self.entry_price * (1.0 + self.take_profit_pct)),
+            ),
+            PositionState::Short => (
+                Some(self.entry_price * (1.0 - stop_pct)),
+                Some(self.entry_price * (1.0 - self.take_profit_pct)),
+            ),
+            PositionState::Flat => (None, None),
+        }
+    }
+
+    /// 손절 기준 수익률 (음수 = 손실 허용폭). 본전 손절이 활성화되면 진입/청산 수수료를
+    /// 모두 회수하는 수준, 아니면 `stop_kind`에 따른 거리
+    fn stop_pct(&self) -> f64 {
+        if self.breakeven_armed {
+            2.0 * ESTIMATED_FEE_RATE
+        } else {
+            -self.stop_kind.stop_pct(self.entry_price, self.stop_loss_pct, TICK_SIZE, self.atr.value())
+        }
+    }
+
+    fn should_close_position(&self, current_price: f64) -> bool {
+        if self.entry_price == 0.0 || self.position_state == PositionState::Flat {
+            return false;
+        }
+
+        let pnl_pct = self.position_pnl_pct(current_price);
+        let stop_pct = self.stop_pct();
+        // 수수료를 빼면 손실인 익절은 보류
+        let take_profit = pnl_pct >= self.take_profit_pct
+            && self.fees.clears_round_trip(pnl_pct, self.min_profit_over_fees);
+        pnl_pct <= stop_pct || take_profit
+    }
+
+    fn create_backtest(&self, data_file: &str, preloaded: Option<Vec<Event>>) -> Result<Backtest<HashMapMarketDepth>> {
+        let latency_model = ConstantLatency::new(TAKER_ORDER_LATENCY_NS, TAKER_ORDER_LATENCY_NS);
+        let queue_model = ProbQueueModel::new(PowerProbQueueFunc3::new(TAKER_QUEUE_POWER));
+        // The same flat rate the runner's own PnL charges, so `--reconcile` compares like with like
+        let fee_model = TradingValueFeeModel::new(CommonFees::new(ESTIMATED_FEE_RATE, ESTIMATED_FEE_RATE));
 
         let hbt = Backtest::builder()
             .add_asset(
                 L2AssetBuilder::new()
                     .data(vec![
-                        DataSource::File(data_file.to_string())
+                        data_source(data_file, preloaded)
                     ])
                     .latency_model(latency_model)
                     .queue_model(queue_model)
-                    .asset_type(asset_type)
+                    .asset_type(ASSET_KIND)
                     .fee_model(fee_model)
-                    .exchange(ExchangeKind::NoPartialFillExchange)
+                    .exchange(EXCHANGE_KIND)
+                    .last_trades_capacity(LAST_TRADES_CAPACITY)
                     .depth(|| HashMapMarketDepth::new(TICK_SIZE, LOT_SIZE))
                     .build()?,
             )
@@ -829,13 +1636,14 @@ impl PredictionRunner {
         Ok(hbt)
     }
 
-    fn print_final_stats<MD>(&self, realized_pnl: f64, cash: f64, depth: &MD)
+    fn print_final_stats<MD>(&mut self, realized_pnl: f64, cash: f64, depth: &MD)
     where
         MD: MarketDepth,
     {
-        let mid_price = calculate_mid_price(depth);
+        // 한쪽 호가가 비었으면 남은 포지션은 진입가로 평가
+        let mid_price = calculate_mid_price(depth).unwrap_or(self.entry_price);
         let (position_value, _) = self.calculate_position_metrics(mid_price);
-        let final_equity = cash + realized_pnl + position_value;
+        let final_equity = cash + realized_pnl + position_value - self.borrow_cost.accrued();
         let returns_pct = ((final_equity - self.initial_capital) / self.initial_capital) * 100.0;
         let win_rate = if self.num_trades > 0 {
             (self.winning_trades as f64 / self.num_trades as f64) * 100.0
@@ -847,23 +1655,322 @@ impl PredictionRunner {
         } else {
             0.0
         };
+        self.last_result = Some((prediction_accuracy / 100.0, final_equity - self.initial_capital));
 
-        println!("\n{}", "=".repeat(60));
-        println!("📊 ML PREDICTION STRATEGY FINAL STATISTICS");
-        println!("{}", "=".repeat(60));
-        println!("Initial Capital:     ${:.2}", self.initial_capital);
-        println!("Final Equity:        ${:.2}", final_equity);
-        println!("Total Returns:       {:.2}%", returns_pct);
-        println!("Realized P&L:        ${:.2}", realized_pnl);
-        println!("{}", "-".repeat(60));
-        println!("Total Trades:        {}", self.num_trades);
-        println!("Winning Trades:      {}", self.winning_trades);
-        println!("Win Rate:            {:.2}%", win_rate);
-        println!("{}", "-".repeat(60));
-        println!("🧠 MODEL PERFORMANCE");
-        println!("Training Samples:    {}", self.predictor.get_training_samples());
-        println!("Total Predictions:   {}", self.total_predictions);
-        println!("Prediction Accuracy: {:.2}%", prediction_accuracy);
-        println!("{}", "=".repeat(60));
+        log_info!("\n{}", "=".repeat(60));
+        log_info!("📊 ML PREDICTION STRATEGY FINAL STATISTICS");
+        log_info!("{}", "=".repeat(60));
+        log_info!("Initial Capital:     ${:.2}", self.initial_capital);
+        log_info!("Final Equity:        ${:.2}", final_equity);
+        log_info!("Total Returns:       {:.2}%", returns_pct);
+        log_info!("Realized P&L:        ${:.2}", realized_pnl);
+        log_info!("Borrow Costs:        ${:.2}", self.borrow_cost.accrued());
+        let fee_drag = FeeDrag::from_net(final_equity - self.initial_capital, self.fees.total_fees(), self.initial_capital);
+        log_info!("Fees Paid:           {}", fee_drag.summary());
+        log_info!("{}", "-".repeat(60));
+        log_info!("Total Trades:        {}", self.num_trades);
+        log_info!("Winning Trades:      {}", self.winning_trades);
+        log_info!("Win Rate:            {:.2}%", win_rate);
+        log_info!("{}", "-".repeat(60));
+        log_info!("🧠 MODEL PERFORMANCE");
+        log_info!("Training Samples:    {}", self.predictor.get_training_samples());
+        if self.predictor.size() > 1 {
+            log_info!("Ensemble Size:       {}", self.predictor.size());
+        }
+        if self.predictor.is_frozen() {
+            log_info!("Model:               frozen after warmup");
+        }
+        if PREDICTION_REGIME_ACCURACY_FLOOR.is_some() || PREDICTION_REGIME_VOLATILITY_JUMP.is_some() {
+            log_info!("Warm Restarts:       {}", self.warm_restarts);
+        }
+        if self.thin_book_ticks > 0 {
+            log_info!("Thin-Book Skips:     {} ticks", self.thin_book_ticks);
+        }
+        if self.exploration.explored() > 0 {
+            log_info!("Explored Entries:    {} (ε now {:.4})", self.exploration.explored(), self.exploration.epsilon());
+        }
+        if PREDICTION_EARLY_STOPPING_PATIENCE.is_some() {
+            log_info!("Early-Stopped:       {} batches{}", self.predictor.early_stopped_batches(),
+                      if self.predictor.is_training_halted() { " (halted)" } else { "" });
+        }
+        log_info!("Total Predictions:   {}", self.total_predictions);
+        log_info!("Prediction Accuracy: {:.2}%", prediction_accuracy);
+        log_info!("{}", "=".repeat(60));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hftbacktest::types::{
+        DEPTH_EVENT, BUY_EVENT, SELL_EVENT, EXCH_EVENT, LOCAL_EVENT, EXCH_BID_DEPTH_EVENT, LOCAL_BID_DEPTH_EVENT,
+    };
+    use crate::common::test_utils::{backtest_from_events, event, two_sided_book};
+
+    fn test_runner() -> PredictionRunner {
+        PredictionRunner::create_runner(
+            vec![PathBuf::from("unused.npz")], 0.01, 0.005, 0.01, 10_000.0, 0.001, 0.001,
+        ).unwrap()
+    }
+
+    #[test]
+    fn test_resting_exit_keeps_position_until_it_fills() {
+        // 로컬 호가에는 거래소에서 이미 사라진 100.0 매수호가가 남아 있어, 청산 매도는
+        // 2초에 100.0 매수호가가 다시 들어올 때까지 대기
+        let events = vec![
+            event(EXCH_BID_DEPTH_EVENT, 1_000, 99.0, 1.0),
+            event(LOCAL_BID_DEPTH_EVENT, 1_000, 100.0, 1.0),
+            event(DEPTH_EVENT | SELL_EVENT | EXCH_EVENT | LOCAL_EVENT, 1_000, 101.0, 1.0),
+            event(DEPTH_EVENT | BUY_EVENT | EXCH_EVENT | LOCAL_EVENT, 2_000_000_000, 100.0, 1.0),
+            event(DEPTH_EVENT | BUY_EVENT | EXCH_EVENT | LOCAL_EVENT, 10_000_000_000, 99.0, 1.0),
+        ];
+        let mut hbt = backtest_from_events(&events);
+        hbt.elapse(1_000_000).unwrap();
+
+        let mut runner = test_runner();
+        runner.position_state = PositionState::Long;
+        runner.entry_price = 99.0;
+        runner.position_qty = 0.05;
+        let mut realized_pnl = 0.0;
+
+        runner.close_position(&mut hbt, &mut realized_pnl).unwrap();
+        assert_eq!(runner.position_state, PositionState::Long);
+        assert_eq!(runner.position_qty, 0.05);
+        assert!(runner.pending_exit.is_some());
+        assert_eq!(runner.num_trades, 0);
+
+        hbt.elapse(2_000_000_000).unwrap();
+        let exit = runner.pending_exit.take().unwrap();
+        runner.resolve_exit(&mut hbt, exit, &mut realized_pnl).unwrap();
+        assert_eq!(runner.position_state, PositionState::Flat);
+        assert!((hbt.position(0) + 0.05).abs() < 1e-9);
+        assert_eq!(runner.num_trades, 1);
+        assert!(realized_pnl > 0.0);
+    }
+
+    #[test]
+    fn test_thin_side_skips_feature_extraction() {
+        let events = vec![
+            event(DEPTH_EVENT | BUY_EVENT | EXCH_EVENT | LOCAL_EVENT, 1_000, 1.00000, 1.0),
+            event(DEPTH_EVENT | BUY_EVENT | EXCH_EVENT | LOCAL_EVENT, 1_000, 0.99999, 1.0),
+            event(DEPTH_EVENT | BUY_EVENT | EXCH_EVENT | LOCAL_EVENT, 1_000, 0.99998, 1.0),
+            event(DEPTH_EVENT | SELL_EVENT | EXCH_EVENT | LOCAL_EVENT, 1_000, 1.00001, 1.0),
+            event(DEPTH_EVENT | SELL_EVENT | EXCH_EVENT | LOCAL_EVENT, 1_000, 1.00005, 1.0),
+            event(DEPTH_EVENT | BUY_EVENT | EXCH_EVENT | LOCAL_EVENT, 10_000_000_000, 1.00000, 1.0),
+        ];
+        let mut hbt = backtest_from_events(&events);
+        hbt.elapse(1_000_000).unwrap();
+
+        let mut runner = test_runner();
+        let (bids, asks) = runner.extract_levels(hbt.depth(0), 10);
+        assert_eq!((bids.len(), asks.len()), (3, 2));
+        assert!(runner.has_min_feature_levels(&bids, &asks));
+
+        // The ask side only has 2 real levels, the empty ticks in between don't count
+        runner.min_feature_levels = 3;
+        assert!(!runner.has_min_feature_levels(&bids, &asks));
+    }
+
+    #[test]
+    fn test_counters_reset_at_file_start_when_enabled() {
+        let mut runner = test_runner();
+        runner.reset_between_files = true;
+        runner.num_trades = 5;
+        runner.winning_trades = 3;
+        runner.total_orders = 10;
+        runner.total_fills = 8;
+        runner.total_predictions = 100;
+        runner.correct_predictions = 60;
+        runner.position_state = PositionState::Long;
+
+        runner.reset_for_new_file().unwrap();
+
+        assert_eq!(runner.num_trades, 0);
+        assert_eq!(runner.winning_trades, 0);
+        assert_eq!(runner.total_orders, 0);
+        assert_eq!(runner.total_fills, 0);
+        assert_eq!(runner.total_predictions, 0);
+        assert_eq!(runner.correct_predictions, 0);
+        assert_eq!(runner.predictor.get_training_samples(), 0);
+        assert_eq!(runner.position_state, PositionState::Flat);
+    }
+
+    #[test]
+    fn test_counters_kept_across_files_when_disabled() {
+        let mut runner = test_runner();
+        runner.reset_between_files = false;
+        runner.num_trades = 5;
+        runner.position_state = PositionState::Short;
+
+        runner.reset_for_new_file().unwrap();
+
+        assert_eq!(runner.num_trades, 5);
+        assert_eq!(runner.position_state, PositionState::Flat);
+    }
+
+    #[test]
+    fn test_continuous_mode_carries_position_over_file_boundary() {
+        let events = two_sided_book(1.00000, 1.00001, 1.0, [10_000_000_000]);
+        let open_long = |runner: &mut PredictionRunner| {
+            runner.position_state = PositionState::Long;
+            runner.entry_price = 1.0;
+            runner.position_qty = 0.01;
+            runner.num_trades = 3;
+            runner.has_next_file = true;
+        };
+
+        let mut runner = test_runner();
+        runner.file_mode = FileMode::Continuous;
+        open_long(&mut runner);
+        let mut hbt = backtest_from_events(&events);
+        hbt.elapse(1_000_000).unwrap();
+        runner.finish_file(&mut hbt, 12.5, 10_000.0).unwrap();
+        runner.reset_for_new_file().unwrap();
+
+        // No flatten order, the position and PnL are picked up by the next file
+        assert_eq!(runner.total_orders, 0);
+        assert_eq!(runner.position_state, PositionState::Long);
+        assert_eq!(runner.position_qty, 0.01);
+        assert_eq!(runner.num_trades, 3);
+        assert_eq!(runner.carried_pnl, Some(12.5));
+
+        // The last file still closes out
+        runner.carried_pnl = None;
+        runner.has_next_file = false;
+        let mut hbt = backtest_from_events(&events);
+        hbt.elapse(1_000_000).unwrap();
+        runner.finish_file(&mut hbt, 12.5, 10_000.0).unwrap();
+        assert_eq!(runner.carried_pnl, None);
+        assert!(runner.total_orders > 0);
+
+        // Independent mode starts the next file flat
+        let mut runner = test_runner();
+        open_long(&mut runner);
+        let mut hbt = backtest_from_events(&events);
+        hbt.elapse(1_000_000).unwrap();
+        runner.finish_file(&mut hbt, 12.5, 10_000.0).unwrap();
+        runner.reset_for_new_file().unwrap();
+        assert_eq!(runner.carried_pnl, None);
+        assert_eq!(runner.position_state, PositionState::Flat);
+    }
+
+    #[test]
+    fn test_breakeven_stop_closes_on_pullback_to_entry() {
+        let mut runner = test_runner();
+        runner.breakeven_trigger_pct = Some(0.002);
+        runner.position_state = PositionState::Long;
+        runner.entry_price = 100.0;
+        runner.position_qty = 0.01;
+
+        // 본전 손절 전에는 진입가로 되돌아와도 손절(-0.5%)까지 유지
+        assert!(!runner.should_close_position(100.0));
+
+        runner.update_breakeven(100.3);
+        assert!(runner.breakeven_armed);
+        assert!(!runner.should_close_position(100.3));
+
+        assert!(runner.should_close_position(100.0));
+    }
+
+    #[test]
+    fn test_small_opposite_prediction_keeps_position() {
+        let mut hbt = backtest_from_events(&two_sided_book(99.99, 100.01, 10.0, [10_000_000_000]));
+        hbt.elapse(1_000_000).unwrap();
+
+        let mut runner = test_runner();
+        runner.exit_threshold = 0.003;
+        runner.predictor = PredictorEnsemble::new(1, 0.001, 0.003, &FeatureConfig::default()).unwrap();
+        runner.position_state = PositionState::Long;
+        runner.entry_price = 100.0;
+        runner.position_qty = 0.01;
+        let mut realized_pnl = 0.0;
+
+        // 진입 임계값은 넘지만 청산 임계값에는 못 미치는 반대 예측
+        runner.execute_strategy(&mut hbt, &mut realized_pnl, PredictionSignal::Down, -0.002, 1_000_000).unwrap();
+        assert_eq!(runner.position_state, PositionState::Long);
+        assert_eq!(runner.total_orders, 0);
+
+        runner.execute_strategy(&mut hbt, &mut realized_pnl, PredictionSignal::Down, -0.004, 1_000_000).unwrap();
+        assert_eq!(runner.position_state, PositionState::Flat);
+        assert_eq!(runner.num_trades, 1);
+    }
+
+    #[test]
+    fn test_long_closes_when_prediction_edge_fades() {
+        let mut hbt = backtest_from_events(&two_sided_book(99.99, 100.01, 10.0, [10_000_000_000]));
+        hbt.elapse(1_000_000).unwrap();
+
+        let mut runner = test_runner();
+        runner.edge_exit_threshold = Some(0.001);
+        runner.position_state = PositionState::Long;
+        runner.entry_price = 100.0;
+        runner.position_qty = 0.01;
+        let mut realized_pnl = 0.0;
+
+        // 상승 예측이 0을 향해 약해지는 동안 임계값 전까지는 유지
+        for prediction in [0.003, 0.002, 0.0012] {
+            runner.execute_strategy(&mut hbt, &mut realized_pnl, PredictionSignal::Up, prediction, 1_000_000).unwrap();
+            assert_eq!(runner.position_state, PositionState::Long);
+        }
+        assert_eq!(runner.total_orders, 0);
+
+        // 여전히 양수(반전 아님)지만 임계값 아래
+        runner.execute_strategy(&mut hbt, &mut realized_pnl, PredictionSignal::Neutral, 0.0008, 1_000_000).unwrap();
+        assert_eq!(runner.position_state, PositionState::Flat);
+        assert_eq!(runner.num_trades, 1);
+    }
+
+    #[test]
+    fn test_one_tick_spike_does_not_trade_with_signal_delay() {
+        let mut hbt = backtest_from_events(&two_sided_book(99.99, 100.01, 10.0, [10_000_000_000]));
+        hbt.elapse(1_000_000).unwrap();
+
+        let mut runner = test_runner();
+        runner.signal_delay = SignalDelay::new(2, PredictionSignal::Neutral);
+        let mut realized_pnl = 0.0;
+
+        // UP for one tick, then reversed
+        for (signal, prediction) in [
+            (PredictionSignal::Up, 0.002),
+            (PredictionSignal::Down, -0.002),
+            (PredictionSignal::Neutral, 0.0),
+        ] {
+            runner.execute_strategy(&mut hbt, &mut realized_pnl, signal, prediction, 1_000_000).unwrap();
+        }
+        assert_eq!(runner.total_orders, 0);
+        assert_eq!(runner.position_state, PositionState::Flat);
+
+        // Held for the full delay: enters on the second confirming tick
+        for _ in 0..3 {
+            runner.execute_strategy(&mut hbt, &mut realized_pnl, PredictionSignal::Up, 0.002, 1_000_000).unwrap();
+        }
+        assert_eq!(runner.total_orders, 1);
+    }
+
+    #[test]
+    fn test_frozen_model_stops_learning_after_warmup() {
+        use super::super::orderbook_features::{OrderBookFeatures, sample_features};
+        let features = |imbalance| OrderBookFeatures { cvd: Some(0.0), ..sample_features(imbalance) };
+
+        let mut runner = test_runner();
+        runner.freeze_after_warmup = true;
+        runner.warmup_samples = 8;
+        for i in 0..8 {
+            runner.predictor.add_training_sample(&features(i as f64 / 10.0), i as f64 * 0.01);
+            runner.predictor.predict(&features(i as f64 / 10.0)).unwrap();
+        }
+        runner.check_warmup();
+        assert!(runner.is_warmed_up);
+        assert!(runner.predictor.is_frozen());
+
+        // 웜업 이후 샘플과 배치 학습은 무시되고 같은 입력은 같은 예측
+        let (before, _) = runner.predictor.predict(&features(0.3)).unwrap();
+        for i in 0..40 {
+            runner.predictor.add_training_sample(&features(i as f64 / 40.0), 0.05);
+        }
+        assert_eq!(runner.predictor.train_batch(8, 0.1).unwrap(), 0.0);
+        assert_eq!(runner.predictor.get_training_samples(), 8);
+        let (after, _) = runner.predictor.predict(&features(0.3)).unwrap();
+        assert_eq!(before, after);
     }
 }
@@ -1,8 +1,9 @@
-use anyhow::Result;
+use anyhow::{bail, Result};
 use candle_core::{Device, Tensor, DType};
 use candle_nn::{Linear, Module, VarBuilder, VarMap, Optimizer, AdamW, ParamsAdamW, linear};
+use rand::{rngs::StdRng, Rng, SeedableRng};
 use std::collections::VecDeque;
-use super::orderbook_features::OrderBookFeatures;
+use super::orderbook_features::{OrderBookFeatures, FeatureConfig};
 
 /// 예측 신호
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -15,6 +16,17 @@ pub enum PredictionSignal {
     Neutral,
 }
 
+impl PredictionSignal {
+    /// `--signal-log` CSV에 기록되는 신호 이름
+    pub fn label(&self) -> &'static str {
+        match self {
+            PredictionSignal::Up => "up",
+            PredictionSignal::Down => "down",
+            PredictionSignal::Neutral => "neutral",
+        }
+    }
+}
+
 /// 학습 샘플
 #[derive(Debug, Clone)]
 struct TrainingSample {
@@ -22,10 +34,55 @@ struct TrainingSample {
     target: f64, // 1초 후 가격 변화율
 }
 
+/// 학습 전 손실(아직 학습하지 않은 최근 배치의 손실)로 판단하는 조기 종료
+///
+/// `patience` 배치 연속으로 최저 손실을 갱신하지 못하면 학습을 멈추고,
+/// 멈춘 뒤 손실이 최저치보다 나빠지면 다시 `patience` 배치 동안 학습을 재개한다.
+#[derive(Debug, Clone)]
+pub struct EarlyStopping {
+    patience: usize,
+    best_loss: Option<f64>,
+    stale_batches: usize,
+}
+
+impl EarlyStopping {
+    pub fn new(patience: usize) -> Self {
+        Self {
+            patience: patience.max(1),
+            best_loss: None,
+            stale_batches: 0,
+        }
+    }
+
+    /// 이번 배치의 학습 전 손실을 기록하고 학습 여부를 반환
+    pub fn should_train(&mut self, val_loss: f64) -> bool {
+        match self.best_loss {
+            Some(best) if val_loss >= best => {
+                if self.is_halted() && val_loss > best {
+                    // 멈춘 뒤 손실이 악화되면 재개
+                    self.stale_batches = 0;
+                    return true;
+                }
+                self.stale_batches += 1;
+                !self.is_halted()
+            }
+            _ => {
+                self.best_loss = Some(val_loss);
+                self.stale_batches = 0;
+                true
+            }
+        }
+    }
+
+    pub fn is_halted(&self) -> bool {
+        self.stale_batches >= self.patience
+    }
+}
+
 /// MLP 기반 가격 예측 모델
 /// 
 /// 아키텍처:
-/// - Input: 오더북 특성 벡터 (8차원)
+/// - Input: 오더북 특성 벡터 (8차원, CVD 포함 시 9차원)
 /// - Hidden1: 32 neurons + ReLU
 /// - Hidden2: 16 neurons + ReLU
 /// - Output: 1 (가격 변화 예측)
@@ -52,25 +109,46 @@ pub struct PricePredictor {
     // 학습 통계
     total_predictions: usize,
     correct_predictions: usize,
+    /// 마지막 배치 학습의 MSE 손실 (학습 전이면 None)
+    last_loss: Option<f64>,
+    /// NaN/Inf 손실 또는 예측으로 건너뛴 업데이트 수 (전체 / 마지막 정상 학습 이후 연속)
+    bad_updates: usize,
+    consecutive_bad_updates: usize,
+    /// 조기 종료 (None이면 모든 배치 학습)
+    early_stopping: Option<EarlyStopping>,
+    /// 조기 종료로 건너뛴 배치 수
+    early_stopped_batches: usize,
+    /// 웜 리스타트 학습률 배수와 남은 배치 수 (None이면 기본 학습률)
+    lr_boost: Option<(f64, usize)>,
     
-    // 예측 임계값
-    prediction_threshold: f64,
+    // 예측 임계값 (진입 / 반대 신호 청산)
+    entry_threshold: f64,
+    exit_threshold: f64,
     
     // 특성 정규화 파라미터
     feature_means: Vec<f64>,
     feature_stds: Vec<f64>,
     normalization_samples: usize,
+    /// 고정된 모델: 학습 샘플, 배치 학습, 정규화 갱신을 모두 건너뜀
+    frozen: bool,
 }
 
 #[allow(dead_code)]
 impl PricePredictor {
     /// 새 예측 모델 생성
-    pub fn new(prediction_threshold: f64) -> Result<Self> {
+    ///
+    /// `exit_threshold`는 보유 포지션을 반대 신호로 청산할 때 필요한 예측 크기로,
+    /// 작은 예측 변화에 포지션이 뒤집히지 않도록 `entry_threshold` 이상이어야 한다.
+    pub fn new(entry_threshold: f64, exit_threshold: f64, feature_config: &FeatureConfig) -> Result<Self> {
+        if exit_threshold < entry_threshold {
+            anyhow::bail!("Exit threshold ({}) must be at least the entry threshold ({})",
+                          exit_threshold, entry_threshold);
+        }
         let device = Device::Cpu;
         let varmap = VarMap::new();
         let vs = VarBuilder::from_varmap(&varmap, DType::F32, &device);
         
-        let input_dim = OrderBookFeatures::feature_dim();
+        let input_dim = OrderBookFeatures::feature_dim(feature_config);
         let hidden1_dim = 32;
         let hidden2_dim = 16;
         
@@ -93,13 +171,48 @@ impl PricePredictor {
             prediction_history: VecDeque::with_capacity(100),
             total_predictions: 0,
             correct_predictions: 0,
-            prediction_threshold,
+            last_loss: None,
+            bad_updates: 0,
+            consecutive_bad_updates: 0,
+            early_stopping: None,
+            early_stopped_batches: 0,
+            lr_boost: None,
+            entry_threshold,
+            exit_threshold,
             feature_means: vec![0.0; input_dim],
             feature_stds: vec![1.0; input_dim],
             normalization_samples: 0,
+            frozen: false,
         })
     }
 
+    /// 시드로 가중치 재초기화 (Xavier 균등 분포, 편향 0): 같은 시드면 같은 초기 모델
+    pub fn reseed(&mut self, seed: u64) -> Result<()> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let vars = self.varmap.data().lock().unwrap_or_else(|e| e.into_inner());
+        // HashMap 순회 순서는 실행마다 다르므로 이름순으로 채움
+        let mut names: Vec<&String> = vars.keys().collect();
+        names.sort();
+        for name in names {
+            let var = &vars[name];
+            let values: Vec<f32> = match *var.dims() {
+                [fan_out, fan_in] => {
+                    let bound = (6.0 / (fan_in + fan_out) as f64).sqrt();
+                    (0..fan_out * fan_in).map(|_| rng.gen_range(-bound..bound) as f32).collect()
+                }
+                ref dims => vec![0.0; dims.iter().product()],
+            };
+            var.set(&Tensor::from_vec(values, var.dims(), &self.device)?)?;
+        }
+        Ok(())
+    }
+
+    /// 조기 종료 설정 (`patience` 배치 동안 손실 개선이 없으면 학습 중단, None이면 끔)
+    pub fn with_early_stopping(mut self, patience: Option<usize>) -> Self {
+        self.early_stopping = patience.map(EarlyStopping::new);
+        self
+    }
+
     /// Forward pass
     fn forward(&self, x: &Tensor) -> Result<Tensor> {
         let x = self.fc1.forward(x)?;
@@ -141,8 +254,10 @@ impl PricePredictor {
     pub fn predict(&mut self, features: &OrderBookFeatures) -> Result<(f64, PredictionSignal)> {
         let feature_vec = features.to_vec();
         
-        // 정규화 파라미터 업데이트
-        self.update_normalization(&feature_vec);
+        // 정규화 파라미터 업데이트 (고정된 모델은 통계도 고정)
+        if !self.frozen {
+            self.update_normalization(&feature_vec);
+        }
         
         // 특성 정규화
         let normalized = self.normalize_features(&feature_vec);
@@ -155,13 +270,17 @@ impl PricePredictor {
         // 예측
         let output = self.forward(&input)?;
         let prediction = output.squeeze(0)?.squeeze(0)?.to_scalar::<f32>()? as f64;
+        if !prediction.is_finite() {
+            self.record_bad_update();
+            bail!("Non-finite prediction ({})", prediction);
+        }
         
         self.total_predictions += 1;
         
         // 신호 생성
-        let signal = if prediction > self.prediction_threshold {
+        let signal = if prediction > self.entry_threshold {
             PredictionSignal::Up
-        } else if prediction < -self.prediction_threshold {
+        } else if prediction < -self.entry_threshold {
             PredictionSignal::Down
         } else {
             PredictionSignal::Neutral
@@ -170,8 +289,20 @@ impl PricePredictor {
         Ok((prediction, signal))
     }
 
+    /// 보유 포지션 반대 방향으로 `exit_threshold`보다 강한 예측이면 청산 신호
+    pub fn is_exit_signal(&self, prediction: f64, is_long: bool) -> bool {
+        if is_long {
+            prediction < -self.exit_threshold
+        } else {
+            prediction > self.exit_threshold
+        }
+    }
+
     /// 학습 샘플 추가 (1초 후 실제 가격 변화와 함께)
     pub fn add_training_sample(&mut self, features: &OrderBookFeatures, price_change_pct: f64) {
+        if self.frozen {
+            return;
+        }
         let sample = TrainingSample {
             features: features.to_vec(),
             target: price_change_pct,
@@ -194,7 +325,7 @@ impl PricePredictor {
 
     /// 배치 학습 수행
     pub fn train_batch(&mut self, batch_size: usize, learning_rate: f64) -> Result<f64> {
-        if self.training_buffer.len() < batch_size {
+        if self.frozen || self.training_buffer.len() < batch_size {
             return Ok(0.0);
         }
 
@@ -225,7 +356,7 @@ impl PricePredictor {
 
         // 옵티마이저 설정
         let params = ParamsAdamW {
-            lr: learning_rate,
+            lr: self.effective_learning_rate(learning_rate),
             ..Default::default()
         };
         let mut optimizer = AdamW::new(self.varmap.all_vars(), params)?;
@@ -238,12 +369,50 @@ impl PricePredictor {
         let loss = diff.sqr()?.mean_all()?;
         let loss_val = loss.to_scalar::<f32>()? as f64;
 
+        // NaN/Inf 손실로 역전파하면 가중치가 모두 오염되므로 업데이트를 건너뜀
+        if !loss_val.is_finite() {
+            self.record_bad_update();
+            bail!("Non-finite training loss ({}), update skipped", loss_val);
+        }
+
+        // 업데이트 전 손실을 검증 손실로 사용해 조기 종료 판단
+        if let Some(early_stopping) = self.early_stopping.as_mut() {
+            if !early_stopping.should_train(loss_val) {
+                self.early_stopped_batches += 1;
+                return Ok(loss_val);
+            }
+        }
+
         // Backward pass
         optimizer.backward_step(&loss)?;
+        self.consume_lr_boost();
+        self.last_loss = Some(loss_val);
+        self.consecutive_bad_updates = 0;
 
         Ok(loss_val)
     }
 
+    /// 웜 리스타트: 다음 `batches`번의 학습 동안 학습률에 `multiplier`를 곱함
+    pub fn boost_learning_rate(&mut self, multiplier: f64, batches: usize) {
+        self.lr_boost = (batches > 0).then_some((multiplier, batches));
+    }
+
+    /// 현재 부스트를 반영한 학습률
+    pub fn effective_learning_rate(&self, learning_rate: f64) -> f64 {
+        self.lr_boost.map_or(learning_rate, |(multiplier, _)| learning_rate * multiplier)
+    }
+
+    fn consume_lr_boost(&mut self) {
+        if let Some((multiplier, remaining)) = self.lr_boost {
+            self.lr_boost = (remaining > 1).then_some((multiplier, remaining - 1));
+        }
+    }
+
+    /// 정규화 통계의 유효 표본 수를 `max_samples`로 줄여 새 장세에 빠르게 적응
+    pub fn shrink_normalization_window(&mut self, max_samples: usize) {
+        self.normalization_samples = self.normalization_samples.min(max_samples);
+    }
+
     /// 온라인 학습 (한 샘플씩)
     pub fn online_train(&mut self, features: &OrderBookFeatures, target: f64, learning_rate: f64) -> Result<f64> {
         self.add_training_sample(features, target);
@@ -264,6 +433,36 @@ impl PricePredictor {
         self.correct_predictions as f64 / self.total_predictions as f64
     }
 
+    /// 마지막 배치 학습 손실
+    pub fn last_loss(&self) -> Option<f64> {
+        self.last_loss
+    }
+
+    fn record_bad_update(&mut self) {
+        self.bad_updates += 1;
+        self.consecutive_bad_updates += 1;
+    }
+
+    /// 비정상 값으로 건너뛴 업데이트 총 수
+    pub fn bad_updates(&self) -> usize {
+        self.bad_updates
+    }
+
+    /// 마지막 정상 학습 이후 연속으로 건너뛴 업데이트 수
+    pub fn consecutive_bad_updates(&self) -> usize {
+        self.consecutive_bad_updates
+    }
+
+    /// 조기 종료로 학습이 멈춰 있는지
+    pub fn is_training_halted(&self) -> bool {
+        self.early_stopping.as_ref().is_some_and(EarlyStopping::is_halted)
+    }
+
+    /// 조기 종료로 건너뛴 배치 수
+    pub fn early_stopped_batches(&self) -> usize {
+        self.early_stopped_batches
+    }
+
     /// 통계 초기화
     #[allow(dead_code)]
     pub fn reset_stats(&mut self) {
@@ -272,6 +471,15 @@ impl PricePredictor {
         self.prediction_history.clear();
     }
 
+    /// 가중치와 정규화 통계를 현재 값으로 고정 (같은 입력이면 항상 같은 예측)
+    pub fn freeze(&mut self) {
+        self.frozen = true;
+    }
+
+    pub fn is_frozen(&self) -> bool {
+        self.frozen
+    }
+
     /// 학습 샘플 수 반환
     pub fn get_training_samples(&self) -> usize {
         self.training_buffer.len()
@@ -309,33 +517,133 @@ impl PricePredictor {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use super::super::orderbook_features::sample_features;
 
     #[test]
     fn test_predictor_creation() {
-        let predictor = PricePredictor::new(0.001);
+        let predictor = PricePredictor::new(0.001, 0.001, &FeatureConfig::default());
         assert!(predictor.is_ok());
     }
 
     #[test]
     fn test_prediction() {
-        let mut predictor = PricePredictor::new(0.001).unwrap();
+        let mut predictor = PricePredictor::new(0.001, 0.001, &FeatureConfig::default()).unwrap();
         
-        let features = OrderBookFeatures {
-            mid_price: 100.0,
-            spread_bps: 5.0,
-            weighted_mid_price: 100.0,
-            imbalance_level1: 0.1,
-            imbalance_multi_level: 0.05,
-            bid_pressure: 1000.0,
-            ask_pressure: 900.0,
-            pressure_ratio: 0.1,
-            price_change_pct: 0.01,
-            volatility: 10.0,
-            volume_weighted_spread: 5.0,
-            trade_intensity: 0.02,
-        };
+        let features = sample_features(0.1);
 
         let result = predictor.predict(&features);
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn test_early_stopping_halts_on_flat_loss_and_resumes_on_degrade() {
+        let mut early_stopping = EarlyStopping::new(3);
+        let trained = (0..10).filter(|_| early_stopping.should_train(0.5)).count();
+        // 첫 배치 + 개선 없는 patience - 1 배치까지만 학습
+        assert_eq!(trained, 3);
+        assert!(early_stopping.is_halted());
+
+        // 악화되면 재개, 다시 patience 후 정지
+        assert!(early_stopping.should_train(0.8));
+        assert!(!early_stopping.is_halted());
+        assert!(early_stopping.should_train(0.5));
+        assert!(early_stopping.should_train(0.5));
+        assert!(!early_stopping.should_train(0.5));
+
+        // 개선되면 카운트 초기화
+        assert!(early_stopping.should_train(0.4));
+        assert!(!early_stopping.is_halted());
+    }
+
+    #[test]
+    fn test_lag_stack_triples_input_dim() {
+        use super::super::orderbook_features::{Level, OrderBookFeatureExtractor};
+
+        let config = FeatureConfig { lag_depth: 2, ..FeatureConfig::default() };
+        let mut extractor = OrderBookFeatureExtractor::with_config(5, 100, config.clone());
+        let mut predictor = PricePredictor::new(0.001, 0.001, &config).unwrap();
+
+        let single_dim = OrderBookFeatures::feature_dim(&FeatureConfig::default());
+        assert_eq!(OrderBookFeatures::feature_dim(&config), 3 * single_dim);
+        assert_eq!(predictor.input_dim, 3 * single_dim);
+
+        let asks = vec![Level { price: 101.0, quantity: 15.0 }];
+        let mut snapshots = Vec::new();
+        for qty in [10.0, 20.0, 30.0] {
+            let bids = vec![Level { price: 100.0, quantity: qty }];
+            let features = extractor.extract(&bids, &asks).unwrap();
+            assert_eq!(features.to_vec().len(), 3 * single_dim);
+            assert!(predictor.predict(&features).is_ok());
+            snapshots.push(features.snapshot_vec());
+        }
+
+        // 지연 특성은 1틱 전, 2틱 전 스냅샷 순서
+        let last = extractor.extract(&[Level { price: 100.0, quantity: 40.0 }], &asks).unwrap();
+        assert_eq!(&last.lagged[..single_dim], &snapshots[2][..]);
+        assert_eq!(&last.lagged[single_dim..], &snapshots[1][..]);
+    }
+
+    #[test]
+    fn test_last_loss_tracks_latest_batch() {
+        let mut predictor = PricePredictor::new(0.001, 0.001, &FeatureConfig::default()).unwrap();
+        assert_eq!(predictor.last_loss(), None);
+
+        for i in 0..8 {
+            predictor.add_training_sample(&sample_features(i as f64 / 10.0), i as f64 * 0.01);
+        }
+
+        // 샘플 부족으로 학습하지 않은 배치는 손실을 갱신하지 않음
+        assert_eq!(predictor.train_batch(16, 0.001).unwrap(), 0.0);
+        assert_eq!(predictor.last_loss(), None);
+
+        let first = predictor.train_batch(8, 0.001).unwrap();
+        assert_eq!(predictor.last_loss(), Some(first));
+        let second = predictor.train_batch(8, 0.001).unwrap();
+        assert_eq!(predictor.last_loss(), Some(second));
+    }
+
+    #[test]
+    fn test_nan_loss_skips_update_and_counts_error() {
+        let mut predictor = PricePredictor::new(0.001, 0.001, &FeatureConfig::default()).unwrap();
+        let features = sample_features(0.2);
+        // 정규화 통계가 바뀌지 않도록 고정 입력으로 가중치 출력만 비교
+        let input = Tensor::new(&vec![0.5f32; predictor.input_dim][..], &predictor.device).unwrap()
+            .reshape((1, predictor.input_dim)).unwrap();
+        let output = |predictor: &PricePredictor| {
+            predictor.forward(&input).unwrap().squeeze(0).unwrap().squeeze(0).unwrap().to_scalar::<f32>().unwrap()
+        };
+        let before = output(&predictor);
+
+        for _ in 0..8 {
+            predictor.add_training_sample(&features, f64::NAN);
+        }
+        assert!(predictor.train_batch(8, 0.001).is_err());
+        assert!(predictor.train_batch(8, 0.001).is_err());
+        assert_eq!(output(&predictor), before);
+        assert_eq!(predictor.bad_updates(), 2);
+        assert_eq!(predictor.consecutive_bad_updates(), 2);
+        assert_eq!(predictor.last_loss(), None);
+
+        // 정상 배치가 학습되면 연속 카운트만 초기화
+        for _ in 0..8 {
+            predictor.add_training_sample(&features, 0.01);
+        }
+        assert!(predictor.train_batch(8, 0.001).unwrap().is_finite());
+        assert_ne!(output(&predictor), before);
+        assert_eq!(predictor.bad_updates(), 2);
+        assert_eq!(predictor.consecutive_bad_updates(), 0);
+    }
+
+    #[test]
+    fn test_exit_threshold_hysteresis() {
+        assert!(PricePredictor::new(0.002, 0.001, &FeatureConfig::default()).is_err());
+
+        let predictor = PricePredictor::new(0.001, 0.003, &FeatureConfig::default()).unwrap();
+        assert!(!predictor.is_exit_signal(-0.002, true));
+        assert!(predictor.is_exit_signal(-0.004, true));
+        assert!(!predictor.is_exit_signal(0.002, false));
+        assert!(predictor.is_exit_signal(0.004, false));
+        // 같은 방향 예측은 청산하지 않음
+        assert!(!predictor.is_exit_signal(0.01, true));
+    }
 }
@@ -0,0 +1,125 @@
+use std::collections::VecDeque;
+
+/// 기준 변동성 EWMA 가중치 (느리게 따라가 급등을 장세 변화로 판단)
+const BASELINE_VOLATILITY_ALPHA: f64 = 0.001;
+
+/// 장세 변화 감지기: 최근 방향 정확도가 하한 아래로 떨어지거나
+/// 실현 변동성이 기준치의 `volatility_jump`배를 넘으면 웜 리스타트 신호
+#[derive(Debug, Clone)]
+pub struct RegimeChangeDetector {
+    accuracy_floor: Option<f64>,
+    accuracy_window: usize,
+    volatility_jump: Option<f64>,
+    /// 최근 예측의 방향 적중 여부
+    outcomes: VecDeque<bool>,
+    baseline_volatility: Option<f64>,
+    /// 변동성 급등으로 이미 신호를 냈는지 (기준치 근처로 돌아오면 해제)
+    volatility_latched: bool,
+}
+
+impl RegimeChangeDetector {
+    /// 두 조건 모두 None이면 감지하지 않음
+    pub fn new(accuracy_floor: Option<f64>, accuracy_window: usize, volatility_jump: Option<f64>) -> Self {
+        Self {
+            accuracy_floor,
+            accuracy_window: accuracy_window.max(1),
+            volatility_jump,
+            outcomes: VecDeque::new(),
+            baseline_volatility: None,
+            volatility_latched: false,
+        }
+    }
+
+    /// 검증된 예측의 방향 적중 여부 기록, 윈도 정확도가 하한 아래면 true
+    pub fn record_outcome(&mut self, hit: bool) -> bool {
+        let Some(floor) = self.accuracy_floor else {
+            return false;
+        };
+        self.outcomes.push_back(hit);
+        if self.outcomes.len() > self.accuracy_window {
+            self.outcomes.pop_front();
+        }
+        if self.outcomes.len() < self.accuracy_window {
+            return false;
+        }
+
+        let accuracy = self.outcomes.iter().filter(|&&hit| hit).count() as f64 / self.outcomes.len() as f64;
+        if accuracy < floor {
+            // 같은 구간으로 다시 신호를 내지 않도록 윈도 초기화
+            self.outcomes.clear();
+            return true;
+        }
+        false
+    }
+
+    /// 현재 실현 변동성 관측, 기준치 대비 급등이면 true
+    pub fn observe_volatility(&mut self, volatility: f64) -> bool {
+        let Some(jump) = self.volatility_jump else {
+            return false;
+        };
+        if !volatility.is_finite() {
+            return false;
+        }
+        let baseline = *self.baseline_volatility.get_or_insert(volatility);
+        let spiked = baseline > 0.0 && volatility > baseline * jump;
+        self.baseline_volatility = Some(baseline + BASELINE_VOLATILITY_ALPHA * (volatility - baseline));
+
+        if spiked && !self.volatility_latched {
+            self.volatility_latched = true;
+            return true;
+        }
+        if !spiked {
+            self.volatility_latched = false;
+        }
+        false
+    }
+
+    pub fn reset(&mut self) {
+        self.outcomes.clear();
+        self.baseline_volatility = None;
+        self.volatility_latched = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::orderbook_features::{FeatureConfig, sample_features};
+    use super::super::price_predictor::PricePredictor;
+
+    #[test]
+    fn test_accuracy_drop_boosts_learning_rate_for_bounded_batches() {
+        let mut detector = RegimeChangeDetector::new(Some(0.5), 4, None);
+        assert!(!detector.record_outcome(true));
+        assert!(!detector.record_outcome(false));
+        assert!(!detector.record_outcome(true));
+        // 윈도 정확도 50%는 하한 이상
+        assert!(!detector.record_outcome(false));
+        assert!(detector.record_outcome(false));
+
+        let mut predictor = PricePredictor::new(0.001, 0.001, &FeatureConfig::default()).unwrap();
+        assert_eq!(predictor.effective_learning_rate(0.001), 0.001);
+        predictor.boost_learning_rate(3.0, 2);
+        assert!((predictor.effective_learning_rate(0.001) - 0.003).abs() < 1e-12);
+
+        for i in 0..8 {
+            predictor.add_training_sample(&sample_features(i as f64 / 10.0), i as f64 * 0.01);
+        }
+        predictor.train_batch(8, 0.001).unwrap();
+        assert!((predictor.effective_learning_rate(0.001) - 0.003).abs() < 1e-12);
+        predictor.train_batch(8, 0.001).unwrap();
+        assert_eq!(predictor.effective_learning_rate(0.001), 0.001);
+    }
+
+    #[test]
+    fn test_volatility_spike_signals_once() {
+        let mut detector = RegimeChangeDetector::new(None, 10, Some(3.0));
+        for _ in 0..10 {
+            assert!(!detector.observe_volatility(10.0));
+        }
+        assert!(detector.observe_volatility(40.0));
+        assert!(!detector.observe_volatility(40.0));
+        assert!(!detector.observe_volatility(10.0));
+        assert!(detector.observe_volatility(40.0));
+    }
+}
@@ -0,0 +1,100 @@
+use anyhow::Result;
+
+use crate::config::{PREDICTION_POSITION_SIZE, PREDICTION_STOP_LOSS_PCT, PREDICTION_TAKE_PROFIT_PCT,
+    INITIAL_CAPITAL, PREDICTION_CONFIDENCE_THRESHOLD, PREDICTION_LEARNING_RATE, PREDICTION_SEED};
+use super::PredictionRunner;
+
+/// 시드 하나로 돌린 결과
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SeedResult {
+    pub seed: u64,
+    /// 예측 정확도 (0~1)
+    pub accuracy: f64,
+    /// 최종 자산 - 초기 자본
+    pub pnl: f64,
+}
+
+/// 시드별 결과의 평균/표준편차 (표본 표준편차, 시드 하나면 0)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SeedCvSummary {
+    pub runs: usize,
+    pub accuracy_mean: f64,
+    pub accuracy_std: f64,
+    pub pnl_mean: f64,
+    pub pnl_std: f64,
+}
+
+impl SeedCvSummary {
+    /// 결과가 없으면 None
+    pub fn from_results(results: &[SeedResult]) -> Option<Self> {
+        if results.is_empty() {
+            return None;
+        }
+        let (accuracy_mean, accuracy_std) = mean_std(results.iter().map(|r| r.accuracy));
+        let (pnl_mean, pnl_std) = mean_std(results.iter().map(|r| r.pnl));
+        Some(Self { runs: results.len(), accuracy_mean, accuracy_std, pnl_mean, pnl_std })
+    }
+}
+
+fn mean_std(values: impl Iterator<Item = f64> + Clone) -> (f64, f64) {
+    let n = values.clone().count() as f64;
+    let mean = values.clone().sum::<f64>() / n;
+    if n < 2.0 {
+        return (mean, 0.0);
+    }
+    let variance = values.map(|v| (v - mean).powi(2)).sum::<f64>() / (n - 1.0);
+    (mean, variance.sqrt())
+}
+
+/// 같은 파일을 `n_seeds`개의 시드로 GUI 없이 돌려 초기화에 따른 결과 편차 출력
+/// (PREDICTION_SEED부터 차례로, None이면 0부터)
+pub fn run_seed_cv(data_file: &str, n_seeds: u64) -> Result<SeedCvSummary> {
+    let first_seed = PREDICTION_SEED.unwrap_or(0);
+    let mut results = Vec::new();
+    for seed in first_seed..first_seed + n_seeds {
+        let mut runner = PredictionRunner::new_with_files(
+            vec![data_file.to_string()],
+            PREDICTION_POSITION_SIZE, PREDICTION_STOP_LOSS_PCT, PREDICTION_TAKE_PROFIT_PCT,
+            INITIAL_CAPITAL, PREDICTION_CONFIDENCE_THRESHOLD, PREDICTION_LEARNING_RATE,
+        )?.with_seed(seed)?;
+        runner.run_headless()?;
+        let Some((accuracy, pnl)) = runner.last_result() else {
+            anyhow::bail!("Seed {} produced no result", seed);
+        };
+        println!("Seed {:>4}: accuracy {:.2}%, PnL ${:.2}", seed, accuracy * 100.0, pnl);
+        results.push(SeedResult { seed, accuracy, pnl });
+    }
+
+    let summary = SeedCvSummary::from_results(&results)
+        .ok_or_else(|| anyhow::anyhow!("--ml-cv needs at least one seed"))?;
+    println!("\n{}", "=".repeat(60));
+    println!("🎲 SEED CROSS-VALIDATION ({} seeds)", summary.runs);
+    println!("  Accuracy: {:.2}% ± {:.2}%", summary.accuracy_mean * 100.0, summary.accuracy_std * 100.0);
+    println!("  PnL:      ${:.2} ± ${:.2}", summary.pnl_mean, summary.pnl_std);
+    println!("{}", "=".repeat(60));
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summary_mean_and_sample_std() {
+        let results = [
+            SeedResult { seed: 0, accuracy: 0.50, pnl: 10.0 },
+            SeedResult { seed: 1, accuracy: 0.60, pnl: -10.0 },
+            SeedResult { seed: 2, accuracy: 0.70, pnl: 30.0 },
+        ];
+        let summary = SeedCvSummary::from_results(&results).unwrap();
+        assert_eq!(summary.runs, 3);
+        assert!((summary.accuracy_mean - 0.60).abs() < 1e-12);
+        assert!((summary.accuracy_std - 0.10).abs() < 1e-12);
+        assert!((summary.pnl_mean - 10.0).abs() < 1e-12);
+        assert!((summary.pnl_std - 20.0).abs() < 1e-12);
+
+        let single = SeedCvSummary::from_results(&results[..1]).unwrap();
+        assert_eq!(single.accuracy_std, 0.0);
+        assert!(SeedCvSummary::from_results(&[]).is_none());
+    }
+}
@@ -1,9 +1,12 @@
 use anyhow::Result;
-use crossbeam_channel::Sender;
 use std::sync::Arc;
+use crate::config::{CvdConfig, CVD_TIME_IN_FORCE, CVD_SIZING_MODE, CVD_ELAPSE_DURATION_NS,
+    CVD_LOOKBACK_PERIOD, CVD_PRICE_THRESHOLD, CVD_POSITION_SIZE, CVD_STOP_LOSS_PCT, CVD_TAKE_PROFIT_PCT,
+    PortfolioSleeve, PORTFOLIO_ALLOCATIONS};
 use crate::controller::StrategyController;
-use crate::ui::PerformanceData;
-use super::{MarketMakerRunner, MomentumRunner, PredictionRunner};
+use crate::ui::DataSender;
+use super::{CvdRunner, MarketMakerRunner, MomentumRunner, PredictionRunner, PortfolioRunner};
+use super::base::{Strategy, example_strategy::ExampleStrategy};
 
 #[derive(Debug, Clone)]
 pub enum StrategyType {
@@ -33,6 +36,18 @@ pub enum StrategyType {
         confidence_threshold: f64,
         learning_rate: f64,
     },
+    Cvd {
+        lookback_period: usize,
+        price_threshold: f64,
+        position_size: f64,
+        stop_loss_pct: f64,
+        take_profit_pct: f64,
+        initial_capital: f64,
+    },
+    /// The PORTFOLIO_ALLOCATIONS sleeves sharing `initial_capital`
+    Portfolio {
+        initial_capital: f64,
+    },
 }
 
 impl StrategyType {
@@ -41,13 +56,15 @@ impl StrategyType {
             StrategyType::MarketMaker { .. } => "Market Making",
             StrategyType::Momentum { .. } => "Momentum",
             StrategyType::Prediction { .. } => "ML Prediction",
+            StrategyType::Cvd { .. } => "CVD Divergence",
+            StrategyType::Portfolio { .. } => "Portfolio",
         }
     }
 
     pub fn run(
         &self,
         data_files: Vec<String>,
-        sender: Sender<PerformanceData>,
+        sender: DataSender,
         controller: Arc<StrategyController>,
     ) -> Result<()> {
         match self {
@@ -84,6 +101,50 @@ impl StrategyType {
                 )?;
                 runner.run_with_controller(sender, controller)
             }
+            StrategyType::Cvd {
+                lookback_period, price_threshold, position_size,
+                stop_loss_pct, take_profit_pct, initial_capital,
+            } => {
+                let config = CvdConfig {
+                    lookback_period: *lookback_period,
+                    price_threshold: *price_threshold,
+                    position_size: *position_size,
+                    stop_loss_pct: *stop_loss_pct,
+                    take_profit_pct: *take_profit_pct,
+                    time_in_force: CVD_TIME_IN_FORCE,
+                    sizing_mode: CVD_SIZING_MODE,
+                    elapse_duration_ns: CVD_ELAPSE_DURATION_NS,
+                };
+                CvdRunner::run_with_files(data_files, config, *initial_capital, sender, controller)
+            }
+            StrategyType::Portfolio { initial_capital } => {
+                let mut runner = PortfolioRunner::new(data_files, *initial_capital)?;
+                for &(sleeve, fraction) in PORTFOLIO_ALLOCATIONS {
+                    runner = runner.with_sleeve(fraction, |capital| build_sleeve(sleeve, capital))?;
+                }
+                runner.run_with_controller(sender, controller)
+            }
+        }
+    }
+}
+
+fn build_sleeve(sleeve: PortfolioSleeve, capital: f64) -> Box<dyn Strategy> {
+    match sleeve {
+        PortfolioSleeve::Cvd => {
+            let config = CvdConfig {
+                lookback_period: CVD_LOOKBACK_PERIOD,
+                price_threshold: CVD_PRICE_THRESHOLD,
+                position_size: CVD_POSITION_SIZE,
+                stop_loss_pct: CVD_STOP_LOSS_PCT,
+                take_profit_pct: CVD_TAKE_PROFIT_PCT,
+                time_in_force: CVD_TIME_IN_FORCE,
+                sizing_mode: CVD_SIZING_MODE,
+                elapse_duration_ns: CVD_ELAPSE_DURATION_NS,
+            };
+            Box::new(CvdRunner::new(config, capital))
+        }
+        PortfolioSleeve::Example { position_size, threshold } => {
+            Box::new(ExampleStrategy::new(position_size, threshold, capital))
         }
     }
 }
@@ -2,15 +2,37 @@ use eframe::egui;
 use crossbeam_channel::{Sender, Receiver, unbounded};
 use crate::controller::{StrategyCommand, ControlResponse, ControlState, StrategyController};
 use crate::strategy::StrategyType;
-use super::charts::{ChartHistory, ChartRenderer};
+use crate::config::{GUI_DATA_CHANNEL_CAPACITY, GUI_IMBALANCE_ALERT_THRESHOLD, GUI_SHOW_BUY_AND_HOLD, GUI_SERIES_EXPORT_PATH,
+    GUI_TRADE_REPLAY_LEAD_SECS, GUI_EXPORT_RESAMPLE_SECS};
+use super::charts::{ChartHistory, ChartRenderer, SmoothedMetric, YAxisLock};
 use super::control_panel::ControlPanel;
 use super::data::PerformanceData;
-use super::orderbook::OrderbookView;
+use super::{DataSender, data_channel};
+use super::orderbook::{OrderbookView, DepthMode};
+use super::run_export::RunExport;
+use super::resample::resample_carry_forward;
+use super::series_export::SeriesCsvWriter;
+use super::run_summary::RunSummary;
 use super::stats_panel::StatsPanel;
+use super::trade_log::TradeLog;
 
+use std::collections::{HashMap, VecDeque};
+use std::path::Path;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::thread::{self, JoinHandle};
 
+/// Plot id and title of every chart drawn by `render_line_chart`, in settings order
+const LINE_CHARTS: [(&str, &str); 7] = [
+    ("pnl_plot", "PnL"),
+    ("win_rate_plot", "Win Rate"),
+    ("avg_profit_plot", "Avg Profit per Trade"),
+    ("fill_ratio_plot", "Order Fill Ratio"),
+    ("position_hold_time_plot", "Avg Position Hold Time"),
+    ("latency_plot", "Latency"),
+    ("position_plot", "Position"),
+];
+
 pub struct PerformanceMonitor {
     data_receiver: Receiver<PerformanceData>,
     control_response_rx: Receiver<ControlResponse>,
@@ -21,13 +43,33 @@ pub struct PerformanceMonitor {
     initial_equity: f64,
     show_settings: bool,
     data_updated: bool,
+    /// Loaded reference run (raw export; aligned to the live curve when drawn)
+    reference_run: Option<RunExport>,
+    reference_curve: Option<Vec<(f64, f64)>>,
+    /// Per-chart y-axis locks by plot id (missing = auto-scale)
+    y_axis_locks: HashMap<&'static str, YAxisLock>,
+    /// |Book imbalance| above which the price chart marks the point
+    imbalance_alert_threshold: f64,
+    /// Overlay the buy-and-hold benchmark and show the alpha over it
+    show_buy_and_hold: bool,
+    /// Series CSV being recorded, one row per received update
+    series_export: Option<SeriesCsvWriter>,
+    /// Trades of the charted file, and the one selected for replay (0-based)
+    trade_log: TradeLog,
+    replay_trade_index: usize,
+    /// Seek to send instead of `Start` when the next run is spawned (replaying an earlier trade)
+    pending_seek: Option<f64>,
+    /// Files started in the current run before the charted one
+    files_started: usize,
     
     // Thread management
     strategy_type: StrategyType,
     strategy_thread: Option<JoinHandle<anyhow::Result<()>>>,
     controller: Option<Arc<StrategyController>>,
     #[allow(dead_code)]
-    data_tx: Option<Sender<PerformanceData>>,
+    data_tx: Option<DataSender>,
+    /// Updates the current run's data channel discarded because the GUI fell behind
+    dropped_updates: Arc<AtomicU64>,
     cmd_tx: Sender<StrategyCommand>,
     #[allow(dead_code)]
     cmd_rx_holder: Option<Receiver<StrategyCommand>>,
@@ -37,7 +79,8 @@ pub struct PerformanceMonitor {
 
 impl PerformanceMonitor {
     pub fn new(strategy_type: StrategyType, initial_equity: f64, data_file: String) -> Self {
-        let (data_tx, data_rx) = unbounded();
+        let (data_tx, data_rx) = data_channel(GUI_DATA_CHANNEL_CAPACITY);
+        let dropped_updates = data_tx.dropped_counter();
         let (cmd_tx, cmd_rx) = unbounded();
         let (response_tx, response_rx) = unbounded();
         
@@ -45,16 +88,29 @@ impl PerformanceMonitor {
             data_receiver: data_rx,
             control_response_rx: response_rx,
             control_panel: ControlPanel::new(cmd_tx.clone(), data_file),
-            chart_history: ChartHistory::new(500),
+            chart_history: ChartHistory::new(500).with_initial_equity(initial_equity),
             orderbook_view: OrderbookView::new(10),
             current_data: None,
             initial_equity,
             show_settings: false,
             data_updated: false,
+            reference_run: None,
+            reference_curve: None,
+            y_axis_locks: HashMap::new(),
+            imbalance_alert_threshold: GUI_IMBALANCE_ALERT_THRESHOLD,
+            show_buy_and_hold: GUI_SHOW_BUY_AND_HOLD,
+            series_export: GUI_SERIES_EXPORT_PATH.and_then(|path| {
+                SeriesCsvWriter::create(Path::new(path), GUI_EXPORT_RESAMPLE_SECS).map_err(|e| eprintln!("{:#}", e)).ok()
+            }),
+            trade_log: TradeLog::default(),
+            replay_trade_index: 0,
+            pending_seek: None,
+            files_started: 0,
             strategy_type,
             strategy_thread: None,
             controller: None,
             data_tx: Some(data_tx),
+            dropped_updates,
             cmd_tx,
             cmd_rx_holder: Some(cmd_rx),
             response_tx,
@@ -69,10 +125,11 @@ impl PerformanceMonitor {
             return;
         }
         
-        let (data_tx, data_rx) = unbounded();
+        let (data_tx, data_rx) = data_channel(GUI_DATA_CHANNEL_CAPACITY);
         let (cmd_tx, cmd_rx) = unbounded();
         
         self.data_receiver = data_rx;
+        self.dropped_updates = data_tx.dropped_counter();
         self.cmd_rx_holder = None;
         self.control_panel.update_command_sender(cmd_tx.clone());
         self.cmd_tx = cmd_tx.clone();
@@ -90,11 +147,13 @@ impl PerformanceMonitor {
         self.strategy_thread = Some(handle);
         self.can_start_new = false;
         self.chart_history.clear();
+        self.trade_log.clear();
+        self.files_started = 0;
         self.current_data = None;
         self.data_updated = true;
         
-        // Send Start command immediately after spawning
-        let _ = cmd_tx.send(StrategyCommand::Start);
+        // Send Start command immediately after spawning (or fast-forward to a replayed trade)
+        let _ = cmd_tx.send(self.pending_seek.take().map_or(StrategyCommand::Start, StrategyCommand::SeekTo));
     }
 
     fn check_thread_status(&mut self) {
@@ -116,22 +175,39 @@ impl PerformanceMonitor {
             if data.equity == 0.0 && data.mid_price == 0.0 { continue; }
             self.data_updated = true;
             self.chart_history.push(&data);
+            self.trade_log.push(&data);
+            if let Some(writer) = self.series_export.as_mut() {
+                if let Err(e) = writer.write_sample(&data) {
+                    eprintln!("{:#}", e);
+                    self.series_export = None;
+                }
+            }
             self.current_data = Some(data);
         }
         
+        if self.data_updated {
+            self.align_reference_curve();
+        }
+        
         self.check_thread_status();
         self.control_panel.set_can_start_new(self.can_start_new);
         
         while let Ok(response) = self.control_response_rx.try_recv() {
+            // Chart history is cleared only when the runner starts its next file
+            // (`NewFileStarted`) or a new run is spawned, never on speed/state changes
+            if response.clears_chart() {
+                self.chart_history.clear();
+                self.trade_log.clear();
+                self.files_started += 1;
+            }
             match response {
                 ControlResponse::StateChanged(state) => self.control_panel.update_state(state),
                 ControlResponse::SpeedChanged(speed) => self.control_panel.update_speed(speed),
-                ControlResponse::FilesChanged(files) => {
-                    self.control_panel.update_files(files);
-                    self.chart_history.clear();
-                }
-                ControlResponse::Skipped => self.chart_history.clear(),
+                ControlResponse::FilesChanged(files) => self.control_panel.update_files(files),
+                ControlResponse::Skipped | ControlResponse::NewFileStarted => {}
                 ControlResponse::Error(err) => eprintln!("Control error: {}", err),
+                ControlResponse::TargetReached(reason) => println!("🏁 {}", reason),
+                ControlResponse::LogEvent(message) => println!("📝 {}", message),
                 ControlResponse::Completed => {
                     self.control_panel.update_state(ControlState::Completed);
                     // Require new file selection before starting again
@@ -141,27 +217,160 @@ impl PerformanceMonitor {
             }
         }
         
-        if self.control_panel.should_start_new_backtest() {
+        if self.control_panel.should_start_new_backtest() || (self.pending_seek.is_some() && self.can_start_new) {
             self.spawn_strategy_thread();
         }
     }
 
-    fn render_charts(&self, ui: &mut egui::Ui, chart_width: f32, content_width: f32) {
+    /// Watch trade `index` unfold: fast-forward the running file if it lies ahead, otherwise
+    /// stop and rerun the same files from the start up to just before its entry
+    fn replay_trade(&mut self, index: usize) {
+        let Some(target) = self.trade_log.seek_target(index, GUI_TRADE_REPLAY_LEAD_SECS) else {
+            return;
+        };
+        let current_secs = self.current_data.as_ref().map_or(0.0, |data| data.timestamp);
+        if self.strategy_thread.is_some() && target > current_secs {
+            let _ = self.cmd_tx.send(StrategyCommand::SeekTo(target));
+            return;
+        }
+        if self.strategy_thread.is_some() {
+            let _ = self.cmd_tx.send(StrategyCommand::Stop);
+        }
+        // Spawned by `update_data` once the current thread has exited
+        self.pending_seek = Some(target);
+    }
+
+    /// Whether `replay_trade(index)` can get there: a rerun starts from the first file, so trades
+    /// of a later file are only reachable while they still lie ahead
+    fn can_replay_trade(&self, index: usize) -> bool {
+        let Some(target) = self.trade_log.seek_target(index, GUI_TRADE_REPLAY_LEAD_SECS) else {
+            return false;
+        };
+        let current_secs = self.current_data.as_ref().map_or(0.0, |data| data.timestamp);
+        self.pending_seek.is_none()
+            && (self.files_started == 0 || (self.strategy_thread.is_some() && target > current_secs))
+    }
+
+    /// Shift the reference curve so it starts where the live curve starts
+    fn align_reference_curve(&mut self) {
+        if let Some(reference) = &self.reference_run {
+            let start_ts = self.chart_history.equity.front().map(|(ts, _)| *ts).unwrap_or(0.0);
+            self.reference_curve = Some(reference.aligned_curve(start_ts));
+        }
+    }
+
+    fn export_run(&self) {
+        let strategy_name = self.current_data.as_ref()
+            .map(|d| d.strategy_name.clone())
+            .unwrap_or_else(|| self.strategy_type.name().to_string());
+        let equity_curve: Vec<(f64, f64)> = self.chart_history.equity.iter().copied().collect();
+        let export = RunExport {
+            strategy_name,
+            initial_equity: self.initial_equity,
+            equity_curve: match GUI_EXPORT_RESAMPLE_SECS {
+                Some(interval_secs) => resample_carry_forward(&equity_curve, interval_secs),
+                None => equity_curve,
+            },
+        };
+
+        if let Some(path) = rfd::FileDialog::new()
+            .add_filter("Run Export", &["json"])
+            .set_title("Export Run")
+            .set_file_name("run.json")
+            .save_file()
+        {
+            if let Err(e) = export.save(&path) {
+                eprintln!("{:#}", e);
+            }
+            // Markdown summary next to the JSON for pasting into issues/PRs
+            if let Some(summary) = self.run_summary() {
+                let summary_path = path.with_extension("md");
+                if let Err(e) = std::fs::write(&summary_path, summary.to_markdown()) {
+                    eprintln!("Failed to write run summary {}: {}", summary_path.display(), e);
+                }
+            }
+        }
+    }
+
+    /// Start streaming updates to a CSV picked by the user, or stop and close the one being recorded
+    fn export_series_csv(&mut self) {
+        if let Some(writer) = self.series_export.take() {
+            let (path, rows) = (writer.path().display().to_string(), writer.rows());
+            match writer.finish() {
+                Ok(()) => println!("Series export: {} rows written to {}", rows, path),
+                Err(e) => eprintln!("{:#}", e),
+            }
+            return;
+        }
+
+        if let Some(path) = rfd::FileDialog::new()
+            .add_filter("CSV", &["csv"])
+            .set_title("Record Series CSV")
+            .set_file_name("series.csv")
+            .save_file()
+        {
+            match SeriesCsvWriter::create(&path, GUI_EXPORT_RESAMPLE_SECS) {
+                Ok(writer) => self.series_export = Some(writer),
+                Err(e) => eprintln!("{:#}", e),
+            }
+        }
+    }
+
+    /// Summary of the run so far (None before the first data update)
+    fn run_summary(&self) -> Option<RunSummary> {
+        let data = self.current_data.as_ref()?;
+        let equity_curve: Vec<(f64, f64)> = self.chart_history.equity.iter().copied().collect();
+        Some(RunSummary::from_run(data, self.initial_equity, &equity_curve))
+    }
+
+    fn load_reference(&mut self) {
+        if let Some(path) = rfd::FileDialog::new()
+            .add_filter("Run Export", &["json"])
+            .set_title("Load Reference Run")
+            .pick_file()
+        {
+            match RunExport::load(&path) {
+                Ok(reference) => {
+                    self.reference_run = Some(reference);
+                    self.align_reference_curve();
+                    self.data_updated = true;
+                }
+                Err(e) => eprintln!("{:#}", e),
+            }
+        }
+    }
+
+    /// Series drawn by the `LINE_CHARTS` entry `id`
+    fn line_chart_series(&self, id: &str) -> &VecDeque<(f64, f64)> {
+        match id {
+            "pnl_plot" => &self.chart_history.pnl,
+            "win_rate_plot" => &self.chart_history.win_rate,
+            "avg_profit_plot" => &self.chart_history.avg_profit,
+            "fill_ratio_plot" => self.chart_history.metric_series(SmoothedMetric::FillRatio),
+            "position_hold_time_plot" => self.chart_history.metric_series(SmoothedMetric::HoldTime),
+            "latency_plot" => self.chart_history.metric_series(SmoothedMetric::Latency),
+            _ => &self.chart_history.position,
+        }
+    }
+
+    fn render_charts(&mut self, ui: &mut egui::Ui, chart_width: f32, content_width: f32) {
         ui.heading("📈 Performance Charts");
         ui.add_space(10.0);
         
         let chart_spacing = 15.0;
+        let locks = &mut self.y_axis_locks;
         
         ui.columns(2, |columns| {
             columns[0].vertical(|ui| {
-                ChartRenderer::render_line_chart(ui, "equity_plot", "Equity Curve", 
-                    &self.chart_history.equity, chart_width, 
-                    egui::Color32::from_rgb(0, 150, 255), "Equity", false, Some(self.initial_equity));
+                ChartRenderer::render_equity_chart(ui, &self.chart_history.equity, chart_width,
+                    self.initial_equity, self.reference_curve.as_deref(), &self.chart_history.fill_markers,
+                    self.show_buy_and_hold.then_some(&self.chart_history.buy_and_hold));
             });
             columns[1].vertical(|ui| {
                 ChartRenderer::render_line_chart(ui, "pnl_plot", "PnL", 
                     &self.chart_history.pnl, chart_width,
-                    egui::Color32::from_rgb(0, 200, 100), "Total PnL", true, None);
+                    egui::Color32::from_rgb(0, 200, 100), "Total PnL", true, None,
+                    locks.entry("pnl_plot").or_default());
             });
         });
         
@@ -171,12 +380,14 @@ impl PerformanceMonitor {
             columns[0].vertical(|ui| {
                 ChartRenderer::render_line_chart(ui, "win_rate_plot", "Win Rate",
                     &self.chart_history.win_rate, chart_width,
-                    egui::Color32::from_rgb(100, 150, 255), "Win Rate %", false, None);
+                    egui::Color32::from_rgb(100, 150, 255), "Win Rate %", false, None,
+                    locks.entry("win_rate_plot").or_default());
             });
             columns[1].vertical(|ui| {
                 ChartRenderer::render_line_chart(ui, "avg_profit_plot", "Avg Profit per Trade",
                     &self.chart_history.avg_profit, chart_width,
-                    egui::Color32::from_rgb(255, 180, 100), "Avg Profit $", true, None);
+                    egui::Color32::from_rgb(255, 180, 100), "Avg Profit $", true, None,
+                    locks.entry("avg_profit_plot").or_default());
             });
         });
         
@@ -185,13 +396,15 @@ impl PerformanceMonitor {
         ui.columns(2, |columns| {
             columns[0].vertical(|ui| {
                 ChartRenderer::render_line_chart(ui, "fill_ratio_plot", "Order Fill Ratio",
-                    &self.chart_history.fill_ratio, chart_width,
-                    egui::Color32::from_rgb(150, 100, 255), "Fill Ratio %", false, None);
+                    self.chart_history.metric_series(SmoothedMetric::FillRatio), chart_width,
+                    egui::Color32::from_rgb(150, 100, 255), "Fill Ratio %", false, None,
+                    locks.entry("fill_ratio_plot").or_default());
             });
             columns[1].vertical(|ui| {
                 ChartRenderer::render_line_chart(ui, "position_hold_time_plot", "Avg Position Hold Time",
-                    &self.chart_history.position_hold_time, chart_width,
-                    egui::Color32::from_rgb(255, 150, 200), "Hold Time (s)", false, None);
+                    self.chart_history.metric_series(SmoothedMetric::HoldTime), chart_width,
+                    egui::Color32::from_rgb(255, 150, 200), "Hold Time (s)", false, None,
+                    locks.entry("position_hold_time_plot").or_default());
             });
         });
         
@@ -200,21 +413,25 @@ impl PerformanceMonitor {
         ui.columns(2, |columns| {
             columns[0].vertical(|ui| {
                 ChartRenderer::render_line_chart(ui, "latency_plot", "Latency",
-                    &self.chart_history.latency, chart_width,
-                    egui::Color32::from_rgb(200, 100, 150), "Latency (μs)", false, None);
+                    self.chart_history.metric_series(SmoothedMetric::Latency), chart_width,
+                    egui::Color32::from_rgb(200, 100, 150), "Latency (μs)", false, None,
+                    locks.entry("latency_plot").or_default());
             });
             columns[1].vertical(|ui| {
                 ChartRenderer::render_line_chart(ui, "position_plot", "Position",
                     &self.chart_history.position, chart_width,
-                    egui::Color32::from_rgb(255, 150, 0), "Position", true, None);
+                    egui::Color32::from_rgb(255, 150, 0), "Position", true, None,
+                    locks.entry("position_plot").or_default());
             });
         });
         
         ui.add_space(chart_spacing);
         
-        ChartRenderer::render_line_chart(ui, "price_plot", "Mid Price",
-            &self.chart_history.price, content_width,
-            egui::Color32::from_rgb(200, 100, 255), "Mid Price", false, None);
+        let (stop_price, take_price) = self.current_data.as_ref()
+            .map(|d| (d.stop_price, d.take_price))
+            .unwrap_or((None, None));
+        ChartRenderer::render_price_chart(ui, &self.chart_history.price, content_width,
+            stop_price, take_price, self.chart_history.imbalance_markers(self.imbalance_alert_threshold));
     }
 
     fn render_settings_panel(&mut self, ui: &mut egui::Ui) {
@@ -232,6 +449,30 @@ impl PerformanceMonitor {
             });
             
             ui.label(format!("Current: {} points", self.chart_history.len()));
+            ui.label(format!("Dropped updates: {}", self.dropped_updates.load(Ordering::Relaxed)))
+                .on_hover_text("Updates skipped because the GUI fell behind; the newest one is always kept");
+            let mut fit_whole_run = self.chart_history.fit_whole_run();
+            if ui.checkbox(&mut fit_whole_run, "Fit whole run")
+                .on_hover_text("Downsample (keeping highs and lows) instead of dropping the oldest points")
+                .changed() {
+                self.chart_history.set_fit_whole_run(fit_whole_run);
+            }
+            ui.checkbox(&mut self.show_buy_and_hold, "Buy & hold benchmark")
+                .on_hover_text("Initial equity fully invested at the first mid price, marked to market");
+            ui.horizontal(|ui| {
+                let mut show_smoothed = self.chart_history.show_smoothed();
+                if ui.checkbox(&mut show_smoothed, "Smooth metric charts")
+                    .on_hover_text("Draw fill ratio, hold time and latency as an EMA instead of the raw points")
+                    .changed() {
+                    self.chart_history.set_show_smoothed(show_smoothed);
+                }
+                let mut alpha = self.chart_history.smoothing_alpha();
+                if ui.add_enabled(show_smoothed, egui::Slider::new(&mut alpha, 0.01..=1.0).text("α").logarithmic(true))
+                    .on_hover_text("Weight of the newest point (1 = raw)")
+                    .changed() {
+                    self.chart_history.set_smoothing_alpha(alpha);
+                }
+            });
             ui.separator();
             
             ui.horizontal(|ui| {
@@ -242,6 +483,64 @@ impl PerformanceMonitor {
                 }
             });
             
+            ui.horizontal(|ui| {
+                ui.label("Price Display:");
+                let mut multiplier = self.orderbook_view.price_multiplier();
+                egui::ComboBox::from_id_salt("price_multiplier")
+                    .selected_text(format!("× {}", multiplier))
+                    .show_ui(ui, |ui| {
+                        for option in [1.0, 1_000.0, 1_000_000.0] {
+                            ui.selectable_value(&mut multiplier, option, format!("× {}", option));
+                        }
+                    });
+                if multiplier != self.orderbook_view.price_multiplier() {
+                    self.orderbook_view.set_price_multiplier(multiplier);
+                }
+            });
+            
+            ui.horizontal(|ui| {
+                ui.label("Depth Chart:");
+                let mut mode = self.orderbook_view.depth_mode();
+                ui.selectable_value(&mut mode, DepthMode::Quantity, "Quantity");
+                ui.selectable_value(&mut mode, DepthMode::Notional, "Notional")
+                    .on_hover_text("Cumulative price × quantity, to compare liquidity across price");
+                if mode != self.orderbook_view.depth_mode() {
+                    self.orderbook_view.set_depth_mode(mode);
+                }
+            });
+            
+            ui.horizontal(|ui| {
+                ui.label("Imbalance Alert:");
+                ui.add(egui::Slider::new(&mut self.imbalance_alert_threshold, 0.0..=1.0).text("|imbalance|"))
+                    .on_hover_text("Mark the price chart where the book imbalance exceeds this (strategies that report it)");
+            });
+            
+            ui.separator();
+            
+            ui.label("Lock Y-Axis:");
+            for (id, title) in LINE_CHARTS {
+                let mut lock = self.y_axis_locks.get(id).copied().unwrap_or_default();
+                ui.horizontal(|ui| {
+                    let mut locked = lock.is_locked();
+                    if ui.checkbox(&mut locked, title).changed() {
+                        if locked {
+                            lock.lock_to_data(self.line_chart_series(id));
+                        } else {
+                            lock.unlock();
+                        }
+                    }
+                    if let Some((mut min, mut max)) = lock.range() {
+                        let speed = ((max - min).abs() / 100.0).max(1e-6);
+                        let min_changed = ui.add(egui::DragValue::new(&mut min).speed(speed).prefix("min ")).changed();
+                        let max_changed = ui.add(egui::DragValue::new(&mut max).speed(speed).prefix("max ")).changed();
+                        if min_changed || max_changed {
+                            lock.lock(min, max);
+                        }
+                    }
+                });
+                self.y_axis_locks.insert(id, lock);
+            }
+            
             ui.separator();
             
             ui.horizontal(|ui| {
@@ -253,6 +552,58 @@ impl PerformanceMonitor {
                     self.chart_history.set_max_points(1000);
                 }
             });
+            
+            ui.separator();
+            
+            ui.horizontal(|ui| {
+                if ui.button("💾 Export Run").clicked() {
+                    self.export_run();
+                }
+                if ui.add_enabled(self.current_data.is_some(), egui::Button::new("📋 Copy Summary")).clicked() {
+                    if let Some(summary) = self.run_summary() {
+                        ui.ctx().copy_text(summary.to_markdown());
+                    }
+                }
+                let series_label = if self.series_export.is_some() { "⏹ Stop Series CSV" } else { "⏺ Record Series CSV" };
+                if ui.button(series_label)
+                    .on_hover_text("Stream every update from now on (equity, PnL, position, mid, drawdown) to a CSV")
+                    .clicked() {
+                    self.export_series_csv();
+                }
+                if ui.button("📂 Load Reference").clicked() {
+                    self.load_reference();
+                }
+                if self.reference_run.is_some() && ui.button("✖ Clear Reference").clicked() {
+                    self.reference_run = None;
+                    self.reference_curve = None;
+                    self.data_updated = true;
+                }
+            });
+            
+            if let Some(reference) = &self.reference_run {
+                ui.label(format!("Reference: {} ({} points)", reference.strategy_name, reference.equity_curve.len()));
+            }
+
+            ui.separator();
+
+            let trade_count = self.trade_log.trades().len();
+            ui.horizontal(|ui| {
+                ui.label("Replay Trade:");
+                let mut trade_number = self.replay_trade_index + 1;
+                ui.add_enabled(trade_count > 0, egui::DragValue::new(&mut trade_number)
+                    .range(1..=trade_count.max(1)).prefix("#"));
+                self.replay_trade_index = trade_number.saturating_sub(1);
+                if ui.add_enabled(self.can_replay_trade(self.replay_trade_index), egui::Button::new("⏮ Replay"))
+                    .on_hover_text(format!("Seek to {:.0}s before the trade's entry and pause there", GUI_TRADE_REPLAY_LEAD_SECS))
+                    .clicked() {
+                    self.replay_trade(self.replay_trade_index);
+                }
+            });
+            if let Some(trade) = self.trade_log.trades().get(self.replay_trade_index) {
+                let side = if trade.is_long { "Long" } else { "Short" };
+                let exit = trade.exit_secs.map_or("open".to_string(), |secs| format!("T+{:.1}s", secs));
+                ui.label(format!("{} @ {:.5}, T+{:.1}s → {}", side, trade.entry_price, trade.entry_secs, exit));
+            }
         });
     }
 }
@@ -285,7 +636,11 @@ impl eframe::App for PerformanceMonitor {
                 .min_width(300.0)
                 .show(ctx, |ui| {
                     egui::ScrollArea::vertical().show(ui, |ui| {
-                        StatsPanel::render(ui, self.current_data.as_ref(), self.initial_equity);
+                        let reference_return = self.reference_run.as_ref().and_then(|r| r.final_return_pct());
+                        let buy_and_hold_return = self.chart_history.buy_and_hold_return_pct()
+                            .filter(|_| self.show_buy_and_hold);
+                        StatsPanel::render(ui, self.current_data.as_ref(), self.initial_equity, reference_return,
+                            buy_and_hold_return, self.orderbook_view.price_multiplier());
                         ui.add_space(10.0);
                         self.control_panel.render(ui);
                         ui.add_space(10.0);
@@ -0,0 +1,72 @@
+use std::collections::VecDeque;
+
+/// Fixed y-range for one chart. Kept by the app across frames; the renderer applies it every
+/// frame while locked and hands control back to egui_plot's auto-bounds once after unlocking.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct YAxisLock {
+    range: Option<(f64, f64)>,
+    /// Unlocked but auto-bounds not yet restored on the plot
+    release_pending: bool,
+}
+
+impl YAxisLock {
+    pub fn is_locked(&self) -> bool {
+        self.range.is_some()
+    }
+
+    pub fn range(&self) -> Option<(f64, f64)> {
+        self.range
+    }
+
+    /// Lock to `min..max`, swapping them if entered the wrong way round
+    pub fn lock(&mut self, min: f64, max: f64) {
+        self.range = Some((min.min(max), min.max(max)));
+        self.release_pending = false;
+    }
+
+    /// Lock to the series' current value range (no-op while it is empty)
+    pub fn lock_to_data(&mut self, data: &VecDeque<(f64, f64)>) {
+        let values = data.iter().map(|(_, v)| *v);
+        let min = values.clone().fold(f64::INFINITY, f64::min);
+        let max = values.fold(f64::NEG_INFINITY, f64::max);
+        if min.is_finite() && max.is_finite() {
+            self.lock(min, max);
+        }
+    }
+
+    pub fn unlock(&mut self) {
+        if self.range.take().is_some() {
+            self.release_pending = true;
+        }
+    }
+
+    /// True once after `unlock`, when the plot should switch back to auto-bounds
+    pub fn take_release(&mut self) -> bool {
+        std::mem::take(&mut self.release_pending)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lock_to_data_and_unlock_restores_auto_scale_once() {
+        let mut lock = YAxisLock::default();
+        lock.lock_to_data(&VecDeque::new());
+        assert!(!lock.is_locked());
+
+        let data: VecDeque<(f64, f64)> = [(0.0, 5.0), (1.0, -2.0), (2.0, 3.0)].into_iter().collect();
+        lock.lock_to_data(&data);
+        assert_eq!(lock.range(), Some((-2.0, 5.0)));
+        assert!(!lock.take_release());
+
+        lock.lock(10.0, 4.0);
+        assert_eq!(lock.range(), Some((4.0, 10.0)));
+
+        lock.unlock();
+        assert!(!lock.is_locked());
+        assert!(lock.take_release());
+        assert!(!lock.take_release());
+    }
+}
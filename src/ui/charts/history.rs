@@ -1,5 +1,14 @@
 use std::collections::VecDeque;
 use crate::ui::PerformanceData;
+use crate::config::{GUI_METRIC_SMOOTHING_ALPHA, GUI_SMOOTH_METRIC_CHARTS};
+
+/// Extended-metric charts that can be drawn EMA-smoothed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SmoothedMetric {
+    FillRatio,
+    HoldTime,
+    Latency,
+}
 
 pub struct ChartHistory {
     pub equity: VecDeque<(f64, f64)>,
@@ -11,7 +20,27 @@ pub struct ChartHistory {
     pub fill_ratio: VecDeque<(f64, f64)>,
     pub position_hold_time: VecDeque<(f64, f64)>,
     pub latency: VecDeque<(f64, f64)>,
+    /// EMAs of `fill_ratio`, `position_hold_time` and `latency`, pushed alongside the raw points
+    pub fill_ratio_smoothed: VecDeque<(f64, f64)>,
+    pub position_hold_time_smoothed: VecDeque<(f64, f64)>,
+    pub latency_smoothed: VecDeque<(f64, f64)>,
+    /// Weight of the newest point in the smoothed series
+    smoothing_alpha: f64,
+    /// Whether `metric_series` returns the smoothed or the raw series
+    show_smoothed: bool,
+    /// (timestamp, mid price, book imbalance) for updates that carried an imbalance
+    pub imbalance: VecDeque<(f64, f64, f64)>,
+    /// (timestamp, equity) of the updates sent right after a fill
+    pub fill_markers: VecDeque<(f64, f64)>,
+    /// (timestamp, equity) of `initial_equity` fully invested at the first valid mid and marked
+    /// to market, as the benchmark the strategy has to beat
+    pub buy_and_hold: VecDeque<(f64, f64)>,
+    initial_equity: f64,
+    /// Mid price the benchmark bought at (None until the first valid mid)
+    first_mid: Option<f64>,
     max_points: usize,
+    /// Downsample the whole series instead of dropping the oldest points once full
+    fit_whole_run: bool,
 }
 
 impl ChartHistory {
@@ -26,10 +55,27 @@ impl ChartHistory {
             fill_ratio: VecDeque::new(),
             position_hold_time: VecDeque::new(),
             latency: VecDeque::new(),
+            fill_ratio_smoothed: VecDeque::new(),
+            position_hold_time_smoothed: VecDeque::new(),
+            latency_smoothed: VecDeque::new(),
+            smoothing_alpha: GUI_METRIC_SMOOTHING_ALPHA,
+            show_smoothed: GUI_SMOOTH_METRIC_CHARTS,
+            imbalance: VecDeque::new(),
+            fill_markers: VecDeque::new(),
+            buy_and_hold: VecDeque::new(),
+            initial_equity: 0.0,
+            first_mid: None,
             max_points,
+            fit_whole_run: false,
         }
     }
 
+    /// Capital the buy-and-hold benchmark invests (no benchmark while it is 0)
+    pub fn with_initial_equity(mut self, initial_equity: f64) -> Self {
+        self.initial_equity = initial_equity;
+        self
+    }
+
     pub fn set_max_points(&mut self, max_points: usize) {
         self.max_points = max_points;
     }
@@ -38,6 +84,46 @@ impl ChartHistory {
         self.max_points
     }
 
+    pub fn fit_whole_run(&self) -> bool {
+        self.fit_whole_run
+    }
+
+    pub fn set_fit_whole_run(&mut self, fit_whole_run: bool) {
+        self.fit_whole_run = fit_whole_run;
+    }
+
+    pub fn smoothing_alpha(&self) -> f64 {
+        self.smoothing_alpha
+    }
+
+    /// Clamped to (0, 1]; the smoothed series are recomputed from the raw points still held
+    pub fn set_smoothing_alpha(&mut self, alpha: f64) {
+        self.smoothing_alpha = alpha.clamp(f64::EPSILON, 1.0);
+        self.fill_ratio_smoothed = ema_series(&self.fill_ratio, self.smoothing_alpha);
+        self.position_hold_time_smoothed = ema_series(&self.position_hold_time, self.smoothing_alpha);
+        self.latency_smoothed = ema_series(&self.latency, self.smoothing_alpha);
+    }
+
+    pub fn show_smoothed(&self) -> bool {
+        self.show_smoothed
+    }
+
+    pub fn set_show_smoothed(&mut self, show_smoothed: bool) {
+        self.show_smoothed = show_smoothed;
+    }
+
+    /// The metric's series as drawn: smoothed or raw, per `show_smoothed`
+    pub fn metric_series(&self, metric: SmoothedMetric) -> &VecDeque<(f64, f64)> {
+        match (metric, self.show_smoothed) {
+            (SmoothedMetric::FillRatio, false) => &self.fill_ratio,
+            (SmoothedMetric::FillRatio, true) => &self.fill_ratio_smoothed,
+            (SmoothedMetric::HoldTime, false) => &self.position_hold_time,
+            (SmoothedMetric::HoldTime, true) => &self.position_hold_time_smoothed,
+            (SmoothedMetric::Latency, false) => &self.latency,
+            (SmoothedMetric::Latency, true) => &self.latency_smoothed,
+        }
+    }
+
     pub fn len(&self) -> usize {
         self.equity.len()
     }
@@ -52,6 +138,13 @@ impl ChartHistory {
         self.fill_ratio.clear();
         self.position_hold_time.clear();
         self.latency.clear();
+        self.fill_ratio_smoothed.clear();
+        self.position_hold_time_smoothed.clear();
+        self.latency_smoothed.clear();
+        self.imbalance.clear();
+        self.fill_markers.clear();
+        self.buy_and_hold.clear();
+        self.first_mid = None;
     }
 
     pub fn push(&mut self, data: &PerformanceData) {
@@ -61,6 +154,16 @@ impl ChartHistory {
         self.pnl.push_back((ts, data.realized_pnl + data.unrealized_pnl));
         self.position.push_back((ts, data.position));
         self.price.push_back((ts, data.mid_price));
+        if self.initial_equity > 0.0 {
+            if data.mid_price > 0.0 && data.mid_price.is_finite() {
+                self.first_mid.get_or_insert(data.mid_price);
+            }
+            let benchmark = self.first_mid.map_or(self.initial_equity, |first_mid| {
+                let last_mid = if data.mid_price > 0.0 { data.mid_price } else { first_mid };
+                self.initial_equity * last_mid / first_mid
+            });
+            self.buy_and_hold.push_back((ts, benchmark));
+        }
         
         let win_rate = if data.num_trades > 0 {
             (data.winning_trades as f64 / data.num_trades as f64) * 100.0
@@ -79,11 +182,58 @@ impl ChartHistory {
         
         self.position_hold_time.push_back((ts, data.position_hold_time));
         self.latency.push_back((ts, data.latency_micros as f64));
+        let alpha = self.smoothing_alpha;
+        push_ema(&mut self.fill_ratio_smoothed, ts, fill_ratio, alpha);
+        push_ema(&mut self.position_hold_time_smoothed, ts, data.position_hold_time, alpha);
+        push_ema(&mut self.latency_smoothed, ts, data.latency_micros as f64, alpha);
+        if let Some(imbalance) = data.imbalance {
+            self.imbalance.push_back((ts, data.mid_price, imbalance));
+        }
+        if data.fill_update {
+            self.fill_markers.push_back((ts, data.equity));
+        }
         
         self.trim_to_max();
     }
 
+    /// Return of the buy-and-hold benchmark so far, in percent (None before the first valid mid)
+    pub fn buy_and_hold_return_pct(&self) -> Option<f64> {
+        self.first_mid?;
+        let &(_, equity) = self.buy_and_hold.back()?;
+        Some((equity / self.initial_equity - 1.0) * 100.0)
+    }
+
+    /// Price-chart points whose |imbalance| exceeds `threshold`, split into (bid-heavy, ask-heavy)
+    pub fn imbalance_markers(&self, threshold: f64) -> (Vec<[f64; 2]>, Vec<[f64; 2]>) {
+        let mut bid_heavy = Vec::new();
+        let mut ask_heavy = Vec::new();
+        for &(ts, mid_price, imbalance) in &self.imbalance {
+            if imbalance > threshold {
+                bid_heavy.push([ts, mid_price]);
+            } else if imbalance < -threshold {
+                ask_heavy.push([ts, mid_price]);
+            }
+        }
+        (bid_heavy, ask_heavy)
+    }
+
     fn trim_to_max(&mut self) {
+        self.trim_imbalance();
+        if self.fit_whole_run {
+            let max_points = self.max_points;
+            for series in [
+                &mut self.equity, &mut self.pnl, &mut self.position, &mut self.price,
+                &mut self.win_rate, &mut self.avg_profit, &mut self.fill_ratio,
+                &mut self.position_hold_time, &mut self.latency, &mut self.buy_and_hold,
+                &mut self.fill_ratio_smoothed, &mut self.position_hold_time_smoothed, &mut self.latency_smoothed,
+            ] {
+                if series.len() > max_points {
+                    *series = decimate_min_max(series, max_points / 2);
+                }
+            }
+            return;
+        }
+
         while self.equity.len() > self.max_points {
             self.equity.pop_front();
             self.pnl.pop_front();
@@ -94,6 +244,172 @@ impl ChartHistory {
             self.fill_ratio.pop_front();
             self.position_hold_time.pop_front();
             self.latency.pop_front();
+            self.fill_ratio_smoothed.pop_front();
+            self.position_hold_time_smoothed.pop_front();
+            self.latency_smoothed.pop_front();
+        }
+        while self.buy_and_hold.len() > self.max_points {
+            self.buy_and_hold.pop_front();
+        }
+        self.trim_imbalance();
+    }
+
+    /// Drop imbalance samples and fill markers older than the visible series, capped like the
+    /// other series
+    fn trim_imbalance(&mut self) {
+        let start = self.price.front().map_or(f64::INFINITY, |p| p.0);
+        while self.imbalance.front().is_some_and(|p| p.0 < start) || self.imbalance.len() > self.max_points {
+            self.imbalance.pop_front();
         }
+        while self.fill_markers.front().is_some_and(|p| p.0 < start) || self.fill_markers.len() > self.max_points {
+            self.fill_markers.pop_front();
+        }
+    }
+}
+
+/// Append the EMA of `value`, seeded with the first point; the last smoothed point is the state
+fn push_ema(smoothed: &mut VecDeque<(f64, f64)>, ts: f64, value: f64, alpha: f64) {
+    let ema = smoothed.back().map_or(value, |&(_, prev)| prev + alpha * (value - prev));
+    smoothed.push_back((ts, ema));
+}
+
+fn ema_series(raw: &VecDeque<(f64, f64)>, alpha: f64) -> VecDeque<(f64, f64)> {
+    let mut smoothed = VecDeque::with_capacity(raw.len());
+    for &(ts, value) in raw {
+        push_ema(&mut smoothed, ts, value, alpha);
+    }
+    smoothed
+}
+
+/// Min/max-preserving downsampling to about `target` points: the first and latest points are
+/// always kept and the rest is split into `target / 2` buckets, each contributing its lowest
+/// and highest point in time order, so spikes survive at any resolution
+fn decimate_min_max(series: &VecDeque<(f64, f64)>, target: usize) -> VecDeque<(f64, f64)> {
+    if series.len() <= 2 {
+        return series.clone();
+    }
+    let (first, last) = (series[0], series[series.len() - 1]);
+    let body: Vec<(f64, f64)> = series.iter().skip(1).take(series.len() - 2).copied().collect();
+    let buckets = (target / 2).max(1);
+    let bucket_len = body.len().div_ceil(buckets).max(1);
+
+    let mut decimated = VecDeque::with_capacity(buckets * 2 + 2);
+    decimated.push_back(first);
+    for bucket in body.chunks(bucket_len) {
+        let min_idx = (0..bucket.len()).min_by(|&a, &b| bucket[a].1.total_cmp(&bucket[b].1)).unwrap();
+        let max_idx = (0..bucket.len()).max_by(|&a, &b| bucket[a].1.total_cmp(&bucket[b].1)).unwrap();
+        let (earlier, later) = (min_idx.min(max_idx), min_idx.max(max_idx));
+        decimated.push_back(bucket[earlier]);
+        if later != earlier {
+            decimated.push_back(bucket[later]);
+        }
+    }
+    decimated.push_back(last);
+    decimated
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decimation_keeps_global_extremes_and_latest_point() {
+        let series: VecDeque<(f64, f64)> = (0..10_000)
+            .map(|i| {
+                let t = i as f64;
+                let spike = match i { 1_234 => 500.0, 7_777 => -400.0, _ => 0.0 };
+                (t, (t / 100.0).sin() + spike)
+            })
+            .collect();
+
+        let decimated = decimate_min_max(&series, 500);
+        assert!(decimated.len() <= 502);
+        assert!(decimated.iter().any(|&(t, v)| t == 1_234.0 && v > 499.0));
+        assert!(decimated.iter().any(|&(t, v)| t == 7_777.0 && v < -399.0));
+        assert_eq!(decimated.back(), series.back());
+        assert!(decimated.iter().zip(decimated.iter().skip(1)).all(|(a, b)| a.0 < b.0));
+
+        // Whole run stays visible once the history is full
+        let mut history = ChartHistory::new(100);
+        history.set_fit_whole_run(true);
+        history.equity = series;
+        history.trim_to_max();
+        assert_eq!(history.equity.front().map(|p| p.0), Some(0.0));
+        assert!(history.equity.len() <= 100);
+    }
+
+    #[test]
+    fn test_imbalance_markers_only_past_threshold_and_absent_without_imbalance() {
+        let mut history = ChartHistory::new(100);
+        let mut data = crate::ui::PerformanceData::default();
+        for (i, imbalance) in [0.2, 0.8, -0.9, 0.5].into_iter().enumerate() {
+            data.timestamp = i as f64;
+            data.mid_price = 100.0 + i as f64;
+            data.imbalance = Some(imbalance);
+            history.push(&data);
+        }
+        let (bid_heavy, ask_heavy) = history.imbalance_markers(0.6);
+        assert_eq!(bid_heavy, vec![[1.0, 101.0]]);
+        assert_eq!(ask_heavy, vec![[2.0, 102.0]]);
+
+        // Strategies that don't ship an imbalance never get markers
+        let mut history = ChartHistory::new(100);
+        data.imbalance = None;
+        for i in 0..4 {
+            data.timestamp = i as f64;
+            history.push(&data);
+        }
+        let (bid_heavy, ask_heavy) = history.imbalance_markers(0.0);
+        assert!(bid_heavy.is_empty() && ask_heavy.is_empty());
+    }
+
+    #[test]
+    fn test_smoothed_metrics_vary_less_than_raw() {
+        let mut history = ChartHistory::new(1_000);
+        history.set_smoothing_alpha(0.2);
+        let mut data = crate::ui::PerformanceData::default();
+        // Latency alternating around 100us with a +-40us jitter
+        for i in 0..200 {
+            data.timestamp = i as f64;
+            data.latency_micros = if i % 2 == 0 { 60 } else { 140 };
+            history.push(&data);
+        }
+
+        let point_to_point_variance = |series: &VecDeque<(f64, f64)>| {
+            let diffs: Vec<f64> = series.iter().zip(series.iter().skip(1)).map(|(a, b)| b.1 - a.1).collect();
+            let mean = diffs.iter().sum::<f64>() / diffs.len() as f64;
+            diffs.iter().map(|d| (d - mean).powi(2)).sum::<f64>() / diffs.len() as f64
+        };
+        assert_eq!(history.latency_smoothed.len(), history.latency.len());
+        assert!(point_to_point_variance(&history.latency_smoothed) < point_to_point_variance(&history.latency) / 10.0);
+        assert!((history.latency_smoothed.back().unwrap().1 - 100.0).abs() < 10.0);
+
+        // The toggle picks the series; the raw one is always kept
+        assert_eq!(history.metric_series(SmoothedMetric::Latency), &history.latency);
+        history.set_show_smoothed(true);
+        assert_eq!(history.metric_series(SmoothedMetric::Latency), &history.latency_smoothed);
+        // alpha 1 is no smoothing at all
+        history.set_smoothing_alpha(1.0);
+        assert_eq!(history.latency_smoothed, history.latency);
+    }
+
+    #[test]
+    fn test_buy_and_hold_tracks_mid_from_first_valid_price() {
+        let mut history = ChartHistory::new(100).with_initial_equity(10_000.0);
+        let mut data = crate::ui::PerformanceData::default();
+        // The book isn't valid yet on the first update
+        for (i, mid_price) in [0.0, 100.0, 100.0, 100.0].into_iter().enumerate() {
+            data.timestamp = i as f64;
+            data.mid_price = mid_price;
+            history.push(&data);
+        }
+        assert!(history.buy_and_hold.iter().all(|&(_, equity)| equity == 10_000.0));
+        assert_eq!(history.buy_and_hold_return_pct(), Some(0.0));
+
+        data.timestamp = 4.0;
+        data.mid_price = 102.0;
+        history.push(&data);
+        assert_eq!(history.buy_and_hold.back(), Some(&(4.0, 10_200.0)));
+        assert!((history.buy_and_hold_return_pct().unwrap() - 2.0).abs() < 1e-9);
     }
 }
@@ -1,5 +1,7 @@
+mod axis_lock;
 mod history;
 mod renderer;
 
-pub use history::ChartHistory;
+pub use axis_lock::YAxisLock;
+pub use history::{ChartHistory, SmoothedMetric};
 pub use renderer::ChartRenderer;
@@ -1,6 +1,7 @@
 use eframe::egui;
-use egui_plot::{Line, Plot, PlotPoints, Legend, Corner, AxisHints};
+use egui_plot::{HLine, Line, LineStyle, MarkerShape, Plot, PlotBounds, PlotPoints, Points, Legend, Corner, AxisHints};
 use std::collections::VecDeque;
+use super::YAxisLock;
 
 pub struct ChartRenderer;
 
@@ -31,6 +32,7 @@ impl ChartRenderer {
         name: &str,
         show_zero_line: bool,
         baseline: Option<f64>,
+        y_lock: &mut YAxisLock,
     ) {
         ui.label(egui::RichText::new(title).strong().size(14.0));
         
@@ -53,6 +55,15 @@ impl ChartRenderer {
             .show_axes([true, true])
             .custom_x_axes(vec![x_axis])
             .show(ui, |plot_ui| {
+                // Locked: x still follows the data, y stays on the fixed range
+                if let Some((y_min, y_max)) = y_lock.range() {
+                    let x_min = data.front().unwrap().0;
+                    let x_max = data.back().unwrap().0;
+                    plot_ui.set_plot_bounds(PlotBounds::from_min_max([x_min, y_min], [x_max, y_max]));
+                } else if y_lock.take_release() {
+                    plot_ui.set_auto_bounds(true.into());
+                }
+                
                 plot_ui.line(Line::new(points).color(color).name(name).width(2.0));
                 
                 if let Some(baseline_val) = baseline {
@@ -81,4 +92,143 @@ impl ChartRenderer {
                 }
             });
     }
+
+    /// Mid price chart with the open position's stop (red) and take (green) levels
+    pub fn render_price_chart(
+        ui: &mut egui::Ui,
+        data: &VecDeque<(f64, f64)>,
+        width: f32,
+        stop_price: Option<f64>,
+        take_price: Option<f64>,
+        imbalance_markers: (Vec<[f64; 2]>, Vec<[f64; 2]>),
+    ) {
+        ui.label(egui::RichText::new("Mid Price").strong().size(14.0));
+        
+        if data.is_empty() {
+            ui.add_sized([width, 180.0], egui::Label::new("No data available"));
+            return;
+        }
+        
+        let points: PlotPoints = data.iter().map(|(t, v)| [*t, *v]).collect();
+        
+        let x_axis = AxisHints::new_x()
+            .label("Time")
+            .formatter(|mark, _range| Self::format_time_axis(mark.value));
+
+        Plot::new("price_plot")
+            .legend(Legend::default().position(Corner::LeftTop))
+            .height(180.0)
+            .width(width)
+            .show_axes([true, true])
+            .custom_x_axes(vec![x_axis])
+            .show(ui, |plot_ui| {
+                plot_ui.line(Line::new(points)
+                    .color(egui::Color32::from_rgb(200, 100, 255))
+                    .name("Mid Price")
+                    .width(2.0));
+                
+                if let Some(stop) = stop_price {
+                    plot_ui.hline(HLine::new(stop)
+                        .color(egui::Color32::from_rgb(255, 80, 80))
+                        .name("Stop")
+                        .style(LineStyle::Dashed { length: 10.0 }));
+                }
+                
+                if let Some(take) = take_price {
+                    plot_ui.hline(HLine::new(take)
+                        .color(egui::Color32::from_rgb(80, 200, 120))
+                        .name("Take")
+                        .style(LineStyle::Dashed { length: 10.0 }));
+                }
+                
+                let (bid_heavy, ask_heavy) = imbalance_markers;
+                if !bid_heavy.is_empty() {
+                    plot_ui.points(Points::new(bid_heavy)
+                        .color(egui::Color32::from_rgb(80, 200, 120))
+                        .shape(MarkerShape::Up)
+                        .radius(4.0)
+                        .name("Bid-Heavy Book"));
+                }
+                if !ask_heavy.is_empty() {
+                    plot_ui.points(Points::new(ask_heavy)
+                        .color(egui::Color32::from_rgb(255, 80, 80))
+                        .shape(MarkerShape::Down)
+                        .radius(4.0)
+                        .name("Ask-Heavy Book"));
+                }
+            });
+    }
+
+    /// Equity curve with the initial-equity baseline, an optional reference run (gray) and a
+    /// marker at every fill update
+    pub fn render_equity_chart(
+        ui: &mut egui::Ui,
+        data: &VecDeque<(f64, f64)>,
+        width: f32,
+        baseline: f64,
+        reference: Option<&[(f64, f64)]>,
+        fill_markers: &VecDeque<(f64, f64)>,
+        buy_and_hold: Option<&VecDeque<(f64, f64)>>,
+    ) {
+        ui.label(egui::RichText::new("Equity Curve").strong().size(14.0));
+        
+        if data.is_empty() {
+            ui.add_sized([width, 180.0], egui::Label::new("No data available"));
+            return;
+        }
+        
+        let points: PlotPoints = data.iter().map(|(t, v)| [*t, *v]).collect();
+        let start = data.front().unwrap().0;
+        let end = data.back().unwrap().0;
+        
+        let x_axis = AxisHints::new_x()
+            .label("Time")
+            .formatter(|mark, _range| Self::format_time_axis(mark.value));
+
+        Plot::new("equity_plot")
+            .legend(Legend::default().position(Corner::LeftTop))
+            .height(180.0)
+            .width(width)
+            .show_axes([true, true])
+            .custom_x_axes(vec![x_axis])
+            .show(ui, |plot_ui| {
+                if let Some(reference) = reference {
+                    let reference_pts: PlotPoints = reference.iter().map(|(t, v)| [*t, *v]).collect();
+                    plot_ui.line(Line::new(reference_pts)
+                        .color(egui::Color32::GRAY)
+                        .name("Reference")
+                        .width(1.5));
+                }
+                if let Some(buy_and_hold) = buy_and_hold.filter(|series| !series.is_empty()) {
+                    let buy_and_hold_pts: PlotPoints = buy_and_hold.iter().map(|(t, v)| [*t, *v]).collect();
+                    plot_ui.line(Line::new(buy_and_hold_pts)
+                        .color(egui::Color32::LIGHT_GRAY)
+                        .name("Buy & Hold")
+                        .style(LineStyle::Dotted { spacing: 6.0 })
+                        .width(1.5));
+                }
+                
+                plot_ui.line(Line::new(points)
+                    .color(egui::Color32::from_rgb(0, 150, 255))
+                    .name("Equity")
+                    .width(2.0));
+                
+                let baseline_pts: PlotPoints = vec![[start, baseline], [end, baseline]].into();
+                plot_ui.line(
+                    Line::new(baseline_pts)
+                        .color(egui::Color32::GRAY)
+                        .name("Baseline")
+                        .style(LineStyle::Dashed { length: 10.0 })
+                );
+                
+                if !fill_markers.is_empty() {
+                    let fill_pts: PlotPoints = fill_markers.iter().map(|(t, v)| [*t, *v]).collect();
+                    plot_ui.points(Points::new(fill_pts)
+                        .color(egui::Color32::from_rgb(255, 200, 0))
+                        .shape(MarkerShape::Diamond)
+                        .radius(3.0)
+                        .name("Fills"));
+                }
+            });
+    }
 }
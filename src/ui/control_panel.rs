@@ -188,6 +188,11 @@ impl ControlPanel {
                 if ui.add_enabled(can_skip, egui::Button::new("⏭ Skip")).clicked() {
                     let _ = self.command_tx.send(StrategyCommand::Skip);
                 }
+                
+                // Run to End: When Running or Paused (charts freeze until the final update)
+                if ui.add_enabled(can_stop, egui::Button::new("⏩ Run to End")).clicked() {
+                    let _ = self.command_tx.send(StrategyCommand::RunToEnd);
+                }
             });
             
             ui.separator();
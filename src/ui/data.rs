@@ -4,7 +4,37 @@ pub struct OrderBookLevel {
     pub quantity: f64,
 }
 
-#[derive(Debug, Clone)]
+/// Closed trades and net PnL split by position direction
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SideAttribution {
+    pub long_trades: usize,
+    pub long_pnl: f64,
+    pub short_trades: usize,
+    pub short_pnl: f64,
+}
+
+/// Market-making round trips: average sell-minus-buy price of matched fills vs the quoted spread
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SpreadCapture {
+    /// Per unit, over the matched quantity
+    pub captured: f64,
+    /// Average quoted ask-minus-bid
+    pub quoted: f64,
+    pub matched_qty: f64,
+}
+
+/// One strategy's share of a portfolio run
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SleeveBreakdown {
+    pub strategy_name: String,
+    /// Capital allocated to the sleeve at the start of the run
+    pub capital: f64,
+    pub equity: f64,
+    pub position: f64,
+    pub num_trades: usize,
+}
+
+#[derive(Debug, Clone, Default)]
 pub struct PerformanceData {
     pub timestamp: f64,
     pub equity: f64,
@@ -21,4 +51,28 @@ pub struct PerformanceData {
     pub latency_micros: u64,
     pub bids: Vec<OrderBookLevel>,
     pub asks: Vec<OrderBookLevel>,
+    /// Stop-loss price of the open position (None when flat)
+    pub stop_price: Option<f64>,
+    /// Take-profit price of the open position (None when flat)
+    pub take_price: Option<f64>,
+    /// High-water-mark performance fees already taken off `equity`
+    pub performance_fees_accrued: f64,
+    /// Short borrow costs already taken off `equity`
+    pub borrow_costs: f64,
+    /// Trading fees already taken off the PnL (negative = net rebates)
+    pub fees_paid: f64,
+    /// Long/short breakdown (None for strategies that don't track it)
+    pub attribution: Option<SideAttribution>,
+    /// Realized vs quoted spread (None for non-MM strategies or before a round trip)
+    pub spread_capture: Option<SpreadCapture>,
+    /// Book imbalance in [-1, 1] (positive = bid-heavy) for strategies that compute it
+    pub imbalance: Option<f64>,
+    /// Sent right after a fill, out of the regular cadence (marks the exact fill time)
+    pub fill_update: bool,
+    /// Strength of the current entry signal in [0, 1] for strategies that grade it
+    pub signal_confidence: Option<f64>,
+    /// Per-strategy split of a portfolio run (empty for single-strategy runs)
+    pub sleeves: Vec<SleeveBreakdown>,
+    /// Realized simulation seconds per wall-clock second (see `ReplayRateMeter`)
+    pub replay_rate: f64,
 }
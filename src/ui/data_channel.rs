@@ -0,0 +1,87 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use crossbeam_channel::{bounded, Receiver, Sender, TrySendError};
+
+use super::data::PerformanceData;
+
+/// Bounded runner -> GUI data channel. When the GUI falls behind and the channel fills up,
+/// the oldest queued update is discarded so the newest one always gets through; discarded
+/// updates are counted for the settings panel.
+#[derive(Debug, Clone)]
+pub struct DataSender {
+    tx: Sender<PerformanceData>,
+    /// Handle used only to evict the oldest update when full
+    evict_rx: Receiver<PerformanceData>,
+    dropped: Arc<AtomicU64>,
+}
+
+/// Bounded channel holding at most `capacity` updates (at least 1)
+pub fn data_channel(capacity: usize) -> (DataSender, Receiver<PerformanceData>) {
+    let (tx, rx) = bounded(capacity.max(1));
+    let sender = DataSender {
+        tx,
+        evict_rx: rx.clone(),
+        dropped: Arc::new(AtomicU64::new(0)),
+    };
+    (sender, rx)
+}
+
+impl DataSender {
+    /// Never blocks: on a full channel, drops the oldest queued update to make room
+    pub fn send_latest(&self, data: PerformanceData) {
+        let mut data = data;
+        loop {
+            match self.tx.try_send(data) {
+                Ok(()) | Err(TrySendError::Disconnected(_)) => return,
+                Err(TrySendError::Full(rejected)) => {
+                    if self.evict_rx.try_recv().is_ok() {
+                        self.dropped.fetch_add(1, Ordering::Relaxed);
+                    }
+                    data = rejected;
+                }
+            }
+        }
+    }
+
+    /// Shared count of updates discarded so far
+    pub fn dropped_counter(&self) -> Arc<AtomicU64> {
+        Arc::clone(&self.dropped)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn update(timestamp: f64) -> PerformanceData {
+        PerformanceData {
+            timestamp,
+            equity: 10_000.0,
+            mid_price: 100.0,
+            strategy_name: "Test".to_string(),
+            ..PerformanceData::default()
+        }
+    }
+
+    #[test]
+    fn test_flooded_channel_keeps_latest_and_counts_drops() {
+        let (sender, rx) = data_channel(4);
+        let dropped = sender.dropped_counter();
+
+        for i in 0..100 {
+            sender.send_latest(update(i as f64));
+            // Whatever the GUI would read now ends with the update just sent
+            assert_eq!(rx.len(), (i + 1).min(4));
+        }
+
+        let received: Vec<f64> = rx.try_iter().map(|data| data.timestamp).collect();
+        assert_eq!(received, vec![96.0, 97.0, 98.0, 99.0]);
+        assert_eq!(dropped.load(Ordering::Relaxed), 96);
+
+        // Room again: nothing dropped
+        sender.send_latest(update(100.0));
+        assert_eq!(rx.try_recv().unwrap().timestamp, 100.0);
+        assert_eq!(dropped.load(Ordering::Relaxed), 96);
+    }
+}
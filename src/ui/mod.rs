@@ -2,11 +2,23 @@ mod app;
 mod charts;
 mod control_panel;
 mod data;
+mod data_channel;
 mod orderbook;
+mod price_format;
+mod replay_rate;
+mod resample;
+mod run_export;
+mod run_summary;
+mod series_export;
 mod stats_panel;
+mod trade_log;
+mod update_cadence;
 
 pub use app::PerformanceMonitor;
-pub use data::{PerformanceData, OrderBookLevel};
+pub use data::{PerformanceData, OrderBookLevel, SideAttribution, SpreadCapture, SleeveBreakdown};
+pub use data_channel::{DataSender, data_channel};
+pub use update_cadence::UpdateCadence;
+pub use replay_rate::ReplayRateMeter;
 
 use crate::strategy::StrategyType;
 
@@ -1,15 +1,58 @@
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
 use eframe::egui;
 use egui_plot::{Plot, PlotPoints, Line, Legend, Corner, VLine};
-use super::data::PerformanceData;
-use crate::config::PRICE_DECIMAL_PLACES;
+use super::data::{OrderBookLevel, PerformanceData};
+use super::price_format::format_price;
+use crate::config::{PRICE_DISPLAY_MULTIPLIER, GUI_DEPTH_CHART_NOTIONAL};
+
+/// Hard cap on the levels the view ever renders, regardless of the display setting
+/// or how deep the incoming snapshot is
+const DEFAULT_MAX_DEPTH_LEVELS: usize = 50;
+
+/// What the depth chart accumulates along the y-axis
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DepthMode {
+    Quantity,
+    /// price × quantity, for comparing liquidity across price levels
+    Notional,
+}
+
+impl DepthMode {
+    fn axis_label(self) -> &'static str {
+        match self {
+            DepthMode::Quantity => "Cumulative quantity",
+            DepthMode::Notional => "Cumulative notional",
+        }
+    }
+}
+
+/// Depth chart step lines for one snapshot, keyed by a fingerprint of the levels they were built from
+struct DepthCurves {
+    key: u64,
+    bid_points: Vec<[f64; 2]>,
+    ask_points: Vec<[f64; 2]>,
+}
 
 pub struct OrderbookView {
     depth_levels: usize,
+    max_levels: usize,
+    /// Factor prices are multiplied by for display
+    price_multiplier: f64,
+    depth_mode: DepthMode,
+    curves: Option<DepthCurves>,
 }
 
 impl OrderbookView {
     pub fn new(depth_levels: usize) -> Self {
-        Self { depth_levels }
+        Self {
+            depth_levels,
+            max_levels: DEFAULT_MAX_DEPTH_LEVELS,
+            price_multiplier: PRICE_DISPLAY_MULTIPLIER,
+            depth_mode: if GUI_DEPTH_CHART_NOTIONAL { DepthMode::Notional } else { DepthMode::Quantity },
+            curves: None,
+        }
     }
 
     pub fn set_depth_levels(&mut self, levels: usize) {
@@ -20,11 +63,37 @@ impl OrderbookView {
         self.depth_levels
     }
 
-    pub fn render(&self, ui: &mut egui::Ui, data: Option<&PerformanceData>) {
+    pub fn price_multiplier(&self) -> f64 {
+        self.price_multiplier
+    }
+
+    pub fn set_price_multiplier(&mut self, multiplier: f64) {
+        self.price_multiplier = multiplier;
+    }
+
+    pub fn depth_mode(&self) -> DepthMode {
+        self.depth_mode
+    }
+
+    pub fn set_depth_mode(&mut self, mode: DepthMode) {
+        self.depth_mode = mode;
+    }
+
+    #[allow(dead_code)]
+    pub fn set_max_levels(&mut self, levels: usize) {
+        self.max_levels = levels;
+    }
+
+    /// Levels actually shown: the display setting, capped and clamped to the shallower book side
+    fn visible_depth(&self, available: usize) -> usize {
+        self.depth_levels.min(self.max_levels).min(available)
+    }
+
+    pub fn render(&mut self, ui: &mut egui::Ui, data: Option<&PerformanceData>) {
         ui.heading("📖 Order Book (Real-time)");
         
         if let Some(data) = data {
-            let depth = self.depth_levels.min(data.asks.len().min(data.bids.len()));
+            let depth = self.visible_depth(data.asks.len().min(data.bids.len()));
             let orderbook_height = 250.0;
             
             ui.columns(2, |columns| {
@@ -53,7 +122,7 @@ impl OrderbookView {
                                                 let bid = &data.bids[i];
                                                 ui.label(egui::RichText::new(format!("{:.4}", bid.quantity))
                                                     .color(egui::Color32::from_rgb(100, 200, 100)).size(10.0));
-                                                ui.label(egui::RichText::new(format!("{:.prec$}", bid.price, prec = PRICE_DECIMAL_PLACES))
+                                                ui.label(egui::RichText::new(format_price(bid.price, self.price_multiplier))
                                                     .color(egui::Color32::from_rgb(100, 200, 100)).strong().size(10.0));
                                             } else {
                                                 ui.label("-");
@@ -62,7 +131,7 @@ impl OrderbookView {
                                             
                                             if i < data.asks.len() {
                                                 let ask = &data.asks[i];
-                                                ui.label(egui::RichText::new(format!("{:.prec$}", ask.price, prec = PRICE_DECIMAL_PLACES))
+                                                ui.label(egui::RichText::new(format_price(ask.price, self.price_multiplier))
                                                     .color(egui::Color32::from_rgb(255, 100, 100)).strong().size(10.0));
                                                 ui.label(egui::RichText::new(format!("{:.4}", ask.quantity))
                                                     .color(egui::Color32::from_rgb(255, 100, 100)).size(10.0));
@@ -78,7 +147,7 @@ impl OrderbookView {
                                             let spread_bps = (spread / data.mid_price) * 10000.0;
                                             
                                             ui.label("");
-                                            ui.label(egui::RichText::new(format!("Spread: {:.prec$}", spread, prec = PRICE_DECIMAL_PLACES))
+                                            ui.label(egui::RichText::new(format!("Spread: {}", format_price(spread, self.price_multiplier)))
                                                 .small().weak());
                                             ui.label(egui::RichText::new(format!("({:.2}bps)", spread_bps))
                                                 .small().weak());
@@ -107,64 +176,26 @@ impl OrderbookView {
         }
     }
     
-    fn render_depth_chart(&self, ui: &mut egui::Ui, data: &PerformanceData, depth: usize, height: f32) {
+    fn render_depth_chart(&mut self, ui: &mut egui::Ui, data: &PerformanceData, depth: usize, height: f32) {
         if data.bids.is_empty() || data.asks.is_empty() {
             ui.add_sized([ui.available_width(), height], egui::Label::new("No depth data"));
             return;
         }
         
         let mid_price = data.mid_price;
-        
-        // Calculate cumulative quantities for bids (sorted by price descending, so reverse for cumulative)
-        let mut bid_cumulative: Vec<[f64; 2]> = Vec::new();
-        let mut cumulative_qty = 0.0;
-        
-        // Build bid depth from mid price going left (lower prices)
-        // Bids are typically sorted highest to lowest, so we iterate and accumulate
-        for i in 0..depth.min(data.bids.len()) {
-            let bid = &data.bids[i];
-            cumulative_qty += bid.quantity;
-            bid_cumulative.push([bid.price, cumulative_qty]);
-        }
-        // Reverse to have ascending price order for proper line drawing
-        bid_cumulative.reverse();
-        
-        // Add starting point at mid price with 0 cumulative
-        let mut bid_points: Vec<[f64; 2]> = vec![[mid_price, 0.0]];
-        // Add step-like points for bids (going from mid price to lower prices)
-        for i in 0..bid_cumulative.len() {
-            let [price, qty] = bid_cumulative[i];
-            // Add horizontal line to this price level
-            if i == 0 {
-                bid_points.push([price, 0.0]);
-            }
-            bid_points.push([price, qty]);
-            // Add vertical step
-            if i + 1 < bid_cumulative.len() {
-                bid_points.push([bid_cumulative[i + 1][0], qty]);
-            }
-        }
-        
-        // Calculate cumulative quantities for asks (sorted by price ascending)
-        let mut ask_points: Vec<[f64; 2]> = vec![[mid_price, 0.0]];
-        cumulative_qty = 0.0;
-        
-        for i in 0..depth.min(data.asks.len()) {
-            let ask = &data.asks[i];
-            // Add step-like points
-            if i == 0 {
-                ask_points.push([ask.price, 0.0]);
-            }
-            cumulative_qty += ask.quantity;
-            ask_points.push([ask.price, cumulative_qty]);
-            // Add horizontal step to next price
-            if i + 1 < depth.min(data.asks.len()) {
-                ask_points.push([data.asks[i + 1].price, cumulative_qty]);
-            }
+
+        // Only rebuild the cumulative curves when the snapshot actually changed
+        let key = snapshot_key(&data.bids, &data.asks, mid_price, depth, self.depth_mode);
+        if self.curves.as_ref().is_none_or(|curves| curves.key != key) {
+            let (bid_points, ask_points) = depth_curves(&data.bids, &data.asks, mid_price, depth, self.depth_mode);
+            self.curves = Some(DepthCurves { key, bid_points, ask_points });
         }
+        let Some(curves) = self.curves.as_ref() else {
+            return;
+        };
         
-        let bid_line: PlotPoints = bid_points.into_iter().collect();
-        let ask_line: PlotPoints = ask_points.into_iter().collect();
+        let bid_line: PlotPoints = curves.bid_points.iter().copied().collect();
+        let ask_line: PlotPoints = curves.ask_points.iter().copied().collect();
         
         let chart_width = ui.available_width();
         
@@ -173,6 +204,7 @@ impl OrderbookView {
             .height(height)
             .width(chart_width)
             .show_axes([true, true])
+            .y_axis_label(self.depth_mode.axis_label())
             .allow_drag(false)
             .allow_zoom(false)
             .allow_scroll(false)
@@ -200,8 +232,164 @@ impl OrderbookView {
                     VLine::new(mid_price)
                         .color(egui::Color32::from_rgb(255, 255, 100))
                         .style(egui_plot::LineStyle::Dashed { length: 8.0 })
-                        .name(format!("Mid: {:.prec$}", mid_price, prec = PRICE_DECIMAL_PLACES))
+                        .name(format!("Mid: {}", format_price(mid_price, self.price_multiplier)))
                 );
             });
     }
 }
+
+/// Fingerprint of the levels a depth chart is built from: depth, mode and side lengths plus the
+/// bit patterns of every shown price/quantity and the mid price
+fn snapshot_key(bids: &[OrderBookLevel], asks: &[OrderBookLevel], mid_price: f64, depth: usize,
+                mode: DepthMode) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    depth.hash(&mut hasher);
+    mode.hash(&mut hasher);
+    bids.len().hash(&mut hasher);
+    asks.len().hash(&mut hasher);
+    mid_price.to_bits().hash(&mut hasher);
+    for level in bids.iter().take(depth).chain(asks.iter().take(depth)) {
+        level.price.to_bits().hash(&mut hasher);
+        level.quantity.to_bits().hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+/// Step lines of cumulative quantity (or notional) from the mid price outwards, over the first
+/// `depth` levels of each side (bids highest first, asks lowest first)
+fn depth_curves(
+    bids: &[OrderBookLevel],
+    asks: &[OrderBookLevel],
+    mid_price: f64,
+    depth: usize,
+    mode: DepthMode,
+) -> (Vec<[f64; 2]>, Vec<[f64; 2]>) {
+    let amount = |level: &OrderBookLevel| match mode {
+        DepthMode::Quantity => level.quantity,
+        DepthMode::Notional => level.price * level.quantity,
+    };
+
+    // Calculate cumulative quantities for bids (sorted by price descending, so reverse for cumulative)
+    let mut bid_cumulative: Vec<[f64; 2]> = Vec::new();
+    let mut cumulative_qty = 0.0;
+    
+    // Build bid depth from mid price going left (lower prices)
+    // Bids are typically sorted highest to lowest, so we iterate and accumulate
+    for bid in bids.iter().take(depth) {
+        cumulative_qty += amount(bid);
+        bid_cumulative.push([bid.price, cumulative_qty]);
+    }
+    // Reverse to have ascending price order for proper line drawing
+    bid_cumulative.reverse();
+    
+    // Add starting point at mid price with 0 cumulative
+    let mut bid_points: Vec<[f64; 2]> = vec![[mid_price, 0.0]];
+    // Add step-like points for bids (going from mid price to lower prices)
+    for i in 0..bid_cumulative.len() {
+        let [price, qty] = bid_cumulative[i];
+        // Add horizontal line to this price level
+        if i == 0 {
+            bid_points.push([price, 0.0]);
+        }
+        bid_points.push([price, qty]);
+        // Add vertical step
+        if i + 1 < bid_cumulative.len() {
+            bid_points.push([bid_cumulative[i + 1][0], qty]);
+        }
+    }
+    
+    // Calculate cumulative quantities for asks (sorted by price ascending)
+    let mut ask_points: Vec<[f64; 2]> = vec![[mid_price, 0.0]];
+    cumulative_qty = 0.0;
+    let ask_depth = depth.min(asks.len());
+    
+    for i in 0..ask_depth {
+        let ask = &asks[i];
+        // Add step-like points
+        if i == 0 {
+            ask_points.push([ask.price, 0.0]);
+        }
+        cumulative_qty += amount(ask);
+        ask_points.push([ask.price, cumulative_qty]);
+        // Add horizontal step to next price
+        if i + 1 < ask_depth {
+            ask_points.push([asks[i + 1].price, cumulative_qty]);
+        }
+    }
+
+    (bid_points, ask_points)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn level(price: f64, quantity: f64) -> OrderBookLevel {
+        OrderBookLevel { price, quantity }
+    }
+
+    fn snapshot() -> (Vec<OrderBookLevel>, Vec<OrderBookLevel>) {
+        (
+            vec![level(99.5, 1.0), level(99.0, 2.0), level(98.5, 3.0)],
+            vec![level(100.5, 1.5), level(101.0, 0.5), level(101.5, 4.0)],
+        )
+    }
+
+    #[test]
+    fn test_depth_curves_match_fixed_snapshot() {
+        let (bids, asks) = snapshot();
+        let (bids, asks) = depth_curves(&bids, &asks, 100.0, 3, DepthMode::Quantity);
+
+        // Same points the chart drew before the curves were cached
+        assert_eq!(bids, vec![
+            [100.0, 0.0], [98.5, 0.0], [98.5, 6.0], [99.0, 6.0],
+            [99.0, 3.0], [99.5, 3.0], [99.5, 1.0],
+        ]);
+        assert_eq!(asks, vec![
+            [100.0, 0.0], [100.5, 0.0], [100.5, 1.5], [101.0, 1.5],
+            [101.0, 2.0], [101.5, 2.0], [101.5, 6.0],
+        ]);
+    }
+
+    #[test]
+    fn test_notional_mode_accumulates_price_times_quantity() {
+        let (bids, asks) = snapshot();
+        let (bid_points, ask_points) = depth_curves(&bids, &asks, 100.0, 3, DepthMode::Notional);
+
+        // Each level's step height is the running sum of price × qty up to it
+        let running_notional = |levels: &[OrderBookLevel]| -> Vec<f64> {
+            levels.iter().scan(0.0, |sum, level| { *sum += level.price * level.quantity; Some(*sum) }).collect()
+        };
+        assert_eq!(running_notional(&bids), vec![99.5, 297.5, 593.0]);
+        assert_eq!(running_notional(&asks), vec![150.75, 201.25, 607.25]);
+        assert_eq!(bid_points, vec![
+            [100.0, 0.0], [98.5, 0.0], [98.5, 593.0], [99.0, 593.0],
+            [99.0, 297.5], [99.5, 297.5], [99.5, 99.5],
+        ]);
+        assert_eq!(ask_points, vec![
+            [100.0, 0.0], [100.5, 0.0], [100.5, 150.75], [101.0, 150.75],
+            [101.0, 201.25], [101.5, 201.25], [101.5, 607.25],
+        ]);
+
+        // Switching modes invalidates the cached curves
+        assert_ne!(snapshot_key(&bids, &asks, 100.0, 3, DepthMode::Quantity),
+                   snapshot_key(&bids, &asks, 100.0, 3, DepthMode::Notional));
+    }
+
+    #[test]
+    fn test_max_levels_caps_depth_and_key_tracks_snapshot() {
+        let mut view = OrderbookView::new(10);
+        assert_eq!(view.visible_depth(3), 3);
+        view.set_max_levels(2);
+        assert_eq!(view.visible_depth(3), 2);
+
+        let (mut bids, mut asks) = snapshot();
+        let key = snapshot_key(&bids, &asks, 100.0, 2, DepthMode::Quantity);
+        assert_eq!(snapshot_key(&bids, &asks, 100.0, 2, DepthMode::Quantity), key);
+        // Levels past the shown depth don't invalidate the cache; shown ones do
+        bids[2].quantity = 9.0;
+        assert_eq!(snapshot_key(&bids, &asks, 100.0, 2, DepthMode::Quantity), key);
+        asks[0].quantity = 9.0;
+        assert_ne!(snapshot_key(&bids, &asks, 100.0, 2, DepthMode::Quantity), key);
+    }
+}
@@ -0,0 +1,43 @@
+use crate::config::PRICE_DECIMAL_PLACES;
+
+/// Significant digits kept for prices below one, however small the tick
+const MIN_SIGNIFICANT_DIGITS: usize = 4;
+const MAX_DECIMALS: usize = 12;
+
+/// `price * multiplier` with the tick's decimals (fewer as the multiplier shifts the point),
+/// widened so sub-unit prices keep `MIN_SIGNIFICANT_DIGITS` instead of rounding to zero
+pub fn format_price(price: f64, multiplier: f64) -> String {
+    format_price_with(price, multiplier, PRICE_DECIMAL_PLACES)
+}
+
+fn format_price_with(price: f64, multiplier: f64, tick_decimals: usize) -> String {
+    let scaled = price * multiplier;
+    let shift = multiplier.log10().round().max(0.0) as usize;
+    let mut decimals = tick_decimals.saturating_sub(shift);
+
+    let magnitude = scaled.abs();
+    if magnitude > 0.0 && magnitude < 1.0 {
+        let leading_zeros = ((-magnitude.log10()).ceil() as usize).saturating_sub(1);
+        decimals = decimals.max(leading_zeros + MIN_SIGNIFICANT_DIGITS);
+    }
+    format!("{:.prec$}", scaled, prec = decimals.min(MAX_DECIMALS))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tiny_price_keeps_significant_digits() {
+        assert_eq!(format_price_with(0.00001234, 1.0, 2), "0.00001234");
+        assert_eq!(format_price_with(0.00001234, 1.0, 5), "0.00001234");
+        assert_eq!(format_price_with(0.01, 1.0, 2), "0.01000");
+
+        // Multiplier shifts the point and drops the decimals it made redundant
+        assert_eq!(format_price_with(0.00001234, 1000.0, 5), "0.01234");
+        assert_eq!(format_price_with(0.0123, 1000.0, 5), "12.30");
+
+        assert_eq!(format_price_with(64_123.5, 1.0, 2), "64123.50");
+        assert_eq!(format_price_with(0.0, 1.0, 2), "0.00");
+    }
+}
@@ -0,0 +1,85 @@
+use std::time::{Duration, Instant};
+
+/// Realized replay speed: simulated seconds per wall-clock second, measured over `window`
+/// so it shows whether the requested speed is actually reached or the run is CPU-bound.
+/// Before the first full window has passed the rate since the first sample is reported.
+#[derive(Debug, Clone)]
+pub struct ReplayRateMeter {
+    window: Duration,
+    /// Simulation timestamp (ns) and wall time the current window started at
+    anchor: Option<(i64, Instant)>,
+    /// Rate of the last completed window (None until one completes)
+    rate: Option<f64>,
+    provisional: f64,
+}
+
+impl ReplayRateMeter {
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            anchor: None,
+            rate: None,
+            provisional: 0.0,
+        }
+    }
+
+    /// Feed the current simulation timestamp (cheap enough to call every step, which keeps
+    /// run-to-end batches measurable)
+    pub fn observe(&mut self, sim_ns: i64) {
+        self.observe_at(sim_ns, Instant::now());
+    }
+
+    fn observe_at(&mut self, sim_ns: i64, now: Instant) {
+        // The engine reports i64::MAX before the first step
+        if sim_ns == i64::MAX {
+            return;
+        }
+        let Some((anchor_ns, anchor_wall)) = self.anchor else {
+            self.anchor = Some((sim_ns, now));
+            return;
+        };
+        let wall_secs = now.duration_since(anchor_wall).as_secs_f64();
+        if wall_secs <= 0.0 {
+            return;
+        }
+
+        let rate = (sim_ns - anchor_ns) as f64 / 1e9 / wall_secs;
+        if now.duration_since(anchor_wall) >= self.window {
+            self.rate = Some(rate);
+            self.anchor = Some((sim_ns, now));
+        } else if self.rate.is_none() {
+            self.provisional = rate;
+        }
+    }
+
+    /// Simulated seconds per wall second (0 before any progress was measured)
+    pub fn rate(&self) -> f64 {
+        self.rate.unwrap_or(self.provisional)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rate_is_sim_time_over_wall_time_per_window() {
+        let start = Instant::now();
+        let mut meter = ReplayRateMeter::new(Duration::from_secs(1));
+        meter.observe_at(i64::MAX, start);
+        meter.observe_at(0, start);
+        assert_eq!(meter.rate(), 0.0);
+
+        // Half a window in: 10 simulated seconds in 0.5s so far
+        meter.observe_at(10_000_000_000, start + Duration::from_millis(500));
+        assert!((meter.rate() - 20.0).abs() < 1e-9);
+
+        // The window completes at 37x; the next one only replaces it once it completes too
+        meter.observe_at(37_000_000_000, start + Duration::from_secs(1));
+        assert!((meter.rate() - 37.0).abs() < 1e-9);
+        meter.observe_at(37_500_000_000, start + Duration::from_millis(1_500));
+        assert!((meter.rate() - 37.0).abs() < 1e-9);
+        meter.observe_at(39_000_000_000, start + Duration::from_secs(2));
+        assert!((meter.rate() - 2.0).abs() < 1e-9);
+    }
+}
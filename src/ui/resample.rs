@@ -0,0 +1,67 @@
+/// Evenly spaced points from an irregularly sampled series: one every `interval_secs` starting
+/// at the first sample, each carrying the last value at or before it (a non-positive interval
+/// passes samples through unchanged)
+pub struct Resampler<T> {
+    interval_secs: f64,
+    origin: Option<f64>,
+    /// Index of the next grid point to emit
+    next_index: u64,
+    last: Option<T>,
+}
+
+impl<T: Clone> Resampler<T> {
+    pub fn new(interval_secs: f64) -> Self {
+        Self { interval_secs, origin: None, next_index: 0, last: None }
+    }
+
+    /// Feed one sample (timestamps non-decreasing) and return the grid points it completes
+    pub fn push(&mut self, timestamp: f64, value: T) -> Vec<(f64, T)> {
+        if self.interval_secs <= 0.0 {
+            return vec![(timestamp, value)];
+        }
+        let origin = *self.origin.get_or_insert(timestamp);
+        let mut points = Vec::new();
+        let mut next_ts = origin + self.next_index as f64 * self.interval_secs;
+
+        // Grid points before this sample keep the previous value
+        if let Some(last) = &self.last {
+            while next_ts < timestamp {
+                points.push((next_ts, last.clone()));
+                self.next_index += 1;
+                next_ts = origin + self.next_index as f64 * self.interval_secs;
+            }
+        }
+        while next_ts <= timestamp {
+            points.push((next_ts, value.clone()));
+            self.next_index += 1;
+            next_ts = origin + self.next_index as f64 * self.interval_secs;
+        }
+        self.last = Some(value);
+        points
+    }
+}
+
+/// `(timestamp, value)` series resampled to `interval_secs` (see `Resampler`)
+pub fn resample_carry_forward(series: &[(f64, f64)], interval_secs: f64) -> Vec<(f64, f64)> {
+    let mut resampler = Resampler::new(interval_secs);
+    series.iter().flat_map(|&(timestamp, value)| resampler.push(timestamp, value)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_irregular_series_to_minute_points() {
+        let series = [(0.0, 100.0), (10.0, 101.0), (75.0, 102.0), (80.0, 102.5), (130.0, 103.0), (185.0, 104.0)];
+        let resampled = resample_carry_forward(&series, 60.0);
+
+        // 0..=185s holds grid points 0, 60, 120 and 180, each with the last value at or before it
+        assert_eq!(resampled, vec![(0.0, 100.0), (60.0, 101.0), (120.0, 102.5), (180.0, 103.0)]);
+        // A gap spanning several intervals carries the value across all of them
+        let gap = resample_carry_forward(&[(0.0, 1.0), (200.0, 2.0)], 60.0);
+        assert_eq!(gap, vec![(0.0, 1.0), (60.0, 1.0), (120.0, 1.0), (180.0, 1.0)]);
+        // Off: passthrough
+        assert_eq!(resample_carry_forward(&series, 0.0), series.to_vec());
+    }
+}
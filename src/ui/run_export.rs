@@ -0,0 +1,66 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Equity curve of a finished (or in-progress) run, saved as JSON so a later run can be
+/// overlaid against it
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RunExport {
+    pub strategy_name: String,
+    pub initial_equity: f64,
+    /// (simulation seconds, equity)
+    pub equity_curve: Vec<(f64, f64)>,
+}
+
+impl RunExport {
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let json = serde_json::to_string_pretty(self)?;
+        std::fs::write(path, json)
+            .with_context(|| format!("Failed to write run export: {}", path.display()))
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let json = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read run export: {}", path.display()))?;
+        serde_json::from_str(&json)
+            .with_context(|| format!("Invalid run export: {}", path.display()))
+    }
+
+    /// Return of the last equity point in percent (None for an empty curve)
+    pub fn final_return_pct(&self) -> Option<f64> {
+        let (_, equity) = self.equity_curve.last()?;
+        Some((equity - self.initial_equity) / self.initial_equity * 100.0)
+    }
+
+    /// Equity curve shifted so its first point sits at `start_ts` on the x-axis
+    pub fn aligned_curve(&self, start_ts: f64) -> Vec<(f64, f64)> {
+        let offset = match self.equity_curve.first() {
+            Some((ts, _)) => start_ts - ts,
+            None => return Vec::new(),
+        };
+        self.equity_curve.iter().map(|(ts, equity)| (ts + offset, *equity)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip_and_alignment() {
+        let export = RunExport {
+            strategy_name: "Momentum".to_string(),
+            initial_equity: 10_000.0,
+            equity_curve: vec![(5.0, 10_000.0), (6.0, 10_100.0), (7.0, 10_200.0)],
+        };
+
+        let path = std::env::temp_dir().join(format!("run_export_test_{}.json", std::process::id()));
+        export.save(&path).unwrap();
+        let loaded = RunExport::load(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(loaded, export);
+        assert!((loaded.final_return_pct().unwrap() - 2.0).abs() < 1e-9);
+        assert_eq!(loaded.aligned_curve(0.0), vec![(0.0, 10_000.0), (1.0, 10_100.0), (2.0, 10_200.0)]);
+    }
+}
@@ -0,0 +1,151 @@
+use super::data::PerformanceData;
+use crate::common::annualization_factor;
+use crate::config::TRADING_SECONDS_PER_YEAR;
+
+/// Key metrics of a run, formatted for pasting into issues/PRs
+#[derive(Debug, Clone, PartialEq)]
+pub struct RunSummary {
+    pub strategy_name: String,
+    pub initial_equity: f64,
+    pub final_equity: f64,
+    pub return_pct: f64,
+    /// Annualized from the per-sample equity returns (None with fewer than 3 points or flat equity)
+    pub sharpe: Option<f64>,
+    pub max_drawdown_pct: f64,
+    pub realized_pnl: f64,
+    pub num_trades: usize,
+    pub win_rate_pct: f64,
+    pub fill_ratio_pct: f64,
+    /// Simulation seconds covered
+    pub duration_secs: f64,
+}
+
+impl RunSummary {
+    /// `equity_curve` is (simulation seconds, equity), as kept by the chart history
+    pub fn from_run(data: &PerformanceData, initial_equity: f64, equity_curve: &[(f64, f64)]) -> Self {
+        let win_rate_pct = if data.num_trades > 0 {
+            data.winning_trades as f64 / data.num_trades as f64 * 100.0
+        } else {
+            0.0
+        };
+        let fill_ratio_pct = if data.total_orders > 0 {
+            data.total_fills as f64 / data.total_orders as f64 * 100.0
+        } else {
+            0.0
+        };
+
+        Self {
+            strategy_name: data.strategy_name.clone(),
+            initial_equity,
+            final_equity: data.equity,
+            return_pct: (data.equity - initial_equity) / initial_equity * 100.0,
+            sharpe: sharpe_ratio(equity_curve),
+            max_drawdown_pct: max_drawdown_pct(equity_curve),
+            realized_pnl: data.realized_pnl,
+            num_trades: data.num_trades,
+            win_rate_pct,
+            fill_ratio_pct,
+            duration_secs: data.timestamp,
+        }
+    }
+
+    pub fn to_markdown(&self) -> String {
+        let sharpe = self.sharpe.map_or("n/a".to_string(), |s| format!("{:.2}", s));
+        let rows = [
+            ("Initial Equity", format!("${:.2}", self.initial_equity)),
+            ("Final Equity", format!("${:.2}", self.final_equity)),
+            ("Return", format!("{:+.2}%", self.return_pct)),
+            ("Sharpe (annualized)", sharpe),
+            ("Max Drawdown", format!("{:.2}%", self.max_drawdown_pct)),
+            ("Realized PnL", format!("${:+.2}", self.realized_pnl)),
+            ("Trades", self.num_trades.to_string()),
+            ("Win Rate", format!("{:.1}%", self.win_rate_pct)),
+            ("Fill Ratio", format!("{:.1}%", self.fill_ratio_pct)),
+            ("Duration", format!("{:.1}s", self.duration_secs)),
+        ];
+
+        let mut markdown = format!("### {} — Run Summary\n\n| Metric | Value |\n|---|---|\n", self.strategy_name);
+        for (label, value) in rows {
+            markdown.push_str(&format!("| {} | {} |\n", label, value));
+        }
+        markdown
+    }
+}
+
+/// Mean over standard deviation of the point-to-point returns, scaled by the average sample spacing
+fn sharpe_ratio(equity_curve: &[(f64, f64)]) -> Option<f64> {
+    if equity_curve.len() < 3 {
+        return None;
+    }
+
+    let returns: Vec<f64> = equity_curve.windows(2)
+        .filter(|pair| pair[0].1 != 0.0)
+        .map(|pair| pair[1].1 / pair[0].1 - 1.0)
+        .collect();
+    let n = returns.len() as f64;
+    let mean = returns.iter().sum::<f64>() / n;
+    let std = (returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / (n - 1.0)).sqrt();
+    let elapsed = equity_curve[equity_curve.len() - 1].0 - equity_curve[0].0;
+    if std <= 0.0 || elapsed <= 0.0 {
+        return None;
+    }
+
+    let sample_interval_ns = elapsed / (equity_curve.len() - 1) as f64 * 1_000_000_000.0;
+    Some(mean / std * annualization_factor(sample_interval_ns, TRADING_SECONDS_PER_YEAR).sqrt())
+}
+
+/// Largest peak-to-trough equity decline in percent
+fn max_drawdown_pct(equity_curve: &[(f64, f64)]) -> f64 {
+    let mut peak = f64::MIN;
+    let mut max_drawdown: f64 = 0.0;
+    for &(_, equity) in equity_curve {
+        peak = peak.max(equity);
+        if peak > 0.0 {
+            max_drawdown = max_drawdown.max((peak - equity) / peak * 100.0);
+        }
+    }
+    max_drawdown
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn data() -> PerformanceData {
+        PerformanceData {
+            timestamp: 120.0,
+            equity: 10_250.0,
+            realized_pnl: 260.0,
+            mid_price: 100.0,
+            strategy_name: "Momentum".to_string(),
+            num_trades: 4,
+            winning_trades: 3,
+            total_fills: 8,
+            total_orders: 10,
+            latency_micros: 100,
+            ..PerformanceData::default()
+        }
+    }
+
+    #[test]
+    fn test_markdown_contains_metric_labels_and_values() {
+        let curve = [(0.0, 10_000.0), (60.0, 10_400.0), (90.0, 10_100.0), (120.0, 10_250.0)];
+        let summary = RunSummary::from_run(&data(), 10_000.0, &curve);
+        let markdown = summary.to_markdown();
+
+        assert!(markdown.starts_with("### Momentum — Run Summary"));
+        assert!(markdown.contains("| Metric | Value |"));
+        assert!(markdown.contains("| Final Equity | $10250.00 |"));
+        assert!(markdown.contains("| Return | +2.50% |"));
+        assert!(markdown.contains("| Max Drawdown | 2.88% |"));
+        assert!(markdown.contains("| Trades | 4 |"));
+        assert!(markdown.contains("| Win Rate | 75.0% |"));
+        assert!(markdown.contains("| Fill Ratio | 80.0% |"));
+        assert!(markdown.contains("| Sharpe (annualized) | "));
+        assert!(summary.sharpe.is_some());
+
+        // Too short a curve has no Sharpe
+        let short = RunSummary::from_run(&data(), 10_000.0, &curve[..2]);
+        assert!(short.to_markdown().contains("| Sharpe (annualized) | n/a |"));
+    }
+}
@@ -0,0 +1,106 @@
+use anyhow::{Context, Result};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use super::data::PerformanceData;
+use super::resample::Resampler;
+
+pub const SERIES_CSV_HEADER: &str = "timestamp,equity,realized_pnl,unrealized_pnl,position,mid_price,drawdown_pct";
+
+/// Streams every GUI update to a CSV file as it arrives, so the full equity/PnL/position/price
+/// series of a long run is exported without holding it in memory
+pub struct SeriesCsvWriter {
+    path: PathBuf,
+    writer: BufWriter<File>,
+    /// Evenly spaced rows instead of one per update (None = every update)
+    resampler: Option<Resampler<PerformanceData>>,
+    peak_equity: f64,
+    rows: usize,
+}
+
+impl SeriesCsvWriter {
+    /// Create (or truncate) `path` and write the header; with `resample_secs` one row is written
+    /// per that many simulated seconds, carrying the last update forward
+    pub fn create(path: &Path, resample_secs: Option<f64>) -> Result<Self> {
+        let file = File::create(path)
+            .with_context(|| format!("Failed to create series export: {}", path.display()))?;
+        let mut writer = BufWriter::new(file);
+        writeln!(writer, "{}", SERIES_CSV_HEADER)?;
+        Ok(Self {
+            path: path.to_path_buf(),
+            writer,
+            resampler: resample_secs.map(Resampler::new),
+            peak_equity: f64::MIN,
+            rows: 0,
+        })
+    }
+
+    /// Append one sample (or the resampled rows it completes); drawdown is measured from the
+    /// highest equity written so far
+    pub fn write_sample(&mut self, data: &PerformanceData) -> Result<()> {
+        let Some(resampler) = self.resampler.as_mut() else {
+            return self.write_row(data.timestamp, data);
+        };
+        for (timestamp, row) in resampler.push(data.timestamp, data.clone()) {
+            self.write_row(timestamp, &row)?;
+        }
+        Ok(())
+    }
+
+    fn write_row(&mut self, timestamp: f64, data: &PerformanceData) -> Result<()> {
+        self.peak_equity = self.peak_equity.max(data.equity);
+        let drawdown_pct = if self.peak_equity > 0.0 {
+            (self.peak_equity - data.equity) / self.peak_equity * 100.0
+        } else {
+            0.0
+        };
+        writeln!(self.writer, "{},{},{},{},{},{},{}",
+                 timestamp, data.equity, data.realized_pnl, data.unrealized_pnl,
+                 data.position, data.mid_price, drawdown_pct)
+            .with_context(|| format!("Failed to write series export: {}", self.path.display()))?;
+        self.rows += 1;
+        Ok(())
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Flush the buffered rows and close the file
+    pub fn finish(mut self) -> Result<()> {
+        self.writer.flush()
+            .with_context(|| format!("Failed to write series export: {}", self.path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_one_row_per_sample_under_header() {
+        let path = std::env::temp_dir().join(format!("series_export_test_{}.csv", std::process::id()));
+        let mut writer = SeriesCsvWriter::create(&path, None).unwrap();
+        let mut data = PerformanceData::default();
+        for (i, equity) in [10_000.0, 10_200.0, 10_098.0].into_iter().enumerate() {
+            data.timestamp = i as f64;
+            data.equity = equity;
+            data.mid_price = 100.0;
+            writer.write_sample(&data).unwrap();
+        }
+        assert_eq!(writer.rows(), 3);
+        writer.finish().unwrap();
+
+        let csv = std::fs::read_to_string(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], SERIES_CSV_HEADER);
+        assert_eq!(lines.len(), 1 + 3);
+        // 10_098 is 1% below the 10_200 peak
+        assert_eq!(lines[3], "2,10098,0,0,0,100,1");
+    }
+}
@@ -1,5 +1,7 @@
 use eframe::egui;
 use super::data::PerformanceData;
+use super::price_format::format_price;
+use crate::common::FeeDrag;
 
 pub struct StatsPanel;
 
@@ -20,7 +22,26 @@ impl StatsPanel {
         }
     }
 
-    pub fn render(ui: &mut egui::Ui, data: Option<&PerformanceData>, initial_equity: f64) {
+    /// Realized sim seconds per wall second, e.g. "Replay: 37x realtime"
+    fn format_replay_rate(rate: f64) -> String {
+        if rate >= 10.0 {
+            format!("Replay: {:.0}x realtime", rate)
+        } else {
+            format!("Replay: {:.1}x realtime", rate)
+        }
+    }
+
+    /// `reference_return_pct`: final return of a loaded reference run, shown as a delta;
+    /// `buy_and_hold_return_pct`: benchmark return, shown as the strategy's alpha over it;
+    /// prices are shown multiplied by `price_multiplier`
+    pub fn render(
+        ui: &mut egui::Ui,
+        data: Option<&PerformanceData>,
+        initial_equity: f64,
+        reference_return_pct: Option<f64>,
+        buy_and_hold_return_pct: Option<f64>,
+        price_multiplier: f64,
+    ) {
         ui.group(|ui| {
             if let Some(data) = data {
                 let return_pct = ((data.equity - initial_equity) / initial_equity) * 100.0;
@@ -33,6 +54,10 @@ impl StatsPanel {
                             .size(14.0)
                             .color(egui::Color32::LIGHT_GRAY)
                             .monospace());
+                        if data.replay_rate > 0.0 {
+                            ui.label(egui::RichText::new(Self::format_replay_rate(data.replay_rate))
+                                .color(egui::Color32::GRAY));
+                        }
                     });
                 });
                 ui.separator();
@@ -51,6 +76,26 @@ impl StatsPanel {
                             .size(18.0).color(color).strong());
                     });
                     
+                    if let Some(reference_pct) = reference_return_pct {
+                        ui.horizontal(|ui| {
+                            ui.label("🆚 vs Reference:");
+                            let delta = return_pct - reference_pct;
+                            let color = if delta >= 0.0 { egui::Color32::GREEN } else { egui::Color32::RED };
+                            ui.label(egui::RichText::new(format!("{:+.2}%p (ref {:+.2}%)", delta, reference_pct))
+                                .color(color));
+                        });
+                    }
+
+                    if let Some(buy_and_hold_pct) = buy_and_hold_return_pct {
+                        ui.horizontal(|ui| {
+                            ui.label("🆚 Alpha vs B&H:");
+                            let alpha = return_pct - buy_and_hold_pct;
+                            let color = if alpha >= 0.0 { egui::Color32::GREEN } else { egui::Color32::RED };
+                            ui.label(egui::RichText::new(format!("{:+.2}%p (B&H {:+.2}%)", alpha, buy_and_hold_pct))
+                                .color(color));
+                        });
+                    }
+                    
                     ui.horizontal(|ui| {
                         ui.label("💵 Total PnL:");
                         let color = if total_pnl >= 0.0 { egui::Color32::GREEN } else { egui::Color32::RED };
@@ -72,7 +117,7 @@ impl StatsPanel {
                         ui.end_row();
                         
                         ui.label("Mid Price:");
-                        ui.label(format!("${:.2}", data.mid_price));
+                        ui.label(format!("${}", format_price(data.mid_price, price_multiplier)));
                         ui.end_row();
                         
                         ui.label("Trades:");
@@ -92,7 +137,89 @@ impl StatsPanel {
                         } else { 0.0 };
                         ui.label(format!("{:.1}%", fill_ratio));
                         ui.end_row();
+                        
+                        if data.performance_fees_accrued > 0.0 {
+                            ui.label("Perf. Fees:");
+                            ui.label(egui::RichText::new(format!("-${:.2}", data.performance_fees_accrued))
+                                .color(egui::Color32::LIGHT_RED));
+                            ui.end_row();
+                        }
+
+                        if data.borrow_costs > 0.0 {
+                            ui.label("Borrow Costs:");
+                            ui.label(egui::RichText::new(format!("-${:.2}", data.borrow_costs))
+                                .color(egui::Color32::LIGHT_RED));
+                            ui.end_row();
+                        }
+
+                        if data.fees_paid != 0.0 {
+                            let drag = FeeDrag::from_net(data.equity - initial_equity, data.fees_paid, initial_equity);
+                            ui.label("Fees Paid:");
+                            ui.label(egui::RichText::new(drag.summary())
+                                .color(egui::Color32::LIGHT_RED));
+                            ui.end_row();
+                        }
+
+                        if let Some(confidence) = data.signal_confidence {
+                            ui.label("Signal Confidence:");
+                            ui.label(format!("{:.0}%", confidence * 100.0));
+                            ui.end_row();
+                        }
+
+                        if let Some(capture) = &data.spread_capture {
+                            ui.label("Spread Capture:");
+                            ui.label(format!("${:.5} (quoted ${:.5})", capture.captured, capture.quoted));
+                            ui.end_row();
+                        }
                     });
+
+                if let Some(attribution) = &data.attribution {
+                    ui.add_space(6.0);
+                    ui.label(egui::RichText::new("Attribution").strong());
+                    egui::Grid::new("attribution_grid")
+                        .num_columns(3)
+                        .spacing([20.0, 4.0])
+                        .show(ui, |ui| {
+                            ui.label("");
+                            ui.label("Trades");
+                            ui.label("PnL");
+                            ui.end_row();
+                            for (side, trades, pnl) in [
+                                ("Long", attribution.long_trades, attribution.long_pnl),
+                                ("Short", attribution.short_trades, attribution.short_pnl),
+                            ] {
+                                ui.label(side);
+                                ui.label(format!("{}", trades));
+                                let color = if pnl >= 0.0 { egui::Color32::GREEN } else { egui::Color32::RED };
+                                ui.label(egui::RichText::new(format!("${:+.2}", pnl)).color(color));
+                                ui.end_row();
+                            }
+                        });
+                }
+
+                if !data.sleeves.is_empty() {
+                    ui.add_space(6.0);
+                    ui.label(egui::RichText::new("Portfolio").strong());
+                    egui::Grid::new("portfolio_grid")
+                        .num_columns(5)
+                        .spacing([20.0, 4.0])
+                        .show(ui, |ui| {
+                            for header in ["Strategy", "Capital", "Equity", "Return", "Position"] {
+                                ui.label(header);
+                            }
+                            ui.end_row();
+                            for sleeve in &data.sleeves {
+                                let return_pct = (sleeve.equity / sleeve.capital - 1.0) * 100.0;
+                                let color = if return_pct >= 0.0 { egui::Color32::GREEN } else { egui::Color32::RED };
+                                ui.label(&sleeve.strategy_name);
+                                ui.label(format!("${:.2}", sleeve.capital));
+                                ui.label(format!("${:.2}", sleeve.equity));
+                                ui.label(egui::RichText::new(format!("{:+.2}%", return_pct)).color(color));
+                                ui.label(format!("{:.4} ({} trades)", sleeve.position, sleeve.num_trades));
+                                ui.end_row();
+                            }
+                        });
+                }
             } else {
                 ui.heading("📊 Strategy Monitor");
                 ui.separator();
@@ -0,0 +1,89 @@
+use super::data::PerformanceData;
+
+/// A position as seen in the update stream, from the first update it was open to the first one
+/// it was closed (updates are also sent on fills, so these land on the fill)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TradeRecord {
+    /// Simulated seconds into the file
+    pub entry_secs: f64,
+    pub exit_secs: Option<f64>,
+    pub is_long: bool,
+    pub entry_price: f64,
+}
+
+/// Trades of the run shown in the charts, for jumping back to one of them
+#[derive(Debug, Default)]
+pub struct TradeLog {
+    trades: Vec<TradeRecord>,
+    last_position: f64,
+}
+
+impl TradeLog {
+    /// Open/close a record when the position leaves or returns to flat (a flip does both)
+    pub fn push(&mut self, data: &PerformanceData) {
+        let was_long = self.last_position > 0.0;
+        let was_short = self.last_position < 0.0;
+        let is_long = data.position > 0.0;
+        let is_short = data.position < 0.0;
+        self.last_position = data.position;
+
+        if (was_long && !is_long) || (was_short && !is_short) {
+            if let Some(open) = self.trades.last_mut().filter(|trade| trade.exit_secs.is_none()) {
+                open.exit_secs = Some(data.timestamp);
+            }
+        }
+        if (is_long && !was_long) || (is_short && !was_short) {
+            self.trades.push(TradeRecord {
+                entry_secs: data.timestamp,
+                exit_secs: None,
+                is_long,
+                entry_price: data.mid_price,
+            });
+        }
+    }
+
+    pub fn trades(&self) -> &[TradeRecord] {
+        &self.trades
+    }
+
+    pub fn clear(&mut self) {
+        self.trades.clear();
+        self.last_position = 0.0;
+    }
+
+    /// Time to seek to so trade `index` (0-based) replays from `lead_secs` before its entry
+    pub fn seek_target(&self, index: usize, lead_secs: f64) -> Option<f64> {
+        self.trades.get(index).map(|trade| (trade.entry_secs - lead_secs).max(0.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn update(timestamp: f64, position: f64) -> PerformanceData {
+        PerformanceData { timestamp, position, mid_price: 100.0, ..PerformanceData::default() }
+    }
+
+    #[test]
+    fn test_seek_lands_before_the_selected_trade_entry() {
+        let mut log = TradeLog::default();
+        // Long 10-20s, short 30-35s, flip long -> short at 50s, short closed at 70s
+        for (timestamp, position) in [(0.0, 0.0), (10.0, 1.0), (20.0, 0.0), (30.0, -1.0), (35.0, 0.0),
+                                      (40.0, 1.0), (50.0, -1.0), (70.0, 0.0)] {
+            log.push(&update(timestamp, position));
+        }
+
+        let entries: Vec<f64> = log.trades().iter().map(|trade| trade.entry_secs).collect();
+        assert_eq!(entries, vec![10.0, 30.0, 40.0, 50.0]);
+        assert_eq!(log.trades()[2].exit_secs, Some(50.0));
+        assert!(!log.trades()[3].is_long);
+
+        let target = log.seek_target(3, 5.0).unwrap();
+        assert!(target < log.trades()[3].entry_secs);
+        assert_eq!(target, 45.0);
+        // Never before the start of the file, and nothing to seek to past the last trade
+        assert_eq!(log.seek_target(0, 30.0), Some(0.0));
+        assert_eq!(log.seek_target(4, 5.0), None);
+    }
+}
@@ -0,0 +1,82 @@
+use std::time::{Duration, Instant};
+
+/// When a runner pushes a `PerformanceData` update: on a fixed wall-clock interval, plus once
+/// right after any fill when `send_on_fill` is set, so equity steps land at the fill's
+/// simulation time instead of up to one interval later. Run-to-end mode only sends the final state.
+#[derive(Debug, Clone)]
+pub struct UpdateCadence {
+    interval: Duration,
+    send_on_fill: bool,
+    last_update: Instant,
+    /// Runner fill count as of the last `observe_fills`
+    seen_fills: usize,
+    fill_pending: bool,
+}
+
+impl UpdateCadence {
+    pub fn new(interval: Duration, send_on_fill: bool) -> Self {
+        Self {
+            interval,
+            send_on_fill,
+            last_update: Instant::now(),
+            seen_fills: 0,
+            fill_pending: false,
+        }
+    }
+
+    /// Feed the runner's running fill count; an increase schedules an out-of-cadence update
+    pub fn observe_fills(&mut self, total_fills: usize) {
+        if self.send_on_fill && total_fills > self.seen_fills {
+            self.fill_pending = true;
+        }
+        self.seen_fills = total_fills;
+    }
+
+    /// Whether a fill is waiting for its update (the runner stops batching steps until it's sent)
+    pub fn fill_pending(&self) -> bool {
+        self.fill_pending
+    }
+
+    pub fn due(&self, run_to_end: bool, data_ended: bool) -> bool {
+        if run_to_end {
+            data_ended
+        } else {
+            self.fill_pending || self.last_update.elapsed() >= self.interval
+        }
+    }
+
+    pub fn mark_sent(&mut self) {
+        self.last_update = Instant::now();
+        self.fill_pending = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fill_forces_one_update_out_of_cadence() {
+        let mut cadence = UpdateCadence::new(Duration::from_secs(3600), true);
+        cadence.mark_sent();
+        assert!(!cadence.due(false, false));
+
+        // A close fill: update due now, long before the next regular one
+        cadence.observe_fills(1);
+        assert!(cadence.fill_pending());
+        assert!(cadence.due(false, false));
+        cadence.mark_sent();
+        assert!(!cadence.due(false, false));
+
+        // Count unchanged: nothing extra
+        cadence.observe_fills(1);
+        assert!(!cadence.due(false, false));
+
+        // Disabled, or in run-to-end mode, fills don't trigger updates
+        let mut disabled = UpdateCadence::new(Duration::from_secs(3600), false);
+        disabled.observe_fills(5);
+        assert!(!disabled.due(false, false));
+        cadence.observe_fills(2);
+        assert!(!cadence.due(true, false));
+    }
+}